@@ -10,7 +10,18 @@ pub const DEVSERVER_RAW_ADDR_ENV: &str = "DIOXUS_DEVSERVER_ADDR";
 pub const ALWAYS_ON_TOP_ENV: &str = "DIOXUS_ALWAYS_ON_TOP";
 pub const ASSET_ROOT_ENV: &str = "DIOXUS_ASSET_ROOT";
 pub const APP_TITLE_ENV: &str = "DIOXUS_APP_TITLE";
+pub const WEB_HISTORY_ENV: &str = "DIOXUS_WEB_HISTORY";
 pub const OUT_DIR: &str = "DIOXUS_OUT_DIR";
+pub const SSG_OUTPUT_DIR_ENV: &str = "DIOXUS_SSG_OUTPUT_DIR";
+pub const SSG_ROUTES_ENV: &str = "DIOXUS_SSG_ROUTES";
+pub const SITEMAP_OUTPUT_DIR_ENV: &str = "DIOXUS_SITEMAP_OUTPUT_DIR";
+
+/// The name of the copy of the index.html template that is kept next to the pre-rendered pages. Static site
+/// generation replaces `index.html` with the pre-rendered root route, so the server renders from this file instead.
+pub const INDEX_TEMPLATE_NAME: &str = "__dioxus_index.html";
+
+/// The name of the manifest static site generation writes into the output directory
+pub const SSG_MANIFEST_NAME: &str = "ssg-manifest.json";
 
 /// Reads an environment variable at runtime in debug mode or at compile time in
 /// release mode. When bundling in release mode, we will not be running under the
@@ -138,6 +149,55 @@ pub fn format_base_path_meta_element(base_path: &str) -> String {
     format!(r#"<meta name="{ASSET_ROOT_ENV}" content="{base_path}">"#,)
 }
 
+/// Get the kind of history the web app uses from the `history` setting of `Dioxus.toml`, like `hash`.
+#[cfg(feature = "web")]
+pub fn web_history() -> Option<String> {
+    // In debug mode, we get the history from the meta element so it can change without recompiling
+    #[cfg(debug_assertions)]
+    {
+        thread_local! {
+            static HISTORY: std::cell::OnceCell<Option<String>> = const { std::cell::OnceCell::new() };
+        }
+        HISTORY.with(|f| f.get_or_init(|| get_meta_contents(WEB_HISTORY_ENV)).clone())
+    }
+
+    // In release mode, we get the history from the environment variable
+    #[cfg(not(debug_assertions))]
+    {
+        option_env!("DIOXUS_WEB_HISTORY").map(ToString::to_string)
+    }
+}
+
+pub fn format_web_history_meta_element(history: &str) -> String {
+    format!(r#"<meta name="{WEB_HISTORY_ENV}" content="{history}">"#,)
+}
+
 pub fn out_dir() -> Option<PathBuf> {
     std::env::var(OUT_DIR).ok().map(PathBuf::from)
 }
+
+/// Get the directory the server should write statically generated pages into. If this is set, the server
+/// renders every static route and exits instead of serving requests.
+pub fn ssg_output_dir() -> Option<PathBuf> {
+    std::env::var(SSG_OUTPUT_DIR_ENV).ok().map(PathBuf::from)
+}
+
+/// Get the directory the server should write `sitemap.xml` into. If this is set, the server writes the sitemap
+/// and exits instead of serving requests.
+pub fn sitemap_output_dir() -> Option<PathBuf> {
+    std::env::var(SITEMAP_OUTPUT_DIR_ENV)
+        .ok()
+        .map(PathBuf::from)
+}
+
+/// Get the subset of routes static site generation should re-generate. If this is `None`, every route is generated.
+pub fn ssg_routes() -> Option<Vec<String>> {
+    std::env::var(SSG_ROUTES_ENV).ok().map(|routes| {
+        routes
+            .split(',')
+            .map(str::trim)
+            .filter(|route| !route.is_empty())
+            .map(ToString::to_string)
+            .collect()
+    })
+}
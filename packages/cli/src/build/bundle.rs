@@ -2,7 +2,7 @@ use super::templates::InfoPlistData;
 use crate::wasm_bindgen::WasmBindgenBuilder;
 use crate::{BuildRequest, Platform};
 use crate::{Result, TraceSrc};
-use anyhow::Context;
+use anyhow::{bail, Context};
 use dioxus_cli_opt::{process_file_to, AssetManifest};
 use manganis_core::AssetOptions;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
@@ -319,6 +319,9 @@ impl AppBundle {
             //                        glue.js
             //                        snippets/
             //                            ...
+            //                        chunks/
+            //                            Route_Settings.js
+            //                            Route_Settings_bg.wasm
             //                     assets/
             //                        logo.png
             // ```
@@ -328,6 +331,9 @@ impl AppBundle {
                 self.run_wasm_bindgen(&self.app.exe.with_extension("wasm"), &self.build.exe_dir())
                     .await?;
 
+                // Move the components of lazy routes into their own modules
+                self.run_wasm_split(&self.build.exe_dir())?;
+
                 // Only run wasm-opt if the feature is enabled
                 // Wasm-opt has an expensive build script that makes it annoying to keep enabled for iterative dev
                 // We put it behind the "wasm-opt" feature flag so that it can be disabled when iterating on the cli
@@ -338,6 +344,21 @@ impl AppBundle {
                     self.build.root_dir().join("index.html"),
                     self.build.prepare_html()?,
                 )?;
+
+                // SSG keeps a copy of the template from the last build. Remove it so the server renders from the new index.html
+                _ = std::fs::remove_file(
+                    self.build
+                        .root_dir()
+                        .join(dioxus_cli_config::INDEX_TEMPLATE_NAME),
+                );
+
+                // Write the robots.txt file if it is configured in the Dioxus.toml
+                if let Some(robots) = &self.build.krate.config.web.robots {
+                    std::fs::write(
+                        self.build.root_dir().join("robots.txt"),
+                        robots.to_robots_txt(),
+                    )?;
+                }
             }
 
             // this will require some extra oomf to get the multi architecture builds...
@@ -556,9 +577,11 @@ impl AppBundle {
                 .await
                 .unwrap()?;
 
-                // Run SSG and cache static routes
+                // Run SSG and cache static routes. SSG also writes the sitemap
                 if self.build.build.ssg {
                     self.run_ssg().await?;
+                } else if self.build.build.sitemap {
+                    self.run_sitemap().await?;
                 }
             }
             Platform::MacOS => {}
@@ -636,6 +659,41 @@ impl AppBundle {
         Ok(())
     }
 
+    /// Split the modules of `#[lazy]` routes out of the main wasm module and write them to the `chunks` folder
+    pub(crate) fn run_wasm_split(&self, bindgen_outdir: &Path) -> Result<()> {
+        // Chunks from a previous build would make the index.html load routes that no longer exist
+        let chunks_dir = bindgen_outdir.join("chunks");
+        _ = std::fs::remove_dir_all(&chunks_dir);
+
+        let wasm_file =
+            bindgen_outdir.join(format!("{}_bg.wasm", self.build.krate.executable_name()));
+        let start = std::time::Instant::now();
+        let Some(split) = crate::wasm_split::split_wasm(&std::fs::read(&wasm_file)?)
+            .context("Failed to split the wasm module")?
+        else {
+            return Ok(());
+        };
+
+        std::fs::create_dir_all(&chunks_dir)?;
+        for chunk in &split.chunks {
+            std::fs::write(
+                chunks_dir.join(format!("{}_bg.wasm", chunk.name)),
+                &chunk.wasm,
+            )?;
+            std::fs::write(chunks_dir.join(format!("{}.js", chunk.name)), chunk.js())?;
+        }
+        std::fs::write(&wasm_file, &split.main)?;
+
+        tracing::debug!(
+            dx_src = ?TraceSrc::Bundle,
+            "Split {} lazy routes out of the wasm module in {:?}",
+            split.chunks.len(),
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
     #[allow(unused)]
     pub(crate) fn run_wasm_opt(&self, bindgen_outdir: &std::path::Path) -> Result<()> {
         if !self.build.build.release {
@@ -662,92 +720,114 @@ impl AppBundle {
             };
             let wasm_file =
                 bindgen_outdir.join(format!("{}_bg.wasm", self.build.krate.executable_name()));
-            let old_size = wasm_file.metadata()?.len();
-            options
-                // WASM bindgen relies on reference types
-                .enable_feature(wasm_opt::Feature::ReferenceTypes)
-                .debug_info(self.build.krate.config.web.wasm_opt.debug)
-                .run(&wasm_file, &wasm_file)
-                .map_err(|err| crate::Error::Other(anyhow::anyhow!(err)))?;
-
-            let new_size = wasm_file.metadata()?.len();
-            tracing::debug!(
-                dx_src = ?TraceSrc::Build,
-                "wasm-opt reduced WASM size from {} to {} ({:2}%)",
-                old_size,
-                new_size,
-                (new_size as f64 - old_size as f64) / old_size as f64 * 100.0
-            );
+
+            // Optimize the modules of lazy routes along with the main module
+            let chunks = std::fs::read_dir(bindgen_outdir.join("chunks"))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"));
+
+            for wasm_file in std::iter::once(wasm_file).chain(chunks) {
+                let old_size = wasm_file.metadata()?.len();
+                options
+                    // WASM bindgen relies on reference types
+                    .enable_feature(wasm_opt::Feature::ReferenceTypes)
+                    .debug_info(self.build.krate.config.web.wasm_opt.debug)
+                    .run(&wasm_file, &wasm_file)
+                    .map_err(|err| crate::Error::Other(anyhow::anyhow!(err)))?;
+
+                let new_size = wasm_file.metadata()?.len();
+                tracing::debug!(
+                    dx_src = ?TraceSrc::Build,
+                    "wasm-opt reduced WASM size of {} from {} to {} ({:2}%)",
+                    wasm_file.display(),
+                    old_size,
+                    new_size,
+                    (new_size as f64 - old_size as f64) / old_size as f64 * 100.0
+                );
+            }
         }
 
         Ok(())
     }
 
+    /// Generate the static pages of the app into the public folder.
+    ///
+    /// The server renders every route in-process and exits once the pages and the ssg manifest are written, so we
+    /// don't need to wait for it to start listening. A route that fails to render makes the server exit with an error.
     async fn run_ssg(&self) -> anyhow::Result<()> {
-        use futures_util::stream::FuturesUnordered;
-        use futures_util::StreamExt;
-        use tokio::process::Command;
+        tracing::info!("Running SSG");
 
-        const PORT: u16 = 9999;
+        let output_dir = self.build.root_dir();
+        let mut envs = vec![(
+            dioxus_cli_config::SSG_OUTPUT_DIR_ENV,
+            output_dir.clone().into_os_string(),
+        )];
+
+        // Incrementally re-generate only the requested routes
+        if !self.build.build.ssg_routes.is_empty() {
+            envs.push((
+                dioxus_cli_config::SSG_ROUTES_ENV,
+                self.build.build.ssg_routes.join(",").into(),
+            ));
+        }
 
-        tracing::info!("Running SSG");
+        let summary = self.run_server_generator(&envs).await?;
 
-        // Run the server executable
-        let _child = Command::new(
+        tracing::info!(
+            "SSG complete: {summary} ({})",
+            output_dir
+                .join(dioxus_cli_config::SSG_MANIFEST_NAME)
+                .display()
+        );
+
+        Ok(())
+    }
+
+    /// Write the sitemap of the app into the public folder without generating any pages
+    async fn run_sitemap(&self) -> anyhow::Result<()> {
+        tracing::info!("Generating sitemap");
+
+        let summary = self
+            .run_server_generator(&[(
+                dioxus_cli_config::SITEMAP_OUTPUT_DIR_ENV,
+                self.build.root_dir(),
+            )])
+            .await?;
+
+        tracing::info!("{summary}");
+
+        Ok(())
+    }
+
+    /// Run the server executable with the environment variables that make it generate files and exit instead of serving.
+    /// Returns the summary the server printed.
+    async fn run_server_generator(
+        &self,
+        envs: &[(&str, impl AsRef<std::ffi::OsStr>)],
+    ) -> anyhow::Result<String> {
+        let output = Command::new(
             self.server_exe()
                 .context("Failed to find server executable")?,
         )
-        .env(dioxus_cli_config::SERVER_PORT_ENV, PORT.to_string())
-        .env(dioxus_cli_config::SERVER_IP_ENV, "127.0.0.1")
+        .envs(envs.iter().map(|(key, value)| (key, value.as_ref())))
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .kill_on_drop(true)
-        .spawn()?;
-
-        // Wait a second for the server to start
-        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-
-        // Get the routes from the `/static_routes` endpoint
-        let mut routes = reqwest::Client::builder()
-            .build()?
-            .post(format!("http://127.0.0.1:{PORT}/api/static_routes"))
-            .send()
-            .await
-            .context("Failed to get static routes from server")?
-            .text()
-            .await
-            .map(|raw| serde_json::from_str::<Vec<String>>(&raw).unwrap())
-            .inspect(|text| tracing::debug!("Got static routes: {text:?}"))
-            .context("Failed to parse static routes from server")?
-            .into_iter()
-            .map(|line| async move {
-                tracing::info!("SSG: {line}");
-                reqwest::Client::builder()
-                    .build()?
-                    .get(format!("http://127.0.0.1:{PORT}{line}"))
-                    .header("Accept", "text/html")
-                    .send()
-                    .await
-            })
-            .collect::<FuturesUnordered<_>>();
+        .output()
+        .await
+        .context("Failed to run the server executable")?;
 
-        while let Some(route) = routes.next().await {
-            match route {
-                Ok(route) => tracing::debug!("ssg success: {route:?}"),
-                Err(err) => tracing::error!("ssg error: {err:?}"),
-            }
+        if !output.status.success() {
+            bail!(
+                "The server failed to generate files: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
         }
 
-        // Wait a second for the cache to be written by the server
-        tracing::info!("Waiting a moment for isrg to propagate...");
-
-        tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-
-        tracing::info!("SSG complete");
-
-        drop(_child);
-
-        Ok(())
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 
     fn macos_plist_contents(&self) -> Result<String> {
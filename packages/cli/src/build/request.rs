@@ -3,7 +3,7 @@ use crate::dioxus_crate::DioxusCrate;
 use crate::{link::LinkAction, BuildArgs};
 use crate::{AppBundle, Platform, Result, TraceSrc};
 use anyhow::Context;
-use dioxus_cli_config::{APP_TITLE_ENV, ASSET_ROOT_ENV};
+use dioxus_cli_config::{APP_TITLE_ENV, ASSET_ROOT_ENV, WEB_HISTORY_ENV};
 use dioxus_cli_opt::AssetManifest;
 use serde::Deserialize;
 use std::{
@@ -570,13 +570,17 @@ impl BuildRequest {
             // env_vars.push(("PATH", extended_path));
         };
 
-        // If this is a release build, bake the base path and title
+        // If this is a release build, bake the base path, title and history
         // into the binary with env vars
         if self.build.release {
             if let Some(base_path) = &self.krate.config.web.app.base_path {
                 env_vars.push((ASSET_ROOT_ENV, base_path.clone()));
             }
             env_vars.push((APP_TITLE_ENV, self.krate.config.web.app.title.clone()));
+            env_vars.push((
+                WEB_HISTORY_ENV,
+                self.krate.config.web.app.history.as_str().to_string(),
+            ));
         }
 
         Ok(env_vars)
@@ -1,5 +1,6 @@
-use dioxus_cli_config::format_base_path_meta_element;
+use dioxus_cli_config::{format_base_path_meta_element, format_web_history_meta_element};
 
+use crate::config::WebHistoryKind;
 use crate::error::Result;
 use crate::BuildRequest;
 use std::fmt::Write;
@@ -7,6 +8,7 @@ use std::path::{Path, PathBuf};
 
 const DEFAULT_HTML: &str = include_str!("../../assets/web/index.html");
 const TOAST_HTML: &str = include_str!("../../assets/web/toast.html");
+const LAZY_ROUTES_HTML: &str = include_str!("../../assets/web/lazy.html");
 
 impl BuildRequest {
     pub(crate) fn prepare_html(&self) -> Result<String> {
@@ -68,11 +70,15 @@ impl BuildRequest {
             )?;
         }
 
-        // Add the base path to the head if this is a debug build
+        // Add the base path and history to the head if this is a debug build
         if self.is_dev_build() {
             if let Some(base_path) = &self.krate.config.web.app.base_path {
                 head_resources.push_str(&format_base_path_meta_element(base_path));
             }
+            let history = self.krate.config.web.app.history;
+            if history != WebHistoryKind::default() {
+                head_resources.push_str(&format_web_history_meta_element(history.as_str()));
+            }
         }
 
         if !style_list.is_empty() {
@@ -107,6 +113,7 @@ impl BuildRequest {
             import("/{base_path}/wasm/{app_name}.js").then(
                 ({ default: init }) => {
                 init("/{base_path}/wasm/{app_name}_bg.wasm").then((wasm) => {
+                    window.__dx_lazy?.ready(wasm);
                     if (wasm.__wbindgen_start == undefined) {
                     wasm.main();
                     }
@@ -114,10 +121,17 @@ impl BuildRequest {
                 }
             );
             </script>
+            {DX_LAZY_ROUTES}
             {DX_TOAST_UTILITIES}
             </body"#,
         );
 
+        // Only load the modules of lazy routes if the build split some out of the main module
+        *html = match self.exe_dir().join("chunks").exists() {
+            true => html.replace("{DX_LAZY_ROUTES}", LAZY_ROUTES_HTML),
+            false => html.replace("{DX_LAZY_ROUTES}", ""),
+        };
+
         // Trim out the toasts if we're in release, or add them if we're serving
         *html = match self.is_dev_build() {
             true => html.replace("{DX_TOAST_UTILITIES}", TOAST_HTML),
@@ -38,6 +38,16 @@ pub(crate) struct BuildArgs {
     #[clap(long)]
     pub(crate) ssg: bool,
 
+    /// Only re-generate these routes when running ssg. Pages generated for other routes by a previous run are kept
+    #[clap(long = "ssg-route", requires = "ssg")]
+    #[serde(default)]
+    pub(crate) ssg_routes: Vec<String>,
+
+    /// Write the sitemap configured in the fullstack server into the public folder. This is always done when running ssg
+    #[clap(long)]
+    #[serde(default)]
+    pub(crate) sitemap: bool,
+
     /// Skip collecting assets from dependencies [default: false]
     #[clap(long)]
     #[serde(default)]
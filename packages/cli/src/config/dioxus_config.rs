@@ -29,6 +29,7 @@ impl Default for DioxusConfig {
                 app: WebAppConfig {
                     title: default_title(),
                     base_path: None,
+                    history: Default::default(),
                 },
                 proxy: vec![],
                 watcher: Default::default(),
@@ -48,6 +49,7 @@ impl Default for DioxusConfig {
                 },
                 pre_compress: true,
                 wasm_opt: Default::default(),
+                robots: None,
             },
             desktop: DesktopConfig::default(),
             bundle: BundleConfig::default(),
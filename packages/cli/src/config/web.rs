@@ -25,6 +25,10 @@ pub(crate) struct WebConfig {
     /// The wasm-opt configuration
     #[serde(default)]
     pub(crate) wasm_opt: WasmOptConfig,
+
+    /// The robots.txt file to write into the public folder. No robots.txt is written if this is not set
+    #[serde(default)]
+    pub(crate) robots: Option<WebRobotsConfig>,
 }
 
 impl Default for WebConfig {
@@ -37,6 +41,7 @@ impl Default for WebConfig {
             proxy: Default::default(),
             watcher: Default::default(),
             resource: Default::default(),
+            robots: None,
         }
     }
 }
@@ -92,6 +97,8 @@ pub(crate) struct WebAppConfig {
     #[serde(default = "default_title")]
     pub(crate) title: String,
     pub(crate) base_path: Option<String>,
+    #[serde(default)]
+    pub(crate) history: WebHistoryKind,
 }
 
 impl WebAppConfig {
@@ -115,6 +122,27 @@ impl Default for WebAppConfig {
         Self {
             title: default_title(),
             base_path: None,
+            history: WebHistoryKind::default(),
+        }
+    }
+}
+
+/// Where the router of the app stores the current route
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WebHistoryKind {
+    /// The path of the url, like `/blog/1`. The server must serve the app for every route
+    #[default]
+    Path,
+    /// The hash of the url, like `/#/blog/1`. This works on static hosts without rewrite rules
+    Hash,
+}
+
+impl WebHistoryKind {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Hash => "hash",
         }
     }
 }
@@ -173,6 +201,95 @@ pub(crate) struct WebHttpsConfig {
     pub(crate) cert_path: Option<String>,
 }
 
+/// The robots.txt configuration
+///
+/// ```toml
+/// [web.robots]
+/// sitemaps = ["https://example.com/sitemap.xml"]
+///
+/// [[web.robots.rules]]
+/// user_agent = "*"
+/// allow = ["/"]
+/// disallow = ["/admin"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub(crate) struct WebRobotsConfig {
+    /// The groups of rules for each crawler. If there are no rules, every crawler is allowed to crawl every page
+    #[serde(default)]
+    pub(crate) rules: Vec<WebRobotsRule>,
+
+    /// The absolute urls of the sitemaps of the site
+    #[serde(default)]
+    pub(crate) sitemaps: Vec<String>,
+}
+
+/// A group of rules for one crawler in the robots.txt file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WebRobotsRule {
+    /// The crawler the rules apply to [default: *]
+    #[serde(default = "default_user_agent")]
+    pub(crate) user_agent: String,
+
+    /// The paths the crawler is allowed to crawl
+    #[serde(default)]
+    pub(crate) allow: Vec<String>,
+
+    /// The paths the crawler is not allowed to crawl
+    #[serde(default)]
+    pub(crate) disallow: Vec<String>,
+
+    /// The number of seconds the crawler should wait between requests
+    #[serde(default)]
+    pub(crate) crawl_delay: Option<u32>,
+}
+
+impl WebRobotsConfig {
+    /// Generate the contents of the robots.txt file
+    pub(crate) fn to_robots_txt(&self) -> String {
+        let mut robots = String::new();
+
+        let allow_all = [WebRobotsRule {
+            user_agent: default_user_agent(),
+            allow: Vec::new(),
+            disallow: vec![String::new()],
+            crawl_delay: None,
+        }];
+        let rules = match self.rules.is_empty() {
+            true => &allow_all[..],
+            false => &self.rules[..],
+        };
+
+        for (i, rule) in rules.iter().enumerate() {
+            if i > 0 {
+                robots.push('\n');
+            }
+            robots.push_str(&format!("User-agent: {}\n", rule.user_agent));
+            for path in &rule.allow {
+                robots.push_str(&format!("Allow: {path}\n"));
+            }
+            for path in &rule.disallow {
+                robots.push_str(&format!("Disallow: {path}\n"));
+            }
+            if let Some(delay) = rule.crawl_delay {
+                robots.push_str(&format!("Crawl-delay: {delay}\n"));
+            }
+        }
+
+        if !self.sitemaps.is_empty() {
+            robots.push('\n');
+        }
+        for sitemap in &self.sitemaps {
+            robots.push_str(&format!("Sitemap: {sitemap}\n"));
+        }
+
+        robots
+    }
+}
+
+fn default_user_agent() -> String {
+    "*".to_string()
+}
+
 fn true_bool() -> bool {
     true
 }
@@ -184,3 +301,48 @@ fn false_bool() -> bool {
 pub(crate) fn default_title() -> String {
     "dioxus | ⛺".into()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn robots_txt_allows_everything_by_default() {
+        let robots = WebRobotsConfig::default();
+        assert_eq!(robots.to_robots_txt(), "User-agent: *\nDisallow: \n");
+    }
+
+    #[test]
+    fn robots_txt_rules_and_sitemaps() {
+        let robots: WebRobotsConfig = toml::from_str(
+            r#"
+            sitemaps = ["https://example.com/sitemap.xml"]
+
+            [[rules]]
+            allow = ["/"]
+            disallow = ["/admin", "/private"]
+
+            [[rules]]
+            user_agent = "Googlebot"
+            disallow = ["/drafts"]
+            crawl_delay = 10
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            robots.to_robots_txt(),
+            "User-agent: *
+Allow: /
+Disallow: /admin
+Disallow: /private
+
+User-agent: Googlebot
+Disallow: /drafts
+Crawl-delay: 10
+
+Sitemap: https://example.com/sitemap.xml
+"
+        );
+    }
+}
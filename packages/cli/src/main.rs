@@ -19,6 +19,7 @@ mod rustup;
 mod serve;
 mod settings;
 mod wasm_bindgen;
+mod wasm_split;
 
 pub(crate) use build::*;
 pub(crate) use cli::*;
@@ -1,4 +1,5 @@
 use crate::{BuildUpdate, Builder, Error, Platform, Result, ServeArgs, TraceController, TraceSrc};
+use dioxus_devtools_types::InspectorQuery;
 
 mod ansi_buffer;
 mod detect;
@@ -60,6 +61,7 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                 • Press `r` to rebuild the app
                 • Press `o` to open the app
                 • Press `v` to toggle verbose logging
+                • Press `i` to inspect the component tree
                 • Press `/` for more commands and shortcuts
                 Learn more at https://dioxuslabs.com/learn/0.6/getting_started
                ----------------------------------------------------------------"#,
@@ -139,8 +141,8 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                 runner.client_connected().await;
             }
 
-            // Received a message from the devtools server - currently we only use this for
-            // logging, so we just forward it the tui
+            // Received a message from the devtools server - currently we use this for logging and
+            // inspector responses, so we just forward it the tui
             ServeUpdate::WsMessage(msg) => {
                 screen.push_ws_message(Platform::Web, msg);
             }
@@ -238,6 +240,17 @@ pub(crate) async fn serve_all(mut args: ServeArgs) -> Result<()> {
                 }
             }
 
+            ServeUpdate::Inspect { scope } => {
+                devserver
+                    .send_inspector_query(InspectorQuery::ScopeTree)
+                    .await;
+                if let Some(id) = scope {
+                    devserver
+                        .send_inspector_query(InspectorQuery::Scope { id })
+                        .await;
+                }
+            }
+
             ServeUpdate::Redraw => {
                 // simply returning will cause a redraw
             }
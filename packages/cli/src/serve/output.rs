@@ -11,6 +11,7 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
     ExecutableCommand,
 };
+use dioxus_devtools_types::{InspectorResponse, ScopeDetails, ScopeSummary};
 use ratatui::{
     prelude::*,
     widgets::{Block, BorderType, Borders, LineGauge, Paragraph, Wrap},
@@ -29,6 +30,8 @@ const TICK_RATE_MS: u64 = 100;
 const VIEWPORT_MAX_WIDTH: u16 = 100;
 const VIEWPORT_HEIGHT_SMALL: u16 = 5;
 const VIEWPORT_HEIGHT_BIG: u16 = 12;
+const VIEWPORT_HEIGHT_INSPECTOR: u16 = 24;
+const INSPECTOR_REFRESH_MS: u64 = 1000;
 
 /// The TUI that drives the console output.
 ///
@@ -44,6 +47,10 @@ pub struct Output {
 
     // A list of all messages from build, dev, app, and more.
     more_modal_open: bool,
+
+    // The component tree of the running app, if the inspector is open
+    inspector: Option<InspectorState>,
+    inspector_interval: tokio::time::Interval,
     interactive: bool,
     platform: Platform,
 
@@ -65,6 +72,20 @@ pub struct Output {
     throbber: RefCell<throbber_widgets_tui::ThrobberState>,
 }
 
+/// The last state of the running app we received from the devtools, and the scope the user selected
+#[derive(Default)]
+struct InspectorState {
+    scopes: Vec<ScopeSummary>,
+    selected: usize,
+    details: Option<ScopeDetails>,
+}
+
+impl InspectorState {
+    fn selected_scope(&self) -> Option<usize> {
+        self.scopes.get(self.selected).map(|scope| scope.id)
+    }
+}
+
 #[allow(unused)]
 #[derive(Clone, Copy)]
 struct RenderState<'a> {
@@ -97,6 +118,8 @@ impl Output {
             events: None,
             // messages: Vec::new(),
             more_modal_open: false,
+            inspector: None,
+            inspector_interval: tokio::time::interval(Duration::from_millis(INSPECTOR_REFRESH_MS)),
             pending_logs: VecDeque::new(),
             throbber: RefCell::new(throbber_widgets_tui::ThrobberState::default()),
             trace: crate::logging::VERBOSITY.get().unwrap().trace,
@@ -173,6 +196,10 @@ impl Output {
                     self.throbber.borrow_mut().calc_next();
                     return ServeUpdate::Redraw
                 },
+                _ = self.inspector_interval.tick(), if self.inspector.is_some() => {
+                    let scope = self.inspector.as_ref().and_then(InspectorState::selected_scope);
+                    return ServeUpdate::Inspect { scope }
+                },
                 else => futures_util::future::pending().await
             };
 
@@ -233,23 +260,41 @@ impl Output {
             // This is a bit of a hack since crossterm doesn't technically support changing the
             // size of an inline viewport.
             KeyCode::Char('/') => {
-                if let Some(terminal) = self.term.borrow_mut().as_mut() {
-                    // Toggle the more modal, which will change our current viewport height
-                    self.more_modal_open = !self.more_modal_open;
-
-                    // Clear the terminal before resizing it, such that it doesn't tear
-                    terminal.clear()?;
-
-                    // And then set the new viewport, which essentially mimics a resize
-                    *terminal = Terminal::with_options(
-                        CrosstermBackend::new(stdout()),
-                        TerminalOptions {
-                            viewport: Viewport::Inline(self.viewport_current_height()),
-                        },
-                    )?;
+                // Toggle the more modal, which will change our current viewport height
+                self.more_modal_open = !self.more_modal_open;
+                self.resize_viewport()?;
+            }
+
+            // Toggle the inspector, which takes over the space of the more modal
+            KeyCode::Char('i') => {
+                self.inspector = match self.inspector {
+                    Some(_) => None,
+                    None => Some(InspectorState::default()),
+                };
+                self.resize_viewport()?;
+
+                if self.inspector.is_some() {
+                    return Ok(Some(ServeUpdate::Inspect { scope: None }));
                 }
             }
 
+            // Move the selected scope in the inspector
+            KeyCode::Up | KeyCode::Char('k') | KeyCode::Down | KeyCode::Char('j')
+                if self.inspector.is_some() =>
+            {
+                let inspector = self.inspector.as_mut().unwrap();
+                let last = inspector.scopes.len().saturating_sub(1);
+                inspector.selected = match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => inspector.selected.saturating_sub(1),
+                    _ => (inspector.selected + 1).min(last),
+                };
+                inspector.details = None;
+
+                return Ok(Some(ServeUpdate::Inspect {
+                    scope: inspector.selected_scope(),
+                }));
+            }
+
             _ => {}
         }
 
@@ -257,6 +302,27 @@ impl Output {
         Ok(Some(ServeUpdate::Redraw))
     }
 
+    /// Swap the terminal with a new one with the current viewport height.
+    ///
+    /// This is a bit of a hack since crossterm doesn't technically support changing the
+    /// size of an inline viewport.
+    fn resize_viewport(&mut self) -> io::Result<()> {
+        if let Some(terminal) = self.term.borrow_mut().as_mut() {
+            // Clear the terminal before resizing it, such that it doesn't tear
+            terminal.clear()?;
+
+            // And then set the new viewport, which essentially mimics a resize
+            *terminal = Terminal::with_options(
+                CrosstermBackend::new(stdout()),
+                TerminalOptions {
+                    viewport: Viewport::Inline(self.viewport_current_height()),
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Push a TraceMsg to be printed on the next render
     pub fn push_log(&mut self, message: TraceMsg) {
         self.pending_logs.push_front(message);
@@ -290,8 +356,12 @@ impl Output {
         let res = serde_json::from_str::<ClientMsg>(text.as_str());
 
         // Client logs being errors aren't fatal, but we should still report them them
-        let ClientMsg::Log { level, messages } = match res {
-            Ok(msg) => msg,
+        let (level, messages) = match res {
+            Ok(ClientMsg::Log { level, messages }) => (level, messages),
+            Ok(ClientMsg::Inspector(response)) => {
+                self.push_inspector_response(response);
+                return;
+            }
             Err(err) => {
                 tracing::error!(dx_src = ?TraceSrc::Dev, "Error parsing message from {}: {}", platform, err);
                 return;
@@ -314,6 +384,31 @@ impl Output {
         self.push_log(TraceMsg::text(TraceSrc::App(platform), level, content));
     }
 
+    /// Update the inspector with the component tree or scope details the app sent back
+    fn push_inspector_response(&mut self, response: InspectorResponse) {
+        // The app might answer after the inspector was closed
+        let Some(inspector) = self.inspector.as_mut() else {
+            return;
+        };
+
+        match response {
+            InspectorResponse::ScopeTree(scopes) => {
+                // Keep the same scope selected if it still exists
+                let selected = inspector.selected_scope();
+                inspector.selected = scopes
+                    .iter()
+                    .position(|scope| Some(scope.id) == selected)
+                    .unwrap_or(inspector.selected.min(scopes.len().saturating_sub(1)));
+                inspector.scopes = scopes;
+            }
+            InspectorResponse::Scope { id, details } => {
+                if inspector.selected_scope() == Some(id) {
+                    inspector.details = details;
+                }
+            }
+        }
+    }
+
     /// Change internal state based on the build engine's update
     ///
     /// We want to keep internal state as limited as possible, so currently we're only setting our
@@ -392,6 +487,11 @@ impl Output {
     fn render_body_title(&self, frame: &mut Frame<'_>, area: Rect, _state: RenderState) {
         frame.render_widget(
             Line::from(vec![
+                " ".dark_gray(),
+                match self.inspector.is_some() {
+                    true => "i:inspect".light_yellow(),
+                    false => "i:inspect".dark_gray(),
+                },
                 " ".dark_gray(),
                 match self.more_modal_open {
                     true => "/:more".light_yellow(),
@@ -421,7 +521,9 @@ impl Output {
         self.render_gauges(frame, col1, state);
         self.render_stats(frame, col2, state);
 
-        if self.more_modal_open {
+        if let Some(inspector) = &self.inspector {
+            self.render_inspector(frame, more, inspector);
+        } else if self.more_modal_open {
             self.render_more_modal(frame, more, state);
         }
     }
@@ -708,6 +810,94 @@ impl Output {
         );
     }
 
+    /// Render the component tree of the running app next to the props and hooks of the selected scope
+    fn render_inspector(&self, frame: &mut Frame<'_>, area: Rect, inspector: &InspectorState) {
+        let [_spacing, body] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)])
+            .horizontal_margin(1)
+            .areas(area);
+        let [tree, details_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Fill(1)]).areas(body);
+
+        if inspector.scopes.is_empty() {
+            frame.render_widget(
+                Paragraph::new("Waiting for the app to send its component tree...".dark_gray()),
+                tree,
+            );
+            return;
+        }
+
+        // Scroll the tree so the selected scope is always visible
+        let visible = tree.height as usize;
+        let skip = inspector.selected.saturating_sub(visible.saturating_sub(1));
+        let min_height = inspector.scopes.iter().map(|s| s.height).min().unwrap_or(0);
+        let lines: Vec<Line> = inspector
+            .scopes
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(visible)
+            .map(|(idx, scope)| {
+                let indent = "  ".repeat((scope.height - min_height) as usize);
+                let name = match idx == inspector.selected {
+                    true => scope.name.as_str().black().on_light_yellow(),
+                    false => scope.name.as_str().white(),
+                };
+                Line::from(vec![
+                    indent.into(),
+                    name,
+                    format!(" ×{}", scope.render_count).dark_gray(),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), tree);
+
+        let Some(details) = &inspector.details else {
+            return;
+        };
+
+        let mut lines = vec![Line::from(vec![
+            details.scope.name.as_str().light_yellow(),
+            format!(
+                " (scope {}, rendered {} times)",
+                details.scope.id, details.scope.render_count
+            )
+            .dark_gray(),
+        ])];
+
+        if !details.props.is_empty() {
+            lines.push("Props".gray().into());
+            for (name, value) in &details.props {
+                lines.push(Line::from(vec![
+                    format!("  {name}: ").white(),
+                    value.as_str().yellow(),
+                ]));
+            }
+        }
+
+        if !details.hooks.is_empty() {
+            lines.push("Hooks".gray().into());
+            for hook in &details.hooks {
+                let mut line = vec![
+                    format!("  {}: ", hook.index).dark_gray(),
+                    short_type_name(&hook.type_name).white(),
+                ];
+                if let Some(value) = &hook.value {
+                    line.push(" = ".dark_gray());
+                    line.push(value.as_str().yellow());
+                }
+                if !hook.subscribers.is_empty() {
+                    line.push(format!(" → {:?}", hook.subscribers).dark_gray());
+                }
+                lines.push(Line::from(line));
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }),
+            details_area,
+        );
+    }
+
     /// Render borders around the terminal, forcing an inner clear while we're at it
     fn render_borders(&self, frame: &mut Frame, area: Rect) {
         frame.render_widget(ratatui::widgets::Clear, area);
@@ -836,6 +1026,10 @@ impl Output {
     }
 
     fn viewport_current_height(&self) -> u16 {
+        if self.inspector.is_some() {
+            return VIEWPORT_HEIGHT_INSPECTOR;
+        }
+
         match self.more_modal_open {
             true => VIEWPORT_HEIGHT_BIG,
             false => VIEWPORT_HEIGHT_SMALL,
@@ -911,3 +1105,23 @@ impl Output {
         lines
     }
 }
+
+/// Strip the module paths from a type name like `dioxus_signals::signal::Signal<alloc::string::String>`
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    let mut segment_start = 0;
+    for (idx, c) in name.char_indices() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            continue;
+        }
+        short.push_str(last_segment(&name[segment_start..idx]));
+        short.push(c);
+        segment_start = idx + c.len_utf8();
+    }
+    short.push_str(last_segment(&name[segment_start..]));
+    short
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
@@ -19,7 +19,7 @@ use axum::{
     routing::{get, get_service},
     Extension, Router,
 };
-use dioxus_devtools_types::{DevserverMsg, HotReloadMsg};
+use dioxus_devtools_types::{DevserverMsg, HotReloadMsg, InspectorQuery};
 use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures_util::{
     future,
@@ -304,6 +304,12 @@ impl WebServer {
         self.send_devserver_message(DevserverMsg::Shutdown).await;
     }
 
+    /// Ask all connected clients to answer an inspector query about their component tree
+    pub(crate) async fn send_inspector_query(&mut self, query: InspectorQuery) {
+        self.send_devserver_message(DevserverMsg::Inspect(query))
+            .await;
+    }
+
     /// Sends a devserver message to all connected clients.
     async fn send_devserver_message(&mut self, msg: DevserverMsg) {
         for socket in self.hot_reload_sockets.iter_mut() {
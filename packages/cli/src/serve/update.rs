@@ -47,6 +47,11 @@ pub(crate) enum ServeUpdate {
 
     ToggleShouldRebuild,
 
+    /// Ask the running app for its component tree and the details of the selected scope, if any
+    Inspect {
+        scope: Option<usize>,
+    },
+
     Redraw,
 
     TracingLog {
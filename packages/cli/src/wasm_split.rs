@@ -0,0 +1,669 @@
+//! Split the components of lazy routes out of the main wasm module.
+//!
+//! The router exports a function named `__dx_lazy_<chunk>` for every `#[lazy]` route when the `wasm-split` feature is
+//! enabled. Each of those functions becomes the entry of a chunk: a separate wasm module that contains the entry and
+//! every function that only the entry uses. Functions that the main module or several chunks use stay in the main
+//! module.
+//!
+//! Chunks share the memory, tables and globals of the main module and import the functions they call from it. When a
+//! chunk is instantiated, it writes its entry into a slot of the function table that the main module reserved for it,
+//! so the router can call the entry through the table.
+
+use anyhow::{bail, Context};
+use std::collections::{HashMap, HashSet};
+use walrus::{
+    ir::{dfs_in_order, dfs_pre_order_mut, Value, Visitor, VisitorMut},
+    ConstExpr, ElementItems, ElementKind, ExportItem, FunctionId, FunctionKind, GlobalKind, Module,
+};
+
+/// The prefix of the exports the router adds for lazy routes
+const ENTRY_PREFIX: &str = "__dx_lazy_";
+
+/// The name chunks import the items of the main module from
+const MAIN_MODULE: &str = "main";
+
+/// The main module with the lazy routes moved out
+pub(crate) struct SplitWasm {
+    pub(crate) main: Vec<u8>,
+    pub(crate) chunks: Vec<Chunk>,
+}
+
+/// The module of a lazy route
+pub(crate) struct Chunk {
+    pub(crate) name: String,
+    /// The slot of the function table the chunk writes its entry into
+    pub(crate) slot: u32,
+    pub(crate) wasm: Vec<u8>,
+}
+
+impl Chunk {
+    /// The javascript module that instantiates the chunk with the exports of the main module
+    pub(crate) fn js(&self) -> String {
+        format!(
+            r#"export default async function init(main) {{
+    const response = await fetch(new URL("./{name}_bg.wasm", import.meta.url));
+    await WebAssembly.instantiate(await response.arrayBuffer(), {{ {MAIN_MODULE}: main }});
+    return {slot};
+}}
+"#,
+            name = self.name,
+            slot = self.slot,
+        )
+    }
+}
+
+/// Split the lazy routes out of a module that was processed by wasm-bindgen.
+///
+/// Returns `None` if the module doesn't have any lazy routes.
+pub(crate) fn split_wasm(bytes: &[u8]) -> anyhow::Result<Option<SplitWasm>> {
+    let module = Module::from_buffer(bytes).context("Failed to parse the wasm module")?;
+    let funcs = FunctionIndices::new(&module);
+
+    let entries = module
+        .exports
+        .iter()
+        .filter_map(|export| match export.item {
+            ExportItem::Function(func) => Some((
+                export.name.strip_prefix(ENTRY_PREFIX)?.to_string(),
+                funcs.index(func),
+            )),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return Ok(None);
+    }
+
+    let graph = call_graph(&module, &funcs);
+
+    // Everything the main module uses directly or through the function table stays in the main module. Imports can't
+    // move either
+    let mut roots = module
+        .exports
+        .iter()
+        .filter(|export| !export.name.starts_with(ENTRY_PREFIX))
+        .filter_map(|export| match export.item {
+            ExportItem::Function(func) => Some(funcs.index(func)),
+            _ => None,
+        })
+        .chain(module.start.map(|func| funcs.index(func)))
+        .chain(
+            module
+                .elements
+                .iter()
+                .flat_map(|element| match &element.items {
+                    ElementItems::Functions(items) => {
+                        items.iter().map(|func| funcs.index(*func)).collect()
+                    }
+                    ElementItems::Expressions(_, items) => const_funcs(items.iter(), &funcs),
+                }),
+        )
+        .chain(const_funcs(
+            module
+                .globals
+                .iter()
+                .filter_map(|global| match &global.kind {
+                    GlobalKind::Local(init) => Some(init),
+                    GlobalKind::Import(_) => None,
+                }),
+            &funcs,
+        ))
+        .chain(
+            module
+                .funcs
+                .iter()
+                .filter(|func| matches!(func.kind, FunctionKind::Import(_)))
+                .map(|func| funcs.index(func.id())),
+        )
+        .collect::<HashSet<_>>();
+
+    // Functions that several chunks use also stay in the main module. Keeping them can make other functions shared, so
+    // repeat until every function belongs to at most one chunk
+    let (kept, moved) = loop {
+        let kept = reachable(&graph, roots.iter().copied(), &HashSet::new());
+        let moved = entries
+            .iter()
+            .map(|(_, entry)| reachable(&graph, [*entry], &kept))
+            .collect::<Vec<_>>();
+
+        let mut uses = HashMap::<usize, usize>::new();
+        for func in moved.iter().flatten() {
+            *uses.entry(*func).or_default() += 1;
+        }
+        let shared = uses
+            .into_iter()
+            .filter(|(_, uses)| *uses > 1)
+            .map(|(func, _)| func)
+            .collect::<Vec<_>>();
+        if shared.is_empty() {
+            break (kept, moved);
+        }
+        roots.extend(shared);
+    };
+
+    for (name, entry) in &entries {
+        if kept.contains(entry) {
+            bail!("The entry of the lazy route `{name}` is used by the main module");
+        }
+    }
+
+    // The functions of the main module that the chunks call
+    let imported_funcs = moved
+        .iter()
+        .flat_map(|moved| {
+            moved
+                .iter()
+                .flat_map(|func| &graph[*func])
+                .filter(|callee| !moved.contains(callee))
+        })
+        .copied()
+        .collect::<HashSet<_>>();
+
+    let mut main = Module::from_buffer(bytes)?;
+    let table = main
+        .tables
+        .main_function_table()?
+        .context("The wasm module doesn't have a function table")?;
+    let first_slot = grow_table(&mut main, table, entries.len() as u64);
+    export_shared_items(&mut main, &imported_funcs);
+    for (name, _) in &entries {
+        main.exports.remove(format!("{ENTRY_PREFIX}{name}"))?;
+    }
+    walrus::passes::gc::run(&mut main);
+
+    let chunks = entries
+        .iter()
+        .zip(&moved)
+        .enumerate()
+        .map(|(index, ((name, entry), moved))| {
+            let slot = first_slot + index as u64;
+            let wasm = chunk_module(bytes, *entry, moved, slot)
+                .with_context(|| format!("Failed to split the lazy route `{name}`"))?;
+            Ok(Chunk {
+                name: name.clone(),
+                slot: slot as u32,
+                wasm,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(Some(SplitWasm {
+        main: main.emit_wasm(),
+        chunks,
+    }))
+}
+
+/// Build the module of a chunk by removing everything except the moved functions and importing the rest from the
+/// main module
+fn chunk_module(
+    bytes: &[u8],
+    entry: usize,
+    moved: &HashSet<usize>,
+    slot: u64,
+) -> anyhow::Result<Vec<u8>> {
+    let mut module = Module::from_buffer(bytes)?;
+    let funcs = FunctionIndices::new(&module);
+    let table = module
+        .tables
+        .main_function_table()?
+        .context("The wasm module doesn't have a function table")?;
+
+    // The data and the table elements are already in the main module
+    let data = module.data.iter().map(|data| data.id()).collect::<Vec<_>>();
+    for data in data {
+        module.data.delete(data);
+    }
+    let elements = module
+        .elements
+        .iter()
+        .map(|element| element.id())
+        .collect::<Vec<_>>();
+    for element in elements {
+        module.elements.delete(element);
+    }
+
+    // Import the memories, tables and globals of the main module
+    let memories = module
+        .memories
+        .iter()
+        .map(|memory| memory.id())
+        .collect::<Vec<_>>();
+    for (index, memory) in memories.into_iter().enumerate() {
+        let import = module
+            .imports
+            .add(MAIN_MODULE, &format!("__dx_memory_{index}"), memory);
+        let memory = module.memories.get_mut(memory);
+        memory.data_segments.clear();
+        if let Some(old) = memory.import.replace(import) {
+            module.imports.delete(old);
+        }
+    }
+    let tables = module
+        .tables
+        .iter()
+        .map(|table| table.id())
+        .collect::<Vec<_>>();
+    for (index, id) in tables.into_iter().enumerate() {
+        let import = module
+            .imports
+            .add(MAIN_MODULE, &format!("__dx_table_{index}"), id);
+        let table = module.tables.get_mut(id);
+        table.elem_segments.clear();
+        if let Some(old) = table.import.replace(import) {
+            module.imports.delete(old);
+        }
+    }
+    let globals = module
+        .globals
+        .iter()
+        .map(|global| global.id())
+        .collect::<Vec<_>>();
+    for (index, global) in globals.into_iter().enumerate() {
+        let import = module
+            .imports
+            .add(MAIN_MODULE, &format!("__dx_global_{index}"), global);
+        let kind = std::mem::replace(
+            &mut module.globals.get_mut(global).kind,
+            GlobalKind::Import(import),
+        );
+        if let GlobalKind::Import(old) = kind {
+            module.imports.delete(old);
+        }
+    }
+    // The table of the main module already grew to fit the entries of every chunk. An import without a maximum accepts
+    // the table whatever its maximum is
+    let main_table = module.tables.get_mut(table);
+    main_table.initial = main_table.initial.max(slot + 1);
+    main_table.maximum = None;
+
+    // Call the functions that stay in the main module through imports
+    let mut imports = HashMap::new();
+    for func in moved {
+        let FunctionKind::Local(local) = &module.funcs.get(funcs.id(*func)).kind else {
+            continue;
+        };
+        let mut callees = Callees::default();
+        dfs_in_order(&mut callees, local, local.entry_block());
+        for callee in callees.0 {
+            let index = funcs.index(callee);
+            if moved.contains(&index) || imports.contains_key(&callee) {
+                continue;
+            }
+            let ty = module.funcs.get(callee).ty();
+            let (import, _) =
+                module.add_import_func(MAIN_MODULE, &format!("__dx_func_{index}"), ty);
+            imports.insert(callee, import);
+        }
+    }
+    let mut redirect = Redirect(imports);
+    for func in moved {
+        if let FunctionKind::Local(local) = &mut module.funcs.get_mut(funcs.id(*func)).kind {
+            let entry_block = local.entry_block();
+            dfs_pre_order_mut(&mut redirect, local, entry_block);
+        }
+    }
+
+    // Place the entry in its slot when the chunk is instantiated. Nothing else is a root, so everything that isn't
+    // moved is removed
+    let exports = module
+        .exports
+        .iter()
+        .map(|export| export.id())
+        .collect::<Vec<_>>();
+    for export in exports {
+        module.exports.delete(export);
+    }
+    module.start = None;
+    let element = module.elements.add(
+        ElementKind::Active {
+            table,
+            offset: ConstExpr::Value(Value::I32(slot as i32)),
+        },
+        ElementItems::Functions(vec![funcs.id(entry)]),
+    );
+    module.tables.get_mut(table).elem_segments.insert(element);
+
+    walrus::passes::gc::run(&mut module);
+
+    Ok(module.emit_wasm())
+}
+
+/// Grow the function table to reserve a slot for the entry of every chunk. Returns the first reserved slot
+fn grow_table(module: &mut Module, table: walrus::TableId, slots: u64) -> u64 {
+    let table = module.tables.get_mut(table);
+    let first_slot = table.initial;
+    table.initial += slots;
+    table.maximum = table.maximum.map(|maximum| maximum.max(table.initial));
+    first_slot
+}
+
+/// Export everything the chunks import from the main module
+fn export_shared_items(module: &mut Module, funcs: &HashSet<usize>) {
+    let memories = module
+        .memories
+        .iter()
+        .map(|memory| memory.id())
+        .collect::<Vec<_>>();
+    for (index, memory) in memories.into_iter().enumerate() {
+        module.exports.add(&format!("__dx_memory_{index}"), memory);
+    }
+    let tables = module
+        .tables
+        .iter()
+        .map(|table| table.id())
+        .collect::<Vec<_>>();
+    for (index, table) in tables.into_iter().enumerate() {
+        module.exports.add(&format!("__dx_table_{index}"), table);
+    }
+    let globals = module
+        .globals
+        .iter()
+        .map(|global| global.id())
+        .collect::<Vec<_>>();
+    for (index, global) in globals.into_iter().enumerate() {
+        module.exports.add(&format!("__dx_global_{index}"), global);
+    }
+    let ids = FunctionIndices::new(module);
+    for func in funcs {
+        module
+            .exports
+            .add(&format!("__dx_func_{func}"), ids.id(*func));
+    }
+}
+
+/// The functions each function references, by index
+fn call_graph(module: &Module, funcs: &FunctionIndices) -> Vec<Vec<usize>> {
+    module
+        .funcs
+        .iter()
+        .map(|func| match &func.kind {
+            FunctionKind::Local(local) => {
+                let mut callees = Callees::default();
+                dfs_in_order(&mut callees, local, local.entry_block());
+                callees
+                    .0
+                    .into_iter()
+                    .map(|callee| funcs.index(callee))
+                    .collect()
+            }
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+/// Every function reachable from the starting functions without passing through a stopping function
+fn reachable(
+    graph: &[Vec<usize>],
+    start: impl IntoIterator<Item = usize>,
+    stop: &HashSet<usize>,
+) -> HashSet<usize> {
+    let mut seen = HashSet::new();
+    let mut stack = start.into_iter().collect::<Vec<_>>();
+    while let Some(func) = stack.pop() {
+        if stop.contains(&func) || !seen.insert(func) {
+            continue;
+        }
+        stack.extend(graph[func].iter().copied());
+    }
+    seen
+}
+
+/// The functions referenced by constant expressions
+fn const_funcs<'a>(
+    exprs: impl Iterator<Item = &'a ConstExpr>,
+    funcs: &FunctionIndices,
+) -> Vec<usize> {
+    exprs
+        .filter_map(|expr| match expr {
+            ConstExpr::RefFunc(func) => Some(funcs.index(*func)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Maps function ids to their index in the module they were parsed from. The indices are the same every time the same
+/// bytes are parsed, so they identify a function across the main module and the chunks
+struct FunctionIndices {
+    ids: Vec<FunctionId>,
+    indices: HashMap<FunctionId, usize>,
+}
+
+impl FunctionIndices {
+    fn new(module: &Module) -> Self {
+        let ids = module
+            .funcs
+            .iter()
+            .map(|func| func.id())
+            .collect::<Vec<_>>();
+        let indices = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect();
+        Self { ids, indices }
+    }
+
+    fn index(&self, id: FunctionId) -> usize {
+        self.indices[&id]
+    }
+
+    fn id(&self, index: usize) -> FunctionId {
+        self.ids[index]
+    }
+}
+
+/// Collects the functions an instruction sequence calls or references
+#[derive(Default)]
+struct Callees(Vec<FunctionId>);
+
+impl Visitor<'_> for Callees {
+    fn visit_function_id(&mut self, function: &FunctionId) {
+        self.0.push(*function);
+    }
+}
+
+/// Replaces references to functions of the main module with their imports
+struct Redirect(HashMap<FunctionId, FunctionId>);
+
+impl VisitorMut for Redirect {
+    fn visit_function_id_mut(&mut self, function: &mut FunctionId) {
+        if let Some(import) = self.0.get(function) {
+            *function = *import;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use walrus::{FunctionBuilder, ImportKind, ModuleConfig, RefType, ValType};
+
+    /// A module with an exported `main` function and the entries of two lazy routes, `a` and `b`
+    fn app_module() -> Vec<u8> {
+        let mut module = Module::with_config(ModuleConfig::new());
+        let memory = module.memories.add_local(false, false, 1, None, None);
+        module.exports.add("memory", memory);
+        let stack_pointer = module.globals.add_local(
+            ValType::I32,
+            true,
+            false,
+            ConstExpr::Value(Value::I32(1024)),
+        );
+        let table = module.tables.add_local(false, 1, None, RefType::Funcref);
+        let ty = module.types.add(&[], &[]);
+        let (log, _) = module.add_import_func("wbg", "log", ty);
+
+        let mut func = |name: &str, body: &dyn Fn(&mut walrus::InstrSeqBuilder)| {
+            let mut builder = FunctionBuilder::new(&mut module.types, &[], &[]);
+            builder.name(name.to_string());
+            body(&mut builder.func_body());
+            builder.finish(vec![], &mut module.funcs)
+        };
+        let shared = func("shared", &|body| {
+            body.call(log);
+        });
+        let indirect = func("indirect", &|_| {});
+        let common = func("common", &|body| {
+            body.call(shared);
+        });
+        let only_a = func("only_a", &|body| {
+            body.global_get(stack_pointer).drop().call(log);
+        });
+        let only_b = func("only_b", &|_| {});
+        let main = func("main", &|body| {
+            body.call(shared);
+        });
+        let entry_a = func("entry_a", &|body| {
+            body.call(only_a).call(common);
+        });
+        let entry_b = func("entry_b", &|body| {
+            body.call(only_b).call(common);
+        });
+
+        let element = module.elements.add(
+            ElementKind::Active {
+                table,
+                offset: ConstExpr::Value(Value::I32(0)),
+            },
+            ElementItems::Functions(vec![indirect]),
+        );
+        module.tables.get_mut(table).elem_segments.insert(element);
+        module.exports.add("main", main);
+        module.exports.add("__dx_lazy_a", entry_a);
+        module.exports.add("__dx_lazy_b", entry_b);
+        module.emit_wasm()
+    }
+
+    fn func_names(module: &Module) -> Vec<&str> {
+        let mut names = module
+            .funcs
+            .iter()
+            .filter(|func| matches!(func.kind, FunctionKind::Local(_)))
+            .filter_map(|func| func.name.as_deref())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn moves_functions_only_a_lazy_route_uses() {
+        let split = split_wasm(&app_module()).unwrap().unwrap();
+
+        let main = Module::from_buffer(&split.main).unwrap();
+        assert_eq!(func_names(&main), ["common", "indirect", "main", "shared"]);
+        assert!(main.exports.get_func("__dx_lazy_a").is_err());
+        let table = main.tables.main_function_table().unwrap().unwrap();
+        assert_eq!(main.tables.get(table).initial, 3);
+
+        let names = split
+            .chunks
+            .iter()
+            .map(|chunk| (chunk.name.as_str(), chunk.slot))
+            .collect::<Vec<_>>();
+        assert_eq!(names, [("a", 1), ("b", 2)]);
+
+        // The chunk imports what it uses from the main module, and every import is exported by the main module
+        let chunk = Module::from_buffer(&split.chunks[0].wasm).unwrap();
+        assert_eq!(func_names(&chunk), ["entry_a", "only_a"]);
+        for import in chunk.imports.iter() {
+            assert_eq!(import.module, MAIN_MODULE);
+            assert!(main.exports.iter().any(|export| export.name == import.name));
+        }
+        let mut imports = chunk
+            .imports
+            .iter()
+            .map(|import| match import.kind {
+                ImportKind::Function(_) => {
+                    let func = main.exports.get_func(&import.name).unwrap();
+                    match main.imports.get_imported_func(func) {
+                        Some(import) => import.name.as_str(),
+                        None => main.funcs.get(func).name.as_deref().unwrap(),
+                    }
+                }
+                _ => import.name.as_str(),
+            })
+            .collect::<Vec<_>>();
+        imports.sort();
+        assert_eq!(imports, ["__dx_global_0", "__dx_table_0", "common", "log"]);
+
+        let chunk = Module::from_buffer(&split.chunks[1].wasm).unwrap();
+        assert_eq!(func_names(&chunk), ["entry_b", "only_b"]);
+    }
+
+    /// Split a module rustc built from `tests/fixtures/lazy_routes.rs`
+    #[test]
+    fn splits_a_module_built_by_rustc() {
+        let bytes = include_bytes!("../tests/fixtures/lazy_routes.wasm");
+        let split = split_wasm(bytes).unwrap().unwrap();
+
+        let main = Module::from_buffer(&split.main).unwrap();
+        let main_names = func_names(&main);
+        assert!(main_names.iter().any(|name| name.contains("render_count")));
+        assert!(main_names.iter().any(|name| name.contains("layout")));
+        assert!(!main_names.iter().any(|name| name.contains("settings_form")));
+        assert!(!main_names.iter().any(|name| name.contains("about_text")));
+        assert!(main.exports.get_func("main_app").is_ok());
+
+        let names = split
+            .chunks
+            .iter()
+            .map(|chunk| chunk.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Route_About", "Route_Settings"]);
+
+        for (chunk, only) in split.chunks.iter().zip(["about_text", "settings_form"]) {
+            // Parsing validates the chunk
+            let module = Module::from_buffer(&chunk.wasm).unwrap();
+            let chunk_names = func_names(&module);
+            assert!(chunk_names.iter().any(|name| name.contains(only)));
+            assert!(!chunk_names.iter().any(|name| name.contains("layout")));
+
+            // Everything the chunk imports is exported by the main module with the same type
+            for import in module.imports.iter() {
+                assert_eq!(import.module, MAIN_MODULE);
+                let export = main
+                    .exports
+                    .iter()
+                    .find(|export| export.name == import.name)
+                    .unwrap_or_else(|| panic!("{} is not exported", import.name));
+                if let (ImportKind::Function(func), ExportItem::Function(exported)) =
+                    (&import.kind, &export.item)
+                {
+                    let ty = module.types.get(module.funcs.get(*func).ty());
+                    let exported = main.types.get(main.funcs.get(*exported).ty());
+                    assert_eq!(ty.params(), exported.params());
+                    assert_eq!(ty.results(), exported.results());
+                }
+            }
+
+            // The chunk places its entry in the slot reserved for it, and accepts the grown table of the main module
+            let table = module.tables.main_function_table().unwrap().unwrap();
+            let main_table = main.tables.main_function_table().unwrap().unwrap();
+            assert!(module.tables.get(table).initial <= main.tables.get(main_table).initial);
+            assert_eq!(module.tables.get(table).maximum, None);
+            let element = module
+                .tables
+                .get(table)
+                .elem_segments
+                .iter()
+                .next()
+                .unwrap();
+            let ElementKind::Active {
+                offset: ConstExpr::Value(Value::I32(offset)),
+                ..
+            } = module.elements.get(*element).kind
+            else {
+                panic!("the entry is not placed in the table");
+            };
+            assert_eq!(offset, chunk.slot as i32);
+        }
+    }
+
+    #[test]
+    fn modules_without_lazy_routes_are_not_split() {
+        let mut module = Module::from_buffer(&app_module()).unwrap();
+        module.exports.remove("__dx_lazy_a").unwrap();
+        module.exports.remove("__dx_lazy_b").unwrap();
+        assert!(split_wasm(&module.emit_wasm()).unwrap().is_none());
+    }
+}
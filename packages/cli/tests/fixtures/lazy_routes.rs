@@ -0,0 +1,75 @@
+//! A small app with two lazy routes that the wasm split tests split. Rebuild `lazy_routes.wasm` after changing it with:
+//!
+//! ```sh
+//! rustc lazy_routes.rs --target wasm32-unknown-unknown --crate-type cdylib -C opt-level=1 -C panic=abort -C strip=debuginfo -o lazy_routes.wasm
+//! ```
+
+#![no_std]
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+extern "C" {
+    fn record_render(renders: u32);
+}
+
+static RENDERS: AtomicU32 = AtomicU32::new(0);
+
+/// Used by the main module and both routes
+#[inline(never)]
+fn render_count() -> u32 {
+    let renders = RENDERS.fetch_add(1, Ordering::Relaxed) + 1;
+    unsafe { record_render(renders) };
+    renders
+}
+
+/// Used by both routes
+#[inline(never)]
+fn layout(content: u32) -> u32 {
+    content * 10 + render_count()
+}
+
+trait View {
+    fn render(&self) -> u32;
+}
+
+struct Home;
+
+impl View for Home {
+    fn render(&self) -> u32 {
+        render_count()
+    }
+}
+
+/// Only used by the settings route
+#[inline(never)]
+fn settings_form(id: u32) -> u32 {
+    (0..id).fold(7, |hash, value| hash.wrapping_mul(31).wrapping_add(value))
+}
+
+/// Only used by the about route
+#[inline(never)]
+fn about_text(id: u32) -> u32 {
+    id.rotate_left(3) ^ 0x5a5a
+}
+
+#[no_mangle]
+pub extern "C" fn main_app() -> u32 {
+    // Rendering through a trait object puts the render function in the function table
+    let view: &dyn View = core::hint::black_box(&Home);
+    view.render()
+}
+
+#[export_name = "__dx_lazy_Route_Settings"]
+pub extern "C" fn settings(route: *const u32, out: *mut u32) {
+    unsafe { out.write(layout(settings_form(*route))) }
+}
+
+#[export_name = "__dx_lazy_Route_About"]
+pub extern "C" fn about(route: *const u32, out: *mut u32) {
+    unsafe { out.write(layout(about_text(*route))) }
+}
@@ -748,6 +748,20 @@ mod struct_info {
             }
         }
 
+        /// Format every field with `Debug` if it implements it, or as its type name otherwise
+        fn debug_props_impl(&self) -> TokenStream {
+            let names = self.fields.iter().map(|f| f.name);
+            let labels = self
+                .fields
+                .iter()
+                .map(|f| strip_raw_ident_prefix(f.name.to_string()));
+            quote! {
+                #[allow(unused_imports)]
+                use dioxus_core::internal::{DebugPropFallback as _, DebugPropWithDebug as _};
+                vec![#( (#labels, (&&self.#names).debug_prop()) ),*]
+            }
+        }
+
         pub fn builder_creation_impl(&self) -> Result<TokenStream, Error> {
             let StructInfo {
                 ref vis,
@@ -836,6 +850,7 @@ Finally, call `.build()` to create the instance of `{name}`.
             }
 
             let memoize = self.memoize_impl()?;
+            let debug_props = self.debug_props_impl();
 
             let global_fields = self
                 .extend_fields()
@@ -888,6 +903,10 @@ Finally, call `.build()` to create the instance of `{name}`.
                     fn memoize(&mut self, new: &Self) -> bool {
                         #memoize
                     }
+                    #[cfg(debug_assertions)]
+                    fn debug_props(&self) -> Vec<(&'static str, String)> {
+                        #debug_props
+                    }
                 }
             })
         }
@@ -1479,6 +1498,10 @@ Finally, call `.build()` to create the instance of `{name}`.
                         fn memoize(&mut self, new: &Self) -> bool {
                             self.inner.memoize(&new.inner)
                         }
+                        #[cfg(debug_assertions)]
+                        fn debug_props(&self) -> Vec<(&'static str, String)> {
+                            self.inner.debug_props()
+                        }
                     }
 
                     #[allow(dead_code, non_camel_case_types, missing_docs)]
@@ -0,0 +1,61 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Fields, Result};
+
+/// Generate an extension trait for `Store<T>` with a method for each field of the struct that returns the lens for that
+/// field
+pub fn derive_store(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(not_named_struct(input)),
+        },
+        _ => return Err(not_named_struct(input)),
+    };
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let trait_name = format_ident!("{}StoreExt", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let trait_doc = format!(
+        " Lenses into the fields of a [`{name}`] store. This trait is generated by `#[derive(Store)]`."
+    );
+
+    let mut definitions = Vec::new();
+    let mut implementations = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("named fields have an identifier");
+        let ty = &field.ty;
+        let doc = format!(" Get the lens for the `{ident}` field of the store");
+        definitions.push(quote! {
+            #[doc = #doc]
+            fn #ident(&self) -> dioxus_signals::Store<#ty>;
+        });
+        implementations.push(quote! {
+            fn #ident(&self) -> dioxus_signals::Store<#ty> {
+                self.scope(#index, |value| &value.#ident, |value| &mut value.#ident)
+            }
+        });
+    }
+
+    Ok(quote! {
+        #[doc = #trait_doc]
+        #vis trait #trait_name #impl_generics #where_clause {
+            #(#definitions)*
+        }
+
+        impl #impl_generics #trait_name #ty_generics for dioxus_signals::Store<#name #ty_generics> #where_clause {
+            #(#implementations)*
+        }
+    })
+}
+
+fn not_named_struct(input: &DeriveInput) -> syn::Error {
+    syn::Error::new(
+        input.span(),
+        "Store can only be derived for structs with named fields",
+    )
+}
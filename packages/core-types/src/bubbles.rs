@@ -8,7 +8,10 @@
 ///
 /// The real solution to this problem is that events need to mark themselves as "bubbling" or "not bubbling"
 /// in their definition, which gets passed as part of the mutations.
+///
+/// Capture listeners are named `event:capture` and bubble if the event bubbles.
 pub fn event_bubbles(evt: &str) -> bool {
+    let evt = evt.strip_suffix(":capture").unwrap_or(evt);
     match evt {
         "copy" => true,
         "cut" => true,
@@ -15,12 +15,17 @@ pub(crate) trait AnyProps: 'static {
     fn props_mut(&mut self) -> &mut dyn Any;
     /// Duplicate this component into a new boxed component.
     fn duplicate(&self) -> BoxedAnyProps;
+    /// Get the name and debug representation of each prop.
+    #[cfg(debug_assertions)]
+    fn debug_props(&self) -> Vec<(&'static str, String)>;
 }
 
 /// A component along with the props the component uses to render.
 pub(crate) struct VProps<F: ComponentFunction<P, M>, P, M> {
     render_fn: F,
     memo: fn(&mut P, &P) -> bool,
+    #[cfg(debug_assertions)]
+    debug: fn(&P) -> Vec<(&'static str, String)>,
     props: P,
     name: &'static str,
     phantom: std::marker::PhantomData<M>,
@@ -31,6 +36,8 @@ impl<F: ComponentFunction<P, M>, P: Clone, M> Clone for VProps<F, P, M> {
         Self {
             render_fn: self.render_fn.clone(),
             memo: self.memo,
+            #[cfg(debug_assertions)]
+            debug: self.debug,
             props: self.props.clone(),
             name: self.name,
             phantom: std::marker::PhantomData,
@@ -49,11 +56,20 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> VProps<
         VProps {
             render_fn,
             memo,
+            #[cfg(debug_assertions)]
+            debug: |_| Vec::new(),
             props,
             name,
             phantom: std::marker::PhantomData,
         }
     }
+
+    /// Set the function that formats the props for debugging tools. Props are only formatted in debug builds.
+    #[cfg(debug_assertions)]
+    pub fn with_debug(mut self, debug: fn(&P) -> Vec<(&'static str, String)>) -> Self {
+        self.debug = debug;
+        self
+    }
 }
 
 impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> AnyProps
@@ -94,9 +110,16 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> AnyProp
         Box::new(Self {
             render_fn: self.render_fn.clone(),
             memo: self.memo,
+            #[cfg(debug_assertions)]
+            debug: self.debug,
             props: self.props.clone(),
             name: self.name,
             phantom: std::marker::PhantomData,
         })
     }
+
+    #[cfg(debug_assertions)]
+    fn debug_props(&self) -> Vec<(&'static str, String)> {
+        (self.debug)(&self.props)
+    }
 }
@@ -87,6 +87,8 @@ impl VirtualDom {
         };
 
         self.dirty_scopes.remove(&ScopeOrder::new(height, id));
+        #[cfg(feature = "profiling")]
+        self.runtime.profiler.borrow_mut().remove_scope(id);
 
         // If this scope was a suspense boundary, remove it from the resolved scopes
         self.resolved_scopes.retain(|s| s != &id);
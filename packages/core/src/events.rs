@@ -69,6 +69,9 @@ impl<T: ?Sized> Event<T> {
 
     /// Prevent this event from continuing to bubble up the tree to parent elements.
     ///
+    /// In a capture listener, this prevents the event from reaching the elements inside the current element. The other
+    /// listeners of the current element still run.
+    ///
     /// # Example
     ///
     /// ```rust, no_run
@@ -94,6 +97,9 @@ impl<T: ?Sized> Event<T> {
 
     /// Prevent this event from continuing to bubble up the tree to parent elements.
     ///
+    /// In a capture listener, this prevents the event from reaching the elements inside the current element. The other
+    /// listeners of the current element still run.
+    ///
     /// # Example
     ///
     /// ```rust, no_run
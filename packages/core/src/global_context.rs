@@ -1,5 +1,6 @@
+use crate::innerlude::{SuspendedFuture, UpdatePriority};
 use crate::runtime::RuntimeError;
-use crate::{innerlude::SuspendedFuture, runtime::Runtime, CapturedError, Element, ScopeId, Task};
+use crate::{runtime::Runtime, CapturedError, Element, ScopeId, Task};
 use std::future::Future;
 use std::sync::Arc;
 
@@ -326,6 +327,46 @@ pub fn needs_update_any(id: ScopeId) {
     let _ = Runtime::with_current_scope(|cx| cx.needs_update_any(id));
 }
 
+/// Run a closure where every update it schedules has [`UpdatePriority::Transition`] priority. Transitions are rendered
+/// after all other updates, and [`VirtualDom::render_with_deadline`](crate::VirtualDom::render_with_deadline) can
+/// interrupt them to handle more urgent updates like user input first.
+///
+/// This is useful for expensive updates that don't need to be visible immediately, like filtering a large list while
+/// the user types in a search box:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn App() -> Element {
+///     let mut query = use_signal(String::new);
+///     let mut filter = use_signal(String::new);
+///
+///     rsx! {
+///         input {
+///             value: "{query}",
+///             oninput: move |event| {
+///                 // The input updates immediately
+///                 query.set(event.value());
+///                 // The list can update later
+///                 start_transition(|| filter.set(event.value()));
+///             },
+///         }
+///         SearchResults { filter }
+///     }
+/// }
+///
+/// #[component]
+/// fn SearchResults(filter: ReadOnlySignal<String>) -> Element {
+///     rsx! {
+///         for i in (0..10_000).filter(|i| i.to_string().contains(&*filter.read())) {
+///             div { key: "{i}", "{i}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn start_transition<O>(f: impl FnOnce() -> O) -> O {
+    UpdatePriority::Transition.run(f)
+}
+
 /// Schedule an update for the current component
 ///
 /// Note: Unlike [`needs_update`], the function returned by this method will work outside of the dioxus runtime.
@@ -15,6 +15,8 @@ mod global_context;
 mod launch;
 mod mutations;
 mod nodes;
+#[cfg(feature = "profiling")]
+pub mod profiling;
 mod properties;
 mod reactive_context;
 mod render_error;
@@ -33,7 +35,9 @@ mod hotreload_utils;
 /// Items exported from this module are used in macros and should not be used directly.
 #[doc(hidden)]
 pub mod internal {
-    pub use crate::properties::verify_component_called_as_component;
+    pub use crate::properties::{
+        verify_component_called_as_component, DebugPropFallback, DebugPropWithDebug,
+    };
 
     #[doc(hidden)]
     pub use crate::hotreload_utils::{
@@ -83,8 +87,8 @@ pub use crate::innerlude::{
     AnyValue, Attribute, AttributeValue, CapturedError, Component, ComponentFunction, DynamicNode,
     Element, ElementId, Event, Fragment, HasAttributes, IntoDynNode, LaunchConfig, MarkerWrapper,
     Mutation, Mutations, NoOpMutations, Ok, Properties, Result, Runtime, ScopeId, ScopeState,
-    SpawnIfAsync, Task, Template, TemplateAttribute, TemplateNode, VComponent, VNode, VNodeInner,
-    VPlaceholder, VText, VirtualDom, WriteMutations,
+    SpawnIfAsync, Task, Template, TemplateAttribute, TemplateNode, UpdatePriority, VComponent,
+    VNode, VNodeInner, VPlaceholder, VText, VirtualDom, WriteMutations,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
@@ -95,15 +99,15 @@ pub mod prelude {
         consume_context, consume_context_from_scope, current_owner, current_scope_id,
         fc_to_builder, generation, has_context, needs_update, needs_update_any, parent_scope,
         provide_context, provide_error_boundary, provide_root_context, queue_effect, remove_future,
-        schedule_update, schedule_update_any, spawn, spawn_forever, spawn_isomorphic, suspend,
-        throw_error, try_consume_context, use_after_render, use_before_render, use_drop, use_hook,
-        use_hook_with_cleanup, with_owner, AnyValue, Attribute, Callback, Component,
-        ComponentFunction, Context, Element, ErrorBoundary, ErrorContext, Event, EventHandler,
-        Fragment, HasAttributes, IntoAttributeValue, IntoDynNode, OptionStringFromMarker,
-        Properties, ReactiveContext, RenderError, Runtime, RuntimeGuard, ScopeId, ScopeState,
-        SuperFrom, SuperInto, SuspendedFuture, SuspenseBoundary, SuspenseBoundaryProps,
-        SuspenseContext, SuspenseExtension, Task, Template, TemplateAttribute, TemplateNode, VNode,
-        VNodeInner, VirtualDom,
+        schedule_update, schedule_update_any, spawn, spawn_forever, spawn_isomorphic,
+        start_transition, suspend, throw_error, try_consume_context, use_after_render,
+        use_before_render, use_drop, use_hook, use_hook_with_cleanup, with_owner, AnyValue,
+        Attribute, Callback, Component, ComponentFunction, Context, Element, ErrorBoundary,
+        ErrorContext, Event, EventHandler, Fragment, HasAttributes, IntoAttributeValue,
+        IntoDynNode, OptionStringFromMarker, Properties, ReactiveContext, RenderError, Runtime,
+        RuntimeGuard, ScopeId, ScopeState, SuperFrom, SuperInto, SuspendedFuture, SuspenseBoundary,
+        SuspenseBoundaryProps, SuspenseContext, SuspenseExtension, Task, Template,
+        TemplateAttribute, TemplateNode, UpdatePriority, VNode, VNodeInner, VirtualDom,
     };
 }
 
@@ -611,12 +611,10 @@ impl VComponent {
         P: Properties + 'static,
     {
         let render_fn = component.id();
-        let props = Box::new(VProps::new(
-            component,
-            <P as Properties>::memoize,
-            props,
-            fn_name,
-        ));
+        let props = VProps::new(component, <P as Properties>::memoize, props, fn_name);
+        #[cfg(debug_assertions)]
+        let props = props.with_debug(<P as Properties>::debug_props);
+        let props = Box::new(props);
 
         VComponent {
             name: fn_name,
@@ -0,0 +1,246 @@
+//! Record every render of the [`VirtualDom`](crate::VirtualDom), how long it took and why it happened.
+//!
+//! Profiling is only available with the `profiling` feature. Renders are recorded from the time the virtual dom is
+//! created and can be read with [`VirtualDom::render_profile`](crate::VirtualDom::render_profile):
+//!
+//! ```rust
+//! # use dioxus::prelude::*;
+//! fn app() -> Element {
+//!     rsx! { Counter {} }
+//! }
+//!
+//! #[component]
+//! fn Counter() -> Element {
+//!     rsx! { "hello world" }
+//! }
+//!
+//! let mut dom = VirtualDom::new(app);
+//! dom.rebuild_in_place();
+//!
+//! let profile = dom.take_render_profile();
+//! assert_eq!(profile.render_count("Counter"), 1);
+//! assert_eq!(profile.scope_render_count(ScopeId::APP), 1);
+//!
+//! // The profile can be opened in chrome://tracing or https://ui.perfetto.dev
+//! let trace = profile.to_chrome_trace();
+//! ```
+
+use crate::ScopeId;
+use rustc_hash::FxHashMap;
+use std::{cell::Cell, fmt::Display, panic::Location};
+use web_time::{Duration, Instant};
+
+thread_local! {
+    static WRITE_ORIGIN: Cell<Option<&'static Location<'static>>> = const { Cell::new(None) };
+}
+
+/// Run a closure that marks scopes dirty because of a write at `origin`. Any scope marked dirty inside the closure
+/// records the write as a [`RenderCause::Write`] of its next render.
+///
+/// Signals call this automatically when they are written to. Other reactive primitives can use it to show up in the
+/// profile as well.
+pub fn with_write_origin<O>(origin: &'static Location<'static>, f: impl FnOnce() -> O) -> O {
+    struct ResetOrigin(Option<&'static Location<'static>>);
+
+    impl Drop for ResetOrigin {
+        fn drop(&mut self) {
+            WRITE_ORIGIN.with(|origin| origin.set(self.0));
+        }
+    }
+
+    let _reset = ResetOrigin(WRITE_ORIGIN.with(|current| current.replace(Some(origin))));
+    f()
+}
+
+/// Why a scope rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderCause {
+    /// The scope rendered for the first time
+    Mount,
+    /// The parent of the scope rerendered and passed props that changed
+    PropsChanged,
+    /// A signal or other reactive value the scope subscribed to was written to at this location
+    Write(&'static Location<'static>),
+    /// The scope was marked dirty without a known write, for example with [`ScopeId::needs_update`]
+    MarkedDirty,
+}
+
+impl Display for RenderCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderCause::Mount => write!(f, "mount"),
+            RenderCause::PropsChanged => write!(f, "props changed"),
+            RenderCause::Write(location) => write!(f, "write at {location}"),
+            RenderCause::MarkedDirty => write!(f, "marked dirty"),
+        }
+    }
+}
+
+/// A single render of a scope
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderRecord {
+    /// The scope that rendered
+    pub scope: ScopeId,
+    /// The name of the component of the scope
+    pub name: &'static str,
+    /// When the render started, relative to the creation of the virtual dom
+    pub start: Duration,
+    /// How long the component took to run
+    pub duration: Duration,
+    /// Every reason the scope rendered. A scope that was marked dirty by multiple writes before it reran has
+    /// multiple causes.
+    pub causes: Vec<RenderCause>,
+}
+
+/// Every render recorded by a [`VirtualDom`](crate::VirtualDom) with the `profiling` feature
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderProfile {
+    records: Vec<RenderRecord>,
+}
+
+impl RenderProfile {
+    /// Every render in the order it happened
+    pub fn records(&self) -> &[RenderRecord] {
+        &self.records
+    }
+
+    /// Every render of a component. The component can be named with its full path like `my_app::Counter` or
+    /// just its name like `Counter`.
+    pub fn renders_of<'a>(&'a self, component: &'a str) -> impl Iterator<Item = &'a RenderRecord> {
+        self.records
+            .iter()
+            .filter(move |record| component_matches(record.name, component))
+    }
+
+    /// The number of times a component rendered. The component can be named with its full path like
+    /// `my_app::Counter` or just its name like `Counter`.
+    pub fn render_count(&self, component: &str) -> usize {
+        self.renders_of(component).count()
+    }
+
+    /// The number of times a scope rendered
+    pub fn scope_render_count(&self, scope: ScopeId) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.scope == scope)
+            .count()
+    }
+
+    /// The total time spent rendering components
+    pub fn total_duration(&self) -> Duration {
+        self.records.iter().map(|record| record.duration).sum()
+    }
+
+    /// Export the profile in the [Chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU).
+    /// The trace can be opened in `chrome://tracing` or <https://ui.perfetto.dev>.
+    pub fn to_chrome_trace(&self) -> String {
+        let events: Vec<_> = self
+            .records
+            .iter()
+            .map(|record| {
+                serde_json::json!({
+                    "name": record.name,
+                    "cat": "render",
+                    "ph": "X",
+                    "ts": record.start.as_secs_f64() * 1_000_000.0,
+                    "dur": record.duration.as_secs_f64() * 1_000_000.0,
+                    "pid": 1,
+                    "tid": 1,
+                    "args": {
+                        "scope": record.scope.0,
+                        "causes": record.causes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    },
+                })
+            })
+            .collect();
+
+        serde_json::json!({ "traceEvents": events, "displayTimeUnit": "ms" }).to_string()
+    }
+}
+
+fn component_matches(name: &str, component: &str) -> bool {
+    name == component
+        || name
+            .strip_suffix(component)
+            .is_some_and(|path| path.ends_with("::"))
+}
+
+/// The profiler of a runtime. This collects the causes of scopes that are marked dirty until they render.
+pub(crate) struct Profiler {
+    created: Instant,
+    pending_causes: FxHashMap<ScopeId, Vec<RenderCause>>,
+    pub(crate) profile: RenderProfile,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self {
+            created: Instant::now(),
+            pending_causes: Default::default(),
+            profile: Default::default(),
+        }
+    }
+}
+
+impl Profiler {
+    /// Remember why a scope was marked dirty for its next render
+    pub(crate) fn mark_dirty(&mut self, id: ScopeId) {
+        let cause = match WRITE_ORIGIN.with(Cell::get) {
+            Some(origin) => RenderCause::Write(origin),
+            None => RenderCause::MarkedDirty,
+        };
+        let causes = self.pending_causes.entry(id).or_default();
+        if !causes.contains(&cause) {
+            causes.push(cause);
+        }
+    }
+
+    /// Make sure a dirty scope that is about to rerun has a cause, even if it was marked dirty directly with
+    /// [`crate::VirtualDom::mark_dirty`]
+    pub(crate) fn rerun_dirty(&mut self, id: ScopeId) {
+        let causes = self.pending_causes.entry(id).or_default();
+        if causes.is_empty() {
+            causes.push(RenderCause::MarkedDirty);
+        }
+    }
+
+    /// Record a finished render of a scope. Scopes that rerun without being marked dirty rerun because their
+    /// parent passed new props.
+    pub(crate) fn record_render(
+        &mut self,
+        scope: ScopeId,
+        name: &'static str,
+        start: Instant,
+        mounted: bool,
+    ) {
+        let duration = start.elapsed();
+        let pending = self.pending_causes.remove(&scope).unwrap_or_default();
+        let causes = if mounted {
+            vec![RenderCause::Mount]
+        } else if pending.is_empty() {
+            vec![RenderCause::PropsChanged]
+        } else {
+            pending
+        };
+
+        self.profile.records.push(RenderRecord {
+            scope,
+            name,
+            start: start.saturating_duration_since(self.created),
+            duration,
+            causes,
+        });
+    }
+
+    /// Forget the causes of a scope that was removed before it rendered again
+    pub(crate) fn remove_scope(&mut self, scope: ScopeId) {
+        self.pending_causes.remove(&scope);
+    }
+}
+
+/// Remember why a scope was marked dirty in the profiler of the current runtime
+pub(crate) fn record_dirty(id: ScopeId) {
+    if let Ok(runtime) = crate::Runtime::current() {
+        runtime.profiler.borrow_mut().mark_dirty(id);
+    }
+}
@@ -56,6 +56,13 @@ pub trait Properties: Clone + Sized + 'static {
     /// Make the old props equal to the new props. Return if the props were equal and should be memoized.
     fn memoize(&mut self, other: &Self) -> bool;
 
+    /// Get the name and debug representation of each prop for debugging tools like the devtools inspector. The
+    /// derive macro formats props that implement [`Debug`](std::fmt::Debug) and shows the type name of other props in
+    /// debug builds.
+    fn debug_props(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
     /// Create a component from the props.
     fn into_vcomponent<M: 'static>(self, render_fn: impl ComponentFunction<Self, M>) -> VComponent {
         let type_name = std::any::type_name_of_val(&render_fn);
@@ -73,6 +80,32 @@ impl Properties for () {
     }
 }
 
+/// Format a prop with [`Debug`](std::fmt::Debug) for [`Properties::debug_props`]. The `Props` derive macro calls
+/// `(&&prop).debug_prop()` with this trait and [`DebugPropFallback`] in scope, so props that implement `Debug` use this
+/// implementation and other props fall back to their type name.
+#[doc(hidden)]
+pub trait DebugPropWithDebug {
+    fn debug_prop(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> DebugPropWithDebug for T {
+    fn debug_prop(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+/// The fallback for [`DebugPropWithDebug`] that formats a prop as its type name.
+#[doc(hidden)]
+pub trait DebugPropFallback {
+    fn debug_prop(&self) -> String;
+}
+
+impl<T> DebugPropFallback for &&T {
+    fn debug_prop(&self) -> String {
+        std::any::type_name::<T>().to_string()
+    }
+}
+
 /// Root properties never need to be memoized, so we can use a dummy implementation.
 pub(crate) struct RootProps<P>(pub P);
 
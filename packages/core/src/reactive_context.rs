@@ -1,5 +1,6 @@
 use crate::{
     prelude::{current_scope_id, ScopeId},
+    scheduler::UpdatePriority,
     scope_context::Scope,
     tasks::SchedulerMsg,
     Runtime,
@@ -10,7 +11,7 @@ use std::{
     cell::RefCell,
     collections::HashSet,
     hash::Hash,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, PoisonError},
 };
 
 #[doc = include_str!("../docs/reactivity.md")]
@@ -114,7 +115,11 @@ impl ReactiveContext {
         let sender = runtime.sender.clone();
         let update_scope = move || {
             tracing::trace!("Marking scope {:?} as dirty", id);
-            sender.unbounded_send(SchedulerMsg::Immediate(id)).unwrap();
+            #[cfg(feature = "profiling")]
+            crate::profiling::record_dirty(id);
+            sender
+                .unbounded_send(SchedulerMsg::Immediate(id, UpdatePriority::current()))
+                .unwrap();
         };
 
         // Otherwise, create a new context at the current scope
@@ -311,3 +316,20 @@ struct Inner {
     // The scope that this reactive context is associated with
     scope: Option<ScopeId>,
 }
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // Remove the context from the values it is subscribed to so they don't hold onto it after it is dropped
+        let Some(self_) = self.self_.take() else {
+            return;
+        };
+        // A poisoned subscriber list is still cleaned up instead of panicking while the context may already be unwinding
+        for subscriber in std::mem::take(&mut self.subscribers) {
+            subscriber
+                .0
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(&self_);
+        }
+    }
+}
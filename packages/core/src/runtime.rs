@@ -1,5 +1,5 @@
 use crate::arena::ElementRef;
-use crate::innerlude::{DirtyTasks, Effect};
+use crate::innerlude::{DirtyTasks, Effect, UpdatePriority};
 use crate::nodes::VNodeMount;
 use crate::scheduler::ScopeOrder;
 use crate::scope_context::SuspenseLocation;
@@ -64,6 +64,10 @@ pub struct Runtime {
     // We need to store this information on the virtual dom so that we know what nodes are mounted where when we bubble events
     // Each mount is associated with a whole rsx block. [`VirtualDom::elements`] link to a specific node in the block
     pub(crate) mounts: RefCell<Slab<VNodeMount>>,
+
+    // The renders recorded for the profiler and the causes of scopes that are waiting to render
+    #[cfg(feature = "profiling")]
+    pub(crate) profiler: RefCell<crate::profiling::Profiler>,
 }
 
 impl Runtime {
@@ -85,6 +89,8 @@ impl Runtime {
             dirty_tasks: Default::default(),
             elements: RefCell::new(elements),
             mounts: Default::default(),
+            #[cfg(feature = "profiling")]
+            profiler: Default::default(),
         })
     }
 
@@ -295,18 +301,115 @@ impl Runtime {
     /// It is up to the listeners themselves to mark nodes as dirty.
     ///
     /// If you have multiple events, you can call this method multiple times before calling "render_with_deadline"
+    ///
+    /// Any scopes the listeners mark dirty are rerun with [`UpdatePriority::UserInput`] priority unless they are
+    /// updated inside of [`crate::prelude::start_transition`].
     #[instrument(skip(self, event), level = "trace", name = "Runtime::handle_event")]
     pub fn handle_event(self: &Rc<Self>, name: &str, event: Event<dyn Any>, element: ElementId) {
         let _runtime = RuntimeGuard::new(self.clone());
         let elements = self.elements.borrow();
 
         if let Some(Some(parent_path)) = elements.get(element.0).copied() {
-            if event.propagates() {
-                self.handle_bubbling_event(parent_path, name, event);
-            } else {
-                self.handle_non_bubbling_event(parent_path, name, event);
+            UpdatePriority::UserInput.run(|| {
+                let bubbles = event.propagates();
+                match self.handle_capturing_event(parent_path, name, &event) {
+                    CapturePhase::Finished if bubbles => {
+                        self.handle_bubbling_event(parent_path, name, event);
+                    }
+                    // Stopping propagation at the target still calls the other listeners of the target
+                    CapturePhase::Finished | CapturePhase::StoppedAtTarget => {
+                        self.handle_non_bubbling_event(parent_path, name, event);
+                    }
+                    CapturePhase::Stopped => {}
+                }
+            });
+        }
+    }
+
+    /// Call the capture listeners of the event from the root down to the target element. Capture listeners run for
+    /// both bubbling and non-bubbling events.
+    ///
+    /// This walks the tree the same way as [`Self::handle_bubbling_event`], but collects every listener before calling
+    /// them in the opposite order.
+    #[instrument(
+        skip(self, uievent),
+        level = "trace",
+        name = "VirtualDom::handle_capturing_event"
+    )]
+    fn handle_capturing_event(
+        &self,
+        target: ElementRef,
+        name: &str,
+        uievent: &Event<dyn Any>,
+    ) -> CapturePhase {
+        let mounts = self.mounts.borrow();
+
+        // Accumulate the listeners of each template from the target up to the root. Each entry is the listener and
+        // whether it is attached to the target element
+        let mut listeners = vec![];
+        let mut parent = Some(target);
+        let mut target_template = true;
+        while let Some(path) = parent {
+            let Some(mount) = mounts.get(path.mount.0) else {
+                // If the node is suspended and not mounted, we can just ignore the event
+                return CapturePhase::Stopped;
+            };
+            let el_ref = &mount.node;
+            let node_template = el_ref.template;
+            let target_path = path.path;
+            let mut template_listeners = vec![];
+
+            // Dynamic attributes are in depth first order, so parents come before their children
+            for (idx, this_path) in node_template.attr_paths.iter().enumerate() {
+                let attrs = &*el_ref.dynamic_attrs[idx];
+
+                for attr in attrs.iter() {
+                    let capture_name = attr
+                        .name
+                        .get(2..)
+                        .and_then(|attr_name| attr_name.strip_suffix(":capture"));
+                    if capture_name == Some(name) && target_path.is_descendant(this_path) {
+                        let at_target = target_template && target_path == this_path;
+                        template_listeners.push((&attr.value, at_target));
+                        if target_path == this_path {
+                            break;
+                        }
+                    }
+                }
+            }
+            listeners.push(template_listeners);
+            target_template = false;
+
+            let mount = el_ref.mount.get().as_usize();
+            parent = mount.and_then(|id| mounts.get(id).and_then(|el| el.parent));
+        }
+
+        tracing::event!(
+            tracing::Level::TRACE,
+            "Calling {} capture listeners",
+            listeners.iter().map(Vec::len).sum::<usize>()
+        );
+
+        // Non-bubbling events start out without propagation. Capture listeners can still stop them
+        let bubbles = uievent.propagates();
+        uievent.metadata.borrow_mut().propagates = true;
+        for (listener, at_target) in listeners.into_iter().rev().flatten() {
+            if let AttributeValue::Listener(listener) = listener {
+                self.rendering.set(false);
+                listener.call(uievent.clone());
+                self.rendering.set(true);
+
+                if !uievent.propagates() {
+                    return match at_target {
+                        true => CapturePhase::StoppedAtTarget,
+                        false => CapturePhase::Stopped,
+                    };
+                }
             }
         }
+        uievent.metadata.borrow_mut().propagates = bubbles;
+
+        CapturePhase::Finished
     }
 
     /*
@@ -317,7 +420,8 @@ impl Runtime {
     With the target path, we try and move up to the parent until there is no parent.
     Due to how bubbling works, we call the listeners before walking to the parent.
 
-    If we wanted to do capturing, then we would accumulate all the listeners and call them in reverse order.
+    Capturing works the same way in handle_capturing_event, but accumulates all the listeners and calls them in
+    reverse order.
     ----------------------
 
     For a visual demonstration, here we present a tree on the left and whether or not a listener is collected on the
@@ -431,6 +535,16 @@ impl Runtime {
     }
 }
 
+/// How the capture phase of an event ended
+enum CapturePhase {
+    /// Every capture listener ran
+    Finished,
+    /// A capture listener on the target element stopped propagation. The other listeners of the target still run
+    StoppedAtTarget,
+    /// A capture listener on a parent of the target stopped propagation
+    Stopped,
+}
+
 /// A guard for a new runtime. This must be used to override the current runtime when importing components from a dynamic library that has it's own runtime.
 ///
 /// ```rust
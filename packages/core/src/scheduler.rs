@@ -67,6 +67,7 @@
 //! 1. Dirty Scopes:
 //!    Description: When a scope is marked dirty, a rerun of the scope will be scheduled. This will cause the scope to rerun and update the DOM if any changes are detected during the diffing phase.
 //!    Priority: These are the highest priority tasks. Dirty scopes will be rerun in order from the scope closest to the root to the scope furthest from the root. We follow this order to ensure that if a higher component reruns and drops a lower component, the lower component will not be run after it should be dropped.
+//!    Lanes: Every dirty scope is queued in one lane of [`UpdatePriority`]. Scopes marked dirty while handling user input are rerun before scopes marked dirty by tasks and effects, which are rerun before scopes marked dirty inside of [`crate::prelude::start_transition`]. If a less urgent lane contains a parent of the next scope, the parent is rerun first to keep the guarantee above.
 //!
 //! 2. Tasks:
 //!    Description: Futures spawned in the dioxus runtime each have an unique task id. When the waker for that future is called, the task is rerun.
@@ -81,10 +82,117 @@ use crate::ScopeId;
 use crate::Task;
 use crate::VirtualDom;
 use std::borrow::Borrow;
-use std::cell::RefCell;
-use std::collections::VecDeque;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeSet, VecDeque};
 use std::hash::Hash;
 
+thread_local! {
+    static CURRENT_PRIORITY: Cell<UpdatePriority> = const { Cell::new(UpdatePriority::Default) };
+}
+
+/// The priority of an update. Scopes marked dirty with a more urgent priority are rerun first, and less urgent updates
+/// can be interrupted by [`VirtualDom::render_with_deadline`] to keep the app responsive.
+///
+/// The priority of an update is decided when the scope is marked dirty, for example when a signal is written:
+/// - Writes inside of event handlers are [`UpdatePriority::UserInput`]
+/// - Writes inside of [`crate::prelude::start_transition`] are [`UpdatePriority::Transition`]
+/// - Everything else, like writes in tasks and effects, is [`UpdatePriority::Default`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum UpdatePriority {
+    /// Updates caused by user input like clicks and key presses. These are always rendered, even after the deadline
+    /// of [`VirtualDom::render_with_deadline`] has passed.
+    UserInput,
+    /// Updates from tasks, effects and any other code that doesn't set a priority.
+    #[default]
+    Default,
+    /// Low priority updates started with [`crate::prelude::start_transition`] that can be interrupted by more urgent updates.
+    Transition,
+}
+
+impl UpdatePriority {
+    const LANES: usize = 3;
+
+    /// Get the priority of updates scheduled on this thread right now
+    pub fn current() -> Self {
+        CURRENT_PRIORITY.with(|priority| priority.get())
+    }
+
+    /// Run a closure where every update scheduled on this thread has this priority
+    pub fn run<O>(self, f: impl FnOnce() -> O) -> O {
+        struct ResetPriority(UpdatePriority);
+
+        impl Drop for ResetPriority {
+            fn drop(&mut self) {
+                CURRENT_PRIORITY.with(|priority| priority.set(self.0));
+            }
+        }
+
+        let _reset = ResetPriority(CURRENT_PRIORITY.with(|priority| priority.replace(self)));
+        f()
+    }
+}
+
+/// The dirty scopes of the virtual dom, sorted into a lane for each [`UpdatePriority`]. A scope is only ever queued in
+/// the most urgent lane it was marked dirty with.
+#[derive(Debug, Default)]
+pub(crate) struct DirtyScopes {
+    lanes: [BTreeSet<ScopeOrder>; UpdatePriority::LANES],
+}
+
+impl DirtyScopes {
+    /// Queue a scope in the lane of a priority
+    pub(crate) fn insert(&mut self, order: ScopeOrder, priority: UpdatePriority) {
+        let lane = priority as usize;
+        // If the scope is already queued with the same or a more urgent priority, there is nothing to do
+        if self.lanes[..=lane]
+            .iter()
+            .any(|queued| queued.contains(&order))
+        {
+            return;
+        }
+        for queued in &mut self.lanes[lane + 1..] {
+            queued.remove(&order);
+        }
+        self.lanes[lane].insert(order);
+    }
+
+    /// Remove a scope from every lane
+    pub(crate) fn remove(&mut self, order: &ScopeOrder) {
+        for lane in &mut self.lanes {
+            lane.remove(order);
+        }
+    }
+
+    /// Check if the scope is queued in any lane
+    pub(crate) fn contains(&self, order: &ScopeOrder) -> bool {
+        self.lanes.iter().any(|lane| lane.contains(order))
+    }
+
+    /// Check if there are no dirty scopes in any lane
+    pub(crate) fn is_empty(&self) -> bool {
+        self.lanes.iter().all(BTreeSet::is_empty)
+    }
+
+    /// Check if any scope is queued in a lane less urgent than the priority
+    fn has_less_urgent(&self, priority: UpdatePriority) -> bool {
+        self.lanes[priority as usize + 1..]
+            .iter()
+            .any(|lane| !lane.is_empty())
+    }
+
+    /// Get the highest scope in the most urgent lane
+    pub(crate) fn first(&self) -> Option<(UpdatePriority, ScopeOrder)> {
+        [
+            UpdatePriority::UserInput,
+            UpdatePriority::Default,
+            UpdatePriority::Transition,
+        ]
+        .into_iter()
+        .zip(&self.lanes)
+        .find_map(|(priority, lane)| lane.first().map(|order| (priority, *order)))
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq)]
 pub struct ScopeOrder {
     pub(crate) height: u32,
@@ -136,8 +244,8 @@ impl VirtualDom {
     }
 
     /// Queue a scope to be rerendered
-    pub(crate) fn queue_scope(&mut self, order: ScopeOrder) {
-        self.dirty_scopes.insert(order);
+    pub(crate) fn queue_scope(&mut self, order: ScopeOrder, priority: UpdatePriority) {
+        self.dirty_scopes.insert(order, priority);
     }
 
     /// Check if there are any dirty scopes
@@ -175,9 +283,49 @@ impl VirtualDom {
         Some(effect)
     }
 
+    /// Get the priority of the most urgent dirty scope
+    pub(crate) fn next_scope_priority(&self) -> Option<UpdatePriority> {
+        self.dirty_scopes.first().map(|(priority, _)| priority)
+    }
+
+    /// Get the next scope that should be rerun. This is the highest scope in the most urgent lane unless one of its
+    /// parents is queued in a less urgent lane. The parent may drop the scope when it reruns, so we rerun the highest
+    /// dirty parent first
+    fn next_dirty_scope(&self) -> Option<ScopeOrder> {
+        let (priority, mut next) = self.dirty_scopes.first()?;
+        if !self.dirty_scopes.has_less_urgent(priority) {
+            return Some(next);
+        }
+
+        let mut parent = self
+            .runtime
+            .get_state(next.id)
+            .and_then(|scope| scope.parent_id());
+        while let Some(id) = parent {
+            let Some(scope) = self.runtime.get_state(id) else {
+                break;
+            };
+            let order = ScopeOrder::new(scope.height, id);
+            if self.dirty_scopes.contains(&order) {
+                next = order;
+            }
+            parent = scope.parent_id();
+        }
+
+        Some(next)
+    }
+
+    /// Take a scope out of the dirty scopes to rerun it
+    fn rerun_dirty_scope(&mut self, scope: ScopeOrder) -> Work {
+        self.dirty_scopes.remove(&scope);
+        #[cfg(feature = "profiling")]
+        self.runtime.profiler.borrow_mut().rerun_dirty(scope.id);
+        Work::RerunScope(scope)
+    }
+
     /// Take any work from the highest scope. This may include rerunning the scope and/or running tasks
     pub(crate) fn pop_work(&mut self) -> Option<Work> {
-        let dirty_scope = self.dirty_scopes.first();
+        let dirty_scope = self.next_dirty_scope();
         // Make sure the top dirty scope is valid
         #[cfg(debug_assertions)]
         if let Some(scope) = dirty_scope {
@@ -204,19 +352,13 @@ impl VirtualDom {
             (Some(scope), Some(task)) => {
                 let tasks_order = task.borrow();
                 match scope.cmp(tasks_order) {
-                    std::cmp::Ordering::Less => {
-                        let scope = self.dirty_scopes.pop_first().unwrap();
-                        Some(Work::RerunScope(scope))
-                    }
+                    std::cmp::Ordering::Less => Some(self.rerun_dirty_scope(scope)),
                     std::cmp::Ordering::Equal | std::cmp::Ordering::Greater => {
                         Some(Work::PollTask(self.pop_task().unwrap()))
                     }
                 }
             }
-            (Some(_), None) => {
-                let scope = self.dirty_scopes.pop_first().unwrap();
-                Some(Work::RerunScope(scope))
-            }
+            (Some(scope), None) => Some(self.rerun_dirty_scope(scope)),
             (None, Some(_)) => Some(Work::PollTask(self.pop_task().unwrap())),
             (None, None) => None,
         }
@@ -55,6 +55,11 @@ impl VirtualDom {
 
         self.runtime.clone().with_scope_on_stack(scope_id, || {
             let scope = &self.scopes[scope_id.0];
+
+            #[cfg(feature = "profiling")]
+            let (render_start, mounted) =
+                (web_time::Instant::now(), scope.last_rendered_node.is_none());
+
             let output = {
                 let scope_state = scope.state();
 
@@ -98,6 +103,15 @@ impl VirtualDom {
             // remove this scope from dirty scopes
             self.dirty_scopes
                 .remove(&ScopeOrder::new(scope_state.height, scope_id));
+
+            #[cfg(feature = "profiling")]
+            self.runtime.profiler.borrow_mut().record_render(
+                scope_id,
+                scope_state.name,
+                render_start,
+                mounted,
+            );
+
             output
         })
     }
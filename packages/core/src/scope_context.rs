@@ -1,9 +1,12 @@
 use crate::runtime::RuntimeError;
-use crate::{innerlude::SchedulerMsg, Runtime, ScopeId, Task};
 use crate::{
     innerlude::{throw_into, CapturedError},
     prelude::SuspenseContext,
 };
+use crate::{
+    innerlude::{SchedulerMsg, UpdatePriority},
+    Runtime, ScopeId, Task,
+};
 use generational_box::{AnyStorage, Owner};
 use rustc_hash::FxHashSet;
 use std::{
@@ -53,6 +56,9 @@ pub(crate) struct Scope {
 
     // Note: the order of the hook and context fields is important. The hooks field must be dropped before the contexts field in case a hook drop implementation tries to access a context.
     pub(crate) hooks: RefCell<Vec<Box<dyn Any>>>,
+    // The type name of each hook for debugging tools like the devtools inspector
+    #[cfg(debug_assertions)]
+    pub(crate) hook_type_names: RefCell<Vec<&'static str>>,
     pub(crate) hook_index: Cell<usize>,
     pub(crate) shared_contexts: RefCell<Vec<Box<dyn Any>>>,
     pub(crate) spawned_tasks: RefCell<FxHashSet<Task>>,
@@ -82,6 +88,8 @@ impl Scope {
             shared_contexts: RefCell::new(vec![]),
             spawned_tasks: RefCell::new(FxHashSet::default()),
             hooks: RefCell::new(vec![]),
+            #[cfg(debug_assertions)]
+            hook_type_names: RefCell::new(vec![]),
             hook_index: Cell::new(0),
             before_render: RefCell::new(vec![]),
             after_render: RefCell::new(vec![]),
@@ -140,8 +148,10 @@ impl Scope {
 
     /// Mark this scope as dirty, and schedule a render for it.
     pub fn needs_update_any(&self, id: ScopeId) {
+        #[cfg(feature = "profiling")]
+        crate::profiling::record_dirty(id);
         self.sender()
-            .unbounded_send(SchedulerMsg::Immediate(id))
+            .unbounded_send(SchedulerMsg::Immediate(id, UpdatePriority::current()))
             .expect("Scheduler to exist if scope exists");
     }
 
@@ -150,7 +160,11 @@ impl Scope {
     /// ## Notice: you should prefer using [`Self::schedule_update_any`] and [`Self::scope_id`]
     pub fn schedule_update(&self) -> Arc<dyn Fn() + Send + Sync + 'static> {
         let (chan, id) = (self.sender(), self.id);
-        Arc::new(move || drop(chan.unbounded_send(SchedulerMsg::Immediate(id))))
+        Arc::new(move || {
+            #[cfg(feature = "profiling")]
+            crate::profiling::record_dirty(id);
+            drop(chan.unbounded_send(SchedulerMsg::Immediate(id, UpdatePriority::current())))
+        })
     }
 
     /// Schedule an update for any component given its [`ScopeId`].
@@ -161,7 +175,10 @@ impl Scope {
     pub fn schedule_update_any(&self) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
         let chan = self.sender();
         Arc::new(move |id| {
-            chan.unbounded_send(SchedulerMsg::Immediate(id)).unwrap();
+            #[cfg(feature = "profiling")]
+            crate::profiling::record_dirty(id);
+            chan.unbounded_send(SchedulerMsg::Immediate(id, UpdatePriority::current()))
+                .unwrap();
         })
     }
 
@@ -448,6 +465,10 @@ impl Scope {
 
         if cur_hook >= hooks.len() {
             hooks.push(Box::new(initializer()));
+            #[cfg(debug_assertions)]
+            self.hook_type_names
+                .borrow_mut()
+                .push(std::any::type_name::<State>());
         }
 
         hooks
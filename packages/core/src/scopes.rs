@@ -2,7 +2,7 @@ use crate::{
     any_props::BoxedAnyProps, nodes::AsVNode, reactive_context::ReactiveContext,
     scope_context::Scope, Element, Runtime, VNode,
 };
-use std::{cell::Ref, rc::Rc};
+use std::{any::Any, cell::Ref, rc::Rc};
 
 /// A component's unique identifier.
 ///
@@ -106,6 +106,51 @@ impl ScopeState {
         self.context_id
     }
 
+    /// Get the name of the component this scope renders
+    pub fn name(&self) -> &'static str {
+        self.state().name
+    }
+
+    /// Get the id of the parent scope if this scope is not the root scope
+    pub fn parent_id(&self) -> Option<ScopeId> {
+        self.state().parent_id
+    }
+
+    /// Get the height of this scope in the component tree. The root scope has a height of 0.
+    pub fn height(&self) -> u32 {
+        self.state().height
+    }
+
+    /// Get the number of times this scope has rendered
+    pub fn render_count(&self) -> usize {
+        self.state().render_count.get()
+    }
+
+    /// Get the name and debug representation of each prop of the component. Props that don't implement
+    /// [`Debug`](std::fmt::Debug) are shown as their type name.
+    ///
+    /// This is empty for props that were not created with the `Props` derive macro. Props are only formatted in debug
+    /// builds.
+    #[cfg(debug_assertions)]
+    pub fn debug_props(&self) -> Vec<(&'static str, String)> {
+        self.props.debug_props()
+    }
+
+    /// Get the type name of every hook in this scope in the order the hooks were created. Type names are only
+    /// recorded in debug builds.
+    #[cfg(debug_assertions)]
+    pub fn hook_type_names(&self) -> Vec<&'static str> {
+        self.state().hook_type_names.borrow().clone()
+    }
+
+    /// Run a closure with the value of the hook at the index if it exists. This is useful for debugging tools that
+    /// want to inspect the state of a component.
+    pub fn with_hook<O>(&self, index: usize, f: impl FnOnce(&dyn Any) -> O) -> Option<O> {
+        let state = self.state();
+        let hooks = state.hooks.try_borrow().ok()?;
+        hooks.get(index).map(|hook| f(hook.as_ref()))
+    }
+
     pub(crate) fn state(&self) -> Ref<'_, Scope> {
         self.runtime.get_state(self.context_id).unwrap()
     }
@@ -1,6 +1,6 @@
 use crate::innerlude::Effect;
-use crate::innerlude::ScopeOrder;
 use crate::innerlude::{remove_future, spawn, Runtime};
+use crate::innerlude::{ScopeOrder, UpdatePriority};
 use crate::scope_context::ScopeStatus;
 use crate::scope_context::SuspenseLocation;
 use crate::ScopeId;
@@ -372,8 +372,8 @@ impl TaskType {
 /// These messages control how the scheduler will process updates to the UI.
 #[derive(Debug)]
 pub(crate) enum SchedulerMsg {
-    /// Immediate updates from Components that mark them as dirty
-    Immediate(ScopeId),
+    /// Immediate updates from Components that mark them as dirty with the priority they were scheduled with
+    Immediate(ScopeId, UpdatePriority),
 
     /// A task has woken and needs to be progressed
     TaskNotified(slotmap::DefaultKey),
@@ -7,7 +7,10 @@ use crate::properties::RootProps;
 use crate::root_wrapper::RootScopeWrapper;
 use crate::{
     arena::ElementId,
-    innerlude::{NoOpMutations, SchedulerMsg, ScopeOrder, ScopeState, VProps, WriteMutations},
+    innerlude::{
+        DirtyScopes, NoOpMutations, SchedulerMsg, ScopeOrder, ScopeState, UpdatePriority, VProps,
+        WriteMutations,
+    },
     runtime::{Runtime, RuntimeGuard},
     scopes::ScopeId,
     ComponentFunction, Element, Mutations,
@@ -15,9 +18,9 @@ use crate::{
 use crate::{Task, VComponent};
 use futures_util::StreamExt;
 use slab::Slab;
-use std::collections::BTreeSet;
 use std::{any::Any, rc::Rc};
 use tracing::instrument;
+use web_time::Instant;
 
 /// A virtual node system that progresses user events and diffs UI trees.
 ///
@@ -205,7 +208,7 @@ use tracing::instrument;
 pub struct VirtualDom {
     pub(crate) scopes: Slab<ScopeState>,
 
-    pub(crate) dirty_scopes: BTreeSet<ScopeOrder>,
+    pub(crate) dirty_scopes: DirtyScopes,
 
     pub(crate) runtime: Rc<Runtime>,
 
@@ -334,6 +337,29 @@ impl VirtualDom {
         dom
     }
 
+    /// Get an iterator over the state of every scope in the VirtualDom. This is useful for debugging tools that
+    /// inspect the component tree.
+    pub fn scopes(&self) -> impl Iterator<Item = &ScopeState> {
+        self.scopes.iter().map(|(_, scope)| scope)
+    }
+
+    /// Get every render recorded since the VirtualDom was created or the profile was last taken with
+    /// [`Self::take_render_profile`].
+    ///
+    /// This is only available with the `profiling` feature. See the [`crate::profiling`] module for more details.
+    #[cfg(feature = "profiling")]
+    pub fn render_profile(&self) -> std::cell::Ref<'_, crate::profiling::RenderProfile> {
+        std::cell::Ref::map(self.runtime.profiler.borrow(), |profiler| &profiler.profile)
+    }
+
+    /// Take every render recorded so far, leaving an empty profile to record new renders into.
+    ///
+    /// This is only available with the `profiling` feature. See the [`crate::profiling`] module for more details.
+    #[cfg(feature = "profiling")]
+    pub fn take_render_profile(&mut self) -> crate::profiling::RenderProfile {
+        std::mem::take(&mut self.runtime.profiler.borrow_mut().profile)
+    }
+
     /// Get the state for any scope given its ID
     ///
     /// This is useful for inserting or removing contexts from a scope, or rendering out its root node
@@ -377,16 +403,27 @@ impl VirtualDom {
 
     /// Manually mark a scope as requiring a re-render
     ///
-    /// Whenever the Runtime "works", it will re-render this scope
+    /// Whenever the Runtime "works", it will re-render this scope. The scope is queued with the
+    /// [`UpdatePriority::current`] priority.
     pub fn mark_dirty(&mut self, id: ScopeId) {
+        self.mark_dirty_with_priority(id, UpdatePriority::current());
+    }
+
+    /// Manually mark a scope as requiring a re-render with a specific [`UpdatePriority`]
+    pub fn mark_dirty_with_priority(&mut self, id: ScopeId, priority: UpdatePriority) {
         let Some(scope) = self.runtime.get_state(id) else {
             return;
         };
 
-        tracing::event!(tracing::Level::TRACE, "Marking scope {:?} as dirty", id);
+        tracing::event!(
+            tracing::Level::TRACE,
+            "Marking scope {:?} as dirty with {:?} priority",
+            id,
+            priority
+        );
         let order = ScopeOrder::new(scope.height(), id);
         drop(scope);
-        self.queue_scope(order);
+        self.queue_scope(order, priority);
     }
 
     /// Mark a task as dirty
@@ -451,7 +488,7 @@ impl VirtualDom {
     #[instrument(skip(self), level = "trace", name = "VirtualDom::wait_for_event")]
     async fn wait_for_event(&mut self) {
         match self.rx.next().await.expect("channel should never close") {
-            SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+            SchedulerMsg::Immediate(id, priority) => self.mark_dirty_with_priority(id, priority),
             SchedulerMsg::TaskNotified(id) => {
                 // Instead of running the task immediately, we insert it into the runtime's task queue.
                 // The task may be marked dirty at the same time as the scope that owns the task is dropped.
@@ -466,7 +503,9 @@ impl VirtualDom {
         // Prevent a task from deadlocking the runtime by repeatedly queueing itself
         while let Ok(Some(msg)) = self.rx.try_next() {
             match msg {
-                SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                SchedulerMsg::Immediate(id, priority) => {
+                    self.mark_dirty_with_priority(id, priority)
+                }
                 SchedulerMsg::TaskNotified(task) => self.mark_task_dirty(Task::from_id(task)),
                 SchedulerMsg::EffectQueued => {}
             }
@@ -574,17 +613,76 @@ impl VirtualDom {
 
     /// Render whatever the VirtualDom has ready as fast as possible without requiring an executor to progress
     /// suspended subtrees.
+    ///
+    /// Dirty scopes are rerun in order of their [`UpdatePriority`]. To split up large updates, use
+    /// [`VirtualDom::render_with_deadline`] instead.
     #[instrument(skip(self, to), level = "trace", name = "VirtualDom::render_immediate")]
     pub fn render_immediate(&mut self, to: &mut impl WriteMutations) {
+        self.render_until(to, None);
+    }
+
+    /// Render the VirtualDom until all work is finished or the deadline has passed. Returns `true` if all work was
+    /// finished and `false` if rendering was interrupted.
+    ///
+    /// The deadline is checked between scopes, so a single component that takes a long time to render can still run
+    /// past it. Updates with [`UserInput`](UpdatePriority::UserInput) priority are always rendered. Any other work
+    /// left when the deadline passes stays queued: [`VirtualDom::wait_for_work`] will return immediately and the
+    /// next render continues where this one stopped, after any more urgent updates that were scheduled in between.
+    ///
+    /// # Example
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_core::*;
+    /// # fn app() -> Element { rsx! { div {} } }
+    /// # async fn run() {
+    /// let mut dom = VirtualDom::new(app);
+    /// let mut mutations = Mutations::default();
+    /// dom.rebuild(&mut mutations);
+    ///
+    /// loop {
+    ///     dom.wait_for_work().await;
+    ///
+    ///     // Render for at most one frame, then go back to the event loop to handle user input
+    ///     let deadline = std::time::Instant::now() + std::time::Duration::from_millis(16);
+    ///     dom.render_with_deadline(&mut mutations, deadline);
+    /// }
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, to),
+        level = "trace",
+        name = "VirtualDom::render_with_deadline"
+    )]
+    pub fn render_with_deadline(
+        &mut self,
+        to: &mut impl WriteMutations,
+        deadline: Instant,
+    ) -> bool {
+        self.render_until(to, Some(deadline))
+    }
+
+    /// Render dirty scopes and poll tasks until there is no work left or the deadline has passed
+    fn render_until(&mut self, to: &mut impl WriteMutations, deadline: Option<Instant>) -> bool {
         // Process any events that might be pending in the queue
         // Signals marked with .write() need a chance to be handled by the effect driver
         // This also processes futures which might progress into immediately rerunning a scope
         self.process_events();
 
         // Next, diff any dirty scopes
-        // We choose not to poll the deadline since we complete pretty quickly anyways
         let _runtime = RuntimeGuard::new(self.runtime.clone());
-        while let Some(work) = self.pop_work() {
+        let mut finished = true;
+        loop {
+            // Yield between units of work once the deadline passes unless there are urgent updates left
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline)
+                && self.next_scope_priority() != Some(UpdatePriority::UserInput)
+            {
+                finished = !self.has_dirty_scopes() && self.runtime.dirty_tasks.borrow().is_empty();
+                break;
+            }
+            let Some(work) = self.pop_work() else {
+                break;
+            };
             match work {
                 Work::PollTask(task) => {
                     _ = self.runtime.handle_task_wakeup(task);
@@ -599,6 +697,7 @@ impl VirtualDom {
         }
 
         self.runtime.finish_render();
+        finished
     }
 
     /// [`Self::render_immediate`] to a vector of mutations for testing purposes
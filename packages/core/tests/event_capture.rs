@@ -0,0 +1,168 @@
+//! Capture listeners run from the root down to the target before the bubbling listeners, like the capture phase of
+//! events in the DOM: <https://dom.spec.whatwg.org/#concept-event-dispatch>
+
+use dioxus::prelude::*;
+use dioxus_core::{ElementId, Mutation};
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+thread_local! {
+    static CALLS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    static STOP_AT: RefCell<Option<&'static str>> = const { RefCell::new(None) };
+}
+
+/// Record a listener call and stop propagation if the test asked to stop at this listener
+fn record(name: &'static str, event: &Event<impl ?Sized>) {
+    CALLS.with_borrow_mut(|calls| calls.push(name));
+    if STOP_AT.with_borrow(|stop| *stop == Some(name)) {
+        event.stop_propagation();
+    }
+}
+
+fn app() -> Element {
+    rsx! {
+        div {
+            onclick_capture: move |event| record("outer capture", &event),
+            onclick: move |event| record("outer bubble", &event),
+            onfocus_capture: move |event| record("outer focus capture", &event),
+            onfocus: move |event| record("outer focus", &event),
+            Inner {}
+        }
+    }
+}
+
+#[component]
+fn Inner() -> Element {
+    rsx! {
+        div {
+            onclick_capture: move |event| record("inner capture", &event),
+            onclick: move |event| record("inner bubble", &event),
+            button {
+                onclick: move |event| record("target bubble", &event),
+                onclick_capture: move |event| record("target capture", &event),
+                onfocus: move |event| record("target focus", &event),
+            }
+        }
+    }
+}
+
+/// Render the app and get the id of the button
+fn setup(stop_at: Option<&'static str>) -> (VirtualDom, ElementId) {
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+    CALLS.with_borrow_mut(Vec::clear);
+    STOP_AT.with_borrow_mut(|stop| *stop = stop_at);
+
+    let mut dom = VirtualDom::new(app);
+    let mutations = dom.rebuild_to_vec();
+    let target = mutations
+        .edits
+        .iter()
+        .rev()
+        .find_map(|edit| match edit {
+            Mutation::NewEventListener { name, id } if name == "focus" => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    (dom, target)
+}
+
+fn dispatch(dom: &VirtualDom, name: &str, target: ElementId, bubbles: bool) -> Vec<&'static str> {
+    let data: Box<dyn Any> = match name {
+        "focus" => Box::<SerializedFocusData>::default(),
+        _ => Box::<SerializedMouseData>::default(),
+    };
+    let event = Event::new(
+        Rc::new(PlatformEventData::new(data)) as Rc<dyn Any>,
+        bubbles,
+    );
+    dom.runtime().handle_event(name, event, target);
+    CALLS.with_borrow_mut(std::mem::take)
+}
+
+#[test]
+fn capture_listeners_create_capture_event_listeners() {
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+    let mut dom = VirtualDom::new(app);
+    let mutations = dom.rebuild_to_vec();
+    let listeners: Vec<_> = mutations
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Mutation::NewEventListener { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert!(listeners.contains(&"click:capture"));
+    assert!(listeners.contains(&"focus:capture"));
+    assert!(listeners.contains(&"click"));
+}
+
+#[test]
+fn capture_runs_before_bubbling() {
+    let (dom, target) = setup(None);
+    assert_eq!(
+        dispatch(&dom, "click", target, true),
+        [
+            "outer capture",
+            "inner capture",
+            "target capture",
+            "target bubble",
+            "inner bubble",
+            "outer bubble",
+        ]
+    );
+}
+
+#[test]
+fn stopping_in_a_parent_capture_listener_stops_the_event() {
+    let (dom, target) = setup(Some("outer capture"));
+    assert_eq!(dispatch(&dom, "click", target, true), ["outer capture"]);
+
+    let (dom, target) = setup(Some("inner capture"));
+    assert_eq!(
+        dispatch(&dom, "click", target, true),
+        ["outer capture", "inner capture"]
+    );
+}
+
+#[test]
+fn stopping_at_the_target_still_calls_the_target() {
+    let (dom, target) = setup(Some("target capture"));
+    assert_eq!(
+        dispatch(&dom, "click", target, true),
+        [
+            "outer capture",
+            "inner capture",
+            "target capture",
+            "target bubble"
+        ]
+    );
+}
+
+#[test]
+fn stopping_while_bubbling_still_runs_the_capture_phase() {
+    let (dom, target) = setup(Some("target bubble"));
+    assert_eq!(
+        dispatch(&dom, "click", target, true),
+        [
+            "outer capture",
+            "inner capture",
+            "target capture",
+            "target bubble"
+        ]
+    );
+}
+
+#[test]
+fn non_bubbling_events_are_captured() {
+    let (dom, target) = setup(None);
+    assert_eq!(
+        dispatch(&dom, "focus", target, false),
+        ["outer focus capture", "target focus"]
+    );
+
+    let (dom, target) = setup(Some("outer focus capture"));
+    assert_eq!(
+        dispatch(&dom, "focus", target, false),
+        ["outer focus capture"]
+    );
+}
@@ -0,0 +1,166 @@
+//! Dirty scopes are rerun in order of the priority they were marked dirty with, and less urgent work can be
+//! interrupted with a deadline.
+
+use dioxus::prelude::*;
+use dioxus_core::{ElementId, NoOpMutations, UpdatePriority};
+use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+use web_time::{Duration, Instant};
+
+thread_local! {
+    static RENDERS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    static SCOPES: RefCell<HashMap<&'static str, ScopeId>> = RefCell::new(HashMap::new());
+}
+
+/// Record a render of the current component
+fn rendered(name: &'static str) {
+    RENDERS.with_borrow_mut(|renders| renders.push(name));
+    SCOPES.with_borrow_mut(|scopes| scopes.insert(name, current_scope_id().unwrap()));
+}
+
+fn scope(name: &str) -> ScopeId {
+    SCOPES.with_borrow(|scopes| scopes[name])
+}
+
+fn take_renders() -> Vec<&'static str> {
+    RENDERS.with_borrow_mut(std::mem::take)
+}
+
+fn app() -> Element {
+    rendered("app");
+    rsx! {
+        Parent {}
+        Sibling {}
+    }
+}
+
+#[component]
+fn Parent() -> Element {
+    rendered("parent");
+    rsx! { Child {} }
+}
+
+#[component]
+fn Child() -> Element {
+    rendered("child");
+    rsx! { "child" }
+}
+
+#[component]
+fn Sibling() -> Element {
+    rendered("sibling");
+    rsx! { "sibling" }
+}
+
+fn setup() -> VirtualDom {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    take_renders();
+    dom
+}
+
+#[test]
+fn urgent_updates_render_first() {
+    let mut dom = setup();
+
+    dom.in_runtime(|| {
+        start_transition(|| scope("child").needs_update());
+        scope("sibling").needs_update();
+        UpdatePriority::UserInput.run(|| scope("parent").needs_update());
+    });
+    dom.render_immediate(&mut NoOpMutations);
+
+    assert_eq!(take_renders(), ["parent", "sibling", "child"]);
+}
+
+#[test]
+fn transitions_run_after_default_updates() {
+    let mut dom = setup();
+
+    dom.in_runtime(|| {
+        start_transition(|| scope("parent").needs_update());
+        scope("sibling").needs_update();
+    });
+    dom.render_immediate(&mut NoOpMutations);
+
+    assert_eq!(take_renders(), ["sibling", "parent"]);
+}
+
+#[test]
+fn dirty_parents_in_less_urgent_lanes_run_first() {
+    let mut dom = setup();
+
+    dom.mark_dirty_with_priority(scope("parent"), UpdatePriority::Transition);
+    dom.mark_dirty_with_priority(scope("child"), UpdatePriority::UserInput);
+    dom.render_immediate(&mut NoOpMutations);
+
+    // The parent may drop the child, so it reruns before the child even though the child is more urgent
+    assert_eq!(take_renders(), ["parent", "child"]);
+}
+
+#[test]
+fn deadline_interrupts_transitions() {
+    let mut dom = setup();
+
+    dom.in_runtime(|| {
+        start_transition(|| scope("parent").needs_update());
+        UpdatePriority::UserInput.run(|| scope("sibling").needs_update());
+    });
+
+    // User input is rendered even if the deadline already passed
+    let deadline = Instant::now() - Duration::from_millis(1);
+    assert!(!dom.render_with_deadline(&mut NoOpMutations, deadline));
+    assert_eq!(take_renders(), ["sibling"]);
+
+    // The transition continues in the next render
+    let deadline = Instant::now() + Duration::from_secs(60);
+    assert!(dom.render_with_deadline(&mut NoOpMutations, deadline));
+    assert_eq!(take_renders(), ["parent"]);
+}
+
+#[test]
+fn event_handlers_schedule_user_input_updates() {
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+    fn app() -> Element {
+        let mut count = use_signal(|| 0);
+        let mut filter = use_signal(|| 0);
+        rsx! {
+            button {
+                onclick: move |_| {
+                    start_transition(move || filter += 1);
+                    count += 1;
+                },
+            }
+            Count { count }
+            Filter { filter }
+        }
+    }
+
+    #[component]
+    fn Count(count: ReadOnlySignal<i32>) -> Element {
+        rendered("count");
+        rsx! { "{count}" }
+    }
+
+    #[component]
+    fn Filter(filter: ReadOnlySignal<i32>) -> Element {
+        rendered("filter");
+        rsx! { "{filter}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    take_renders();
+
+    let event = Event::new(
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())) as Rc<dyn Any>,
+        true,
+    );
+    dom.runtime().handle_event("click", event, ElementId(1));
+
+    let deadline = Instant::now() - Duration::from_millis(1);
+    assert!(!dom.render_with_deadline(&mut NoOpMutations, deadline));
+    assert_eq!(take_renders(), ["count"]);
+
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(take_renders(), ["filter"]);
+}
@@ -187,6 +187,13 @@ impl App {
     pub fn handle_close_requested(&mut self, id: WindowId) {
         use WindowCloseBehaviour::*;
 
+        // Let navigation blockers keep the window open
+        if let Some(webview) = self.webviews.get(&id) {
+            if webview.desktop_context.close_blocked.get() && !crate::history::confirm_close() {
+                return;
+            }
+        }
+
         match self.window_behavior {
             LastWindowExitsApp => {
                 #[cfg(debug_assertions)]
@@ -283,6 +290,7 @@ impl App {
             .with_mutation_state_mut(|f| view.dom.rebuild(f));
 
         view.edits.wry_queue.send_edits();
+        view.scroll_to_restore = view.history.take_scroll_to_restore();
 
         view.desktop_context
             .window
@@ -313,6 +321,18 @@ impl App {
         view.desktop_context.query.send(result);
     }
 
+    pub fn handle_scroll_msg(&mut self, msg: IpcMessage, id: WindowId) {
+        let Ok(scroll) = serde_json::from_value(msg.params()) else {
+            return;
+        };
+
+        let Some(view) = self.webviews.get(&id) else {
+            return;
+        };
+
+        view.history.set_scroll(scroll);
+    }
+
     #[cfg(all(feature = "devtools", debug_assertions))]
     pub fn handle_hot_reload_msg(&mut self, msg: dioxus_devtools::DevserverMsg) {
         use dioxus_devtools::DevserverMsg;
@@ -339,6 +359,15 @@ impl App {
             DevserverMsg::Shutdown => {
                 self.control_flow = ControlFlow::Exit;
             }
+            DevserverMsg::Inspect(query) => {
+                // The inspector only shows the tree of one window
+                if let Some(webview) = self.webviews.values().next() {
+                    let response = dioxus_devtools::inspect(&webview.dom, &query);
+                    dioxus_devtools::send_to_devserver(dioxus_devtools::ClientMsg::Inspector(
+                        response,
+                    ));
+                }
+            }
         }
     }
 
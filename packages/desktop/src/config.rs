@@ -57,6 +57,7 @@ pub struct Config {
     pub(crate) disable_context_menu: bool,
     pub(crate) resource_dir: Option<PathBuf>,
     pub(crate) data_dir: Option<PathBuf>,
+    pub(crate) persist_history: bool,
     pub(crate) custom_head: Option<String>,
     pub(crate) custom_index: Option<String>,
     pub(crate) root_name: String,
@@ -102,6 +103,7 @@ impl Config {
             disable_context_menu: !cfg!(debug_assertions),
             resource_dir: None,
             data_dir: None,
+            persist_history: false,
             custom_head: None,
             custom_index: None,
             root_name: "main".to_string(),
@@ -125,6 +127,16 @@ impl Config {
         self
     }
 
+    /// Set whether the router history is saved to the data directory and restored when the app launches again, so
+    /// users come back to the page they left. The state attached to each page and the scroll position of each page is
+    /// restored as well.
+    ///
+    /// If the app has several windows, the history of the window that changed pages last is restored.
+    pub fn with_persisted_history(mut self, persist: bool) -> Self {
+        self.persist_history = persist;
+        self
+    }
+
     /// Set whether or not the right-click context menu should be disabled.
     pub fn with_disable_context_menu(mut self, disable: bool) -> Self {
         self.disable_context_menu = disable;
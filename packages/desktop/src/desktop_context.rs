@@ -12,7 +12,10 @@ use dioxus_core::{
     prelude::{Callback, ScopeId},
     VirtualDom,
 };
-use std::rc::{Rc, Weak};
+use std::{
+    cell::Cell,
+    rc::{Rc, Weak},
+};
 use tao::{
     event::Event,
     event_loop::EventLoopWindowTarget,
@@ -61,6 +64,9 @@ pub struct DesktopService {
     pub(crate) asset_handlers: AssetHandlerRegistry,
     pub(crate) file_hover: NativeFileHover,
 
+    /// Whether a navigation blocker asks for confirmation before the window closes
+    pub(crate) close_blocked: Cell<bool>,
+
     #[cfg(target_os = "ios")]
     pub(crate) views: Rc<std::cell::RefCell<Vec<*mut objc::runtime::Object>>>,
 }
@@ -88,6 +94,7 @@ impl DesktopService {
             shared,
             asset_handlers,
             file_hover,
+            close_blocked: Cell::new(false),
             query: Default::default(),
             #[cfg(target_os = "ios")]
             views: Default::default(),
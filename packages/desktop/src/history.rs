@@ -0,0 +1,226 @@
+use std::{
+    cell::Cell,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use dioxus_history::{History, HistorySnapshot, MemoryHistory, ScrollPosition};
+
+use crate::DesktopContext;
+
+/// How long the history has to stay the same before it is written to the data directory
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// The history of a window. Routes are kept in memory, and navigation blockers that block leaving the app ask the user
+/// for confirmation before the window closes.
+///
+/// If [`Config::with_persisted_history`](crate::Config::with_persisted_history) is enabled, the history is saved to
+/// the data directory shortly after the user stops navigating or scrolling and when the window closes. It is restored
+/// when the window is created.
+///
+/// The webview reports where each page is scrolled to, and the scroll position is restored when the user goes back or
+/// forward to the page.
+pub(crate) struct DesktopHistory {
+    history: MemoryHistory,
+    desktop_context: DesktopContext,
+    writer: Option<HistoryWriter>,
+    scroll_to_restore: Cell<Option<ScrollPosition>>,
+}
+
+/// Writes snapshots of the history on a background thread. Snapshots that are sent in quick succession are only
+/// written once, and the last snapshot is written before the writer is dropped.
+struct HistoryWriter {
+    sender: Option<Sender<String>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HistoryWriter {
+    fn new(file: PathBuf) -> Self {
+        let (sender, receiver) = mpsc::channel::<String>();
+        let thread = std::thread::spawn(move || {
+            while let Ok(mut json) = receiver.recv() {
+                loop {
+                    match receiver.recv_timeout(SAVE_DEBOUNCE) {
+                        Ok(newer) => json = newer,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => {
+                            write_history(&file, &json);
+                            return;
+                        }
+                    }
+                }
+                write_history(&file, &json);
+            }
+        });
+        Self {
+            sender: Some(sender),
+            thread: Some(thread),
+        }
+    }
+
+    fn save(&self, json: String) {
+        if let Some(sender) = &self.sender {
+            _ = sender.send(json);
+        }
+    }
+}
+
+impl Drop for HistoryWriter {
+    fn drop(&mut self) {
+        // Closing the channel makes the thread write the last snapshot right away
+        self.sender.take();
+        if let Some(thread) = self.thread.take() {
+            _ = thread.join();
+        }
+    }
+}
+
+fn write_history(file: &Path, json: &str) {
+    let result = file
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(file, json));
+    if let Err(err) = result {
+        tracing::error!("Failed to save the history: {err}");
+    }
+}
+
+impl DesktopHistory {
+    pub(crate) fn new(desktop_context: DesktopContext, data_dir: Option<PathBuf>) -> Self {
+        let file = data_dir.map(|dir| dir.join("history.json"));
+        let history = file
+            .as_deref()
+            .and_then(|file| std::fs::read_to_string(file).ok())
+            .and_then(
+                |json| match serde_json::from_str::<HistorySnapshot>(&json) {
+                    Ok(snapshot) => Some(MemoryHistory::from_snapshot(snapshot)),
+                    Err(err) => {
+                        tracing::warn!("Failed to restore the saved history: {err}");
+                        None
+                    }
+                },
+            )
+            .unwrap_or_default();
+        Self {
+            scroll_to_restore: Cell::new(history.scroll()),
+            history,
+            desktop_context,
+            writer: file.map(HistoryWriter::new),
+        }
+    }
+
+    /// Save the position the current page is scrolled to
+    pub(crate) fn set_scroll(&self, scroll: ScrollPosition) {
+        self.history.set_scroll(self.history.index(), scroll);
+        self.save();
+    }
+
+    /// Take the scroll position of the page the user went back or forward to, if it should be restored
+    pub(crate) fn take_scroll_to_restore(&self) -> Option<ScrollPosition> {
+        self.scroll_to_restore.take()
+    }
+
+    /// Queue the history to be saved to the data directory if it is persisted
+    fn save(&self) {
+        let Some(writer) = &self.writer else {
+            return;
+        };
+        match serde_json::to_string(&self.history.snapshot()) {
+            Ok(json) => writer.save(json),
+            Err(err) => tracing::error!("Failed to save the history: {err}"),
+        }
+    }
+}
+
+impl History for DesktopHistory {
+    fn current_route(&self) -> String {
+        self.history.current_route()
+    }
+
+    fn current_prefix(&self) -> Option<String> {
+        self.history.current_prefix()
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.history.can_go_back()
+    }
+
+    fn go_back(&self) {
+        self.history.go_back();
+        self.scroll_to_restore.set(self.history.scroll());
+        self.save();
+    }
+
+    fn can_go_forward(&self) -> bool {
+        self.history.can_go_forward()
+    }
+
+    fn go_forward(&self) {
+        self.history.go_forward();
+        self.scroll_to_restore.set(self.history.scroll());
+        self.save();
+    }
+
+    fn route_at(&self, delta: isize) -> Option<String> {
+        self.history.route_at(delta)
+    }
+
+    fn push(&self, route: String) {
+        self.history.push(route);
+        self.save();
+    }
+
+    fn push_with_state(&self, route: String, state: String) {
+        self.history.push_with_state(route, state);
+        self.save();
+    }
+
+    fn current_state(&self) -> Option<String> {
+        self.history.current_state()
+    }
+
+    fn replace(&self, path: String) {
+        self.history.replace(path);
+        self.save();
+    }
+
+    fn block_unload(&self, blocked: bool) {
+        self.desktop_context.close_blocked.set(blocked)
+    }
+}
+
+/// Ask the user if a window with a navigation blocker should close anyway. Returns `true` if the window should close.
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub(crate) fn confirm_close() -> bool {
+    let result = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Leave app?")
+        .set_description("Changes you made may not be saved.")
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show();
+    result == rfd::MessageDialogResult::Yes
+}
+
+/// Mobile platforms don't let apps stop windows from closing
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+pub(crate) fn confirm_close() -> bool {
+    true
+}
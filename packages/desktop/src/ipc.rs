@@ -62,6 +62,7 @@ pub enum IpcMethod<'a> {
     Query,
     BrowserOpen,
     Initialize,
+    Scroll,
     Other(&'a str),
 }
 
@@ -73,6 +74,7 @@ impl IpcMessage {
             "query" => IpcMethod::Query,
             "browser_open" => IpcMethod::BrowserOpen,
             "initialize" => IpcMethod::Initialize,
+            "scroll" => IpcMethod::Scroll,
             _ => IpcMethod::Other(&self.method),
         }
     }
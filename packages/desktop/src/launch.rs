@@ -101,6 +101,7 @@ pub fn launch_virtual_dom_blocking(virtual_dom: VirtualDom, mut desktop_config:
                     IpcMethod::UserEvent => {}
                     IpcMethod::Query => app.handle_query_msg(msg, id),
                     IpcMethod::BrowserOpen => app.handle_browser_open(msg),
+                    IpcMethod::Scroll => app.handle_scroll_msg(msg, id),
                     IpcMethod::Other(_) => {}
                 },
             },
@@ -15,12 +15,14 @@ mod element;
 mod event_handlers;
 mod events;
 mod file_upload;
+mod history;
 mod hooks;
 mod ipc;
 mod menubar;
 mod protocol;
 mod query;
 mod shortcut;
+mod storage;
 mod waker;
 mod webview;
 
@@ -195,6 +195,17 @@ fn module_loader(root_id: &str, headless: bool) -> String {
         }}
         window.interpreter.waitForRequest({headless});
     }}
+
+    // Report where the page is scrolled to once per frame so the history can restore it
+    let scrollReport = null;
+    window.addEventListener("scroll", () => {{
+        scrollReport ??= requestAnimationFrame(() => {{
+            scrollReport = null;
+            window.ipc.postMessage(
+                window.interpreter.serializeIpcMessage("scroll", {{ x: window.scrollX, y: window.scrollY }})
+            );
+        }});
+    }});
 </script>
 <script type="module">
     // Include the code for eval
@@ -0,0 +1,67 @@
+use std::{path::PathBuf, rc::Rc};
+
+use dioxus_document::{MemoryStorage, PersistentStorage, StorageWatchers};
+use futures_channel::mpsc::UnboundedReceiver;
+
+thread_local! {
+    // Every window runs on the main thread, so the watchers are shared between windows through a thread local
+    static WATCHERS: Rc<StorageWatchers> = Rc::new(StorageWatchers::default());
+}
+
+/// Create the persistent storage for a window. Values are stored in the data directory set with
+/// [`Config::with_data_directory`](crate::Config::with_data_directory) or the local data directory of the platform.
+pub(crate) fn create_storage(data_dir: Option<PathBuf>) -> Rc<dyn PersistentStorage> {
+    match data_dir.or_else(default_data_dir) {
+        Some(dir) => Rc::new(DesktopStorage::new(dir)),
+        None => {
+            tracing::warn!("Unable to find a data directory for persistent values. Set one with Config::with_data_directory to save them between runs.");
+            Rc::new(MemoryStorage::default())
+        }
+    }
+}
+
+pub(crate) fn default_data_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let name = exe.file_stem()?;
+    Some(dirs::data_local_dir()?.join(name))
+}
+
+/// A [`PersistentStorage`] that stores each value in a file in the data directory of the app. Values are synced between
+/// every window of the app.
+pub(crate) struct DesktopStorage {
+    dir: PathBuf,
+    watchers: Rc<StorageWatchers>,
+}
+
+impl DesktopStorage {
+    fn new(data_dir: PathBuf) -> Self {
+        Self {
+            dir: data_dir.join("persistent"),
+            watchers: WATCHERS.with(Clone::clone),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        // Keys are encoded so they can't escape the storage directory
+        self.dir.join(format!("{}.json", urlencoding::encode(key)))
+    }
+}
+
+impl PersistentStorage for DesktopStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(key)).ok()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        let result =
+            std::fs::create_dir_all(&self.dir).and_then(|_| std::fs::write(self.path(key), value));
+        if let Err(err) = result {
+            tracing::error!("Failed to save the persistent value {key:?}: {err}");
+        }
+        self.watchers.notify(key, value);
+    }
+
+    fn watch(&self, key: &str) -> UnboundedReceiver<String> {
+        self.watchers.watch(key)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::document::DesktopDocument;
 use crate::element::DesktopElement;
 use crate::file_upload::DesktopFileDragEvent;
+use crate::history::DesktopHistory;
 use crate::menubar::DioxusMenu;
 use crate::{
     app::SharedContext,
@@ -14,7 +15,7 @@ use crate::{
 };
 use dioxus_core::{Runtime, ScopeId, VirtualDom};
 use dioxus_document::Document;
-use dioxus_history::{History, MemoryHistory};
+use dioxus_history::{History, ScrollPosition};
 use dioxus_hooks::to_owned;
 use dioxus_html::{HasFileData, HtmlEvent, PlatformEventData};
 use futures_util::{pin_mut, FutureExt};
@@ -146,6 +147,10 @@ pub(crate) struct WebviewInstance {
     pub edits: WebviewEdits,
     pub desktop_context: DesktopContext,
     pub waker: Waker,
+    pub history: Rc<DesktopHistory>,
+
+    /// The scroll position to restore once the edits that were sent last are applied
+    pub scroll_to_restore: Option<ScrollPosition>,
 
     // Wry assumes the webcontext is alive for the lifetime of the webview.
     // We need to keep the webcontext alive, otherwise the webview will crash
@@ -403,11 +408,22 @@ impl WebviewInstance {
         // Provide the desktop context to the virtual dom and edit handler
         edits.set_desktop_context(desktop_context.clone());
         let provider: Rc<dyn Document> = Rc::new(DesktopDocument::new(desktop_context.clone()));
-        let history_provider: Rc<dyn History> = Rc::new(MemoryHistory::default());
+        let history_file = cfg.persist_history.then(|| {
+            cfg.data_dir
+                .clone()
+                .or_else(crate::storage::default_data_dir)
+        });
+        let history = Rc::new(DesktopHistory::new(
+            desktop_context.clone(),
+            history_file.flatten(),
+        ));
+        let history_provider: Rc<dyn History> = history.clone();
+        let storage_provider = crate::storage::create_storage(cfg.data_dir.clone());
         dom.in_runtime(|| {
             ScopeId::ROOT.provide_context(desktop_context.clone());
             ScopeId::ROOT.provide_context(provider);
             ScopeId::ROOT.provide_context(history_provider);
+            ScopeId::ROOT.provide_context(storage_provider);
         });
 
         WebviewInstance {
@@ -415,6 +431,8 @@ impl WebviewInstance {
             edits,
             waker: tao_waker(shared.proxy.clone(), desktop_context.window.id()),
             desktop_context,
+            history,
+            scroll_to_restore: None,
             _menu: menu,
             _web_context: web_context,
         }
@@ -433,6 +451,14 @@ impl WebviewInstance {
                 return;
             }
 
+            // The page the user went back or forward to has been rendered, so it can be scrolled to where they left it
+            if let Some(scroll) = self.scroll_to_restore.take() {
+                _ = self
+                    .desktop_context
+                    .webview
+                    .evaluate_script(&format!("window.scrollTo({}, {})", scroll.x, scroll.y));
+            }
+
             {
                 let fut = self.dom.wait_for_work();
                 pin_mut!(fut);
@@ -450,6 +476,7 @@ impl WebviewInstance {
                 .wry_queue
                 .with_mutation_state_mut(|f| self.dom.render_immediate(f));
             self.edits.wry_queue.send_edits();
+            self.scroll_to_restore = self.history.take_scroll_to_restore();
         }
     }
 
@@ -20,6 +20,9 @@ pub enum DevserverMsg {
 
     /// The program is shutting down completely - maybe toss up a splash screen or something?
     Shutdown,
+
+    /// Inspect the live component tree of the app. The app answers with a [`ClientMsg::Inspector`] message.
+    Inspect(InspectorQuery),
 }
 
 /// A message the client sends from the frontend to the devserver
@@ -31,6 +34,67 @@ pub enum ClientMsg {
         level: String,
         messages: Vec<String>,
     },
+
+    /// The answer to a [`DevserverMsg::Inspect`] query
+    Inspector(InspectorResponse),
+}
+
+/// A query the devserver sends to inspect the component tree of the app
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum InspectorQuery {
+    /// Get a summary of every scope in the component tree
+    ScopeTree,
+
+    /// Get the props and hooks of a single scope
+    Scope { id: usize },
+}
+
+/// The answer to an [`InspectorQuery`]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum InspectorResponse {
+    /// Every scope in the component tree, sorted so parents come before their children
+    ScopeTree(Vec<ScopeSummary>),
+
+    /// The details of a scope, or `None` if the scope doesn't exist anymore
+    Scope {
+        id: usize,
+        details: Option<ScopeDetails>,
+    },
+}
+
+/// A summary of a scope in the component tree
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScopeSummary {
+    pub id: usize,
+    pub parent: Option<usize>,
+    pub height: u32,
+    pub name: String,
+    pub render_count: usize,
+}
+
+/// The props and hooks of a scope
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScopeDetails {
+    pub scope: ScopeSummary,
+
+    /// The name and debug representation of each prop
+    pub props: Vec<(String, String)>,
+
+    /// The hooks of the scope in the order they were created
+    pub hooks: Vec<HookDetails>,
+}
+
+/// A hook of a scope
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HookDetails {
+    pub index: usize,
+    pub type_name: String,
+
+    /// The debug representation of the value of the hook if the type of the hook is known to the inspector
+    pub value: Option<String>,
+
+    /// The scopes subscribed to the hook if it is a signal or memo
+    pub subscribers: Vec<usize>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
@@ -0,0 +1,215 @@
+//! Inspect the live component tree of a [`VirtualDom`] for the devtools.
+//!
+//! Hooks are stored without their types, so the inspector can only show the value of hooks, signals and memos with
+//! a type it knows about. Common types like numbers and strings are known by default and more types can be added with
+//! [`register_inspectable`].
+
+use dioxus_core::{ScopeId, ScopeState, VirtualDom};
+use dioxus_devtools_types::{
+    HookDetails, InspectorQuery, InspectorResponse, ScopeDetails, ScopeSummary,
+};
+use dioxus_signals::{CopyValue, Memo, Readable, Signal};
+use std::{any::Any, cell::RefCell, fmt::Debug};
+
+/// Values longer than this are cut off so a large list in a hook doesn't flood the devserver connection
+const MAX_VALUE_LEN: usize = 512;
+
+/// Format a type erased hook if it holds a known type
+type HookInspector = fn(&dyn Any) -> Option<HookValue>;
+
+struct HookValue {
+    value: Option<String>,
+    subscribers: Vec<ScopeId>,
+}
+
+thread_local! {
+    static HOOK_INSPECTORS: RefCell<Vec<HookInspector>> = RefCell::new(default_inspectors());
+}
+
+macro_rules! inspectors {
+    ($($ty:ty),* $(,)?) => {
+        vec![$(inspect_hook::<$ty>),*]
+    };
+}
+
+fn default_inspectors() -> Vec<HookInspector> {
+    inspectors![
+        bool,
+        char,
+        i8,
+        i16,
+        i32,
+        i64,
+        i128,
+        isize,
+        u8,
+        u16,
+        u32,
+        u64,
+        u128,
+        usize,
+        f32,
+        f64,
+        String,
+        &'static str,
+        Option<bool>,
+        Option<i32>,
+        Option<i64>,
+        Option<usize>,
+        Option<f64>,
+        Option<String>,
+        Vec<i32>,
+        Vec<String>,
+    ]
+}
+
+/// Let the inspector show the value of hooks, signals, memos and copy values that hold a `T`.
+///
+/// ```rust
+/// #[derive(Debug, PartialEq)]
+/// struct Todo {
+///     title: String,
+///     done: bool,
+/// }
+///
+/// dioxus_devtools::register_inspectable::<Vec<Todo>>();
+/// ```
+pub fn register_inspectable<T: Debug + PartialEq + 'static>() {
+    HOOK_INSPECTORS.with_borrow_mut(|inspectors| inspectors.push(inspect_hook::<T>));
+}
+
+fn inspect_hook<T: Debug + PartialEq + 'static>(hook: &dyn Any) -> Option<HookValue> {
+    if let Some(signal) = hook.downcast_ref::<Signal<T>>() {
+        return Some(HookValue {
+            value: signal.try_peek().ok().map(|value| format_value(&*value)),
+            subscribers: signal.subscribers(),
+        });
+    }
+    if let Some(memo) = hook.downcast_ref::<Memo<T>>() {
+        return Some(HookValue {
+            value: memo.try_peek().ok().map(|value| format_value(&*value)),
+            subscribers: memo.subscribers(),
+        });
+    }
+    if let Some(value) = hook.downcast_ref::<CopyValue<T>>() {
+        return Some(HookValue {
+            value: value.try_peek().ok().map(|value| format_value(&*value)),
+            subscribers: Vec::new(),
+        });
+    }
+    hook.downcast_ref::<T>().map(|value| HookValue {
+        value: Some(format_value(value)),
+        subscribers: Vec::new(),
+    })
+}
+
+fn format_value(value: &impl Debug) -> String {
+    truncate(format!("{value:?}"))
+}
+
+fn truncate(mut value: String) -> String {
+    if value.len() > MAX_VALUE_LEN {
+        let mut end = MAX_VALUE_LEN;
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+        value.push('…');
+    }
+    value
+}
+
+/// Answer an inspector query from the devserver with the current state of the [`VirtualDom`]
+pub fn inspect(dom: &VirtualDom, query: &InspectorQuery) -> InspectorResponse {
+    match *query {
+        InspectorQuery::ScopeTree => {
+            let mut scopes: Vec<_> = dom.scopes().map(summarize).collect();
+            scopes.sort_by_key(|scope| (scope.height, scope.id));
+            InspectorResponse::ScopeTree(scopes)
+        }
+        InspectorQuery::Scope { id } => InspectorResponse::Scope {
+            id,
+            details: dom
+                .get_scope(ScopeId(id))
+                .map(|scope| dom.in_runtime(|| details(scope))),
+        },
+    }
+}
+
+fn summarize(scope: &ScopeState) -> ScopeSummary {
+    ScopeSummary {
+        id: scope.id().0,
+        parent: scope.parent_id().map(|id| id.0),
+        height: scope.height(),
+        name: short_name(scope.name()),
+        render_count: scope.render_count(),
+    }
+}
+
+/// Strip the module path from a component name like `my_app::components::Counter<T>`
+fn short_name(name: &str) -> String {
+    let generics = name.find('<').unwrap_or(name.len());
+    let (path, generics) = name.split_at(generics);
+    let name = path.rsplit("::").next().unwrap_or(path);
+    format!("{name}{generics}")
+}
+
+fn details(scope: &ScopeState) -> ScopeDetails {
+    let props = debug_props(scope)
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), truncate(value)))
+        .collect();
+
+    ScopeDetails {
+        scope: summarize(scope),
+        props,
+        hooks: hook_details(scope),
+    }
+}
+
+fn hook_details(scope: &ScopeState) -> Vec<HookDetails> {
+    let type_names = hook_type_names(scope);
+    (0..)
+        .map_while(|index| {
+            scope.with_hook(index, |hook| {
+                HOOK_INSPECTORS
+                    .with_borrow(|inspectors| inspectors.iter().find_map(|inspect| inspect(hook)))
+            })
+        })
+        .enumerate()
+        .map(|(index, value)| HookDetails {
+            index,
+            type_name: type_names
+                .get(index)
+                .copied()
+                .unwrap_or("unknown")
+                .to_string(),
+            value: value.as_ref().and_then(|value| value.value.clone()),
+            subscribers: value
+                .map(|value| value.subscribers.into_iter().map(|id| id.0).collect())
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+#[cfg(debug_assertions)]
+fn debug_props(scope: &ScopeState) -> Vec<(&'static str, String)> {
+    scope.debug_props()
+}
+
+/// Props are only formatted in debug builds
+#[cfg(not(debug_assertions))]
+fn debug_props(_: &ScopeState) -> Vec<(&'static str, String)> {
+    Vec::new()
+}
+
+#[cfg(debug_assertions)]
+fn hook_type_names(scope: &ScopeState) -> Vec<&'static str> {
+    scope.hook_type_names()
+}
+
+/// Hook types are only recorded in debug builds
+#[cfg(not(debug_assertions))]
+fn hook_type_names(_: &ScopeState) -> Vec<&'static str> {
+    Vec::new()
+}
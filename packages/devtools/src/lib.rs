@@ -3,6 +3,14 @@ pub use dioxus_devtools_types::*;
 use dioxus_signals::{GlobalKey, Writable};
 use warnings::Warning;
 
+mod inspector;
+pub use inspector::*;
+
+/// Messages waiting to be sent to the devserver by the connection started with [`connect`]
+#[cfg(not(target_arch = "wasm32"))]
+static OUTGOING: std::sync::Mutex<Option<std::sync::mpsc::Sender<ClientMsg>>> =
+    std::sync::Mutex::new(None);
+
 /// Applies template and literal changes to the VirtualDom
 ///
 /// Assets need to be handled by the renderer.
@@ -29,23 +37,93 @@ pub fn apply_changes(dom: &VirtualDom, msg: &HotReloadMsg) {
     });
 }
 
-/// Connect to the devserver and handle its messages with a callback.
+/// Connect to the devserver and handle its messages with a callback. Messages can be sent back to the devserver
+/// with [`send_to_devserver`].
 ///
-/// This doesn't use any form of security or protocol, so it's not safe to expose to the internet.
+/// This doesn't use any form of security or protocol, so it's not safe to expose to the internet. Devservers served
+/// over https need the `native-tls` or `rustls` feature.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn connect(endpoint: String, mut callback: impl FnMut(DevserverMsg) + Send + 'static) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    *OUTGOING.lock().unwrap() = Some(tx);
+
     std::thread::spawn(move || {
-        let (mut websocket, _req) = match tungstenite::connect(endpoint.clone()) {
-            Ok((websocket, req)) => (websocket, req),
-            Err(_) => return,
+        let Some((mut websocket, socket)) = connect_websocket(endpoint) else {
+            return;
         };
 
-        while let Ok(msg) = websocket.read() {
-            if let tungstenite::Message::Text(text) = msg {
-                if let Ok(msg) = serde_json::from_str(&text) {
-                    callback(msg);
+        // Stop waiting for the devserver every so often to send any queued messages. The timeout is set on the socket
+        // under the websocket so it applies to plain and TLS connections
+        _ = socket.set_read_timeout(Some(std::time::Duration::from_millis(50)));
+
+        loop {
+            while let Ok(msg) = rx.try_recv() {
+                let Ok(text) = serde_json::to_string(&msg) else {
+                    continue;
+                };
+                if websocket.send(tungstenite::Message::Text(text)).is_err() {
+                    return;
                 }
             }
+
+            match websocket.read() {
+                Ok(tungstenite::Message::Text(text)) => {
+                    if let Ok(msg) = serde_json::from_str(&text) {
+                        callback(msg);
+                    }
+                }
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(err))
+                    if matches!(
+                        err.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) => {}
+                Err(_) => return,
+            }
         }
     });
 }
+
+/// Open a websocket to the devserver along with a handle to the TCP socket under it
+#[cfg(not(target_arch = "wasm32"))]
+fn connect_websocket(
+    endpoint: String,
+) -> Option<(
+    tungstenite::WebSocket<tungstenite::stream::MaybeTlsStream<std::net::TcpStream>>,
+    std::net::TcpStream,
+)> {
+    use tungstenite::{client::IntoClientRequest, stream::Mode};
+
+    let request = endpoint.into_client_request().ok()?;
+    let uri = request.uri();
+    let mode = tungstenite::client::uri_mode(uri).ok()?;
+    let host = uri.host()?.trim_start_matches('[').trim_end_matches(']');
+    let port = uri.port_u16().unwrap_or(match mode {
+        Mode::Plain => 80,
+        Mode::Tls => 443,
+    });
+    let stream = std::net::TcpStream::connect((host, port)).ok()?;
+    let socket = stream.try_clone().ok()?;
+
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    let (websocket, _) = tungstenite::client_tls(request, stream).ok()?;
+
+    #[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+    let (websocket, _) = match mode {
+        Mode::Plain => {
+            tungstenite::client(request, tungstenite::stream::MaybeTlsStream::Plain(stream)).ok()?
+        }
+        Mode::Tls => return None,
+    };
+
+    Some((websocket, socket))
+}
+
+/// Send a message to the devserver over the connection started with [`connect`]. The message is dropped if there is
+/// no connection.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn send_to_devserver(msg: ClientMsg) {
+    if let Some(tx) = OUTGOING.lock().unwrap().as_ref() {
+        _ = tx.send(msg);
+    }
+}
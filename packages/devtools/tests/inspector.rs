@@ -0,0 +1,95 @@
+//! The inspector reports the component tree, props and hooks of a running app.
+
+use dioxus::prelude::*;
+use dioxus_devtools::{inspect, InspectorQuery, InspectorResponse, ScopeSummary};
+
+fn app() -> Element {
+    let count = use_signal(|| 1);
+    rsx! {
+        Counter { label: "clicks", count }
+    }
+}
+
+#[component]
+fn Counter(label: String, count: Signal<i32>) -> Element {
+    let doubled = use_memo(move || count() * 2);
+    let _untracked = use_hook(|| vec![1, 2, 3]);
+    rsx! { "{label}: {count} {doubled}" }
+}
+
+fn scope_tree(dom: &VirtualDom) -> Vec<ScopeSummary> {
+    match inspect(dom, &InspectorQuery::ScopeTree) {
+        InspectorResponse::ScopeTree(scopes) => scopes,
+        other => panic!("expected a scope tree, got {other:?}"),
+    }
+}
+
+#[test]
+fn reports_scope_tree() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    let scopes = scope_tree(&dom);
+    let names: Vec<_> = scopes.iter().map(|scope| scope.name.as_str()).collect();
+    assert!(names.contains(&"app"));
+    assert!(names.contains(&"Counter"));
+
+    let counter = scopes.iter().find(|scope| scope.name == "Counter").unwrap();
+    let app = scopes.iter().find(|scope| scope.name == "app").unwrap();
+    assert!(counter.height > app.height);
+    assert_eq!(counter.render_count, 1);
+}
+
+// Props and hook types are only recorded in debug builds
+#[test]
+#[cfg(debug_assertions)]
+fn reports_scope_details() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    let counter = scope_tree(&dom)
+        .into_iter()
+        .find(|scope| scope.name == "Counter")
+        .unwrap();
+    let InspectorResponse::Scope {
+        details: Some(details),
+        ..
+    } = inspect(&dom, &InspectorQuery::Scope { id: counter.id })
+    else {
+        panic!("expected the details of the counter scope");
+    };
+
+    assert_eq!(
+        details.props,
+        [
+            ("label".to_string(), "\"clicks\"".to_string()),
+            ("count".to_string(), "1".to_string()),
+        ]
+    );
+
+    let hook = |ty: &str| {
+        details
+            .hooks
+            .iter()
+            .find(|hook| hook.type_name.contains(ty))
+            .unwrap()
+    };
+
+    let memo = hook("Memo<i32>");
+    assert_eq!(memo.value.as_deref(), Some("2"));
+    assert_eq!(memo.subscribers, [counter.id]);
+
+    let list = hook("Vec<i32>");
+    assert_eq!(list.value.as_deref(), Some("[1, 2, 3]"));
+}
+
+#[test]
+fn missing_scope_has_no_details() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    assert!(matches!(
+        inspect(&dom, &InspectorQuery::Scope { id: 1000 }),
+        InspectorResponse::Scope { details: None, .. }
+    ));
+}
@@ -33,17 +33,24 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "html")))]
     pub use dioxus_history::{history, History};
 
+    #[cfg(feature = "html")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "html")))]
+    pub use dioxus_document::use_persistent;
+
     #[cfg(feature = "hooks")]
     pub use crate::hooks::*;
 
     #[cfg(feature = "signals")]
     pub use dioxus_signals::*;
 
+    #[cfg(feature = "signals")]
+    pub use dioxus_signals;
+
     pub use dioxus_core::prelude::*;
 
     #[cfg(feature = "macro")]
     #[allow(deprecated)]
-    pub use dioxus_core_macro::{component, rsx, Props};
+    pub use dioxus_core_macro::{component, rsx, Props, Store};
 
     #[cfg(feature = "macro")]
     pub use dioxus_config_macro::*;
@@ -10,8 +10,10 @@
 //! - `hooks`: (default) re-exports `dioxus-hooks`
 //! - `hot-reload`: (default) enables hot rsx reloading in all renderers that support it
 //! - `router`: exports the [router](https://dioxuslabs.com/learn/0.6/router) and enables any router features for the current platform
+//! - `wasm-split`: splits the components of `#[lazy]` routes into separately loaded wasm modules when the app is built with `dx`
 //! - `third-party-renderer`: Just disables warnings about no active platform when no renderers are enabled
 //! - `logger`: Enable the default tracing subscriber for Dioxus apps
+//! - `profiling`: Record how long every component render takes and why it happened. See [`dioxus_core::profiling`]
 //!
 //! Platform features (the current platform determines what platform the [`launch()`] function runs):
 //!
@@ -79,6 +81,10 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "document")))]
     pub use dioxus_history::{history, History};
 
+    #[cfg(feature = "document")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "document")))]
+    pub use dioxus_document::use_persistent;
+
     #[cfg(feature = "launch")]
     #[cfg_attr(docsrs, doc(cfg(feature = "launch")))]
     pub use crate::launch::*;
@@ -91,12 +97,16 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "signals")))]
     pub use dioxus_signals::*;
 
+    #[cfg(feature = "signals")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signals")))]
+    pub use dioxus_signals;
+
     pub use dioxus_core::prelude::*;
 
     #[cfg(feature = "macro")]
     #[cfg_attr(docsrs, doc(cfg(feature = "macro")))]
     #[allow(deprecated)]
-    pub use dioxus_core_macro::{component, rsx, Props};
+    pub use dioxus_core_macro::{component, rsx, Props, Store};
 
     #[cfg(feature = "launch")]
     #[cfg_attr(docsrs, doc(cfg(feature = "launch")))]
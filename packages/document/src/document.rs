@@ -45,6 +45,19 @@ fn create_element_in_head(
     format!(r#"{helpers};window.createElementInHead({tag}, {attributes}, {children});"#)
 }
 
+fn remove_element_in_head(tag: &str, attributes: &[(&str, String)]) -> String {
+    let attributes = format_attributes(attributes);
+    let tag = format_string_for_js(tag);
+    format!(
+        r#"for (const element of document.head.querySelectorAll({tag})) {{
+    if ({attributes}.every(([key, value]) => element.getAttribute(key) === value)) {{
+        element.remove();
+        break;
+    }}
+}}"#
+    )
+}
+
 /// A provider for document-related functionality.
 ///
 /// Provides things like a history API, a title, a way to run JS, and some other basics/essentials used
@@ -77,12 +90,23 @@ pub trait Document: 'static {
         self.eval(create_element_in_head(name, attributes, contents));
     }
 
+    /// Remove the first element in the head with the tag name and all of the attributes
+    fn remove_head_element(&self, name: &str, attributes: &[(&str, String)]) {
+        self.eval(remove_element_in_head(name, attributes));
+    }
+
     /// Create a new meta tag in the head
     fn create_meta(&self, props: MetaProps) {
         let attributes = props.attributes();
         self.create_head_element("meta", &attributes, None);
     }
 
+    /// Remove a meta tag that was created with [`Document::create_meta`] from the head
+    fn remove_meta(&self, props: MetaProps) {
+        let attributes = props.attributes();
+        self.remove_head_element("meta", &attributes);
+    }
+
     /// Create a new script tag in the head
     fn create_script(&self, props: ScriptProps) {
         let attributes = props.attributes();
@@ -117,6 +141,12 @@ pub trait Document: 'static {
         let attributes = props.attributes();
         self.create_head_element("link", &attributes, None);
     }
+
+    /// Remove a link tag that was created with [`Document::create_link`] from the head
+    fn remove_link(&self, props: LinkProps) {
+        let attributes = props.attributes();
+        self.remove_head_element("link", &attributes);
+    }
 }
 
 /// A document that does nothing
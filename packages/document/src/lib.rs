@@ -4,11 +4,13 @@ mod document;
 mod elements;
 mod error;
 mod eval;
+mod storage;
 
 pub use document::*;
 pub use elements::*;
 pub use error::*;
 pub use eval::*;
+pub use storage::*;
 
 /// Get the document provider for the current platform or a no-op provider if the platform doesn't document functionality.
 pub fn document() -> Rc<dyn Document> {
@@ -0,0 +1,213 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use dioxus_core::prelude::*;
+use dioxus_hooks::{use_effect, use_signal};
+use dioxus_signals::{CopyValue, Readable, Signal, Writable};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::StreamExt;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Get the persistent storage provider for the current platform. If the platform doesn't provide one, values are only
+/// stored in memory until the app closes.
+pub fn persistent_storage() -> Rc<dyn PersistentStorage> {
+    match try_consume_context::<Rc<dyn PersistentStorage>>() {
+        Some(storage) => storage,
+        None => {
+            tracing::error!("Unable to find a persistent storage provider in the renderer. Falling back to the in-memory storage provider.");
+            provide_root_context(Rc::new(MemoryStorage::default()) as Rc<dyn PersistentStorage>)
+        }
+    }
+}
+
+/// A key value store that keeps values between runs of the app. This is used by [`use_persistent`].
+///
+/// On web this is implemented with `localStorage`. On desktop and mobile values are written to the data directory of
+/// the app.
+pub trait PersistentStorage {
+    /// Get the value stored for a key
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Store a value for a key. This should notify every watcher of the key, including watchers in other tabs or
+    /// windows of the app.
+    fn set(&self, key: &str, value: &str);
+
+    /// Watch a key for new values. The receiver gets the new value every time the key is set.
+    fn watch(&self, key: &str) -> UnboundedReceiver<String>;
+
+    /// Check if the app is hydrating from server rendered HTML. While the app is hydrating, stored values are only
+    /// loaded after the first render so the client renders the same values as the server.
+    fn hydrating(&self) -> bool {
+        false
+    }
+}
+
+/// A list of watchers for each key in a [`PersistentStorage`]. Closed watchers are removed the next time the key is
+/// set.
+#[derive(Default)]
+pub struct StorageWatchers {
+    watchers: RefCell<HashMap<String, Vec<UnboundedSender<String>>>>,
+}
+
+impl StorageWatchers {
+    /// Create a new watcher for a key
+    pub fn watch(&self, key: &str) -> UnboundedReceiver<String> {
+        let (tx, rx) = unbounded();
+        self.watchers
+            .borrow_mut()
+            .entry(key.to_string())
+            .or_default()
+            .push(tx);
+        rx
+    }
+
+    /// Send a new value to every watcher of a key
+    pub fn notify(&self, key: &str, value: &str) {
+        if let Some(watchers) = self.watchers.borrow_mut().get_mut(key) {
+            watchers.retain(|watcher| watcher.unbounded_send(value.to_string()).is_ok());
+        }
+    }
+}
+
+/// A [`PersistentStorage`] that only keeps values in memory. Values are lost when the storage is dropped.
+#[derive(Default)]
+pub struct MemoryStorage {
+    values: RefCell<HashMap<String, String>>,
+    watchers: StorageWatchers,
+}
+
+impl PersistentStorage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.values
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        self.watchers.notify(key, value);
+    }
+
+    fn watch(&self, key: &str) -> UnboundedReceiver<String> {
+        self.watchers.watch(key)
+    }
+}
+
+/// Create a signal that is saved to the [`PersistentStorage`] of the platform every time it changes and restored the
+/// next time the app starts. Values are serialized as JSON.
+///
+/// The signal is kept in sync with every other signal that uses the same key, including signals in other tabs on web
+/// and other windows on desktop.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+/// enum Theme {
+///     Light,
+///     Dark,
+/// }
+///
+/// fn App() -> Element {
+///     let mut theme = use_persistent("theme", || Theme::Light);
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| theme.set(match theme() {
+///                 Theme::Light => Theme::Dark,
+///                 Theme::Dark => Theme::Light,
+///             }),
+///             "Toggle theme"
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Hydration
+///
+/// The server doesn't have access to the values stored on the client. If the app is hydrating server rendered HTML,
+/// the signal starts with the value from `init` on both the server and the client, and the stored value is loaded
+/// right after hydration.
+pub fn use_persistent<T: Serialize + DeserializeOwned + 'static>(
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+) -> Signal<T> {
+    let key = use_hook(|| key.to_string());
+    let storage = use_hook(persistent_storage);
+    let hydrating = use_hook(|| storage.hydrating());
+
+    // The last value that was read from or written to the storage. Values that match it don't need to be written back
+    let mut last_saved = use_hook(|| CopyValue::new(None::<String>));
+    let state = use_signal(|| {
+        let stored = match hydrating {
+            true => None,
+            false => storage
+                .get(&key)
+                .and_then(|value| deserialize(&key, &value)),
+        };
+        let value = stored.unwrap_or_else(init);
+        last_saved.set(serialize(&key, &value));
+        value
+    });
+
+    use_hook(|| {
+        let key = key.clone();
+        let storage = storage.clone();
+        // Start watching before the task is polled so values that are set before then aren't missed
+        let mut watcher = storage.watch(&key);
+        spawn(async move {
+            if hydrating {
+                if let Some(value) = storage.get(&key) {
+                    apply_stored(&key, value, state, last_saved);
+                }
+            }
+            while let Some(value) = watcher.next().await {
+                apply_stored(&key, value, state, last_saved);
+            }
+        })
+    });
+
+    use_effect(move || {
+        let Some(value) = serialize(&key, &*state.read()) else {
+            return;
+        };
+        if last_saved.peek().as_ref() != Some(&value) {
+            storage.set(&key, &value);
+            last_saved.set(Some(value));
+        }
+    });
+
+    state
+}
+
+/// Update the signal with a value from the storage if it is different from the current value
+fn apply_stored<T: DeserializeOwned + 'static>(
+    key: &str,
+    value: String,
+    mut state: Signal<T>,
+    mut last_saved: CopyValue<Option<String>>,
+) {
+    if last_saved.peek().as_ref() == Some(&value) {
+        return;
+    }
+    if let Some(new) = deserialize(key, &value) {
+        last_saved.set(Some(value));
+        state.set(new);
+    }
+}
+
+fn serialize<T: Serialize>(key: &str, value: &T) -> Option<String> {
+    serde_json::to_string(value)
+        .inspect_err(|err| {
+            tracing::error!("Failed to serialize the persistent value {key:?}: {err}")
+        })
+        .ok()
+}
+
+fn deserialize<T: DeserializeOwned>(key: &str, value: &str) -> Option<T> {
+    serde_json::from_str(value)
+        .inspect_err(|err| {
+            tracing::error!("Failed to deserialize the stored value for {key:?}: {err}")
+        })
+        .ok()
+}
@@ -0,0 +1,129 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Wake, Waker},
+};
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_document::{MemoryStorage, PersistentStorage};
+use futures_channel::mpsc::UnboundedReceiver;
+
+thread_local! {
+    static SEEN: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+}
+
+fn app() -> Element {
+    let mut count = use_persistent("count", || 0u32);
+    SEEN.with(|seen| seen.borrow_mut().push(count()));
+    provide_context(count);
+    rsx! {
+        button { onclick: move |_| count += 1, "{count}" }
+    }
+}
+
+fn dom_with_storage(storage: Rc<dyn PersistentStorage>) -> VirtualDom {
+    let mut dom = VirtualDom::new(app);
+    dom.provide_root_context(storage);
+    dom.rebuild_in_place();
+    dom
+}
+
+/// Rerun everything that changed until the virtual dom has no more work
+async fn settle(dom: &mut VirtualDom) {
+    loop {
+        let woken = Arc::new(Woken::default());
+        let waker = Waker::from(woken.clone());
+        let ready = std::pin::pin!(dom.wait_for_work())
+            .poll(&mut Context::from_waker(&waker))
+            .is_ready();
+        if ready {
+            dom.render_immediate(&mut NoOpMutations);
+            continue;
+        }
+        // Let the tasks that were polled wake up, then stop if none of them queued more work
+        tokio::task::yield_now().await;
+        if !woken.0.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+}
+
+/// A waker that records if the virtual dom was notified of new work
+#[derive(Default)]
+struct Woken(AtomicBool);
+
+impl Wake for Woken {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+fn count_of(dom: &VirtualDom) -> Signal<u32> {
+    dom.in_runtime(|| ScopeId::APP.consume_context::<Signal<u32>>().unwrap())
+}
+
+fn seen() -> Vec<u32> {
+    SEEN.with(|seen| seen.take())
+}
+
+#[tokio::test]
+async fn persistent_values_are_saved_and_synced() {
+    let storage: Rc<dyn PersistentStorage> = Rc::new(MemoryStorage::default());
+    let mut first = dom_with_storage(storage.clone());
+    let mut second = dom_with_storage(storage.clone());
+    assert_eq!(seen(), [0, 0]);
+    // The initial value is not written until it changes
+    assert_eq!(storage.get("count"), None);
+
+    first.in_runtime(|| *count_of(&first).write() = 5);
+    settle(&mut first).await;
+    assert_eq!(storage.get("count").as_deref(), Some("5"));
+
+    // The other app is updated through the watcher of the key
+    settle(&mut second).await;
+    assert_eq!(second.in_runtime(|| count_of(&second)()), 5);
+
+    // A new app starts from the stored value
+    seen();
+    let _third = dom_with_storage(storage.clone());
+    assert_eq!(seen(), [5]);
+}
+
+#[tokio::test]
+async fn hydrating_loads_the_stored_value_after_the_first_render() {
+    #[derive(Default)]
+    struct HydratingStorage(MemoryStorage);
+
+    impl PersistentStorage for HydratingStorage {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.get(key)
+        }
+
+        fn set(&self, key: &str, value: &str) {
+            self.0.set(key, value)
+        }
+
+        fn watch(&self, key: &str) -> UnboundedReceiver<String> {
+            self.0.watch(key)
+        }
+
+        fn hydrating(&self) -> bool {
+            true
+        }
+    }
+
+    let storage = Rc::new(HydratingStorage::default());
+    storage.set("count", "3");
+    let mut dom = dom_with_storage(storage.clone());
+
+    // The first render matches the server, then the stored value is loaded without being written back
+    settle(&mut dom).await;
+    assert_eq!(seen(), [0, 3]);
+    assert_eq!(storage.get("count").as_deref(), Some("3"));
+}
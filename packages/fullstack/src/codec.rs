@@ -0,0 +1,231 @@
+//! Encodings for server functions that are not built into [`server_fn`].
+//!
+//! Binary encodings like [`Cbor`](server_fn::codec::Cbor) and [`MsgPack`](server_fn::codec::MsgPack) come from
+//! [`server_fn::codec`] and can be used directly in the `#[server]` macro:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! #[server(input = Cbor, output = MsgPack)]
+//! async fn sum(values: Vec<f64>) -> Result<f64, ServerFnError> {
+//!     Ok(values.iter().sum())
+//! }
+//! ```
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use server_fn::{
+    codec::{Encoding, FromRes, IntoRes},
+    error::{NoCustomError, ServerFnErrorSerde},
+    response::{ClientRes, Res},
+    ServerFnError,
+};
+
+/// An encoding for server functions that return a stream of values. Each value is encoded with CBOR and sent to the
+/// client as soon as the server produces it.
+///
+/// A server function that uses this as its output encoding should return a [`CborStream`]:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[server(output = StreamingCbor)]
+/// async fn countdown(from: u32) -> Result<CborStream<u32>, ServerFnError> {
+///     Ok(CborStream::from(futures_util::stream::iter((0..=from).rev())))
+/// }
+/// ```
+pub struct StreamingCbor;
+
+impl Encoding for StreamingCbor {
+    const CONTENT_TYPE: &'static str = "application/x-dioxus-cbor-stream";
+    const METHOD: http::Method = http::Method::POST;
+}
+
+/// The number of bytes used to encode the length of each frame in a [`StreamingCbor`] response
+const FRAME_LENGTH_BYTES: usize = 4;
+
+/// A stream of values returned from a server function with the [`StreamingCbor`] output encoding.
+///
+/// On the client, the stream yields each value as soon as it is received from the server. Use
+/// [`use_server_stream`](crate::prelude::use_server_stream) to read the values in a component.
+pub struct CborStream<T, CustErr = NoCustomError>(
+    Pin<Box<dyn Stream<Item = Result<T, ServerFnError<CustErr>>> + Send>>,
+);
+
+impl<T> CborStream<T> {
+    /// Create a new `CborStream` from a stream of values that may fail
+    pub fn new(stream: impl Stream<Item = Result<T, ServerFnError>> + Send + 'static) -> Self {
+        Self(Box::pin(stream))
+    }
+}
+
+impl<T, CustErr> CborStream<T, CustErr> {
+    /// Consumes the wrapper, returning the stream of values
+    pub fn into_inner(self) -> impl Stream<Item = Result<T, ServerFnError<CustErr>>> + Send {
+        self.0
+    }
+}
+
+impl<S, T: 'static> From<S> for CborStream<T>
+where
+    S: Stream<Item = T> + Send + 'static,
+{
+    fn from(stream: S) -> Self {
+        Self(Box::pin(stream.map(Ok)))
+    }
+}
+
+impl<T, CustErr> Stream for CborStream<T, CustErr> {
+    type Item = Result<T, ServerFnError<CustErr>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_next(cx)
+    }
+}
+
+impl<T, CustErr> std::fmt::Debug for CborStream<T, CustErr> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CborStream").finish()
+    }
+}
+
+impl<T, CustErr, Response> IntoRes<StreamingCbor, Response, CustErr> for CborStream<T, CustErr>
+where
+    Response: Res<CustErr>,
+    T: Serialize + Send + 'static,
+    CustErr: std::fmt::Display + std::str::FromStr + 'static,
+{
+    async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
+        let frames = self.into_inner().map(|item| {
+            // Errors are sent to the client in the stream so they show up at the right position
+            let item = item.map_err(|err| err.ser().unwrap_or_default());
+            encode_frame(&item)
+        });
+        Response::try_from_stream(StreamingCbor::CONTENT_TYPE, frames)
+    }
+}
+
+impl<T, CustErr, Response> FromRes<StreamingCbor, Response, CustErr> for CborStream<T>
+where
+    Response: ClientRes<CustErr> + Send,
+    T: DeserializeOwned + Send + 'static,
+{
+    async fn from_res(res: Response) -> Result<Self, ServerFnError<CustErr>> {
+        let bytes = res.try_into_stream()?;
+        Ok(Self(Box::pin(decode_frames(bytes))))
+    }
+}
+
+/// Encode one item of the stream as a length prefixed CBOR frame
+fn encode_frame<T: Serialize, CustErr>(
+    item: &Result<T, String>,
+) -> Result<Bytes, ServerFnError<CustErr>> {
+    let mut frame = vec![0; FRAME_LENGTH_BYTES];
+    ciborium::into_writer(item, &mut frame)
+        .map_err(|err| ServerFnError::Serialization(err.to_string()))?;
+    let length = u32::try_from(frame.len() - FRAME_LENGTH_BYTES)
+        .map_err(|err| ServerFnError::Serialization(err.to_string()))?;
+    frame[..FRAME_LENGTH_BYTES].copy_from_slice(&length.to_be_bytes());
+    Ok(frame.into())
+}
+
+/// Decode a stream of chunks into the length prefixed CBOR frames they contain. Frames may be split across chunks.
+fn decode_frames<T: DeserializeOwned>(
+    bytes: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
+) -> impl Stream<Item = Result<T, ServerFnError>> + Send {
+    futures_util::stream::unfold(
+        (Box::pin(bytes), BytesMut::new(), false),
+        |(mut bytes, mut buffer, done)| async move {
+            if done {
+                return None;
+            }
+            loop {
+                if let Some(frame) = take_frame(&mut buffer) {
+                    let item = ciborium::from_reader::<Result<T, String>, _>(frame.as_ref())
+                        .map_err(|err| ServerFnError::Deserialization(err.to_string()))
+                        .and_then(|item| item.map_err(|err| ServerFnError::de(&err)));
+                    return Some((item, (bytes, buffer, false)));
+                }
+                match bytes.next().await {
+                    Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                    Some(Err(err)) => return Some((Err(err), (bytes, buffer, true))),
+                    None if buffer.is_empty() => return None,
+                    None => {
+                        let err = ServerFnError::Deserialization(
+                            "The stream ended in the middle of a value".to_string(),
+                        );
+                        return Some((Err(err), (bytes, buffer, true)));
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Take the next complete frame out of the buffer if it has been fully received
+fn take_frame(buffer: &mut BytesMut) -> Option<Bytes> {
+    let length: [u8; FRAME_LENGTH_BYTES] = buffer.get(..FRAME_LENGTH_BYTES)?.try_into().ok()?;
+    let length = u32::from_be_bytes(length) as usize;
+    if buffer.len() < FRAME_LENGTH_BYTES + length {
+        return None;
+    }
+    buffer.advance(FRAME_LENGTH_BYTES);
+    Some(buffer.split_to(length).freeze())
+}
+
+#[test]
+fn frames_round_trip_across_chunks() {
+    let items: Vec<Result<Vec<u32>, String>> = vec![
+        Ok(vec![1, 2, 3]),
+        Ok(Vec::new()),
+        Err(
+            ServerFnError::<NoCustomError>::ServerError("oops".to_string())
+                .ser()
+                .unwrap(),
+        ),
+    ];
+    let encoded: Vec<u8> = items
+        .iter()
+        .flat_map(|item| encode_frame::<_, NoCustomError>(item).unwrap().to_vec())
+        .collect();
+
+    // Split the encoded frames into chunks that don't line up with the frame boundaries
+    let chunks: Vec<Result<Bytes, ServerFnError>> = encoded
+        .chunks(3)
+        .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+        .collect();
+    let decoded: Vec<Result<Vec<u32>, ServerFnError>> =
+        block_on(decode_frames(futures_util::stream::iter(chunks)).collect());
+
+    assert_eq!(decoded.len(), 3);
+    assert_eq!(decoded[0].as_ref().unwrap(), &vec![1, 2, 3]);
+    assert!(decoded[1].as_ref().unwrap().is_empty());
+    assert_eq!(
+        decoded[2].as_ref().unwrap_err(),
+        &ServerFnError::ServerError("oops".to_string())
+    );
+}
+
+#[test]
+fn truncated_frames_are_an_error() {
+    let frame = encode_frame::<_, NoCustomError>(&Ok::<_, String>(42u8)).unwrap();
+    let chunks = vec![Ok(frame.slice(..frame.len() - 1))];
+    let decoded: Vec<Result<u8, ServerFnError>> =
+        block_on(decode_frames(futures_util::stream::iter(chunks)).collect());
+    assert!(matches!(
+        decoded.as_slice(),
+        [Err(ServerFnError::Deserialization(_))]
+    ));
+}
+
+#[cfg(test)]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
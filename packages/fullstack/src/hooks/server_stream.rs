@@ -0,0 +1,132 @@
+use dioxus_lib::prelude::*;
+use futures_util::{Stream, StreamExt};
+use server_fn::ServerFnError;
+use std::future::Future;
+
+/// Start a server function that returns a stream of values and collect the values into a signal as they arrive.
+///
+/// The stream only runs on the client after the component is mounted. Nothing is streamed while rendering on the server.
+///
+/// Like [`use_resource`], the closure that creates the stream is reactive. If it reads any signals, the stream is
+/// restarted when those signals change.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[server(output = StreamingCbor)]
+/// async fn countdown(from: u32) -> Result<CborStream<u32>, ServerFnError> {
+///     Ok(CborStream::from(futures_util::stream::iter((0..=from).rev())))
+/// }
+///
+/// fn App() -> Element {
+///     let mut from = use_signal(|| 10);
+///     // The stream restarts whenever `from` changes
+///     let countdown = use_server_stream(move || countdown(from()));
+///
+///     rsx! {
+///         button { onclick: move |_| from += 10, "Count down from {from + 10}" }
+///         for value in countdown.items().iter() {
+///             p { "{value}" }
+///         }
+///         if let Some(error) = countdown.error().as_ref() {
+///             p { "Error: {error}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_server_stream<T, S, F>(mut stream: impl FnMut() -> F + 'static) -> UseServerStream<T>
+where
+    T: 'static,
+    S: Stream<Item = Result<T, ServerFnError>> + 'static,
+    F: Future<Output = Result<S, ServerFnError>> + 'static,
+{
+    let mut items = use_signal(Vec::new);
+    let mut error = use_signal(|| None);
+    let mut finished = use_signal(|| false);
+    let restarts = use_signal(|| 0);
+    let mut task = use_signal(|| None::<Task>);
+
+    use_effect(move || {
+        // Subscribe to restarts so calling `restart` reruns this effect
+        restarts();
+        let future = stream();
+
+        if let Some(task) = task.take() {
+            task.cancel();
+        }
+        items.write().clear();
+        error.set(None);
+        finished.set(false);
+
+        task.set(Some(spawn(async move {
+            match future.await {
+                Ok(stream) => {
+                    let mut stream = std::pin::pin!(stream);
+                    while let Some(item) = stream.next().await {
+                        match item {
+                            Ok(item) => items.write().push(item),
+                            Err(err) => {
+                                error.set(Some(err));
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            finished.set(true);
+        })));
+    });
+
+    UseServerStream {
+        items,
+        error,
+        finished,
+        restarts,
+    }
+}
+
+/// The values received from a server function that returns a stream. Created with [`use_server_stream`].
+pub struct UseServerStream<T: 'static> {
+    items: Signal<Vec<T>>,
+    error: Signal<Option<ServerFnError>>,
+    finished: Signal<bool>,
+    restarts: Signal<usize>,
+}
+
+impl<T> UseServerStream<T> {
+    /// Get every value received from the stream so far
+    pub fn items(&self) -> ReadOnlySignal<Vec<T>> {
+        self.items.into()
+    }
+
+    /// Get the error that ended the stream, if any
+    pub fn error(&self) -> ReadOnlySignal<Option<ServerFnError>> {
+        self.error.into()
+    }
+
+    /// Check if the stream has ended, either because the server finished sending values or because of an error
+    pub fn finished(&self) -> bool {
+        (self.finished)()
+    }
+
+    /// Clear the received values and restart the stream
+    pub fn restart(&mut self) {
+        self.restarts += 1;
+    }
+}
+
+impl<T> Clone for UseServerStream<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UseServerStream<T> {}
+
+impl<T> PartialEq for UseServerStream<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
@@ -14,6 +14,8 @@ pub mod server;
 
 mod hooks;
 
+pub mod codec;
+
 pub mod document;
 #[cfg(feature = "server")]
 mod render;
@@ -31,7 +33,13 @@ mod server_context;
 /// A prelude of commonly used items in dioxus-fullstack.
 pub mod prelude {
     use crate::hooks;
-    pub use hooks::{server_cached::use_server_cached, server_future::use_server_future};
+    pub use hooks::{
+        server_cached::use_server_cached,
+        server_future::use_server_future,
+        server_stream::{use_server_stream, UseServerStream},
+    };
+
+    pub use crate::codec::{CborStream, StreamingCbor};
 
     #[cfg(feature = "axum")]
     #[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
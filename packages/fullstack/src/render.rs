@@ -4,7 +4,7 @@ use crate::streaming::{Mount, StreamingRenderer};
 use dioxus_cli_config::base_path;
 use dioxus_interpreter_js::INITIALIZE_STREAMING_JS;
 use dioxus_isrg::{CachedRender, IncrementalRendererError, RenderFreshness};
-use dioxus_lib::document::Document;
+use dioxus_lib::document::{Document, MemoryStorage, PersistentStorage};
 use dioxus_ssr::Renderer;
 use futures_channel::mpsc::Sender;
 use futures_util::{Stream, StreamExt};
@@ -145,14 +145,22 @@ impl SsrRendererPool {
         >(1000);
 
         // before we even spawn anything, we can check synchronously if we have the route cached
+        let mut stale_freshness = None;
         if let Some(freshness) = self.check_cached_route(&route, &mut into) {
-            return Ok((
-                freshness,
-                ReceiverWithDrop {
-                    receiver: rx,
-                    cancel_task: None,
-                },
-            ));
+            // If the cached route is stale, serve it immediately and re-render it in the background
+            // unless another request is already re-rendering it
+            if !freshness.is_stale() || !self.begin_revalidation(&route) {
+                return Ok((
+                    freshness,
+                    ReceiverWithDrop {
+                        receiver: rx,
+                        cancel_task: None,
+                    },
+                ));
+            }
+            // The background render streams into a channel nobody reads. It is only used to fill the cache
+            into = futures_channel::mpsc::channel(1000).0;
+            stale_freshness = Some(freshness);
         }
 
         let wrapper = FullstackHTMLTemplate { cfg: cfg.clone() };
@@ -168,7 +176,13 @@ impl SsrRendererPool {
         let myself = self.clone();
         let streaming_mode = cfg.streaming_mode;
 
+        let revalidation_guard = stale_freshness.is_some().then(|| RevalidationGuard {
+            pool: self.clone(),
+            route: route.clone(),
+        });
+
         let join_handle = spawn_platform(move || async move {
+            let _revalidation_guard = revalidation_guard;
             let mut virtual_dom = virtual_dom_factory();
             let document = std::rc::Rc::new(crate::document::server::ServerDocument::default());
             virtual_dom.provide_root_context(document.clone());
@@ -186,6 +200,10 @@ impl SsrRendererPool {
             }
             virtual_dom.provide_root_context(Rc::new(history) as Rc<dyn dioxus_history::History>);
             virtual_dom.provide_root_context(document.clone() as std::rc::Rc<dyn Document>);
+            // The server can't read the values persisted on the client, so every render starts from the initial values
+            virtual_dom.provide_root_context(
+                Rc::new(MemoryStorage::default()) as Rc<dyn PersistentStorage>
+            );
 
             // poll the future, which may call server_context()
             with_server_context(server_context.clone(), || virtual_dom.rebuild_in_place());
@@ -201,7 +219,9 @@ impl SsrRendererPool {
             let scope_to_mount_mapping = Arc::new(RwLock::new(HashMap::new()));
 
             renderer.pre_render = true;
-            {
+            if streaming_mode == StreamingMode::InOrder {
+                renderer.set_render_components(in_order_render_component_callback());
+            } else {
                 let scope_to_mount_mapping = scope_to_mount_mapping.clone();
                 let stream = stream.clone();
                 renderer.set_render_components(streaming_render_component_callback(
@@ -223,8 +243,20 @@ impl SsrRendererPool {
                 virtual_dom.wait_for_suspense().await;
             }
 
-            // Render the initial frame with loading placeholders
-            let mut initial_frame = renderer.render(&virtual_dom);
+            // Render the initial frame with loading placeholders. If we are streaming in order, there are no placeholders.
+            // Instead we stream every part of the body that is ready and wait for each suspense boundary to resolve in order
+            let mut initial_frame = if streaming_mode == StreamingMode::InOrder {
+                match render_in_order(&mut renderer, &mut virtual_dom, &stream, &server_context)
+                    .await
+                {
+                    Ok(remaining) => remaining,
+                    Err(err) => {
+                        throw_error!(err);
+                    }
+                }
+            } else {
+                renderer.render(&virtual_dom)
+            };
 
             // Along with the initial frame, we render the html after the main element, but before the body tag closes. This should include the script that starts loading the wasm bundle.
             if let Err(err) = wrapper.render_after_main(&mut initial_frame, &virtual_dom) {
@@ -313,7 +345,11 @@ impl SsrRendererPool {
                 cached_render.push_str(&post_streaming);
 
                 if let Ok(mut incremental) = incremental.write() {
-                    let _ = incremental.cache(route, cached_render);
+                    let _ = incremental.cache_with_tags(
+                        route,
+                        cached_render,
+                        server_context.cache_tags(),
+                    );
                 }
             }
 
@@ -323,6 +359,17 @@ impl SsrRendererPool {
             myself.renderers.write().unwrap().push(renderer);
         });
 
+        // If we are serving a stale render, the background render should keep running even if the client disconnects
+        if let Some(freshness) = stale_freshness {
+            return Ok((
+                freshness,
+                ReceiverWithDrop {
+                    receiver: rx,
+                    cancel_task: None,
+                },
+            ));
+        }
+
         Ok((
             RenderFreshness::now(None),
             ReceiverWithDrop {
@@ -331,6 +378,31 @@ impl SsrRendererPool {
             },
         ))
     }
+
+    /// Mark a route as being re-rendered in the background. Returns false if the route is already being re-rendered
+    fn begin_revalidation(&self, route: &str) -> bool {
+        self.incremental_cache
+            .as_ref()
+            .and_then(|incremental| incremental.write().ok())
+            .is_some_and(|mut incremental| incremental.begin_revalidation(route))
+    }
+}
+
+/// Removes the revalidation mark for a route when a background render finishes, even if the render failed
+/// before the route was cached again
+struct RevalidationGuard {
+    pool: Arc<SsrRendererPool>,
+    route: String,
+}
+
+impl Drop for RevalidationGuard {
+    fn drop(&mut self) {
+        if let Some(incremental) = &self.pool.incremental_cache {
+            if let Ok(mut incremental) = incremental.write() {
+                incremental.end_revalidation(&self.route);
+            }
+        }
+    }
 }
 
 /// Create the streaming render component callback. It will keep track of what scopes are mounted to what pending
@@ -401,6 +473,76 @@ fn streaming_render_component_callback(
     }
 }
 
+/// A marker the in order render callback writes in place of a suspense boundary that is still loading
+const PENDING_SUSPENSE_MARKER: &str = "\0dioxus-pending-suspense\0";
+
+/// Create the in order render component callback. Suspense boundaries that are still loading are replaced with
+/// [`PENDING_SUSPENSE_MARKER`] instead of their fallback so we know where the ready part of the body ends.
+fn in_order_render_component_callback(
+) -> impl Fn(&mut Renderer, &mut dyn Write, &VirtualDom, ScopeId) -> std::fmt::Result
+       + Send
+       + Sync
+       + 'static {
+    |renderer, to, vdom, scope| {
+        let is_pending_suspense_boundary =
+            SuspenseContext::downcast_suspense_boundary_from_scope(&vdom.runtime(), scope)
+                .filter(|s| s.has_suspended_tasks())
+                .is_some();
+        if is_pending_suspense_boundary {
+            to.write_str(PENDING_SUSPENSE_MARKER)
+        } else {
+            renderer.render_scope(to, vdom, scope)
+        }
+    }
+}
+
+/// Stream the body of the page in order. Every part of the body before the first suspense boundary that is still loading
+/// is sent as soon as it is rendered. Once every suspense boundary is resolved, the rest of the body is returned.
+///
+/// The body is re-rendered from the start after each suspense boundary resolves so the hydration ids match a render
+/// without streaming. Only the part after what was already sent is streamed. If the part that was already sent changes,
+/// rendering fails instead of sending html the client can't hydrate.
+async fn render_in_order(
+    renderer: &mut Renderer,
+    virtual_dom: &mut VirtualDom,
+    stream: &StreamingRenderer<IncrementalRendererError>,
+    server_context: &DioxusServerContext,
+) -> Result<String, IncrementalRendererError> {
+    let mut sent = String::new();
+    loop {
+        renderer.reset_hydration();
+        let mut frame = String::new();
+        renderer
+            .render_to(&mut frame, virtual_dom)
+            .map_err(IncrementalRendererError::RenderError)?;
+
+        // The part that was already sent can't be taken back, so the page can't be finished if it changed
+        if !frame.starts_with(&sent) {
+            return Err(IncrementalRendererError::Other(
+                "the html before a suspense boundary changed after it was streamed in order".into(),
+            ));
+        }
+
+        let ready = frame.find(PENDING_SUSPENSE_MARKER);
+        let chunk = &frame[sent.len()..ready.unwrap_or(frame.len())];
+        if ready.is_none() {
+            return Ok(chunk.to_string());
+        }
+        if !chunk.is_empty() {
+            stream.render(chunk);
+            sent.push_str(chunk);
+        }
+
+        ProvideServerContext::new(virtual_dom.wait_for_suspense_work(), server_context.clone())
+            .await;
+        ProvideServerContext::new(
+            virtual_dom.render_suspense_immediate(),
+            server_context.clone(),
+        )
+        .await;
+    }
+}
+
 /// Start capturing errors at a suspense boundary. If the parent suspense boundary is frozen, we need to capture the errors in the suspense boundary
 /// and send them to the client to continue bubbling up
 fn start_capturing_errors(suspense_scope: ScopeId) {
@@ -433,6 +575,25 @@ impl SSRState {
         }
     }
 
+    /// Remove a route from the incremental cache. This does nothing if incremental rendering is disabled.
+    pub fn invalidate(&self, route: &str) {
+        if let Some(incremental) = &self.renderers.incremental_cache {
+            if let Ok(mut incremental) = incremental.write() {
+                incremental.invalidate(route);
+            }
+        }
+    }
+
+    /// Remove every route that was rendered with a tag from the incremental cache. Tags are registered while
+    /// rendering with [`DioxusServerContext::add_cache_tag`]. This does nothing if incremental rendering is disabled.
+    pub fn invalidate_tag(&self, tag: &str) {
+        if let Some(incremental) = &self.renderers.incremental_cache {
+            if let Ok(mut incremental) = incremental.write() {
+                incremental.invalidate_tag(tag);
+            }
+        }
+    }
+
     /// Render the application to HTML.
     pub async fn render<'a>(
         &'a self,
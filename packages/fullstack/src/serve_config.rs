@@ -2,13 +2,20 @@
 #![allow(non_snake_case)]
 
 use std::fs::File;
+use std::future::Future;
 use std::io::Read;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
 
 use dioxus_lib::prelude::dioxus_core::LaunchConfig;
 
 use crate::server::ContextProviders;
 
+/// A function that generates the contents of the `sitemap.xml` file
+pub(crate) type SitemapFn =
+    Arc<dyn Fn() -> Pin<Box<dyn Future<Output = String> + Send>> + Send + Sync>;
+
 /// A ServeConfig is used to configure how to serve a Dioxus application. It contains information about how to serve static assets, and what content to render with [`dioxus-ssr`].
 #[derive(Clone, Default)]
 pub struct ServeConfigBuilder {
@@ -18,6 +25,7 @@ pub struct ServeConfigBuilder {
     pub(crate) incremental: Option<dioxus_isrg::IncrementalRendererConfig>,
     pub(crate) context_providers: ContextProviders,
     pub(crate) streaming_mode: StreamingMode,
+    pub(crate) sitemap: Option<SitemapFn>,
 }
 
 impl LaunchConfig for ServeConfigBuilder {}
@@ -32,6 +40,7 @@ impl ServeConfigBuilder {
             incremental: None,
             context_providers: Default::default(),
             streaming_mode: StreamingMode::default(),
+            sitemap: None,
         }
     }
 
@@ -177,15 +186,84 @@ impl ServeConfigBuilder {
         self
     }
 
+    /// Enable in order streaming. This will stream the page to the client in order as it renders, without any javascript to swap in resolved content.
+    ///
+    /// It is equivalent to calling `streaming_mode(StreamingMode::InOrder)`
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder().enable_in_order_streaming()
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn enable_in_order_streaming(mut self) -> Self {
+        self.streaming_mode = StreamingMode::InOrder;
+        self
+    }
+
+    /// Serve a `sitemap.xml` file generated by the function at `/sitemap.xml`. The function is called every time the
+    /// sitemap is requested, so it can read dynamic routes from a database.
+    ///
+    /// The sitemap is also written into the public folder when you build with `dx build --ssg` or `dx build --sitemap`.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// use dioxus::router::sitemap::{ChangeFrequency, SiteMap, SiteMapEntry};
+    ///
+    /// #[derive(Routable, Clone, PartialEq)]
+    /// enum Route {
+    ///     #[route("/")]
+    ///     Home {},
+    ///     #[route("/blog/:id")]
+    ///     Blog { id: usize },
+    /// }
+    /// # #[component] fn Home() -> Element { VNode::empty() }
+    /// # #[component] fn Blog(id: usize) -> Element { VNode::empty() }
+    /// # async fn load_post_ids() -> Vec<usize> { vec![] }
+    ///
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_cfg(server_only! {
+    ///         ServeConfig::builder().sitemap(|| async move {
+    ///             let posts = load_post_ids().await;
+    ///             SiteMap::builder::<Route>("https://example.com")
+    ///                 .dynamic_routes(|pattern| match pattern {
+    ///                     "/blog/:id" => posts
+    ///                         .iter()
+    ///                         .map(|id| SiteMapEntry::new(Route::Blog { id: *id }.to_string()))
+    ///                         .collect(),
+    ///                     _ => Vec::new(),
+    ///                 })
+    ///                 .annotate(|entry| entry.changefreq = Some(ChangeFrequency::Daily))
+    ///                 .build()
+    ///                 .to_xml()
+    ///         })
+    ///     })
+    ///     .launch(|| rsx! { Router::<Route> {} });
+    /// ```
+    pub fn sitemap<F>(mut self, sitemap: impl Fn() -> F + Send + Sync + 'static) -> Self
+    where
+        F: Future<Output = String> + Send + 'static,
+    {
+        self.sitemap = Some(Arc::new(move || Box::pin(sitemap())));
+        self
+    }
+
     /// Build the ServeConfig. This may fail if the index.html file is not found.
     pub fn build(self) -> Result<ServeConfig, UnableToLoadIndex> {
         // The CLI always bundles static assets into the exe/public directory
         let public_path = public_path();
 
-        let index_path = self
-            .index_path
-            .map(PathBuf::from)
-            .unwrap_or_else(|| public_path.join("index.html"));
+        // If static site generation replaced index.html with the pre-rendered root route, render from the copy of the template instead
+        let index_path = self.index_path.unwrap_or_else(|| {
+            let template_path = public_path.join(dioxus_cli_config::INDEX_TEMPLATE_NAME);
+            match template_path.exists() {
+                true => template_path,
+                false => public_path.join("index.html"),
+            }
+        });
 
         let root_id = self.root_id.unwrap_or("main");
 
@@ -201,6 +279,7 @@ impl ServeConfigBuilder {
             incremental: self.incremental,
             context_providers: self.context_providers,
             streaming_mode: self.streaming_mode,
+            sitemap: self.sitemap,
         })
     }
 }
@@ -308,6 +387,10 @@ pub enum StreamingMode {
     /// Out of order streaming is enabled; server futures are resolved out of order and streamed to the client
     /// as they resolve
     OutOfOrder,
+    /// In order streaming is enabled; the head and every part of the body that is ready are streamed immediately,
+    /// and the response only waits at the first suspense boundary that is still loading. This doesn't require
+    /// javascript to swap in resolved content, so it works for crawlers and clients with javascript disabled.
+    InOrder,
 }
 
 /// Used to configure how to serve a Dioxus application. It contains information about how to serve static assets, and what content to render with [`dioxus-ssr`].
@@ -318,6 +401,7 @@ pub struct ServeConfig {
     pub(crate) incremental: Option<dioxus_isrg::IncrementalRendererConfig>,
     pub(crate) context_providers: ContextProviders,
     pub(crate) streaming_mode: StreamingMode,
+    pub(crate) sitemap: Option<SitemapFn>,
 }
 
 impl LaunchConfig for ServeConfig {}
@@ -52,6 +52,54 @@ pub fn launch(
                 cfg
             });
 
+            // If the CLI is generating a static site, render every static route into the output directory and exit instead of serving
+            if let Some(output_dir) = dioxus_cli_config::ssg_output_dir() {
+                let cfg = match platform_config {
+                    Ok(cfg) => cfg,
+                    Err(err) => {
+                        eprintln!("Failed to load the config for static site generation: {err}");
+                        std::process::exit(1);
+                    }
+                };
+                let routes = dioxus_cli_config::ssg_routes();
+                match crate::server::ssg::generate_static_site(cfg, root, &output_dir, routes).await
+                {
+                    Ok(manifest) => {
+                        println!("Generated {} static pages", manifest.routes.len());
+                        std::process::exit(0);
+                    }
+                    Err(err) => {
+                        eprintln!("{err}");
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            // If the CLI only needs the sitemap, write it into the output directory and exit
+            if let Some(output_dir) = dioxus_cli_config::sitemap_output_dir() {
+                let written = match &platform_config {
+                    Ok(cfg) => crate::server::ssg::generate_sitemap(cfg, &output_dir).await,
+                    Err(err) => {
+                        eprintln!("Failed to load the config for sitemap generation: {err}");
+                        std::process::exit(1);
+                    }
+                };
+                match written {
+                    Ok(true) => {
+                        println!("Generated sitemap.xml");
+                        std::process::exit(0);
+                    }
+                    Ok(false) => {
+                        println!("No sitemap is configured");
+                        std::process::exit(0);
+                    }
+                    Err(err) => {
+                        eprintln!("{err}");
+                        std::process::exit(1);
+                    }
+                }
+            }
+
             // Get the address the server should run on. If the CLI is running, the CLI proxies fullstack into the main address
             // and we use the generated address the CLI gives us
             let address = dioxus_cli_config::fullstack_address_or_localhost();
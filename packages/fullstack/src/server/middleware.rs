@@ -0,0 +1,209 @@
+//! Guards and middleware that run before the body of a server function.
+//!
+//! Middleware is declared per server function with the `middleware` argument of the `#[server]` macro. Each item in the
+//! list can either be a [`Guard`] or a tower [`Layer`](tower_layer::Layer):
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! use axum::body::Body;
+//! use http::{header::CACHE_CONTROL, HeaderValue, Response};
+//!
+//! struct RequireAuth;
+//!
+//! impl Guard for RequireAuth {
+//!     async fn check(&self, context: DioxusServerContext) -> Result<(), ServerFnError> {
+//!         match context.request_parts().headers.contains_key("authorization") {
+//!             true => Ok(()),
+//!             false => Err(ServerFnError::ServerError("Unauthorized".to_string())),
+//!         }
+//!     }
+//! }
+//!
+//! fn no_store(mut response: Response<Body>) -> Response<Body> {
+//!     response.headers_mut().insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+//!     response
+//! }
+//!
+//! #[server(middleware = [tower::util::MapResponseLayer::new(no_store), RequireAuth])]
+//! async fn delete_account() -> Result<(), ServerFnError> {
+//!     // This only runs if the request passed every guard
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Middleware runs in the order it is listed, inside the [`DioxusServerContext`] of the request. Routes registered with
+//! [`DioxusRouterExt::register_server_functions_with_context`](super::DioxusRouterExt::register_server_functions_with_context)
+//! can read the context providers of the app from guards with [`FromContext`](crate::prelude::FromContext).
+
+use std::{future::Future, marker::PhantomData, pin::Pin, sync::Arc};
+
+use axum::body::Body;
+use http::{Request, Response};
+use server_fn::{
+    error::NoCustomError,
+    middleware::{BoxedService, Layer, Service},
+    response::Res,
+    ServerFnError,
+};
+
+use crate::prelude::{server_context, DioxusServerContext};
+
+/// A check that runs before the body of a server function. If the check fails, the server function responds with the
+/// error instead of running.
+///
+/// Guards are implemented for async functions and closures that take the [`DioxusServerContext`] of the request:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// async fn require_json(context: DioxusServerContext) -> Result<(), ServerFnError> {
+///     let parts = context.request_parts();
+///     match parts.headers.get("accept").and_then(|accept| accept.to_str().ok()) {
+///         Some(accept) if accept.contains("json") => Ok(()),
+///         _ => Err(ServerFnError::Request("Only json responses are supported".to_string())),
+///     }
+/// }
+///
+/// #[server(middleware = [require_json])]
+/// async fn version() -> Result<String, ServerFnError> {
+///     Ok("1.0.0".to_string())
+/// }
+/// ```
+///
+/// Server functions with a custom error type can return that error from a guard by implementing `Guard<MyError>`.
+pub trait Guard<E = NoCustomError>: Send + Sync + 'static {
+    /// Check if the request is allowed to run the server function
+    fn check(
+        &self,
+        context: DioxusServerContext,
+    ) -> impl Future<Output = Result<(), ServerFnError<E>>> + Send;
+}
+
+impl<E, F, Fut> Guard<E> for F
+where
+    F: Fn(DioxusServerContext) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), ServerFnError<E>>> + Send,
+{
+    fn check(
+        &self,
+        context: DioxusServerContext,
+    ) -> impl Future<Output = Result<(), ServerFnError<E>>> + Send {
+        self(context)
+    }
+}
+
+/// A type that can be used in the `middleware` argument of the `#[server]` macro. This is implemented for every [`Guard`]
+/// and every tower [`Layer`](tower_layer::Layer) that can wrap a server function.
+///
+/// The `Marker` type only exists to keep the guard and layer implementations apart. You should not need to name it.
+pub trait IntoServerFnMiddleware<Marker> {
+    /// Convert the middleware into a layer that wraps the server function
+    fn into_layer(self) -> Arc<dyn Layer<Request<Body>, Response<Body>>>;
+}
+
+#[doc(hidden)]
+pub struct GuardMarker<E>(PhantomData<E>);
+
+#[doc(hidden)]
+pub struct LayerMarker;
+
+impl<G, E> IntoServerFnMiddleware<GuardMarker<E>> for G
+where
+    G: Guard<E>,
+    E: std::fmt::Debug + std::fmt::Display + std::str::FromStr + Send + Sync + 'static,
+{
+    fn into_layer(self) -> Arc<dyn Layer<Request<Body>, Response<Body>>> {
+        Arc::new(GuardLayer {
+            guard: Arc::new(self),
+            error: PhantomData,
+        })
+    }
+}
+
+impl<L> IntoServerFnMiddleware<LayerMarker> for L
+where
+    L: Layer<Request<Body>, Response<Body>>,
+{
+    fn into_layer(self) -> Arc<dyn Layer<Request<Body>, Response<Body>>> {
+        Arc::new(self)
+    }
+}
+
+/// The guards and layers declared with `#[server(middleware = [...])]`. The `#[server]` macro chains every item in the
+/// list with [`ServerFnMiddleware::new`] and [`ServerFnMiddleware::and`] before it is added to the server function.
+pub struct ServerFnMiddleware(Vec<Arc<dyn Layer<Request<Body>, Response<Body>>>>);
+
+impl ServerFnMiddleware {
+    /// Create a new middleware from a [`Guard`] or tower [`Layer`](tower_layer::Layer)
+    pub fn new<Marker>(middleware: impl IntoServerFnMiddleware<Marker>) -> Self {
+        Self(vec![middleware.into_layer()])
+    }
+
+    /// Add a [`Guard`] or tower [`Layer`](tower_layer::Layer) that runs after the existing middleware
+    pub fn and<Marker>(mut self, middleware: impl IntoServerFnMiddleware<Marker>) -> Self {
+        self.0.push(middleware.into_layer());
+        self
+    }
+}
+
+impl Layer<Request<Body>, Response<Body>> for ServerFnMiddleware {
+    fn layer(
+        &self,
+        mut service: BoxedService<Request<Body>, Response<Body>>,
+    ) -> BoxedService<Request<Body>, Response<Body>> {
+        // The last layer applied runs first, so the middleware is applied in reverse
+        for layer in self.0.iter().rev() {
+            service = layer.layer(service);
+        }
+        service
+    }
+}
+
+/// A layer that runs a [`Guard`] before the server function it wraps
+struct GuardLayer<G, E> {
+    guard: Arc<G>,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<G, E> Layer<Request<Body>, Response<Body>> for GuardLayer<G, E>
+where
+    G: Guard<E>,
+    E: std::fmt::Debug + std::fmt::Display + std::str::FromStr + Send + Sync + 'static,
+{
+    fn layer(
+        &self,
+        inner: BoxedService<Request<Body>, Response<Body>>,
+    ) -> BoxedService<Request<Body>, Response<Body>> {
+        BoxedService::new(GuardService {
+            guard: self.guard.clone(),
+            inner,
+            error: PhantomData,
+        })
+    }
+}
+
+struct GuardService<G, E> {
+    guard: Arc<G>,
+    inner: BoxedService<Request<Body>, Response<Body>>,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<G, E> Service<Request<Body>, Response<Body>> for GuardService<G, E>
+where
+    G: Guard<E>,
+    E: std::fmt::Debug + std::fmt::Display + std::str::FromStr + Send + Sync + 'static,
+{
+    fn run(&mut self, req: Request<Body>) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+        let guard = self.guard.clone();
+        let path = req.uri().path().to_string();
+        // The inner service only creates the future here. It isn't polled until the guard passes, so requests don't
+        // need to wait for each other to reach the inner service
+        let response = self.inner.0.run(req);
+        Box::pin(async move {
+            // The guard is polled inside of the server context the server function runs in
+            if let Err(err) = guard.check(server_context()).await {
+                return Response::<Body>::error_response(&path, &err);
+            }
+            response.await
+        })
+    }
+}
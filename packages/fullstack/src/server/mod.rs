@@ -56,6 +56,10 @@
 //! ```
 
 pub mod launch;
+pub mod middleware;
+pub mod ssg;
+
+pub use middleware::{Guard, IntoServerFnMiddleware, ServerFnMiddleware};
 
 #[allow(unused)]
 pub(crate) type ContextProviders =
@@ -68,6 +72,7 @@ use axum::{
     http::{Request, Response, StatusCode},
     response::IntoResponse,
 };
+use dioxus_cli_config::{INDEX_TEMPLATE_NAME, SSG_MANIFEST_NAME};
 use dioxus_lib::prelude::{Element, VirtualDom};
 use http::header::*;
 
@@ -103,6 +108,9 @@ pub trait DioxusRouterExt<S> {
 
     /// Registers server functions with some additional context to insert into the [`DioxusServerContext`] for that handler.
     ///
+    /// Guards and layers declared with `#[server(middleware = [...])]` wrap each server function. Guards run inside the
+    /// server context, so they can read the additional context with [`FromContext`].
+    ///
     /// # Example
     /// ```rust, no_run
     /// # use dioxus_lib::prelude::*;
@@ -200,48 +208,8 @@ where
         self
     }
 
-    fn serve_static_assets(mut self) -> Self {
-        use tower_http::services::{ServeDir, ServeFile};
-
-        let public_path = crate::public_path();
-
-        if !public_path.exists() {
-            return self;
-        }
-
-        // Serve all files in public folder except index.html
-        let dir = std::fs::read_dir(&public_path).unwrap_or_else(|e| {
-            panic!(
-                "Couldn't read public directory at {:?}: {}",
-                &public_path, e
-            )
-        });
-
-        for entry in dir.flatten() {
-            let path = entry.path();
-            if path.ends_with("index.html") {
-                continue;
-            }
-            let route = path
-                .strip_prefix(&public_path)
-                .unwrap()
-                .iter()
-                .map(|segment| {
-                    segment.to_str().unwrap_or_else(|| {
-                        panic!("Failed to convert path segment {:?} to string", segment)
-                    })
-                })
-                .collect::<Vec<_>>()
-                .join("/");
-            let route = format!("/{}", route);
-            if path.is_dir() {
-                self = self.nest_service(&route, ServeDir::new(path).precompressed_br());
-            } else {
-                self = self.nest_service(&route, ServeFile::new(path).precompressed_br());
-            }
-        }
-
-        self
+    fn serve_static_assets(self) -> Self {
+        serve_public_files(self, &["index.html"])
     }
 
     fn serve_dioxus_application<Cfg, Error>(self, cfg: Cfg, app: fn() -> Element) -> Self
@@ -255,10 +223,18 @@ where
             .map(|cfg| cfg.context_providers.clone())
             .unwrap_or_default();
 
+        // If the sitemap is generated by the server, serve it instead of the file written into the public folder
+        let sitemap = cfg.as_ref().ok().and_then(|cfg| cfg.sitemap.clone());
+        let server = match sitemap {
+            Some(sitemap) => serve_public_files(self, &["index.html", "sitemap.xml"]).route(
+                "/sitemap.xml",
+                get(move || sitemap_handler(sitemap.clone())),
+            ),
+            None => self.serve_static_assets(),
+        };
+
         // Add server functions and render index.html
-        let server = self
-            .serve_static_assets()
-            .register_server_functions_with_context(context_providers);
+        let server = server.register_server_functions_with_context(context_providers);
 
         match cfg {
             Ok(cfg) => {
@@ -276,6 +252,66 @@ where
     }
 }
 
+/// Serve every file in the public folder except the files in `skip` and the files static site generation uses internally
+fn serve_public_files<S>(mut router: Router<S>, skip: &[&str]) -> Router<S>
+where
+    S: Send + Sync + Clone + 'static,
+{
+    use tower_http::services::{ServeDir, ServeFile};
+
+    let public_path = crate::public_path();
+
+    if !public_path.exists() {
+        return router;
+    }
+
+    // Serve all files in public folder except the skipped files
+    let dir = std::fs::read_dir(&public_path).unwrap_or_else(|e| {
+        panic!(
+            "Couldn't read public directory at {:?}: {}",
+            &public_path, e
+        )
+    });
+
+    for entry in dir.flatten() {
+        let path = entry.path();
+        if skip
+            .iter()
+            .chain(&[INDEX_TEMPLATE_NAME, SSG_MANIFEST_NAME])
+            .any(|file| path.ends_with(file))
+        {
+            continue;
+        }
+        let route = path
+            .strip_prefix(&public_path)
+            .unwrap()
+            .iter()
+            .map(|segment| {
+                segment.to_str().unwrap_or_else(|| {
+                    panic!("Failed to convert path segment {:?} to string", segment)
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let route = format!("/{}", route);
+        if path.is_dir() {
+            router = router.nest_service(&route, ServeDir::new(path).precompressed_br());
+        } else {
+            router = router.nest_service(&route, ServeFile::new(path).precompressed_br());
+        }
+    }
+
+    router
+}
+
+/// Respond with the sitemap generated by [`ServeConfigBuilder::sitemap`](crate::ServeConfigBuilder::sitemap)
+pub(crate) async fn sitemap_handler(sitemap: crate::serve_config::SitemapFn) -> impl IntoResponse {
+    (
+        [(http::header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        sitemap().await,
+    )
+}
+
 fn apply_request_parts_to_response<B>(
     headers: hyper::header::HeaderMap,
     response: &mut axum::response::Response<B>,
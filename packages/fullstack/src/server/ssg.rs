@@ -0,0 +1,299 @@
+//! Static site generation for fullstack apps.
+//!
+//! Static site generation renders every static route of the app in-process, without binding to a port, and writes the
+//! pages directly into the output directory along with a manifest of the generated routes. The CLI runs this when you
+//! build with `dx build --ssg` by launching the server with the [`dioxus_cli_config::SSG_OUTPUT_DIR_ENV`] environment variable set.
+//!
+//! The routes are read from a server function registered at `/api/static_routes` that returns the list of routes:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! # #[derive(Routable, Clone, PartialEq)] enum Route { #[route("/")] Index {} }
+//! # #[component] fn Index() -> Element { VNode::empty() }
+//! #[server(endpoint = "static_routes")]
+//! async fn static_routes() -> Result<Vec<String>, ServerFnError> {
+//!     Ok(Route::static_routes()
+//!         .into_iter()
+//!         .map(|route| route.to_string())
+//!         .collect::<Vec<_>>())
+//! }
+//! ```
+//!
+//! If a sitemap is configured with [`ServeConfigBuilder::sitemap`](crate::ServeConfigBuilder::sitemap), it is written
+//! into the output directory as `sitemap.xml` along with the pages.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use axum::{body::Body, routing::get, Router};
+use dioxus_cli_config::{INDEX_TEMPLATE_NAME, SSG_MANIFEST_NAME};
+use dioxus_lib::prelude::Element;
+use futures_util::StreamExt;
+use http::{header::ACCEPT, Method, Request};
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+
+use crate::prelude::*;
+use crate::server::{render_handler, RenderHandleState};
+use crate::StreamingMode;
+
+/// The endpoint of the server function that returns the static routes of the app
+const STATIC_ROUTES_ENDPOINT: &str = "/api/static_routes";
+
+/// The number of routes that are rendered at the same time
+const CONCURRENT_RENDERS: usize = 16;
+
+/// A manifest of the pages written by static site generation. The manifest is stored in the output directory and is used to
+/// incrementally re-generate the site.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SsgManifest {
+    /// Every generated route and the page it was written to
+    pub routes: BTreeMap<String, SsgPage>,
+}
+
+/// A page written by static site generation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SsgPage {
+    /// The path of the page relative to the output directory
+    pub file: PathBuf,
+    /// The time the page was last changed in seconds since the unix epoch
+    pub updated_at: u64,
+}
+
+impl SsgManifest {
+    /// Read the manifest from an output directory. Returns an empty manifest if the site has not been generated yet.
+    pub fn load(output_dir: &Path) -> Result<Self, SsgError> {
+        match std::fs::read_to_string(output_dir.join(SSG_MANIFEST_NAME)) {
+            Ok(manifest) => Ok(serde_json::from_str(&manifest)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn save(&self, output_dir: &Path) -> Result<(), SsgError> {
+        std::fs::write(
+            output_dir.join(SSG_MANIFEST_NAME),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+}
+
+/// An error that can occur while generating a static site
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SsgError {
+    /// The list of static routes could not be read from the server
+    #[error("Failed to get the static routes from {STATIC_ROUTES_ENDPOINT}: {0}")]
+    StaticRoutes(String),
+    /// A route failed to render
+    #[error("Failed to render route {route:?}: {message}")]
+    Render {
+        /// The route that failed to render
+        route: String,
+        /// Why the route failed to render
+        message: String,
+    },
+    /// A route can't be written to a page inside the output directory
+    #[error("Route {0:?} can't be written to a page inside the output directory")]
+    InvalidRoute(String),
+    /// The pages or manifest could not be written
+    #[error("IoError: {0}")]
+    Io(#[from] std::io::Error),
+    /// The manifest could not be read or written
+    #[error("Invalid ssg manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+}
+
+/// Render the static routes of an app and write them into `output_dir`.
+///
+/// If `only_routes` is set, only those routes are re-generated and every other page in the existing manifest is kept.
+/// Otherwise every static route is generated and pages for routes that no longer exist are removed. Pages are only
+/// rewritten if their contents changed.
+///
+/// If any route fails to render, generation stops and the error is returned.
+pub async fn generate_static_site(
+    mut cfg: ServeConfig,
+    app: fn() -> Element,
+    output_dir: &Path,
+    only_routes: Option<Vec<String>>,
+) -> Result<SsgManifest, SsgError> {
+    let sitemap = cfg.sitemap.clone();
+
+    // Static pages need to contain the whole page without any streaming placeholders and should never hit the incremental cache
+    cfg.streaming_mode = StreamingMode::Disabled;
+    cfg.incremental = None;
+
+    // Requests are sent to the router in-process, so we don't need to wait for a server to start listening.
+    // We don't serve static assets here so old pages in the output directory are never served instead of rendering
+    let router = Router::new()
+        .register_server_functions_with_context(cfg.context_providers.clone())
+        .fallback(get(render_handler).with_state(RenderHandleState::new(cfg, app)));
+
+    let mut manifest = SsgManifest::load(output_dir)?;
+    let full_generation = only_routes.is_none();
+    let routes = match only_routes {
+        Some(routes) => routes,
+        None => static_routes(&router).await?,
+    };
+
+    // The root route replaces index.html, so keep a copy of the template for the server to render dynamic routes from
+    let template_path = output_dir.join(INDEX_TEMPLATE_NAME);
+    let index_path = output_dir.join("index.html");
+    if !template_path.exists() && index_path.exists() {
+        std::fs::copy(&index_path, &template_path)?;
+    }
+
+    let mut renders = futures_util::stream::iter(routes.iter().cloned())
+        .map(|route| {
+            let router = router.clone();
+            async move {
+                let html = render_route(router, &route).await;
+                (route, html)
+            }
+        })
+        .buffer_unordered(CONCURRENT_RENDERS);
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    while let Some((route, html)) = renders.next().await {
+        let html = html?;
+        let file = page_path(&route)?;
+        let path = output_dir.join(&file);
+        let unchanged = std::fs::read(&path).is_ok_and(|existing| existing == html);
+        let updated_at = match manifest.routes.get(&route) {
+            Some(page) if unchanged => page.updated_at,
+            _ => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&path, &html)?;
+                now
+            }
+        };
+        manifest.routes.insert(route, SsgPage { file, updated_at });
+    }
+
+    // Remove any pages for routes that no longer exist
+    if full_generation {
+        manifest.routes.retain(|route, page| {
+            let keep = routes.contains(route);
+            if !keep {
+                _ = std::fs::remove_file(output_dir.join(&page.file));
+            }
+            keep
+        });
+    }
+
+    manifest.save(output_dir)?;
+
+    if let Some(sitemap) = sitemap {
+        std::fs::write(output_dir.join("sitemap.xml"), sitemap().await)?;
+    }
+
+    Ok(manifest)
+}
+
+/// Write the sitemap configured with [`ServeConfigBuilder::sitemap`](crate::ServeConfigBuilder::sitemap) into
+/// `output_dir/sitemap.xml`. Returns `false` if no sitemap is configured.
+pub async fn generate_sitemap(cfg: &ServeConfig, output_dir: &Path) -> Result<bool, SsgError> {
+    let Some(sitemap) = &cfg.sitemap else {
+        return Ok(false);
+    };
+    std::fs::create_dir_all(output_dir)?;
+    std::fs::write(output_dir.join("sitemap.xml"), sitemap().await)?;
+    Ok(true)
+}
+
+/// Get the static routes from the server function registered at [`STATIC_ROUTES_ENDPOINT`]
+async fn static_routes(router: &Router) -> Result<Vec<String>, SsgError> {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(STATIC_ROUTES_ENDPOINT)
+        .header(
+            http::header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        )
+        .body(Body::empty())
+        .map_err(|err| SsgError::StaticRoutes(err.to_string()))?;
+    let response = router
+        .clone()
+        .oneshot(request)
+        .await
+        .map_err(|err| SsgError::StaticRoutes(err.to_string()))?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map_err(|err| SsgError::StaticRoutes(err.to_string()))?;
+    if !status.is_success() {
+        return Err(SsgError::StaticRoutes(format!(
+            "{status}: {}",
+            String::from_utf8_lossy(&body)
+        )));
+    }
+    serde_json::from_slice(&body).map_err(|err| SsgError::StaticRoutes(err.to_string()))
+}
+
+/// Render a single route in-process
+async fn render_route(router: Router, route: &str) -> Result<Vec<u8>, SsgError> {
+    let render_error = |message: String| SsgError::Render {
+        route: route.to_string(),
+        message,
+    };
+    let request = Request::builder()
+        .uri(route)
+        .header(ACCEPT, "text/html")
+        .body(Body::empty())
+        .map_err(|err| render_error(err.to_string()))?;
+    let response = router
+        .oneshot(request)
+        .await
+        .map_err(|err| render_error(err.to_string()))?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map_err(|err| render_error(err.to_string()))?;
+    if !status.is_success() {
+        return Err(render_error(format!(
+            "{status}: {}",
+            String::from_utf8_lossy(&body)
+        )));
+    }
+    Ok(body.to_vec())
+}
+
+/// Get the path of the page for a route relative to the output directory
+fn page_path(route: &str) -> Result<PathBuf, SsgError> {
+    let (path, _) = route.split_once(['?', '#']).unwrap_or((route, ""));
+    let mut file = PathBuf::new();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        // Pages are only ever written inside the output directory
+        if segment == ".." {
+            return Err(SsgError::InvalidRoute(route.to_string()));
+        }
+        file.push(segment);
+    }
+    file.push("index.html");
+    Ok(file)
+}
+
+#[test]
+fn page_paths() {
+    assert_eq!(page_path("/").unwrap(), PathBuf::from("index.html"));
+    assert_eq!(
+        page_path("/blog/1").unwrap(),
+        PathBuf::from("blog/1/index.html")
+    );
+    assert_eq!(
+        page_path("/blog/1/?lang=en#top").unwrap(),
+        PathBuf::from("blog/1/index.html")
+    );
+    assert!(matches!(
+        page_path("/blog/../../etc"),
+        Err(SsgError::InvalidRoute(_))
+    ));
+}
@@ -26,6 +26,7 @@ pub struct DioxusServerContext {
     shared_context: std::sync::Arc<RwLock<SendSyncAnyMap>>,
     response_parts: std::sync::Arc<RwLock<http::response::Parts>>,
     pub(crate) parts: Arc<RwLock<http::request::Parts>>,
+    cache_tags: Arc<RwLock<Vec<String>>>,
 }
 
 enum ContextType {
@@ -51,6 +52,7 @@ impl Default for DioxusServerContext {
                 http::response::Response::new(()).into_parts().0,
             )),
             parts: std::sync::Arc::new(RwLock::new(http::request::Request::new(()).into_parts().0)),
+            cache_tags: Default::default(),
         }
     }
 }
@@ -69,6 +71,7 @@ mod server_fn_impl {
                 response_parts: std::sync::Arc::new(RwLock::new(
                     http::response::Response::new(()).into_parts().0,
                 )),
+                cache_tags: Default::default(),
             }
         }
 
@@ -81,6 +84,7 @@ mod server_fn_impl {
                 response_parts: std::sync::Arc::new(RwLock::new(
                     http::response::Response::new(()).into_parts().0,
                 )),
+                cache_tags: Default::default(),
             }
         }
 
@@ -261,6 +265,32 @@ mod server_fn_impl {
         pub async fn extract<M, T: FromServerContext<M>>(&self) -> Result<T, T::Rejection> {
             T::from_request(self).await
         }
+
+        /// Tag the page that is currently rendering. If incremental rendering is enabled, the tags are stored with the cached page
+        /// and every page with a tag can be removed from the cache at once with [`SSRState::invalidate_tag`](crate::prelude::SSRState::invalidate_tag).
+        ///
+        /// # Example
+        ///
+        /// ```rust, no_run
+        /// # use dioxus::prelude::*;
+        /// #[server]
+        /// async fn get_product(id: u32) -> Result<String, ServerFnError> {
+        ///     server_context().add_cache_tag(format!("product:{id}"));
+        ///     Ok(format!("Product {id}"))
+        /// }
+        /// ```
+        pub fn add_cache_tag(&self, tag: impl Into<String>) {
+            let tag = tag.into();
+            let mut tags = self.cache_tags.write();
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        /// Get the cache tags registered with [`DioxusServerContext::add_cache_tag`] while rendering the current page.
+        pub fn cache_tags(&self) -> Vec<String> {
+            self.cache_tags.read().clone()
+        }
     }
 }
 
@@ -272,6 +302,15 @@ fn server_context_as_any_map() {
     assert_eq!(server_context.get::<u32>().unwrap(), 1234u32);
 }
 
+#[test]
+fn server_context_cache_tags() {
+    let server_context = DioxusServerContext::default();
+    server_context.add_cache_tag("product:42");
+    server_context.clone().add_cache_tag("product:43");
+    server_context.add_cache_tag("product:42");
+    assert_eq!(server_context.cache_tags(), ["product:42", "product:43"]);
+}
+
 std::thread_local! {
     pub(crate) static SERVER_CONTEXT: std::cell::RefCell<Box<DioxusServerContext>> = Default::default();
 }
@@ -0,0 +1,124 @@
+#![cfg(feature = "axum")]
+
+use axum::{body::Body, Router};
+use dioxus::prelude::*;
+use http::{header::CONTENT_TYPE, Request};
+use tower::ServiceExt;
+
+#[derive(serde::Serialize)]
+struct SumArgs {
+    values: Vec<f64>,
+}
+
+#[server(input = Cbor, output = MsgPack, endpoint = "sum")]
+async fn sum(values: Vec<f64>) -> Result<f64, ServerFnError> {
+    Ok(values.iter().sum())
+}
+
+// Encodings that are written as a path are used as is
+#[server(input = server_fn::codec::Cbor, output = server_fn::codec::Json, endpoint = "product")]
+async fn product(values: Vec<f64>) -> Result<f64, ServerFnError> {
+    Ok(values.iter().product())
+}
+
+#[server(output = StreamingCbor, endpoint = "count")]
+async fn count(to: u32) -> Result<CborStream<u32>, ServerFnError> {
+    Ok(CborStream::from(futures_util::stream::iter(1..=to)))
+}
+
+#[server(output = StreamingCbor, endpoint = "count_then_fail")]
+async fn count_then_fail(to: u32) -> Result<CborStream<u32>, ServerFnError> {
+    let values = (1..=to).map(Ok).chain([Err(ServerFnError::ServerError(
+        "out of numbers".to_string(),
+    ))]);
+    Ok(CborStream::new(futures_util::stream::iter(values)))
+}
+
+async fn call(path: &str, content_type: &str, body: Vec<u8>) -> (String, Vec<u8>) {
+    let router = Router::new().register_server_functions();
+    let request = Request::post(path)
+        .header(CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert!(response.status().is_success());
+    let content_type = response.headers()[CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (content_type, body.to_vec())
+}
+
+/// Split a `StreamingCbor` response into the values it contains
+fn decode_frames(mut body: &[u8]) -> Vec<Result<u32, String>> {
+    let mut items = Vec::new();
+    while !body.is_empty() {
+        let length = u32::from_be_bytes(body[..4].try_into().unwrap()) as usize;
+        items.push(ciborium::from_reader(&body[4..4 + length]).unwrap());
+        body = &body[4 + length..];
+    }
+    items
+}
+
+#[tokio::test]
+async fn binary_encodings() {
+    let mut args = Vec::new();
+    ciborium::into_writer(
+        &SumArgs {
+            values: vec![1.5, 2.5, 3.0],
+        },
+        &mut args,
+    )
+    .unwrap();
+
+    let (content_type, body) = call("/api/sum", "application/cbor", args).await;
+    assert_eq!(content_type, "application/msgpack");
+    // MessagePack encodes a f64 as the 0xcb marker followed by the big endian bytes
+    let mut expected = vec![0xcb];
+    expected.extend_from_slice(&7.0f64.to_be_bytes());
+    assert_eq!(body, expected);
+}
+
+#[tokio::test]
+async fn encoding_paths() {
+    let mut args = Vec::new();
+    ciborium::into_writer(
+        &SumArgs {
+            values: vec![1.5, 2.0, 3.0],
+        },
+        &mut args,
+    )
+    .unwrap();
+
+    let (content_type, body) = call("/api/product", "application/cbor", args).await;
+    assert_eq!(content_type, "application/json");
+    assert_eq!(body, b"9.0");
+}
+
+#[tokio::test]
+async fn streaming_cbor() {
+    let (content_type, body) = call(
+        "/api/count",
+        "application/x-www-form-urlencoded",
+        b"to=3".to_vec(),
+    )
+    .await;
+    assert_eq!(content_type, "application/x-dioxus-cbor-stream");
+    assert_eq!(decode_frames(&body), vec![Ok(1), Ok(2), Ok(3)]);
+}
+
+#[tokio::test]
+async fn streaming_cbor_errors_are_sent_in_order() {
+    let (_, body) = call(
+        "/api/count_then_fail",
+        "application/x-www-form-urlencoded",
+        b"to=2".to_vec(),
+    )
+    .await;
+    let items = decode_frames(&body);
+    assert_eq!(items[..2], [Ok(1), Ok(2)]);
+    assert!(items[2].as_ref().unwrap_err().contains("out of numbers"));
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "axum")]
+
+use std::{
+    fmt::Display,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use axum::{body::Body, Router};
+use dioxus::prelude::*;
+use http::{header::CONTENT_TYPE, Request, StatusCode};
+use server_fn::error::ServerFnErrorSerde;
+use tower::ServiceExt;
+
+/// The number of times a guarded server function body ran
+static BODY_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
+struct ApiKey(&'static str);
+
+struct RequireApiKey;
+
+impl Guard for RequireApiKey {
+    async fn check(&self, context: DioxusServerContext) -> Result<(), ServerFnError> {
+        let FromContext(ApiKey(key)) = context.extract().await?;
+        let parts = context.request_parts();
+        match parts.headers.get("x-api-key") {
+            Some(value) if value == key => Ok(()),
+            _ => Err(ServerFnError::ServerError("missing api key".to_string())),
+        }
+    }
+}
+
+async fn reject_all(_: DioxusServerContext) -> Result<(), ServerFnError> {
+    Err(ServerFnError::ServerError(
+        "rejected by the first guard".to_string(),
+    ))
+}
+
+#[server(endpoint = "secret", middleware = [RequireApiKey])]
+async fn secret() -> Result<String, ServerFnError> {
+    BODY_RUNS.fetch_add(1, Ordering::SeqCst);
+    Ok("the secret".to_string())
+}
+
+#[server(middleware = [reject_all, RequireApiKey], endpoint = "ordered")]
+async fn ordered() -> Result<(), ServerFnError> {
+    BODY_RUNS.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+#[server(
+    endpoint = "layered",
+    middleware = [
+        tower::util::MapResponseLayer::new(|mut response: http::Response<Body>| {
+            response.headers_mut().insert("x-layer", "applied".parse().unwrap());
+            response
+        }),
+        RequireApiKey,
+    ]
+)]
+async fn layered() -> Result<u32, ServerFnError> {
+    Ok(42)
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum AccountError {
+    Locked,
+}
+
+impl Display for AccountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "locked")
+    }
+}
+
+impl FromStr for AccountError {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "locked" => Ok(AccountError::Locked),
+            _ => Err(()),
+        }
+    }
+}
+
+async fn account_locked(_: DioxusServerContext) -> Result<(), ServerFnError<AccountError>> {
+    Err(ServerFnError::WrappedServerError(AccountError::Locked))
+}
+
+#[server(endpoint = "account", middleware = [account_locked])]
+async fn account() -> Result<(), ServerFnError<AccountError>> {
+    Ok(())
+}
+
+async fn call(path: &str, api_key: Option<&str>) -> (StatusCode, http::HeaderMap, String) {
+    let router =
+        Router::new().register_server_functions_with_context(Arc::new(vec![Box::new(|| {
+            Box::new(ApiKey("hunter2"))
+        })]));
+    let mut request = Request::post(path).header(CONTENT_TYPE, "application/x-www-form-urlencoded");
+    if let Some(api_key) = api_key {
+        request = request.header("x-api-key", api_key);
+    }
+    let response = router
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn guards_short_circuit_server_functions() {
+    let runs = BODY_RUNS.load(Ordering::SeqCst);
+    let (status, _, body) = call("/api/secret", Some("wrong")).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        ServerFnError::<server_fn::error::NoCustomError>::de(&body),
+        ServerFnError::ServerError("missing api key".to_string())
+    );
+
+    let (status, _, body) = call("/api/secret", Some("hunter2")).await;
+    assert!(status.is_success());
+    assert_eq!(body, "\"the secret\"");
+    // Only the request that passed the guard ran the body
+    assert_eq!(BODY_RUNS.load(Ordering::SeqCst), runs + 1);
+}
+
+#[tokio::test]
+async fn guards_run_in_order() {
+    // Both guards reject this request, but only the first guard in the list runs
+    let (status, _, body) = call("/api/ordered", None).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        ServerFnError::<server_fn::error::NoCustomError>::de(&body),
+        ServerFnError::ServerError("rejected by the first guard".to_string())
+    );
+}
+
+#[tokio::test]
+async fn guards_and_layers_can_be_mixed() {
+    let (status, headers, _) = call("/api/layered", None).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    // The layer wraps the guard, so it sees the rejected response
+    assert_eq!(headers["x-layer"], "applied");
+
+    let (status, headers, body) = call("/api/layered", Some("hunter2")).await;
+    assert!(status.is_success());
+    assert_eq!(headers["x-layer"], "applied");
+    assert_eq!(body, "42");
+}
+
+#[tokio::test]
+async fn guards_return_typed_errors() {
+    let (status, _, body) = call("/api/account", None).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        ServerFnError::<AccountError>::de(&body),
+        ServerFnError::WrappedServerError(AccountError::Locked)
+    );
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "axum")]
+
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_fullstack::StreamingMode;
+use futures_util::StreamExt;
+
+const INDEX_HTML: &str = r#"<!DOCTYPE html><html><head><title>Streaming</title></head><body><div id="main"></div></body></html>"#;
+
+fn app() -> Element {
+    rsx! {
+        h1 { "Header" }
+        SuspenseBoundary {
+            fallback: |_| rsx! { "Loading..." },
+            Slow {}
+        }
+        footer { "Footer" }
+    }
+}
+
+#[component]
+fn Slow() -> Element {
+    let value = use_resource(|| async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        "Loaded"
+    })
+    .suspend()?;
+
+    rsx! {
+        p { "{value}" }
+    }
+}
+
+/// Render the app and return every chunk of the response
+async fn render_chunks(streaming_mode: StreamingMode) -> Vec<String> {
+    let cfg = ServeConfig::builder()
+        .index_html(INDEX_HTML.to_string())
+        .streaming_mode(streaming_mode)
+        .build()
+        .unwrap();
+    let state = SSRState::new(&cfg);
+    let (_, stream) = state
+        .render(
+            "/".to_string(),
+            &cfg,
+            || VirtualDom::new(app),
+            &DioxusServerContext::default(),
+        )
+        .await
+        .unwrap();
+
+    stream.map(|chunk| chunk.unwrap()).collect().await
+}
+
+#[tokio::test]
+async fn in_order_streaming_flushes_ready_prefix() {
+    let chunks = render_chunks(StreamingMode::InOrder).await;
+
+    // The head and the header are sent before the suspense boundary resolves
+    let loaded = chunks
+        .iter()
+        .position(|chunk| chunk.contains("Loaded"))
+        .unwrap();
+    assert!(chunks[..loaded].iter().any(|chunk| chunk.contains("<head>")));
+    assert!(chunks[..loaded].iter().any(|chunk| chunk.contains("Header")));
+    assert!(!chunks[..loaded].iter().any(|chunk| chunk.contains("Footer")));
+
+    // The fallback and the out of order swap markup are never sent
+    let html = chunks.concat();
+    assert!(!html.contains("Loading..."));
+    assert!(!html.contains("hidden id=\"ds-"));
+    assert!(!html.contains("<div id=\"ds-"));
+
+    let header = html.find("Header").unwrap();
+    let loaded = html.find("Loaded").unwrap();
+    let footer = html.find("Footer").unwrap();
+    assert!(header < loaded && loaded < footer);
+}
+
+#[tokio::test]
+async fn in_order_streaming_matches_unstreamed_render() {
+    // The streamed chunks should form the same page as rendering without streaming so the client hydrates the same nodes
+    let in_order = render_chunks(StreamingMode::InOrder).await.concat();
+    let disabled = render_chunks(StreamingMode::Disabled).await.concat();
+    assert_eq!(in_order, disabled);
+}
+
+#[tokio::test]
+async fn in_order_streaming_fails_when_streamed_html_changes() {
+    fn app() -> Element {
+        let status = use_signal(|| "Waiting");
+        rsx! {
+            h1 { "{status}" }
+            SuspenseBoundary {
+                fallback: |_| rsx! { "Loading..." },
+                Finish { status }
+            }
+        }
+    }
+
+    #[component]
+    fn Finish(status: Signal<&'static str>) -> Element {
+        let value = use_resource(move || async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            status.set("Done");
+            "Loaded"
+        })
+        .suspend()?;
+
+        rsx! {
+            p { "{value}" }
+        }
+    }
+
+    let cfg = ServeConfig::builder()
+        .index_html(INDEX_HTML.to_string())
+        .streaming_mode(StreamingMode::InOrder)
+        .build()
+        .unwrap();
+    let state = SSRState::new(&cfg);
+    let (_, stream) = state
+        .render(
+            "/".to_string(),
+            &cfg,
+            || VirtualDom::new(app),
+            &DioxusServerContext::default(),
+        )
+        .await
+        .unwrap();
+    let chunks: Vec<_> = stream.collect().await;
+
+    // The header was streamed with the old status, so the rest of the page is never sent
+    assert!(chunks.iter().any(|chunk| chunk.is_err()));
+    assert!(!chunks
+        .iter()
+        .flatten()
+        .any(|chunk| chunk.contains("Loaded")));
+}
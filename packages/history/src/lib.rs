@@ -178,6 +178,28 @@ pub trait History {
     /// ```
     fn go_forward(&self);
 
+    /// Move through the history by a number of pages. Negative numbers go back and positive numbers go forward.
+    ///
+    /// The default implementation calls [`History::go_back`] or [`History::go_forward`] once for every page.
+    fn go(&self, delta: isize) {
+        for _ in 0..delta.unsigned_abs() {
+            if delta < 0 {
+                self.go_back();
+            } else {
+                self.go_forward();
+            }
+        }
+    }
+
+    /// Get the route `delta` pages away from the current page without moving there. The router uses this to check
+    /// navigation blockers before it moves through the history.
+    ///
+    /// If a [`HistoryProvider`] can't know the route before it moves, it should return [`None`].
+    #[allow(unused_variables)]
+    fn route_at(&self, delta: isize) -> Option<String> {
+        None
+    }
+
     /// Go to another page.
     ///
     /// This should do three things:
@@ -207,6 +229,33 @@ pub trait History {
     /// ```
     fn push(&self, route: String);
 
+    /// Go to another page and attach state to it, like a serialized form or the selected tab. The state is kept while
+    /// the user moves through the history, and can be read with [`History::current_state`] once the page is current.
+    ///
+    /// Pushing the current route again only replaces its state.
+    ///
+    /// The default implementation pushes the route without the state.
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.push_with_state("/search".to_string(), "dioxus".to_string());
+    /// history.push("/settings".to_string());
+    /// assert_eq!(history.current_state(), None);
+    ///
+    /// history.go_back();
+    /// assert_eq!(history.current_state(), Some("dioxus".to_string()));
+    /// ```
+    #[allow(unused_variables)]
+    fn push_with_state(&self, route: String, state: String) {
+        self.push(route);
+    }
+
+    /// Get the state attached to the current page with [`History::push_with_state`].
+    fn current_state(&self) -> Option<String> {
+        None
+    }
+
     /// Replace the current page with another one.
     ///
     /// This should merge the current URL with the `path` parameter (which may also include a query
@@ -253,6 +302,21 @@ pub trait History {
     #[allow(unused_variables)]
     fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {}
 
+    /// Get the number of pages the last update from outside the router moved through the history. This is used to undo
+    /// navigations like the browser back button when a navigation blocker rejects them.
+    ///
+    /// If a [`HistoryProvider`] doesn't receive updates from outside the router, or can't know how far they moved, it
+    /// should return [`None`].
+    fn last_update_delta(&self) -> Option<isize> {
+        None
+    }
+
+    /// Ask the user for confirmation before they leave the app, for example by closing the tab or window.
+    ///
+    /// The router calls this whenever a navigation blocker starts or stops blocking the current page from being left.
+    #[allow(unused_variables)]
+    fn block_unload(&self, blocked: bool) {}
+
     /// Whether the router should include the legacy prevent default attribute instead of the new
     /// prevent default method. This should only be used by liveview.
     fn include_prevent_default(&self) -> bool {
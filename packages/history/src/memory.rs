@@ -1,11 +1,59 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, cmp::Ordering};
 
 use crate::History;
 
+/// The position the page was scrolled to
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScrollPosition {
+    /// The horizontal scroll position in pixels
+    pub x: f64,
+    /// The vertical scroll position in pixels
+    pub y: f64,
+}
+
+/// A page in a [`MemoryHistory`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryEntry {
+    /// The route of the page
+    pub route: String,
+    /// The state attached to the page with [`History::push_with_state`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub state: Option<String>,
+    /// The position the page was scrolled to when the user left it
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scroll: Option<ScrollPosition>,
+}
+
+impl HistoryEntry {
+    fn new(route: String) -> Self {
+        Self {
+            route,
+            state: None,
+            scroll: None,
+        }
+    }
+}
+
+/// Every page of a [`MemoryHistory`], used to save the history and restore it later.
+///
+/// With the `serde` feature, snapshots can be serialized.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistorySnapshot {
+    /// The pages before the current page, starting with the oldest one
+    pub back: Vec<HistoryEntry>,
+    /// The current page
+    pub current: HistoryEntry,
+    /// The pages after the current page, starting with the next one
+    pub forward: Vec<HistoryEntry>,
+}
+
 struct MemoryHistoryState {
-    current: String,
-    history: Vec<String>,
-    future: Vec<String>,
+    current: HistoryEntry,
+    history: Vec<HistoryEntry>,
+    future: Vec<HistoryEntry>,
 }
 
 /// A [`History`] provider that stores all navigation information in memory.
@@ -45,9 +93,9 @@ impl MemoryHistory {
     pub fn with_initial_path(path: impl ToString) -> Self {
         Self {
             state: MemoryHistoryState{
-                current: path.to_string().parse().unwrap_or_else(|err| {
+                current: HistoryEntry::new(path.to_string().parse().unwrap_or_else(|err| {
                     panic!("index route does not exist:\n{err}\n use MemoryHistory::with_initial_path to set a custom path")
-                }),
+                })),
                 history: Vec::new(),
                 future: Vec::new(),
             }.into(),
@@ -55,6 +103,35 @@ impl MemoryHistory {
         }
     }
 
+    /// Restore a [`MemoryHistory`] from a snapshot taken with [`MemoryHistory::snapshot`].
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.push("/settings".to_string());
+    ///
+    /// let restored = MemoryHistory::from_snapshot(history.snapshot());
+    /// assert_eq!(restored.current_route(), "/settings");
+    /// assert!(restored.can_go_back());
+    /// ```
+    pub fn from_snapshot(snapshot: HistorySnapshot) -> Self {
+        let HistorySnapshot {
+            back,
+            current,
+            mut forward,
+        } = snapshot;
+        forward.reverse();
+        Self {
+            state: MemoryHistoryState {
+                current,
+                history: back,
+                future: forward,
+            }
+            .into(),
+            base_path: None,
+        }
+    }
+
     /// Set the base path for the history. All routes will be prefixed with this path when rendered.
     ///
     /// ```rust
@@ -68,6 +145,45 @@ impl MemoryHistory {
         self.base_path = Some(prefix.to_string());
         self
     }
+
+    /// Take a snapshot of every page in the history, including the state and scroll position of each page.
+    pub fn snapshot(&self) -> HistorySnapshot {
+        let state = self.state.borrow();
+        HistorySnapshot {
+            back: state.history.clone(),
+            current: state.current.clone(),
+            forward: state.future.iter().rev().cloned().collect(),
+        }
+    }
+
+    /// Get the position of the current page in the history. The first page is at position `0`.
+    pub fn index(&self) -> usize {
+        self.state.borrow().history.len()
+    }
+
+    /// Get the position the current page was scrolled to when the user last left it.
+    pub fn scroll(&self) -> Option<ScrollPosition> {
+        self.state.borrow().current.scroll
+    }
+
+    /// Save the position the page at `index` is scrolled to. Pages that are no longer in the history are ignored.
+    pub fn set_scroll(&self, index: usize, scroll: ScrollPosition) {
+        let mut write = self.state.borrow_mut();
+        let current = write.history.len();
+        let entry = match index.cmp(&current) {
+            Ordering::Less => write.history.get_mut(index),
+            Ordering::Equal => Some(&mut write.current),
+            Ordering::Greater => {
+                let future = index - current;
+                let len = write.future.len();
+                len.checked_sub(future)
+                    .and_then(|index| write.future.get_mut(index))
+            }
+        };
+        if let Some(entry) = entry {
+            entry.scroll = Some(scroll);
+        }
+    }
 }
 
 impl History for MemoryHistory {
@@ -76,7 +192,11 @@ impl History for MemoryHistory {
     }
 
     fn current_route(&self) -> String {
-        self.state.borrow().current.clone()
+        self.state.borrow().current.route.clone()
+    }
+
+    fn current_state(&self) -> Option<String> {
+        self.state.borrow().current.state.clone()
     }
 
     fn can_go_back(&self) -> bool {
@@ -103,19 +223,45 @@ impl History for MemoryHistory {
         }
     }
 
+    fn route_at(&self, delta: isize) -> Option<String> {
+        let state = self.state.borrow();
+        let distance = delta.unsigned_abs();
+        // The closest pages are at the end of the back and forward stacks
+        let entry = match delta.cmp(&0) {
+            Ordering::Less => state
+                .history
+                .len()
+                .checked_sub(distance)
+                .and_then(|index| state.history.get(index)),
+            Ordering::Equal => Some(&state.current),
+            Ordering::Greater => state
+                .future
+                .len()
+                .checked_sub(distance)
+                .and_then(|index| state.future.get(index)),
+        };
+        entry.map(|entry| entry.route.clone())
+    }
+
     fn push(&self, new: String) {
         let mut write = self.state.borrow_mut();
         // don't push the same route twice
-        if write.current == new {
+        if write.current.route == new {
             return;
         }
-        let old = std::mem::replace(&mut write.current, new);
+        let old = std::mem::replace(&mut write.current, HistoryEntry::new(new));
         write.history.push(old);
         write.future.clear();
     }
 
+    fn push_with_state(&self, route: String, state: String) {
+        self.push(route);
+        // Pushing the current route again only updates its state
+        self.state.borrow_mut().current.state = Some(state);
+    }
+
     fn replace(&self, path: String) {
         let mut write = self.state.borrow_mut();
-        write.current = path;
+        write.current = HistoryEntry::new(path);
     }
 }
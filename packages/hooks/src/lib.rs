@@ -72,8 +72,11 @@ pub use use_future::*;
 mod use_reactive;
 pub use use_reactive::*;
 
-// mod use_sorted;
-// pub use use_sorted::*;
+mod use_sorted;
+pub use use_sorted::*;
+
+mod use_collection;
+pub use use_collection::*;
 
 mod use_resource;
 pub use use_resource::*;
@@ -93,5 +96,8 @@ pub use use_hook_did_run::*;
 mod use_signal;
 pub use use_signal::*;
 
+mod use_store;
+pub use use_store::*;
+
 mod use_set_compare;
 pub use use_set_compare::*;
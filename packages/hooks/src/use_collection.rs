@@ -1,76 +1,581 @@
-/*
-a form of use_signal explicitly for map-style collections (BTreeMap, HashMap, etc).
-
-Why?
----
-Traditionally, it's possible to use the "use_state" hook for collections in the React world.
-Adding a new entry would look something similar to:
-
-```js
-let (map, set_map) = useState({});
-set_map({ ...map, [key]: value });
-```
-The new value then causes the appropriate update when passed into children.
-
-This is moderately efficient because the fields of the map are moved, but the data itself is not cloned.
-However, if you used similar approach with Dioxus:
-
-```rust
-let (map, set_map) = use_signal(|| HashMap::new());
-set_map({
-    let mut newmap = map.clone();
-    newmap.set(key, value);
-    newmap
-})
-```
-Unfortunately, you'd be cloning the entire state every time a value is changed. The obvious solution is to
-wrap every element in the HashMap with an Rc. That way, cloning the HashMap is on par with its JS equivalent.
-
-Fortunately, we can make this operation even more efficient in Dioxus, leveraging the borrow rules of Rust.
-
-This hook provides a memoized collection, memoized setters, and memoized getters. This particular hook is
-extremely powerful for implementing lists and supporting core state management needs for small apps.
-
-If you need something even more powerful, check out the dedicated atomic state management Dioxus Dataflow, which
-uses the same memoization on top of the use_context API.
-
-Here's a fully-functional todo app using the use_map API:
-```rust
-static TodoList: Component = |cx| {
-    let todos = use_map(|| HashMap::new());
-    let input = use_signal(|| None);
-
-    rsx! {
-        div {
-            button {
-                "Add todo"
-                onclick: move |_| {
-                    let new_todo = TodoItem::new(input.contents());
-                    todos.insert(new_todo.id.clone(), new_todo);
-                    input.clear();
-                }
-            }
-            button {
-                "Clear todos"
-                onclick: move |_| todos.clear()
-            }
-            input {
-                placeholder: "What needs to be done?"
-                ref: input
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use dioxus_core::prelude::*;
+use dioxus_signals::{CopyValue, Readable, ReadableRef, Signal, Writable};
+
+/// The fewest tracked keys before keys without readers are dropped
+const MIN_CLEANUP_KEYS: usize = 64;
+
+/// A map of signals that track reads of individual keys in a collection. The signals don't hold any data. They are only
+/// used to subscribe to and notify the readers of a key.
+struct KeySubscribers<K: 'static> {
+    keys: CopyValue<HashMap<K, Signal<()>>>,
+    /// The number of tracked keys that triggers the next cleanup of keys without readers
+    cleanup_at: CopyValue<usize>,
+}
+
+impl<K: Eq + Hash + Clone> KeySubscribers<K> {
+    fn new() -> Self {
+        Self {
+            keys: CopyValue::new(HashMap::new()),
+            cleanup_at: CopyValue::new(MIN_CLEANUP_KEYS),
+        }
+    }
+
+    /// Subscribe the current reactive context to changes of the key
+    fn track(&self, key: &K) {
+        let existing = self.keys.peek().get(key).copied();
+        let signal = match existing {
+            Some(signal) => signal,
+            None => {
+                self.drop_unread();
+                // The signal is owned by the collection so it lives as long as the collection, not the reader
+                let signal = Signal::new_in_scope((), self.keys.origin_scope());
+                self.keys.write_unchecked().insert(key.clone(), signal);
+                signal
             }
-            ul {
-                {todos.iter().map(|todo| rsx!(
-                    li {
-                        key: todo.id
-                        span { "{todo.content}" }
-                        button {"x", onclick: move |_| todos.remove(todo.key.clone())}
-                    }
-                ))}
+        };
+        signal.read();
+    }
+
+    /// Stop tracking keys that nothing reads anymore, including keys that are not in the collection. This runs each
+    /// time the number of tracked keys doubles so reading many different keys doesn't grow the map without bound.
+    fn drop_unread(&self) {
+        if self.keys.peek().len() < *self.cleanup_at.peek() {
+            return;
+        }
+        let mut unread = Vec::new();
+        self.keys.write_unchecked().retain(|_, signal| {
+            let read = !signal.subscribers().is_empty();
+            if !read {
+                unread.push(*signal);
             }
+            read
+        });
+        for signal in unread {
+            signal.manually_drop();
+        }
+        let remaining = self.keys.peek().len();
+        *self.cleanup_at.write_unchecked() = (remaining * 2).max(MIN_CLEANUP_KEYS);
+    }
+
+    /// Rerun everything that read the key
+    fn notify(&self, key: &K) {
+        let signal = self.keys.peek().get(key).copied();
+        if let Some(mut signal) = signal {
+            signal.set(());
         }
-    })
+    }
+
+    /// Rerun everything that read a key that matches the filter
+    fn notify_where(&self, mut filter: impl FnMut(&K) -> bool) {
+        let signals: Vec<_> = self
+            .keys
+            .peek()
+            .iter()
+            .filter(|(key, _)| filter(key))
+            .map(|(_, signal)| *signal)
+            .collect();
+        for mut signal in signals {
+            signal.set(());
+        }
+    }
+
+    /// Rerun everything that read the key and stop tracking it
+    fn remove(&self, key: &K) {
+        let signal = self.keys.write_unchecked().remove(key);
+        if let Some(mut signal) = signal {
+            signal.set(());
+            signal.manually_drop();
+        }
+    }
+
+    /// Rerun everything that read any key and stop tracking all keys
+    fn clear(&self) {
+        let signals: Vec<_> = self.keys.write_unchecked().drain().collect();
+        for (_, mut signal) in signals {
+            signal.set(());
+            signal.manually_drop();
+        }
+    }
 }
 
-```
+impl<K> Clone for KeySubscribers<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for KeySubscribers<K> {}
+
+/// Creates a new reactive [`HashMap`] with fine grained subscriptions. Reading a single key only subscribes to that key,
+/// so a component that reads one key doesn't rerun when another key changes.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use std::collections::HashMap;
+///
+/// fn App() -> Element {
+///     let mut scores = use_map(|| HashMap::from([("alice", 0), ("bob", 0)]));
+///
+///     rsx! {
+///         for name in scores.keys() {
+///             Score { key: "{name}", name, scores }
+///         }
+///         button {
+///             // Only the score for alice will rerender
+///             onclick: move |_| { scores.update(&"alice", |score| *score += 1); },
+///             "Point for alice"
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Score(name: &'static str, scores: UseMap<&'static str, u32>) -> Element {
+///     let score = scores.get(&name).unwrap_or_default();
+///     rsx! { "{name}: {score}" }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[must_use]
+pub fn use_map<K: Eq + Hash + Clone, V>(init: impl FnOnce() -> HashMap<K, V>) -> UseMap<K, V> {
+    use_hook(|| UseMap::new(init()))
+}
+
+/// A reactive [`HashMap`] created with [`use_map`].
+pub struct UseMap<K: 'static, V: 'static> {
+    map: CopyValue<HashMap<K, V>>,
+    entries: KeySubscribers<K>,
+    // Subscribers to the set of keys in the map
+    keys: Signal<()>,
+    // Subscribers to the whole map
+    all: Signal<()>,
+}
 
-*/
+impl<K: Eq + Hash + Clone, V> UseMap<K, V> {
+    /// Create a new reactive map owned by the current scope
+    pub fn new(map: HashMap<K, V>) -> Self {
+        Self {
+            map: CopyValue::new(map),
+            entries: KeySubscribers::new(),
+            keys: Signal::new(()),
+            all: Signal::new(()),
+        }
+    }
+
+    /// Get a clone of the value for a key. This only subscribes to changes of that key.
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.with_key(key, |value| value.cloned())
+    }
+
+    /// Run a closure with a reference to the value for a key. This only subscribes to changes of that key.
+    pub fn with_key<O>(&self, key: &K, f: impl FnOnce(Option<&V>) -> O) -> O {
+        self.entries.track(key);
+        f(self.map.peek().get(key))
+    }
+
+    /// Check if the map contains a key. This only subscribes to changes of that key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.with_key(key, |value| value.is_some())
+    }
+
+    /// Get the number of entries in the map. This subscribes to keys being added or removed.
+    pub fn len(&self) -> usize {
+        self.keys.read();
+        self.map.peek().len()
+    }
+
+    /// Check if the map is empty. This subscribes to keys being added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the keys in the map. This subscribes to keys being added or removed, but not to changes of the values.
+    pub fn keys(&self) -> Vec<K> {
+        self.keys.read();
+        self.map.peek().keys().cloned().collect()
+    }
+
+    /// Read the whole map. This subscribes to every change of the map.
+    pub fn read(&self) -> ReadableRef<'_, CopyValue<HashMap<K, V>>> {
+        self.all.read();
+        self.map.peek()
+    }
+
+    /// Run a closure with a reference to the whole map. This subscribes to every change of the map.
+    pub fn with<O>(&self, f: impl FnOnce(&HashMap<K, V>) -> O) -> O {
+        f(&self.read())
+    }
+
+    /// Insert a value into the map, returning the previous value for the key
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let previous = self.map.write().insert(key.clone(), value);
+        self.entries.notify(&key);
+        if previous.is_none() {
+            self.keys.set(());
+        }
+        self.all.set(());
+        previous
+    }
+
+    /// Remove a key from the map, returning the value if it was in the map
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let removed = self.map.write().remove(key);
+        if removed.is_some() {
+            self.entries.remove(key);
+            self.keys.set(());
+            self.all.set(());
+        }
+        removed
+    }
+
+    /// Modify the value for a key in place. Returns `None` if the key is not in the map.
+    pub fn update<O>(&mut self, key: &K, f: impl FnOnce(&mut V) -> O) -> Option<O> {
+        let output = self.map.write().get_mut(key).map(f);
+        if output.is_some() {
+            self.entries.notify(key);
+            self.all.set(());
+        }
+        output
+    }
+
+    /// Remove every entry from the map
+    pub fn clear(&mut self) {
+        self.map.write().clear();
+        self.entries.clear();
+        self.keys.set(());
+        self.all.set(());
+    }
+}
+
+impl<K, V> Clone for UseMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for UseMap<K, V> {}
+
+impl<K, V> PartialEq for UseMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+/// Creates a new reactive [`HashSet`] with fine grained subscriptions. Checking if the set contains a value only subscribes
+/// to that value, so a component doesn't rerun when other values are added or removed.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use std::collections::HashSet;
+///
+/// fn App() -> Element {
+///     let selected = use_set(HashSet::new);
+///
+///     rsx! {
+///         for id in 0..100 {
+///             // Selecting a row only rerenders that row
+///             Row { key: "{id}", id, selected }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Row(id: usize, selected: UseSet<usize>) -> Element {
+///     let is_selected = selected.contains(&id);
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 if !selected.remove(&id) {
+///                     selected.insert(id);
+///                 }
+///             },
+///             "{id}: {is_selected}"
+///         }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[must_use]
+pub fn use_set<T: Eq + Hash + Clone>(init: impl FnOnce() -> HashSet<T>) -> UseSet<T> {
+    use_hook(|| UseSet::new(init()))
+}
+
+/// A reactive [`HashSet`] created with [`use_set`].
+pub struct UseSet<T: 'static> {
+    set: CopyValue<HashSet<T>>,
+    values: KeySubscribers<T>,
+    // Subscribers to the whole set
+    all: Signal<()>,
+}
+
+impl<T: Eq + Hash + Clone> UseSet<T> {
+    /// Create a new reactive set owned by the current scope
+    pub fn new(set: HashSet<T>) -> Self {
+        Self {
+            set: CopyValue::new(set),
+            values: KeySubscribers::new(),
+            all: Signal::new(()),
+        }
+    }
+
+    /// Check if the set contains a value. This only subscribes to that value being added or removed.
+    pub fn contains(&self, value: &T) -> bool {
+        self.values.track(value);
+        self.set.peek().contains(value)
+    }
+
+    /// Get the number of values in the set. This subscribes to every change of the set.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Check if the set is empty. This subscribes to every change of the set.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read the whole set. This subscribes to every change of the set.
+    pub fn read(&self) -> ReadableRef<'_, CopyValue<HashSet<T>>> {
+        self.all.read();
+        self.set.peek()
+    }
+
+    /// Run a closure with a reference to the whole set. This subscribes to every change of the set.
+    pub fn with<O>(&self, f: impl FnOnce(&HashSet<T>) -> O) -> O {
+        f(&self.read())
+    }
+
+    /// Add a value to the set. Returns `true` if the value was not already in the set.
+    pub fn insert(&mut self, value: T) -> bool {
+        let inserted = self.set.write().insert(value.clone());
+        if inserted {
+            self.values.notify(&value);
+            self.all.set(());
+        }
+        inserted
+    }
+
+    /// Remove a value from the set. Returns `true` if the value was in the set.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let removed = self.set.write().remove(value);
+        if removed {
+            self.values.remove(value);
+            self.all.set(());
+        }
+        removed
+    }
+
+    /// Remove every value from the set
+    pub fn clear(&mut self) {
+        self.set.write().clear();
+        self.values.clear();
+        self.all.set(());
+    }
+}
+
+impl<T> Clone for UseSet<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UseSet<T> {}
+
+impl<T> PartialEq for UseSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.set == other.set
+    }
+}
+
+/// Creates a new reactive [`Vec`] with fine grained subscriptions. Reading a single index only subscribes to that index,
+/// so a component doesn't rerun when another index changes.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// fn App() -> Element {
+///     let mut todos = use_vec(|| vec!["Write docs".to_string()]);
+///
+///     rsx! {
+///         for index in 0..todos.len() {
+///             Todo { key: "{index}", index, todos }
+///         }
+///         button {
+///             // Pushing a todo only rerenders the list, not the existing todos
+///             onclick: move |_| todos.push("New todo".to_string()),
+///             "Add todo"
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Todo(index: usize, todos: UseVec<String>) -> Element {
+///     let todo = todos.get(index).unwrap_or_default();
+///     rsx! { "{todo}" }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[must_use]
+pub fn use_vec<T>(init: impl FnOnce() -> Vec<T>) -> UseVec<T> {
+    use_hook(|| UseVec::new(init()))
+}
+
+/// A reactive [`Vec`] created with [`use_vec`].
+pub struct UseVec<T: 'static> {
+    vec: CopyValue<Vec<T>>,
+    indexes: KeySubscribers<usize>,
+    // Subscribers to the length of the vec
+    len: Signal<()>,
+    // Subscribers to the whole vec
+    all: Signal<()>,
+}
+
+impl<T> UseVec<T> {
+    /// Create a new reactive vec owned by the current scope
+    pub fn new(vec: Vec<T>) -> Self {
+        Self {
+            vec: CopyValue::new(vec),
+            indexes: KeySubscribers::new(),
+            len: Signal::new(()),
+            all: Signal::new(()),
+        }
+    }
+
+    /// Get a clone of the value at an index. This only subscribes to changes of that index.
+    pub fn get(&self, index: usize) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.with_index(index, |value| value.cloned())
+    }
+
+    /// Run a closure with a reference to the value at an index. This only subscribes to changes of that index.
+    pub fn with_index<O>(&self, index: usize, f: impl FnOnce(Option<&T>) -> O) -> O {
+        self.indexes.track(&index);
+        f(self.vec.peek().get(index))
+    }
+
+    /// Get the length of the vec. This only subscribes to changes of the length.
+    pub fn len(&self) -> usize {
+        self.len.read();
+        self.vec.peek().len()
+    }
+
+    /// Check if the vec is empty. This only subscribes to changes of the length.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read the whole vec. This subscribes to every change of the vec.
+    pub fn read(&self) -> ReadableRef<'_, CopyValue<Vec<T>>> {
+        self.all.read();
+        self.vec.peek()
+    }
+
+    /// Run a closure with a reference to the whole vec. This subscribes to every change of the vec.
+    pub fn with<O>(&self, f: impl FnOnce(&Vec<T>) -> O) -> O {
+        f(&self.read())
+    }
+
+    /// Push a value onto the end of the vec
+    pub fn push(&mut self, value: T) {
+        let index = {
+            let mut vec = self.vec.write();
+            vec.push(value);
+            vec.len() - 1
+        };
+        self.indexes.notify(&index);
+        self.notify_len();
+    }
+
+    /// Remove the last value from the vec
+    pub fn pop(&mut self) -> Option<T> {
+        let (value, index) = {
+            let mut vec = self.vec.write();
+            (vec.pop(), vec.len())
+        };
+        if value.is_some() {
+            self.indexes.remove(&index);
+            self.notify_len();
+        }
+        value
+    }
+
+    /// Insert a value at an index, shifting every value after it to the right
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.vec.write().insert(index, value);
+        self.indexes.notify_where(|&i| i >= index);
+        self.notify_len();
+    }
+
+    /// Remove the value at an index, shifting every value after it to the left
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        let (value, len) = {
+            let mut vec = self.vec.write();
+            (vec.remove(index), vec.len())
+        };
+        self.indexes.notify_where(|&i| i >= index);
+        self.indexes.remove(&len);
+        self.notify_len();
+        value
+    }
+
+    /// Replace the value at an index, returning the old value. Returns `None` if the index is out of bounds.
+    pub fn set(&mut self, index: usize, value: T) -> Option<T> {
+        self.update(index, |current| std::mem::replace(current, value))
+    }
+
+    /// Modify the value at an index in place. Returns `None` if the index is out of bounds.
+    pub fn update<O>(&mut self, index: usize, f: impl FnOnce(&mut T) -> O) -> Option<O> {
+        let output = self.vec.write().get_mut(index).map(f);
+        if output.is_some() {
+            self.indexes.notify(&index);
+            self.all.set(());
+        }
+        output
+    }
+
+    /// Remove every value from the vec
+    pub fn clear(&mut self) {
+        self.vec.write().clear();
+        self.indexes.clear();
+        self.notify_len();
+    }
+
+    fn notify_len(&mut self) {
+        self.len.set(());
+        self.all.set(());
+    }
+}
+
+impl<T> Clone for UseVec<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UseVec<T> {}
+
+impl<T> PartialEq for UseVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vec == other.vec
+    }
+}
@@ -1,67 +1,126 @@
 use std::cmp::Ordering;
-use std::ops::DerefMut;
 
 use crate::use_memo;
-use dioxus_signals::{ReadOnlySignal, Signal};
+use dioxus_core::prelude::*;
+use dioxus_signals::{CopyValue, Memo, Writable};
 
-pub fn use_sorted<V: 'static, T: PartialEq>(
-    collection: impl FnMut() -> Signal<V>,
-) -> ReadOnlySignal<Vec<T>>
-// pub fn use_sorted<S, I, T>(iterable: impl FnMut() -> Signal<V>) -> ReadOnlySignal<T>
-// where
-//     S: Into<MaybeSignal<I>>,
-//     T: Ord,
-//     I: DerefMut<Target = [T]> + Clone + PartialEq,
-{
+/// Creates a sorted view of a list that updates when the list changes.
+///
+/// The view is updated incrementally. If only the end of the list changed since the last time it was sorted, like after
+/// a `push`, `pop` or `truncate`, the changed values are moved into place instead of sorting the whole list again.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// fn App() -> Element {
+///     let mut scores = use_signal(|| vec![3, 1, 2]);
+///     let sorted = use_sorted(move || scores.cloned());
+///
+///     rsx! {
+///         for score in sorted() {
+///             "{score}"
+///         }
+///         button { onclick: move |_| scores.push(0), "Add score" }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+pub fn use_sorted<T: Ord + Clone + 'static>(f: impl FnMut() -> Vec<T> + 'static) -> Memo<Vec<T>> {
+    use_sorted_by(f, T::cmp)
+}
+
+/// Version of [`use_sorted`] with a compare function. Like [`slice::sort_by`], the sort is stable.
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+pub fn use_sorted_by<T: PartialEq + Clone + 'static>(
+    mut f: impl FnMut() -> Vec<T> + 'static,
+    mut compare: impl FnMut(&T, &T) -> Ordering + 'static,
+) -> Memo<Vec<T>> {
+    let mut view = use_hook(|| CopyValue::new(SortedView::default()));
     use_memo(move || {
-        unimplemented!()
-        // let mut iterable = collection();
-        // iterable.sort();
-        // iterable
+        let input = f();
+        view.write().update(input, &mut compare)
     })
-    // let iterable = iterable.into();
+}
+
+/// Version of [`use_sorted`] that sorts by a key. Like [`slice::sort_by_key`], the sort is stable.
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+pub fn use_sorted_by_key<T: PartialEq + Clone + 'static, K: Ord>(
+    f: impl FnMut() -> Vec<T> + 'static,
+    mut key: impl FnMut(&T) -> K + 'static,
+) -> Memo<Vec<T>> {
+    use_sorted_by(f, move |a, b| key(a).cmp(&key(b)))
+}
 
-    // // use_memo(f)
+/// The last input of a sorted view and the sorted values
+struct SortedView<T> {
+    input: Vec<T>,
+    sorted: Vec<T>,
+}
 
-    // create_memo(move |_| {
-    //     let mut iterable = iterable.get();
-    //     iterable.sort();
-    //     iterable
-    // })
-    // .into()
+impl<T> Default for SortedView<T> {
+    fn default() -> Self {
+        Self {
+            input: Vec::new(),
+            sorted: Vec::new(),
+        }
+    }
 }
 
-// /// Version of [`use_sorted`] with a compare function.
-// pub fn use_sorted_by<S, I, T, F>(iterable: S, cmp_fn: F) -> Signal<I>
-// where
-//     S: Into<MaybeSignal<I>>,
-//     I: DerefMut<Target = [T]> + Clone + PartialEq,
-//     F: FnMut(&T, &T) -> Ordering + Clone + 'static,
-// {
-//     let iterable = iterable.into();
+impl<T: PartialEq + Clone> SortedView<T> {
+    /// Update the sorted view with a new input and return the sorted values
+    fn update(&mut self, input: Vec<T>, compare: &mut impl FnMut(&T, &T) -> Ordering) -> Vec<T> {
+        let unchanged = self
+            .input
+            .iter()
+            .zip(&input)
+            .take_while(|(old, new)| old == new)
+            .count();
+        let removed = &self.input[unchanged..];
+        let added = &input[unchanged..];
 
-//     create_memo(move |_| {
-//         let mut iterable = iterable.get();
-//         iterable.sort_by(cmp_fn.clone());
-//         iterable
-//     })
-//     .into()
-// }
+        // If most of the list changed, sorting from scratch is faster than moving every value
+        let small_change = (removed.len() + added.len()) * 2 <= input.len();
+        if !(small_change && patch_sorted(&mut self.sorted, removed, added, compare)) {
+            self.sorted = input.clone();
+            self.sorted.sort_by(|a, b| compare(a, b));
+        }
 
-// /// Version of [`use_sorted`] by key.
-// pub fn use_sorted_by_key<S, I, T, K, F>(iterable: S, key_fn: F) -> Signal<I>
-// where
-//     S: Into<MaybeSignal<I>>,
-//     I: DerefMut<Target = [T]> + Clone + PartialEq,
-//     K: Ord,
-//     F: FnMut(&T) -> K + Clone + 'static,
-// {
-//     let iterable = iterable.into();
+        self.input = input;
+        self.sorted.clone()
+    }
+}
 
-//     create_memo(move |_| {
-//         let mut iterable = iterable.get();
-//         iterable.sort_by_key(key_fn.clone());
-//         iterable
-//     })
-//     .into()
-// }
+/// Move the values that changed at the end of the input into place. Returns `false` if a removed value could not be
+/// found, which can only happen if the compare function is inconsistent.
+fn patch_sorted<T: PartialEq + Clone>(
+    sorted: &mut Vec<T>,
+    removed: &[T],
+    added: &[T],
+    compare: &mut impl FnMut(&T, &T) -> Ordering,
+) -> bool {
+    for value in removed {
+        // Values that compare equal are kept in the order of the input. Removed values come after every unchanged
+        // value in the input, so the last match is the removed value
+        let start = sorted.partition_point(|other| compare(other, value) == Ordering::Less);
+        let end = sorted.partition_point(|other| compare(other, value) != Ordering::Greater);
+        match sorted[start..end.max(start)]
+            .iter()
+            .rposition(|other| other == value)
+        {
+            Some(index) => _ = sorted.remove(start + index),
+            None => return false,
+        }
+    }
+    for value in added {
+        // Added values come after every value that is already in the view, so they go after any equal values
+        let index = sorted.partition_point(|other| compare(other, value) != Ordering::Greater);
+        sorted.insert(index, value.clone());
+    }
+    true
+}
@@ -0,0 +1,42 @@
+use dioxus_core::prelude::*;
+use dioxus_signals::Store;
+
+/// Creates a new [`Store`]. Stores are a Copy state management solution that track reads and writes to each field of a
+/// struct or item of a vec separately.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Store, Clone)]
+/// struct Counters {
+///     clicks: u32,
+///     key_presses: u32,
+/// }
+///
+/// fn App() -> Element {
+///     let counters = use_store(|| Counters { clicks: 0, key_presses: 0 });
+///
+///     rsx! {
+///         // This component only reruns when clicks changes
+///         Clicks { clicks: counters.clicks() }
+///         input { onkeydown: move |_| *counters.key_presses().write() += 1 }
+///     }
+/// }
+///
+/// #[component]
+/// fn Clicks(clicks: Store<u32>) -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| clicks += 1,
+///             "{clicks}"
+///         }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[must_use]
+#[track_caller]
+pub fn use_store<T: 'static>(f: impl FnOnce() -> T) -> Store<T> {
+    use_hook(|| Store::new(f()))
+}
@@ -0,0 +1,249 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Wake, Waker},
+};
+
+use dioxus::{dioxus_core::NoOpMutations, prelude::*};
+
+thread_local! {
+    static RENDERS: RefCell<HashMap<String, usize>> = RefCell::new(HashMap::new());
+}
+
+fn rendered(name: impl ToString) {
+    RENDERS.with_borrow_mut(|renders| *renders.entry(name.to_string()).or_default() += 1);
+}
+
+fn renders(name: &str) -> usize {
+    RENDERS.with_borrow(|renders| renders.get(name).copied().unwrap_or_default())
+}
+
+/// Rerun everything that changed until the virtual dom has no more work
+async fn settle(dom: &mut VirtualDom) {
+    loop {
+        let woken = Arc::new(Woken::default());
+        let waker = Waker::from(woken.clone());
+        let ready = std::pin::pin!(dom.wait_for_work())
+            .poll(&mut Context::from_waker(&waker))
+            .is_ready();
+        if ready {
+            dom.render_immediate(&mut NoOpMutations);
+            continue;
+        }
+        // Let the tasks that were polled wake up, then stop if none of them queued more work
+        tokio::task::yield_now().await;
+        if !woken.0.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+}
+
+/// A waker that records if the virtual dom was notified of new work
+#[derive(Default)]
+struct Woken(AtomicBool);
+
+impl Wake for Woken {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Get a value the app provided as context
+fn from_app<T: Clone + 'static>(dom: &VirtualDom) -> T {
+    dom.in_runtime(|| ScopeId::APP.consume_context::<T>().unwrap())
+}
+
+#[tokio::test]
+async fn map_subscribes_to_single_keys() {
+    fn app() -> Element {
+        let map = use_map(|| HashMap::from([("a", 1), ("b", 2)]));
+        use_context_provider(|| map);
+        rendered("app");
+
+        rsx! {
+            Entry { name: "a", map }
+            Entry { name: "b", map }
+            Len { map }
+        }
+    }
+
+    #[component]
+    fn Entry(name: &'static str, map: UseMap<&'static str, usize>) -> Element {
+        rendered(name);
+        let value = map.get(&name);
+        rsx! { "{value:?}" }
+    }
+
+    #[component]
+    fn Len(map: UseMap<&'static str, usize>) -> Element {
+        rendered("len");
+        rsx! { "{map.len()}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let mut map: UseMap<&'static str, usize> = from_app(&dom);
+
+    // Changing a value only reruns the component that read that key
+    dom.in_runtime(|| map.update(&"a", |value| *value += 1));
+    settle(&mut dom).await;
+    assert_eq!((renders("a"), renders("b"), renders("len")), (2, 1, 1));
+    assert_eq!(dom.in_runtime(|| map.get(&"a")), Some(2));
+
+    // Adding a key reruns the components that read the keys, but not the other entries
+    dom.in_runtime(|| map.insert("c", 3));
+    settle(&mut dom).await;
+    assert_eq!((renders("a"), renders("b"), renders("len")), (2, 1, 2));
+
+    // Removing a key reruns the component that read it
+    dom.in_runtime(|| map.remove(&"b"));
+    settle(&mut dom).await;
+    assert_eq!((renders("a"), renders("b"), renders("len")), (2, 2, 3));
+    assert_eq!(dom.in_runtime(|| map.get(&"b")), None);
+
+    // Inserting a key that was read while it was missing reruns the reader
+    dom.in_runtime(|| map.insert("b", 4));
+    settle(&mut dom).await;
+    assert_eq!((renders("a"), renders("b"), renders("len")), (2, 3, 4));
+
+    // The component that owns the map never reads it
+    assert_eq!(renders("app"), 1);
+}
+
+#[tokio::test]
+async fn set_subscribes_to_single_values() {
+    fn app() -> Element {
+        let set = use_set(HashSet::new);
+        use_context_provider(|| set);
+
+        rsx! {
+            for value in 0..3 {
+                Contains { value, set }
+            }
+        }
+    }
+
+    #[component]
+    fn Contains(value: usize, set: UseSet<usize>) -> Element {
+        rendered(format!("contains {value}"));
+        rsx! { "{set.contains(&value)}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let mut set: UseSet<usize> = from_app(&dom);
+
+    dom.in_runtime(|| assert!(set.insert(1)));
+    settle(&mut dom).await;
+    assert_eq!(renders("contains 0"), 1);
+    assert_eq!(renders("contains 1"), 2);
+    assert_eq!(renders("contains 2"), 1);
+
+    // Inserting a value that is already in the set doesn't rerun anything
+    dom.in_runtime(|| assert!(!set.insert(1)));
+    settle(&mut dom).await;
+    assert_eq!(renders("contains 1"), 2);
+
+    dom.in_runtime(|| set.clear());
+    settle(&mut dom).await;
+    assert_eq!(renders("contains 0"), 2);
+    assert_eq!(renders("contains 1"), 3);
+    assert_eq!(renders("contains 2"), 2);
+}
+
+#[tokio::test]
+async fn set_keeps_tracking_values_after_unread_values_are_dropped() {
+    fn app() -> Element {
+        let set = use_set(HashSet::new);
+        let start = use_signal(|| 10);
+        use_context_provider(|| (set, start));
+
+        rsx! {
+            for value in (0..10).chain(start()..start() + 90) {
+                Row { key: "{value}", value, set }
+            }
+        }
+    }
+
+    #[component]
+    fn Row(value: usize, set: UseSet<usize>) -> Element {
+        rendered(format!("row {value}"));
+        rsx! { "{set.contains(&value)}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let (mut set, mut start): (UseSet<usize>, Signal<usize>) = from_app(&dom);
+
+    // Replace most of the rows so the values they read are dropped while new values are tracked
+    dom.in_runtime(|| start.set(100));
+    settle(&mut dom).await;
+
+    // The values that are still read keep notifying their readers
+    dom.in_runtime(|| assert!(set.insert(5)));
+    dom.in_runtime(|| assert!(set.insert(150)));
+    settle(&mut dom).await;
+    assert_eq!(renders("row 5"), 2);
+    assert_eq!(renders("row 6"), 1);
+    assert_eq!(renders("row 150"), 2);
+    assert_eq!(renders("row 151"), 1);
+}
+
+#[tokio::test]
+async fn vec_subscribes_to_single_indexes() {
+    fn app() -> Element {
+        let vec = use_vec(|| vec!['a', 'b']);
+        use_context_provider(|| vec);
+
+        rsx! {
+            Item { index: 0, vec }
+            Item { index: 1, vec }
+            Items { vec }
+        }
+    }
+
+    #[component]
+    fn Item(index: usize, vec: UseVec<char>) -> Element {
+        rendered(format!("item {index}"));
+        rsx! { "{vec.get(index):?}" }
+    }
+
+    #[component]
+    fn Items(vec: UseVec<char>) -> Element {
+        rendered("items");
+        rsx! { "{vec.read():?}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let mut vec: UseVec<char> = from_app(&dom);
+
+    // Pushing a value doesn't rerun the existing items
+    dom.in_runtime(|| vec.push('c'));
+    settle(&mut dom).await;
+    assert_eq!(
+        (renders("item 0"), renders("item 1"), renders("items")),
+        (1, 1, 2)
+    );
+
+    dom.in_runtime(|| vec.set(1, 'B'));
+    settle(&mut dom).await;
+    assert_eq!(
+        (renders("item 0"), renders("item 1"), renders("items")),
+        (1, 2, 3)
+    );
+
+    // Removing a value shifts every value after it
+    dom.in_runtime(|| assert_eq!(vec.remove(0), 'a'));
+    settle(&mut dom).await;
+    assert_eq!(
+        (renders("item 0"), renders("item 1"), renders("items")),
+        (2, 3, 4)
+    );
+    assert_eq!(dom.in_runtime(|| vec.read().clone()), vec!['B', 'c']);
+}
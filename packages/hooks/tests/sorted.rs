@@ -0,0 +1,61 @@
+use dioxus::prelude::*;
+
+type Values = Vec<(u8, u32)>;
+
+#[derive(Clone, Copy)]
+struct State {
+    values: Signal<Values>,
+    sorted: Memo<Values>,
+    by_key: Memo<Values>,
+}
+
+#[test]
+fn sorted_views_match_a_full_sort() {
+    fn app() -> Element {
+        let values = use_signal(|| vec![(3, 0), (1, 1), (2, 2), (1, 3)]);
+        let sorted = use_sorted(move || values.cloned());
+        // Sorting by the first value keeps values with the same key in the order of the input
+        let by_key = use_sorted_by_key(move || values.cloned(), |(key, _)| *key);
+        use_context_provider(|| State {
+            values,
+            sorted,
+            by_key,
+        });
+        rsx! {}
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let State {
+        mut values,
+        sorted,
+        by_key,
+    } = dom.in_runtime(|| ScopeId::APP.consume_context::<State>().unwrap());
+
+    let check = move |dom: &VirtualDom| {
+        dom.in_runtime(|| {
+            let mut expected = values();
+            expected.sort();
+            assert_eq!(sorted(), expected);
+            let mut expected = values();
+            expected.sort_by_key(|(key, _)| *key);
+            assert_eq!(by_key(), expected);
+        })
+    };
+    check(&dom);
+
+    let edits: [fn(&mut Values); 8] = [
+        |values| values.push((2, 4)),
+        |values| values.push((0, 5)),
+        |values| _ = values.pop(),
+        |values| values.extend([(1, 6), (3, 7)]),
+        |values| values.truncate(5),
+        |values| values.insert(0, (9, 8)),
+        |values| values.reverse(),
+        |values| values.clear(),
+    ];
+    for edit in edits {
+        dom.in_runtime(|| edit(&mut values.write()));
+        check(&dom);
+    }
+}
@@ -53,6 +53,37 @@ macro_rules! impl_event {
                     #[allow(deprecated)]
                     super::$name(event_handler)
                 }
+
+                // Capture listeners are named `event:capture`. The rsx macro uses this version for `event_capture`
+                $( #[$attr] )*
+                #[inline]
+                pub fn capture<__Marker>(mut _f: impl ::dioxus_core::prelude::SuperInto<::dioxus_core::prelude::EventHandler<::dioxus_core::Event<$data>>, __Marker>) -> ::dioxus_core::Attribute {
+                    let event_handler = _f.super_into();
+                    ::dioxus_core::Attribute::new(
+                        concat!(impl_event!(@name $name $($js_name)?), ":capture"),
+                        ::dioxus_core::AttributeValue::listener(move |e: ::dioxus_core::Event<crate::PlatformEventData>| {
+                            event_handler.call(e.map(|e| e.into()));
+                        }),
+                        None,
+                        false,
+                    ).into()
+                }
+
+                $( #[$attr] )*
+                pub mod capture {
+                    use super::*;
+
+                    $( #[$attr] )*
+                    pub fn call_with_explicit_closure<
+                        __Marker,
+                        Return: ::dioxus_core::SpawnIfAsync<__Marker> + 'static,
+                    >(
+                        event_handler: impl FnMut(::dioxus_core::Event<$data>) -> Return + 'static,
+                    ) -> ::dioxus_core::Attribute {
+                        #[allow(deprecated)]
+                        super::capture(event_handler)
+                    }
+                }
             }
         )*
     };
@@ -42,7 +42,20 @@ export class BaseInterpreter {
     this.stack = [root];
     this.templates = {};
 
-    this.handler = handler;
+    // Events that don't bubble can reach several elements with capture listeners before they reach the target, but the
+    // virtualdom runs every listener for the event at once, so each event is only handled once
+    if (handler) {
+      const handled = new WeakSet<Event>();
+      this.handler = (event) => {
+        if (handled.has(event)) {
+          return;
+        }
+        handled.add(event);
+        handler(event);
+      };
+    } else {
+      this.handler = handler;
+    }
 
     // make sure to set the root element's ID so it still registers events
     root.setAttribute('data-dioxus-id', "0");
@@ -107,7 +120,12 @@ export class BaseInterpreter {
   }
 
   createListener(event_name: string, element: HTMLElement, bubbles: boolean) {
-    if (event_name == "resize") {
+    // Capture listeners are named `event:capture`. The virtualdom calls them in the right order, we just need to make
+    // sure the event reaches us when it happens inside of the element
+    const capture = event_name.endsWith(":capture");
+    if (capture) {
+      event_name = event_name.slice(0, -":capture".length);
+    } else if (event_name == "resize") {
       this.createResizeObserver(element);
     } else if (event_name == "visible") {
       this.createIntersectionObserver(element);
@@ -125,19 +143,24 @@ export class BaseInterpreter {
       if (!this.local[id]) {
         this.local[id] = {};
       }
-      element.addEventListener(event_name, this.handler);
+      element.addEventListener(event_name, this.handler, capture);
     }
   }
 
   removeListener(element: HTMLElement, event_name: string, bubbles: boolean) {
-    if (event_name == "resize") {
+    const capture = event_name.endsWith(":capture");
+    if (capture) {
+      event_name = event_name.slice(0, -":capture".length);
+    }
+
+    if (!capture && event_name == "resize") {
       this.removeResizeObserver(element);
-    } else if (event_name == "visible") {
+    } else if (!capture && event_name == "visible") {
       this.removeIntersectionObserver(element);
     } else if (bubbles) {
       this.removeBubblingListener(event_name);
     } else {
-      this.removeNonBubblingListener(element, event_name);
+      this.removeNonBubblingListener(element, event_name, capture);
     }
   }
 
@@ -152,13 +175,13 @@ export class BaseInterpreter {
     }
   }
 
-  removeNonBubblingListener(element: HTMLElement, event_name: string) {
+  removeNonBubblingListener(element: HTMLElement, event_name: string, capture = false) {
     const id = element.getAttribute("data-dioxus-id");
     delete this.local[id][event_name];
     if (Object.keys(this.local[id]).length === 0) {
       delete this.local[id];
     }
-    element.removeEventListener(event_name, this.handler);
+    element.removeEventListener(event_name, this.handler, capture);
   }
 
   removeAllNonBubblingListeners(element: HTMLElement) {
@@ -214,9 +237,10 @@ export class BaseInterpreter {
       hydrateNode.setAttribute("data-dioxus-id", id.toString());
       for (let j = 1; j < split.length; j++) {
         const listener = split[j];
-        const split2 = listener.split(":");
-        const event_name = split2[0];
-        const bubbles = split2[1] === "1";
+        // Capture listeners contain a colon, so the bubbles flag is after the last colon
+        const separator = listener.lastIndexOf(":");
+        const event_name = listener.slice(0, separator);
+        const bubbles = listener.slice(separator + 1) === "1";
         this.createListener(event_name, hydrateNode, bubbles);
       }
     }
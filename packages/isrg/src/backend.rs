@@ -0,0 +1,102 @@
+//! The storage backend used to persist pre-rendered routes
+
+use crate::{IncrementalRendererError, RenderFreshness};
+
+/// A storage backend for pre-rendered routes.
+///
+/// The [`IncrementalRenderer`](crate::IncrementalRenderer) always keeps a small in memory LRU cache in front of the backend. The backend
+/// is responsible for persisting renders across restarts, or sharing them between multiple server replicas.
+///
+/// Dioxus ships with a few built-in backends:
+/// - [`InMemoryCache`](crate::InMemoryCache): Stores renders in memory only
+/// - [`FileSystemCache`](crate::FileSystemCache): Stores renders as html files in the static directory (default)
+/// - [`SqliteCache`](crate::SqliteCache): Stores renders in a SQLite database file that can be shared between processes (requires the `sqlite` feature)
+///
+/// ```rust
+/// # use dioxus_isrg::*;
+/// # use std::collections::HashMap;
+/// #[derive(Default)]
+/// struct MyCache {
+///     renders: HashMap<String, (RenderFreshness, Vec<u8>)>,
+///     tags: HashMap<String, Vec<String>>,
+/// }
+///
+/// impl CacheBackend for MyCache {
+///     fn get(&mut self, route: &str) -> Result<Option<(RenderFreshness, Vec<u8>)>, IncrementalRendererError> {
+///         Ok(self.renders.get(route).cloned())
+///     }
+///
+///     fn put(&mut self, route: String, freshness: RenderFreshness, html: Vec<u8>) -> Result<(), IncrementalRendererError> {
+///         self.renders.insert(route, (freshness, html));
+///         Ok(())
+///     }
+///
+///     fn set_tags(&mut self, route: &str, tags: &[String]) -> Result<(), IncrementalRendererError> {
+///         for tag in tags {
+///             self.tags.entry(tag.clone()).or_default().push(route.to_string());
+///         }
+///         Ok(())
+///     }
+///
+///     fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+///         self.renders.remove(route);
+///         Ok(())
+///     }
+///
+///     fn invalidate_tag(&mut self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+///         let routes = self.tags.remove(tag).unwrap_or_default();
+///         for route in &routes {
+///             self.renders.remove(route);
+///         }
+///         Ok(routes)
+///     }
+///
+///     fn clear(&mut self) -> Result<(), IncrementalRendererError> {
+///         self.renders.clear();
+///         self.tags.clear();
+///         Ok(())
+///     }
+///
+///     fn list(&self) -> Result<Vec<(String, RenderFreshness)>, IncrementalRendererError> {
+///         Ok(self.renders.iter().map(|(route, (freshness, _))| (route.clone(), *freshness)).collect())
+///     }
+/// }
+///
+/// let renderer = IncrementalRenderer::builder().backend(MyCache::default).build();
+/// ```
+pub trait CacheBackend: Send + Sync + 'static {
+    /// Get the render for a route if it exists. Backends should return `None` for entries where [`RenderFreshness::is_expired`] is true,
+    /// but keep returning stale entries so they can be served while the route is re-rendered.
+    #[allow(clippy::type_complexity)]
+    fn get(
+        &mut self,
+        route: &str,
+    ) -> Result<Option<(RenderFreshness, Vec<u8>)>, IncrementalRendererError>;
+
+    /// Insert or replace the render for a route. The freshness contains the time the route was rendered and how long the render is valid for.
+    fn put(
+        &mut self,
+        route: String,
+        freshness: RenderFreshness,
+        html: Vec<u8>,
+    ) -> Result<(), IncrementalRendererError>;
+
+    /// Replace the set of tags associated with a route. Tags are registered while a route renders and are used to invalidate
+    /// many routes at once with [`CacheBackend::invalidate_tag`].
+    fn set_tags(&mut self, route: &str, tags: &[String]) -> Result<(), IncrementalRendererError>;
+
+    /// Remove the render for a route if it exists.
+    fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError>;
+
+    /// Remove the render for every route associated with the tag. Returns the routes that carried the tag.
+    fn invalidate_tag(&mut self, tag: &str) -> Result<Vec<String>, IncrementalRendererError>;
+
+    /// Remove every render from the cache.
+    fn clear(&mut self) -> Result<(), IncrementalRendererError>;
+
+    /// List every route stored in the cache along with the freshness of the render.
+    fn list(&self) -> Result<Vec<(String, RenderFreshness)>, IncrementalRendererError>;
+}
+
+/// A function that creates a new cache backend. This is called once every time the incremental renderer is built.
+pub(crate) type BackendFactory = std::sync::Arc<dyn Fn() -> Box<dyn CacheBackend> + Send + Sync>;
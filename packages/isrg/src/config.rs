@@ -3,8 +3,9 @@
 #[cfg(not(target_arch = "wasm32"))]
 use crate::fs_cache::PathMapFn;
 
+use crate::backend::BackendFactory;
 use crate::memory_cache::InMemoryCache;
-use crate::IncrementalRenderer;
+use crate::{CacheBackend, IncrementalRenderer};
 
 use std::{
     path::{Path, PathBuf},
@@ -17,11 +18,13 @@ pub struct IncrementalRendererConfig {
     static_dir: PathBuf,
     memory_cache_limit: usize,
     invalidate_after: Option<Duration>,
-    clear_cache: bool,
+    stale_while_revalidate: Option<Duration>,
+    clear_cache: Option<bool>,
     pre_render: bool,
 
     #[cfg(not(target_arch = "wasm32"))]
     map_path: Option<PathMapFn>,
+    backend: Option<BackendFactory>,
 }
 
 impl Default for IncrementalRendererConfig {
@@ -37,16 +40,31 @@ impl IncrementalRendererConfig {
             static_dir: PathBuf::from("./static"),
             memory_cache_limit: 10000,
             invalidate_after: None,
-            clear_cache: true,
+            stale_while_revalidate: None,
+            clear_cache: None,
             pre_render: false,
             #[cfg(not(target_arch = "wasm32"))]
             map_path: None,
+            backend: None,
         }
     }
 
-    /// Clear the cache on startup (default: true)
+    /// Clear the cache when the renderer is built.
+    ///
+    /// By default, only the default [`FileSystemCache`](crate::FileSystemCache) is cleared. Caches set with
+    /// [`Self::backend`] may be shared with other servers or hold renders that should survive a restart, so they are
+    /// kept unless this is set to `true`. Set this to `false` to keep the renders in the static directory between
+    /// restarts:
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::*;
+    /// let renderer = IncrementalRendererConfig::new()
+    ///     .static_dir("./static-keep-doctest")
+    ///     .clear_cache(false)
+    ///     .build();
+    /// ```
     pub fn clear_cache(mut self, clear_cache: bool) -> Self {
-        self.clear_cache = clear_cache;
+        self.clear_cache = Some(clear_cache);
         self
     }
 
@@ -76,6 +94,37 @@ impl IncrementalRendererConfig {
         self
     }
 
+    /// Set the backend used to store pre-rendered routes. This replaces the default [`FileSystemCache`](crate::FileSystemCache) which
+    /// stores renders in the `static_dir`.
+    ///
+    /// The factory is called every time the renderer is built. Renders are still cached in memory in front of the backend, so if the backend is
+    /// shared between multiple servers, you should set [`Self::memory_cache_limit`] to `0` to make sure invalidations are seen by every server.
+    ///
+    /// Unlike the default cache, the backend is not cleared when the renderer is built. See [`Self::clear_cache`].
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::*;
+    /// let renderer = IncrementalRendererConfig::new()
+    ///     .backend(|| InMemoryCache::new(100, None))
+    ///     .build();
+    /// ```
+    pub fn backend<B: CacheBackend>(
+        mut self,
+        backend: impl Fn() -> B + Send + Sync + 'static,
+    ) -> Self {
+        self.backend = Some(std::sync::Arc::new(move || Box::new(backend())));
+        self
+    }
+
+    /// Keep serving routes for this long after [`Self::invalidate_after`] has passed. Stale routes are served immediately
+    /// and re-rendered in the background. Routes are removed from the cache once this window passes.
+    ///
+    /// This has no effect unless [`Self::invalidate_after`] is also set.
+    pub fn stale_while_revalidate(mut self, stale_while_revalidate: Duration) -> Self {
+        self.stale_while_revalidate = Some(stale_while_revalidate);
+        self
+    }
+
     /// Set whether to include hydration ids in the pre-rendered html.
     pub fn pre_render(mut self, pre_render: bool) -> Self {
         self.pre_render = pre_render;
@@ -84,18 +133,31 @@ impl IncrementalRendererConfig {
 
     /// Build the incremental renderer.
     pub fn build(self) -> IncrementalRenderer {
-        let mut renderer = IncrementalRenderer {
+        let clear_cache = self.clear_cache.unwrap_or(self.backend.is_none());
+        let backend = match self.backend {
+            Some(factory) => Some(factory()),
             #[cfg(not(target_arch = "wasm32"))]
-            file_system_cache: crate::fs_cache::FileSystemCache::new(
-                self.static_dir.clone(),
-                self.map_path,
-                self.invalidate_after,
-            ),
-            memory_cache: InMemoryCache::new(self.memory_cache_limit, self.invalidate_after),
+            None => Some(Box::new(
+                crate::fs_cache::FileSystemCache::new(
+                    self.static_dir.clone(),
+                    self.map_path,
+                    self.invalidate_after,
+                )
+                .with_stale_while_revalidate(self.stale_while_revalidate),
+            ) as Box<dyn CacheBackend>),
+            #[cfg(target_arch = "wasm32")]
+            None => None,
+        };
+        let mut renderer = IncrementalRenderer {
+            backend,
+            memory_cache: InMemoryCache::new(self.memory_cache_limit, self.invalidate_after)
+                .with_stale_while_revalidate(self.stale_while_revalidate),
             invalidate_after: self.invalidate_after,
+            stale_while_revalidate: self.stale_while_revalidate,
+            revalidating: Default::default(),
         };
 
-        if self.clear_cache {
+        if clear_cache {
             renderer.invalidate_all();
         }
 
@@ -9,6 +9,8 @@ pub struct RenderFreshness {
     age: u64,
     /// The maximum age of the rendered response
     max_age: Option<u64>,
+    /// How long the rendered response may be served after it is stale while it is re-rendered in the background
+    stale_while_revalidate: Option<u64>,
     /// The time the response was rendered
     timestamp: DateTime<Utc>,
 }
@@ -19,27 +21,20 @@ impl RenderFreshness {
         Self {
             age,
             max_age: Some(max_age),
+            stale_while_revalidate: None,
             timestamp,
         }
     }
 
-    /// Create new freshness information with only the age
-    pub(crate) fn new_age(age: u64, timestamp: DateTime<Utc>) -> Self {
-        Self {
-            age,
-            max_age: None,
-            timestamp,
-        }
-    }
-
-    /// Create new freshness information from a timestamp
-    pub(crate) fn created_at(timestamp: DateTime<Utc>, max_age: Option<Duration>) -> Self {
+    /// Create new freshness information for a response rendered at `timestamp` that is valid for `max_age`
+    pub fn created_at(timestamp: DateTime<Utc>, max_age: Option<Duration>) -> Self {
         Self {
             age: timestamp
                 .signed_duration_since(Utc::now())
                 .num_seconds()
                 .unsigned_abs(),
             max_age: max_age.map(|d| d.as_secs()),
+            stale_while_revalidate: None,
             timestamp,
         }
     }
@@ -49,10 +44,28 @@ impl RenderFreshness {
         Self {
             age: 0,
             max_age: max_age.map(|d| d.as_secs()),
+            stale_while_revalidate: None,
             timestamp: Utc::now(),
         }
     }
 
+    /// Recalculate the age of the response from the time it was rendered
+    pub(crate) fn aged(&self) -> Self {
+        Self {
+            age: Utc::now()
+                .signed_duration_since(self.timestamp)
+                .num_seconds()
+                .unsigned_abs(),
+            ..*self
+        }
+    }
+
+    /// Set how long the response may be served after it is stale while it is re-rendered in the background
+    pub fn with_stale_while_revalidate(mut self, stale_while_revalidate: Option<Duration>) -> Self {
+        self.stale_while_revalidate = stale_while_revalidate.map(|d| d.as_secs());
+        self
+    }
+
     /// Get the age of the rendered response in seconds
     pub fn age(&self) -> u64 {
         self.age
@@ -63,6 +76,24 @@ impl RenderFreshness {
         self.max_age
     }
 
+    /// Get how long the rendered response may be served after it is stale in seconds
+    pub fn stale_while_revalidate(&self) -> Option<u64> {
+        self.stale_while_revalidate
+    }
+
+    /// Check if the rendered response is older than its maximum age. Stale responses may still be served while
+    /// they are re-rendered in the background until they expire.
+    pub fn is_stale(&self) -> bool {
+        self.max_age.is_some_and(|max_age| self.age > max_age)
+    }
+
+    /// Check if the rendered response is too old to be served at all, even while it is re-rendered.
+    pub fn is_expired(&self) -> bool {
+        self.max_age.is_some_and(|max_age| {
+            self.age > max_age + self.stale_while_revalidate.unwrap_or_default()
+        })
+    }
+
     /// Get the time the response was rendered
     pub fn timestamp(&self) -> DateTime<Utc> {
         self.timestamp
@@ -73,9 +104,16 @@ impl RenderFreshness {
         let age = self.age();
         headers.insert(http::header::AGE, age.into());
         if let Some(max_age) = self.max_age() {
+            // Downstream caches compare the age header with the max-age to tell if the response is stale
+            let cache_control = match self.stale_while_revalidate() {
+                Some(stale_while_revalidate) => {
+                    format!("max-age={max_age}, stale-while-revalidate={stale_while_revalidate}")
+                }
+                None => format!("max-age={max_age}"),
+            };
             headers.insert(
                 http::header::CACHE_CONTROL,
-                http::HeaderValue::from_str(&format!("max-age={}", max_age)).unwrap(),
+                http::HeaderValue::from_str(&cache_control).unwrap(),
             );
         }
     }
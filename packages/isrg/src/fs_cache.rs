@@ -2,18 +2,26 @@
 
 use chrono::{DateTime, Utc};
 
-use super::{IncrementalRendererError, RenderFreshness};
-use std::{path::PathBuf, sync::Arc, time::SystemTime};
+use super::{CacheBackend, IncrementalRendererError, RenderFreshness};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
 
-pub(crate) type PathMapFn = Arc<dyn Fn(&str) -> PathBuf + Send + Sync>;
+/// A function that maps a route to the folder its `index.html` file is stored in.
+pub type PathMapFn = Arc<dyn Fn(&str) -> PathBuf + Send + Sync>;
 
-pub(crate) struct FileSystemCache {
+/// A cache backend that stores renders as html files in a static directory. This is the default backend for the incremental renderer.
+pub struct FileSystemCache {
     static_dir: PathBuf,
     map_path: PathMapFn,
     invalidate_after: Option<std::time::Duration>,
+    stale_while_revalidate: Option<std::time::Duration>,
 }
 
 impl FileSystemCache {
+    /// Create a new file system cache that stores renders in `static_dir`. If `map_path` is set, it will be used to find the folder for each route instead of `static_dir`.
     pub fn new(
         static_dir: PathBuf,
         map_path: Option<PathMapFn>,
@@ -32,9 +40,20 @@ impl FileSystemCache {
                 })
             }),
             invalidate_after,
+            stale_while_revalidate: None,
         }
     }
 
+    /// Keep serving routes for this long after they are stale while they are re-rendered in the background.
+    pub fn with_stale_while_revalidate(
+        mut self,
+        stale_while_revalidate: Option<std::time::Duration>,
+    ) -> Self {
+        self.stale_while_revalidate = stale_while_revalidate;
+        self
+    }
+
+    /// Write the render for a route to the file system.
     pub fn put(
         &mut self,
         route: String,
@@ -46,6 +65,13 @@ impl FileSystemCache {
         if let Some(parent) = file_path.parent() {
             if !parent.exists() {
                 std::fs::create_dir_all(parent)?;
+            } else if self.track_timestamps() {
+                // Remove any older renders of the route so a stale render is never served after the route is re-rendered
+                for entry in std::fs::read_dir(parent)?.flatten() {
+                    if ValidCachedPath::try_from_path(entry.path()).is_some() {
+                        let _ = std::fs::remove_file(entry.path());
+                    }
+                }
             }
         }
         let file = std::fs::File::create(file_path)?;
@@ -54,24 +80,72 @@ impl FileSystemCache {
         Ok(())
     }
 
+    /// Remove every render from the static directory.
     pub fn clear(&mut self) {
         // clear the static directory
         let _ = std::fs::remove_dir_all(&self.static_dir);
     }
 
+    /// Remove the render for a route from the file system.
     pub fn invalidate(&mut self, route: &str) {
-        let file_path = self.find_file(route).unwrap().full_path;
-        if let Err(err) = std::fs::remove_file(file_path) {
+        let _ = std::fs::remove_file(self.tags_path(route));
+        let Some(file) = self.find_file(route) else {
+            return;
+        };
+        if let Err(err) = std::fs::remove_file(file.full_path) {
             tracing::error!("Failed to remove file: {}", err);
         }
     }
 
+    /// Write the tags for a route next to the rendered html, one tag per line.
+    pub fn set_tags(
+        &mut self,
+        route: &str,
+        tags: &[String],
+    ) -> Result<(), IncrementalRendererError> {
+        let tags_path = self.tags_path(route);
+        if tags.is_empty() {
+            let _ = std::fs::remove_file(tags_path);
+            return Ok(());
+        }
+        if let Some(parent) = tags_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(tags_path, tags.join("\n"))?;
+        Ok(())
+    }
+
+    /// Remove every route that was rendered with the tag from the file system.
+    pub fn invalidate_tag(&mut self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        let mut invalidated = Vec::new();
+        for (route, _) in CacheBackend::list(self)? {
+            let Ok(tags) = std::fs::read_to_string(self.tags_path(&route)) else {
+                continue;
+            };
+            if tags.lines().any(|line| line == tag) {
+                self.invalidate(&route);
+                invalidated.push(route);
+            }
+        }
+        Ok(invalidated)
+    }
+
+    fn tags_path(&self, route: &str) -> PathBuf {
+        let mut tags_path = (self.map_path)(route);
+        tags_path.push("index.tags");
+        tags_path
+    }
+
+    /// Read the render for a route from the file system if it exists and is still fresh.
+    #[allow(clippy::type_complexity)]
     pub fn get(
         &self,
         route: &str,
     ) -> Result<Option<(RenderFreshness, Vec<u8>)>, IncrementalRendererError> {
         if let Some(file_path) = self.find_file(route) {
-            if let Some(freshness) = file_path.freshness(self.invalidate_after) {
+            if let Some(freshness) =
+                file_path.freshness(self.invalidate_after, self.stale_while_revalidate)
+            {
                 if let Ok(file) = std::fs::File::open(file_path.full_path) {
                     let mut file = std::io::BufReader::new(file);
                     let mut cache_hit = Vec::new();
@@ -87,7 +161,9 @@ impl FileSystemCache {
 
     fn find_file(&self, route: &str) -> Option<ValidCachedPath> {
         let mut file_path = (self.map_path)(route);
-        if let Some(deadline) = self.invalidate_after {
+        if let Some(invalidate_after) = self.invalidate_after {
+            // Stale files are kept around until they expire so they can be served while the route is re-rendered
+            let deadline = invalidate_after + self.stale_while_revalidate.unwrap_or_default();
             // find the first file that matches the route and is a html file
             file_path.push("index");
             if let Ok(dir) = std::fs::read_dir(file_path) {
@@ -135,6 +211,100 @@ impl FileSystemCache {
     fn track_timestamps(&self) -> bool {
         self.invalidate_after.is_some()
     }
+
+    /// Walk the static directory and collect every cached render. Routes are reconstructed relative to the
+    /// static directory, so routes stored with a custom `map_path` outside of it will not be listed.
+    fn collect_routes(&self, dir: &Path, routes: &mut Vec<(String, RenderFreshness)>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if !self.track_timestamps() || path.file_name() != Some(std::ffi::OsStr::new("index")) {
+                self.collect_routes(&path, routes);
+                continue;
+            }
+            // Timestamped renders are stored as index/{timestamp}.html
+            if let Some(route) = self.route_for_dir(dir) {
+                if let Some(freshness) = std::fs::read_dir(&path)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter_map(|entry| ValidCachedPath::try_from_path(entry.path()))
+                    .find_map(|cached| {
+                        cached.freshness(self.invalidate_after, self.stale_while_revalidate)
+                    })
+                    .filter(|freshness| !freshness.is_expired())
+                {
+                    routes.push((route, freshness));
+                }
+            }
+        }
+        // Renders without a timestamp are stored as index.html
+        if !self.track_timestamps() && dir.join("index.html").is_file() {
+            if let Some(route) = self.route_for_dir(dir) {
+                routes.push((route, RenderFreshness::now(None)));
+            }
+        }
+    }
+
+    fn route_for_dir(&self, dir: &Path) -> Option<String> {
+        let relative = dir.strip_prefix(&self.static_dir).ok()?;
+        let mut route = String::new();
+        for segment in relative.iter() {
+            route.push('/');
+            route.push_str(segment.to_str()?);
+        }
+        if route.is_empty() {
+            route.push('/');
+        }
+        Some(route)
+    }
+}
+
+impl CacheBackend for FileSystemCache {
+    fn get(
+        &mut self,
+        route: &str,
+    ) -> Result<Option<(RenderFreshness, Vec<u8>)>, IncrementalRendererError> {
+        FileSystemCache::get(self, route)
+    }
+
+    fn put(
+        &mut self,
+        route: String,
+        freshness: RenderFreshness,
+        html: Vec<u8>,
+    ) -> Result<(), IncrementalRendererError> {
+        FileSystemCache::put(self, route, freshness.timestamp(), html)
+    }
+
+    fn set_tags(&mut self, route: &str, tags: &[String]) -> Result<(), IncrementalRendererError> {
+        FileSystemCache::set_tags(self, route, tags)
+    }
+
+    fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        FileSystemCache::invalidate(self, route);
+        Ok(())
+    }
+
+    fn invalidate_tag(&mut self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        FileSystemCache::invalidate_tag(self, tag)
+    }
+
+    fn clear(&mut self) -> Result<(), IncrementalRendererError> {
+        FileSystemCache::clear(self);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<(String, RenderFreshness)>, IncrementalRendererError> {
+        let mut routes = Vec::new();
+        self.collect_routes(&self.static_dir, &mut routes);
+        Ok(routes)
+    }
 }
 
 pub(crate) struct ValidCachedPath {
@@ -155,10 +325,17 @@ impl ValidCachedPath {
         })
     }
 
-    pub fn freshness(&self, max_age: Option<std::time::Duration>) -> Option<RenderFreshness> {
+    pub fn freshness(
+        &self,
+        max_age: Option<std::time::Duration>,
+        stale_while_revalidate: Option<std::time::Duration>,
+    ) -> Option<RenderFreshness> {
         let age = self.timestamp.elapsed().ok()?.as_secs();
         let max_age = max_age.map(|max_age| max_age.as_secs());
-        Some(RenderFreshness::new(age, max_age?, self.timestamp.into()))
+        Some(
+            RenderFreshness::new(age, max_age?, self.timestamp.into())
+                .with_stale_while_revalidate(stale_while_revalidate),
+        )
     }
 }
 
@@ -2,19 +2,27 @@
 
 #![allow(non_snake_case)]
 
+mod backend;
 mod config;
 mod freshness;
 #[cfg(not(target_arch = "wasm32"))]
 mod fs_cache;
 mod memory_cache;
+#[cfg(feature = "sqlite")]
+mod sqlite_cache;
 
 use std::time::Duration;
 
+pub use backend::*;
 use chrono::Utc;
 pub use config::*;
 pub use freshness::*;
-
-use self::memory_cache::InMemoryCache;
+#[cfg(not(target_arch = "wasm32"))]
+pub use fs_cache::{FileSystemCache, PathMapFn};
+pub use memory_cache::InMemoryCache;
+use rustc_hash::FxHashSet;
+#[cfg(feature = "sqlite")]
+pub use sqlite_cache::SqliteCache;
 
 /// A render that was cached from a previous render.
 pub struct CachedRender<'a> {
@@ -29,9 +37,11 @@ pub struct CachedRender<'a> {
 /// An incremental renderer.
 pub struct IncrementalRenderer {
     pub(crate) memory_cache: InMemoryCache,
-    #[cfg(not(target_arch = "wasm32"))]
-    pub(crate) file_system_cache: fs_cache::FileSystemCache,
+    pub(crate) backend: Option<Box<dyn CacheBackend>>,
     invalidate_after: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
+    /// Routes that are currently being re-rendered in the background
+    revalidating: FxHashSet<String>,
 }
 
 impl IncrementalRenderer {
@@ -43,15 +53,75 @@ impl IncrementalRenderer {
     /// Remove a route from the cache.
     pub fn invalidate(&mut self, route: &str) {
         self.memory_cache.invalidate(route);
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache.invalidate(route);
+        if let Some(backend) = &mut self.backend {
+            if let Err(err) = backend.invalidate(route) {
+                tracing::error!(
+                    "Failed to invalidate route \"{route}\" in the cache backend: {err}"
+                );
+            }
+        }
+    }
+
+    /// Remove every route that was rendered with the tag from the cache. Tags are registered while a route renders
+    /// and stored with [`IncrementalRenderer::cache_with_tags`].
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// let mut renderer = IncrementalRenderer::builder().static_dir("./static-tag-doctest").build();
+    /// renderer.cache_with_tags("/product/42".to_string(), "<h1>Product 42</h1>", ["product:42"]).unwrap();
+    /// renderer.cache_with_tags("/products".to_string(), "<h1>Products</h1>", ["product:42", "product:43"]).unwrap();
+    /// renderer.cache("/about".to_string(), "<h1>About</h1>").unwrap();
+    ///
+    /// renderer.invalidate_tag("product:42");
+    /// assert!(renderer.get("/product/42").unwrap().is_none());
+    /// assert!(renderer.get("/products").unwrap().is_none());
+    /// assert!(renderer.get("/about").unwrap().is_some());
+    /// # renderer.invalidate_all();
+    /// ```
+    pub fn invalidate_tag(&mut self, tag: &str) {
+        let mut routes =
+            CacheBackend::invalidate_tag(&mut self.memory_cache, tag).unwrap_or_default();
+        if let Some(backend) = &mut self.backend {
+            match backend.invalidate_tag(tag) {
+                Ok(backend_routes) => routes.extend(backend_routes),
+                Err(err) => {
+                    tracing::error!(
+                        "Failed to invalidate tag \"{tag}\" in the cache backend: {err}"
+                    )
+                }
+            }
+        }
+        // The memory cache may hold renders read from the backend that it never saw the tags for
+        for route in routes {
+            self.memory_cache.invalidate(&route);
+        }
     }
 
     /// Remove all routes from the cache.
     pub fn invalidate_all(&mut self) {
         self.memory_cache.clear();
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache.clear();
+        if let Some(backend) = &mut self.backend {
+            if let Err(err) = backend.clear() {
+                tracing::error!("Failed to clear the cache backend: {err}");
+            }
+        }
+    }
+
+    /// List every route that is currently cached along with the freshness of the render.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// let mut renderer = IncrementalRenderer::builder().static_dir("./static-list-doctest").build();
+    /// renderer.cache("/index".to_string(), "<html><body>Hello world</body></html>").unwrap();
+    /// let routes = renderer.list().unwrap();
+    /// assert_eq!(routes[0].0, "/index");
+    /// # renderer.invalidate_all();
+    /// ```
+    pub fn list(&self) -> Result<Vec<(String, RenderFreshness)>, IncrementalRendererError> {
+        match &self.backend {
+            Some(backend) => backend.list(),
+            None => CacheBackend::list(&self.memory_cache),
+        }
     }
 
     /// Cache a rendered response.
@@ -67,17 +137,52 @@ impl IncrementalRenderer {
         &mut self,
         route: String,
         html: impl Into<Vec<u8>>,
+    ) -> Result<RenderFreshness, IncrementalRendererError> {
+        self.cache_with_tags(route, html, std::iter::empty::<String>())
+    }
+
+    /// Cache a rendered response along with the tags the route was rendered with. Every route with a tag can be removed
+    /// from the cache at once with [`IncrementalRenderer::invalidate_tag`].
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// let route = "/product/42".to_string();
+    /// let response = b"<html><body>Product 42</body></html>";
+    /// renderer.cache_with_tags(route, response, ["product:42"]).unwrap();
+    /// ```
+    pub fn cache_with_tags(
+        &mut self,
+        route: String,
+        html: impl Into<Vec<u8>>,
+        tags: impl IntoIterator<Item = impl Into<String>>,
     ) -> Result<RenderFreshness, IncrementalRendererError> {
         let timestamp = Utc::now();
         let html = html.into();
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache
-            .put(route.clone(), timestamp, html.clone())?;
-        self.memory_cache.put(route, timestamp, html);
-        Ok(RenderFreshness::created_at(
-            timestamp,
-            self.invalidate_after,
-        ))
+        let tags: Vec<String> = tags.into_iter().map(Into::into).collect();
+        let freshness = RenderFreshness::created_at(timestamp, self.invalidate_after)
+            .with_stale_while_revalidate(self.stale_while_revalidate);
+        if let Some(backend) = &mut self.backend {
+            backend.put(route.clone(), freshness, html.clone())?;
+            backend.set_tags(&route, &tags)?;
+        }
+        CacheBackend::set_tags(&mut self.memory_cache, &route, &tags)?;
+        self.revalidating.remove(&route);
+        self.memory_cache.put(route, freshness, html);
+        Ok(freshness)
+    }
+
+    /// Mark a stale route as being re-rendered in the background. Returns `false` if the route is already being re-rendered,
+    /// in which case the caller should keep serving the stale render without starting another render.
+    ///
+    /// The mark is removed when the route is cached again or with [`IncrementalRenderer::end_revalidation`] if the render fails.
+    pub fn begin_revalidation(&mut self, route: &str) -> bool {
+        self.revalidating.insert(route.to_string())
+    }
+
+    /// Remove the revalidation mark for a route without caching a new render.
+    pub fn end_revalidation(&mut self, route: &str) {
+        self.revalidating.remove(route);
     }
 
     /// Try to get a cached response for a route.
@@ -108,12 +213,10 @@ impl IncrementalRenderer {
     ) -> Result<Option<CachedRender<'a>>, IncrementalRendererError> {
         let Self {
             memory_cache,
-            #[cfg(not(target_arch = "wasm32"))]
-            file_system_cache,
+            backend,
             ..
         } = self;
 
-        #[allow(unused)]
         enum FsGetError {
             NotPresent,
             Error(IncrementalRendererError),
@@ -122,16 +225,12 @@ impl IncrementalRenderer {
         // The borrow checker prevents us from simply using a match/if and returning early. Instead we need to use the more complex closure API
         // non lexical lifetimes will make this possible (it works with polonius)
         let or_insert = || {
-            // check the file cache
-            #[cfg(not(target_arch = "wasm32"))]
-            return match file_system_cache.get(route) {
-                Ok(Some((freshness, bytes))) => Ok((freshness.timestamp(), bytes)),
-                Ok(None) => Err(FsGetError::NotPresent),
-                Err(e) => Err(FsGetError::Error(e)),
-            };
-
-            #[allow(unreachable_code)]
-            Err(FsGetError::NotPresent)
+            // check the cache backend
+            match backend.as_mut().map(|backend| backend.get(route)) {
+                Some(Ok(Some((freshness, bytes)))) => Ok((freshness, bytes)),
+                Some(Ok(None)) | None => Err(FsGetError::NotPresent),
+                Some(Err(e)) => Err(FsGetError::Error(e)),
+            }
         };
 
         match memory_cache.try_get_or_insert(route, or_insert) {
@@ -2,85 +2,197 @@
 
 #![allow(non_snake_case)]
 
-use chrono::offset::Utc;
-use chrono::DateTime;
-use rustc_hash::FxHasher;
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 use std::{hash::BuildHasherDefault, num::NonZeroUsize};
 
+use super::backend::CacheBackend;
 use super::freshness::RenderFreshness;
+use super::IncrementalRendererError;
 
-pub(crate) struct InMemoryCache {
+/// A cache backend that stores renders in memory. Renders are lost when the server restarts.
+pub struct InMemoryCache {
     #[allow(clippy::type_complexity)]
-    lru: Option<lru::LruCache<String, (DateTime<Utc>, Vec<u8>), BuildHasherDefault<FxHasher>>>,
+    lru: Option<lru::LruCache<String, (RenderFreshness, Vec<u8>), BuildHasherDefault<FxHasher>>>,
     invalidate_after: Option<std::time::Duration>,
+    stale_while_revalidate: Option<std::time::Duration>,
+    /// A map from each tag to the routes that were rendered with it
+    tags: FxHashMap<String, FxHashSet<String>>,
+    /// The last render read through the cache while the memory cache is disabled
+    passthrough: Option<(RenderFreshness, Vec<u8>)>,
 }
 
 impl InMemoryCache {
+    /// Create a new in memory cache that holds at most `memory_cache_limit` routes. Routes expire when the freshness
+    /// they were stored with expires. If `invalidate_after` is set, routes also expire from the memory cache after that
+    /// duration, even if the freshness they were stored with allows them to be served for longer.
     pub fn new(memory_cache_limit: usize, invalidate_after: Option<std::time::Duration>) -> Self {
         Self {
             lru: NonZeroUsize::new(memory_cache_limit)
                 .map(|limit| lru::LruCache::with_hasher(limit, Default::default())),
             invalidate_after,
+            stale_while_revalidate: None,
+            tags: Default::default(),
+            passthrough: None,
         }
     }
 
+    /// Keep serving routes for this long after they are stale while they are re-rendered in the background.
+    pub fn with_stale_while_revalidate(
+        mut self,
+        stale_while_revalidate: Option<std::time::Duration>,
+    ) -> Self {
+        self.stale_while_revalidate = stale_while_revalidate;
+        self
+    }
+
+    /// Remove all routes from the cache.
     pub fn clear(&mut self) {
         if let Some(cache) = &mut self.lru {
             cache.clear();
         }
+        self.tags.clear();
     }
 
-    pub fn put(&mut self, route: String, timestamp: DateTime<Utc>, data: Vec<u8>) {
+    /// Insert a route into the cache.
+    pub fn put(&mut self, route: String, freshness: RenderFreshness, data: Vec<u8>) {
         if let Some(cache) = &mut self.lru {
-            cache.put(route, (timestamp, data));
+            cache.put(route, (freshness, data));
         }
     }
 
+    /// Remove a route from the cache.
     pub fn invalidate(&mut self, route: &str) {
         if let Some(cache) = &mut self.lru {
             cache.pop(route);
         }
     }
 
-    pub fn try_get_or_insert<'a, F: FnOnce() -> Result<(DateTime<Utc>, Vec<u8>), E>, E>(
+    pub(crate) fn try_get_or_insert<'a, F: FnOnce() -> Result<(RenderFreshness, Vec<u8>), E>, E>(
         &'a mut self,
         route: &str,
         or_insert: F,
     ) -> Result<Option<(RenderFreshness, &'a [u8])>, E> {
+        let policy = self.policy();
+
         if let Some(memory_cache) = self.lru.as_mut() {
-            let (timestamp, _) = memory_cache.try_get_or_insert(route.to_string(), or_insert)?;
+            let (freshness, _) = memory_cache.try_get_or_insert(route.to_string(), or_insert)?;
 
-            let now = Utc::now();
-            let elapsed = timestamp.signed_duration_since(now);
-            let age = elapsed.num_seconds();
             // The cache entry is out of date, so we need to remove it.
-            if let Some(invalidate_after) = self.invalidate_after {
-                if elapsed.to_std().unwrap() > invalidate_after {
-                    tracing::trace!("memory cache out of date");
-                    memory_cache.pop(route);
-                    return Ok(None);
-                }
+            if policy.is_expired(freshness) {
+                tracing::trace!("memory cache out of date");
+                memory_cache.pop(route);
+                return Ok(None);
             }
 
             // We need to reborrow because we may have invalidated the lifetime if the route was removed.
             // We know it wasn't because we returned... but rust doesn't understand that.
-            let (timestamp, cache_hit) = memory_cache.get(route).unwrap();
-
-            return match self.invalidate_after {
-                Some(invalidate_after) => {
-                    tracing::trace!("memory cache hit");
-                    let max_age = invalidate_after.as_secs();
-                    let freshness = RenderFreshness::new(age as u64, max_age, *timestamp);
-                    Ok(Some((freshness, cache_hit)))
-                }
-                None => {
-                    tracing::trace!("memory cache hit");
-                    let freshness = RenderFreshness::new_age(age as u64, *timestamp);
-                    Ok(Some((freshness, cache_hit)))
-                }
-            };
+            let (freshness, cache_hit) = memory_cache.get(route).unwrap();
+
+            tracing::trace!("memory cache hit");
+            return Ok(Some((freshness.aged(), cache_hit)));
         }
 
-        Ok(None)
+        // If the memory cache is disabled, we still need somewhere to hold the render the backend returned while it is borrowed
+        let (freshness, cache_hit) = self.passthrough.insert(or_insert()?);
+        Ok(Some((freshness.aged(), cache_hit)))
+    }
+
+    fn policy(&self) -> MemoryPolicy {
+        MemoryPolicy {
+            invalidate_after: self.invalidate_after,
+            stale_while_revalidate: self.stale_while_revalidate,
+        }
+    }
+}
+
+/// How long renders may stay in the memory cache, independent of the freshness they were stored with
+#[derive(Clone, Copy)]
+struct MemoryPolicy {
+    invalidate_after: Option<std::time::Duration>,
+    stale_while_revalidate: Option<std::time::Duration>,
+}
+
+impl MemoryPolicy {
+    fn is_expired(&self, freshness: &RenderFreshness) -> bool {
+        freshness.aged().is_expired()
+            || RenderFreshness::created_at(freshness.timestamp(), self.invalidate_after)
+                .with_stale_while_revalidate(self.stale_while_revalidate)
+                .is_expired()
+    }
+}
+
+impl CacheBackend for InMemoryCache {
+    fn get(
+        &mut self,
+        route: &str,
+    ) -> Result<Option<(RenderFreshness, Vec<u8>)>, IncrementalRendererError> {
+        let Some(memory_cache) = self.lru.as_mut() else {
+            return Ok(None);
+        };
+        let Some(&(freshness, _)) = memory_cache.peek(route) else {
+            return Ok(None);
+        };
+        if self.policy().is_expired(&freshness) {
+            self.invalidate(route);
+            return Ok(None);
+        }
+        Ok(self
+            .lru
+            .as_mut()
+            .and_then(|cache| cache.get(route))
+            .map(|(_, html)| (freshness.aged(), html.clone())))
+    }
+
+    fn put(
+        &mut self,
+        route: String,
+        freshness: RenderFreshness,
+        html: Vec<u8>,
+    ) -> Result<(), IncrementalRendererError> {
+        InMemoryCache::put(self, route, freshness, html);
+        Ok(())
+    }
+
+    fn set_tags(&mut self, route: &str, tags: &[String]) -> Result<(), IncrementalRendererError> {
+        for routes in self.tags.values_mut() {
+            routes.remove(route);
+        }
+        for tag in tags {
+            self.tags
+                .entry(tag.clone())
+                .or_default()
+                .insert(route.to_string());
+        }
+        self.tags.retain(|_, routes| !routes.is_empty());
+        Ok(())
+    }
+
+    fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        InMemoryCache::invalidate(self, route);
+        Ok(())
+    }
+
+    fn invalidate_tag(&mut self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        let routes: Vec<_> = self.tags.remove(tag).into_iter().flatten().collect();
+        for route in &routes {
+            InMemoryCache::invalidate(self, route);
+        }
+        Ok(routes)
+    }
+
+    fn clear(&mut self) -> Result<(), IncrementalRendererError> {
+        InMemoryCache::clear(self);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<(String, RenderFreshness)>, IncrementalRendererError> {
+        let policy = self.policy();
+        Ok(self
+            .lru
+            .iter()
+            .flat_map(|cache| cache.iter())
+            .filter(|(_, (freshness, _))| !policy.is_expired(freshness))
+            .map(|(route, (freshness, _))| (route.clone(), freshness.aged()))
+            .collect())
     }
 }
@@ -0,0 +1,250 @@
+//! A cache backend that stores renders in a SQLite database
+
+use std::{path::Path, sync::Mutex, time::Duration};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::{CacheBackend, IncrementalRendererError, RenderFreshness};
+
+/// A cache backend that stores renders in a SQLite database file.
+///
+/// Multiple server processes can open the same database file to share pre-rendered routes. Combine this with
+/// [`IncrementalRendererConfig::memory_cache_limit(0)`](crate::IncrementalRendererConfig::memory_cache_limit) so every
+/// server sees invalidations immediately.
+///
+/// ```rust
+/// # use dioxus_isrg::*;
+/// let path = std::env::temp_dir().join("dioxus-isrg-sqlite-doctest.db");
+/// # let _ = std::fs::remove_file(&path);
+/// let mut renderer = IncrementalRenderer::builder()
+///     .memory_cache_limit(0)
+///     .backend(move || SqliteCache::open(&path).expect("Failed to open the cache database"))
+///     .build();
+///
+/// renderer.cache("/index".to_string(), "<html><body>Hello world</body></html>").unwrap();
+/// let response = renderer.get("/index").unwrap();
+/// assert_eq!(response.unwrap().response, b"<html><body>Hello world</body></html>");
+/// ```
+pub struct SqliteCache {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteCache {
+    /// Open (or create) a SQLite database at the given path to store renders in.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, IncrementalRendererError> {
+        let connection = Connection::open(path).map_err(sqlite_error)?;
+        // Other processes may be writing to the database at the same time. Wait for their locks instead of failing immediately
+        connection
+            .busy_timeout(Duration::from_secs(5))
+            .map_err(sqlite_error)?;
+        connection
+            .pragma_update(None, "journal_mode", "WAL")
+            .map_err(sqlite_error)?;
+        Self::from_connection(connection)
+    }
+
+    /// Create a new cache backed by an in memory SQLite database. This is mostly useful for testing.
+    pub fn open_in_memory() -> Result<Self, IncrementalRendererError> {
+        Self::from_connection(Connection::open_in_memory().map_err(sqlite_error)?)
+    }
+
+    fn from_connection(connection: Connection) -> Result<Self, IncrementalRendererError> {
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS dioxus_isrg_cache (
+                    route TEXT PRIMARY KEY NOT NULL,
+                    timestamp INTEGER NOT NULL,
+                    max_age INTEGER,
+                    stale_while_revalidate INTEGER,
+                    html BLOB NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS dioxus_isrg_tags (
+                    tag TEXT NOT NULL,
+                    route TEXT NOT NULL,
+                    PRIMARY KEY (tag, route)
+                );
+                CREATE INDEX IF NOT EXISTS dioxus_isrg_tags_route ON dioxus_isrg_tags (route);",
+            )
+            .map_err(sqlite_error)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl CacheBackend for SqliteCache {
+    fn get(
+        &mut self,
+        route: &str,
+    ) -> Result<Option<(RenderFreshness, Vec<u8>)>, IncrementalRendererError> {
+        let connection = self.connection();
+        let row = connection
+            .query_row(
+                "SELECT timestamp, max_age, stale_while_revalidate, html FROM dioxus_isrg_cache WHERE route = ?1",
+                params![route],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, Option<i64>>(1)?,
+                        row.get::<_, Option<i64>>(2)?,
+                        row.get::<_, Vec<u8>>(3)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(sqlite_error)?;
+
+        let Some((timestamp, max_age, stale_while_revalidate, html)) = row else {
+            return Ok(None);
+        };
+        let freshness = decode_freshness(timestamp, max_age, stale_while_revalidate);
+        if freshness.is_expired() {
+            connection
+                .execute(
+                    "DELETE FROM dioxus_isrg_cache WHERE route = ?1",
+                    params![route],
+                )
+                .map_err(sqlite_error)?;
+            return Ok(None);
+        }
+
+        tracing::trace!("sqlite cache hit {:?}", route);
+        Ok(Some((freshness, html)))
+    }
+
+    fn put(
+        &mut self,
+        route: String,
+        freshness: RenderFreshness,
+        html: Vec<u8>,
+    ) -> Result<(), IncrementalRendererError> {
+        self.connection()
+            .execute(
+                "INSERT OR REPLACE INTO dioxus_isrg_cache (route, timestamp, max_age, stale_while_revalidate, html) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    route,
+                    freshness.timestamp().timestamp(),
+                    freshness.max_age().map(|max_age| max_age as i64),
+                    freshness
+                        .stale_while_revalidate()
+                        .map(|stale_while_revalidate| stale_while_revalidate as i64),
+                    html
+                ],
+            )
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+
+    fn set_tags(&mut self, route: &str, tags: &[String]) -> Result<(), IncrementalRendererError> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction().map_err(sqlite_error)?;
+        transaction
+            .execute(
+                "DELETE FROM dioxus_isrg_tags WHERE route = ?1",
+                params![route],
+            )
+            .map_err(sqlite_error)?;
+        for tag in tags {
+            transaction
+                .execute(
+                    "INSERT OR IGNORE INTO dioxus_isrg_tags (tag, route) VALUES (?1, ?2)",
+                    params![tag, route],
+                )
+                .map_err(sqlite_error)?;
+        }
+        transaction.commit().map_err(sqlite_error)
+    }
+
+    fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        remove_route(&self.connection(), route).map_err(sqlite_error)
+    }
+
+    fn invalidate_tag(&mut self, tag: &str) -> Result<Vec<String>, IncrementalRendererError> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction().map_err(sqlite_error)?;
+        let routes = {
+            let mut statement = transaction
+                .prepare("SELECT route FROM dioxus_isrg_tags WHERE tag = ?1")
+                .map_err(sqlite_error)?;
+            let routes = statement
+                .query_map(params![tag], |row| row.get::<_, String>(0))
+                .map_err(sqlite_error)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(sqlite_error)?;
+            routes
+        };
+        for route in &routes {
+            remove_route(&transaction, route).map_err(sqlite_error)?;
+        }
+        transaction.commit().map_err(sqlite_error)?;
+        Ok(routes)
+    }
+
+    fn clear(&mut self) -> Result<(), IncrementalRendererError> {
+        self.connection()
+            .execute_batch("DELETE FROM dioxus_isrg_cache; DELETE FROM dioxus_isrg_tags;")
+            .map_err(sqlite_error)
+    }
+
+    fn list(&self) -> Result<Vec<(String, RenderFreshness)>, IncrementalRendererError> {
+        let connection = self.connection();
+        let mut statement = connection
+            .prepare("SELECT route, timestamp, max_age, stale_while_revalidate FROM dioxus_isrg_cache ORDER BY route")
+            .map_err(sqlite_error)?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, Option<i64>>(2)?,
+                    row.get::<_, Option<i64>>(3)?,
+                ))
+            })
+            .map_err(sqlite_error)?;
+
+        let mut routes = Vec::new();
+        for row in rows {
+            let (route, timestamp, max_age, stale_while_revalidate) = row.map_err(sqlite_error)?;
+            let freshness = decode_freshness(timestamp, max_age, stale_while_revalidate);
+            if !freshness.is_expired() {
+                routes.push((route, freshness));
+            }
+        }
+        Ok(routes)
+    }
+}
+
+/// Remove a route and its tags from the cache
+fn remove_route(connection: &Connection, route: &str) -> rusqlite::Result<()> {
+    connection.execute(
+        "DELETE FROM dioxus_isrg_cache WHERE route = ?1",
+        params![route],
+    )?;
+    connection.execute(
+        "DELETE FROM dioxus_isrg_tags WHERE route = ?1",
+        params![route],
+    )?;
+    Ok(())
+}
+
+fn decode_freshness(
+    timestamp: i64,
+    max_age: Option<i64>,
+    stale_while_revalidate: Option<i64>,
+) -> RenderFreshness {
+    let seconds = |seconds: i64| Duration::from_secs(seconds.unsigned_abs());
+    let timestamp = DateTime::<Utc>::from_timestamp(timestamp, 0).unwrap_or_default();
+    RenderFreshness::created_at(timestamp, max_age.map(seconds))
+        .with_stale_while_revalidate(stale_while_revalidate.map(seconds))
+}
+
+fn sqlite_error(err: rusqlite::Error) -> IncrementalRendererError {
+    IncrementalRendererError::Other(Box::new(err))
+}
@@ -0,0 +1,151 @@
+#![cfg(feature = "sqlite")]
+
+use dioxus_isrg::*;
+
+fn shared_renderer(path: std::path::PathBuf) -> IncrementalRenderer {
+    IncrementalRenderer::builder()
+        .memory_cache_limit(0)
+        .backend(move || SqliteCache::open(&path).unwrap())
+        .build()
+}
+
+#[test]
+fn renders_are_shared_between_renderers() {
+    let path = std::env::temp_dir().join(format!("dioxus-isrg-shared-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut first = shared_renderer(path.clone());
+    let mut second = shared_renderer(path.clone());
+
+    first.cache("/blog/1".to_string(), "first").unwrap();
+    first.cache("/blog/2".to_string(), "second").unwrap();
+
+    assert_eq!(second.get("/blog/1").unwrap().unwrap().response, b"first");
+    let routes: Vec<_> = second
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|(route, _)| route)
+        .collect();
+    assert_eq!(routes, ["/blog/1", "/blog/2"]);
+
+    second.invalidate("/blog/1");
+    assert!(first.get("/blog/1").unwrap().is_none());
+
+    first.invalidate_all();
+    assert!(second.list().unwrap().is_empty());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn expired_renders_are_not_returned() {
+    let mut renderer = IncrementalRenderer::builder()
+        .memory_cache_limit(0)
+        .invalidate_after(std::time::Duration::from_secs(60))
+        .backend(|| SqliteCache::open_in_memory().unwrap())
+        .build();
+
+    renderer.cache("/".to_string(), "fresh").unwrap();
+    let cached = renderer.get("/").unwrap().unwrap();
+    assert_eq!(cached.freshness.max_age(), Some(60));
+    assert_eq!(cached.response, b"fresh");
+}
+
+#[test]
+fn tags_are_shared_between_renderers() {
+    let path = std::env::temp_dir().join(format!("dioxus-isrg-tags-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut first = shared_renderer(path.clone());
+    let mut second = shared_renderer(path.clone());
+
+    first
+        .cache_with_tags("/product/42".to_string(), "42", ["product:42"])
+        .unwrap();
+    first
+        .cache_with_tags("/products".to_string(), "all", ["product:42", "product:43"])
+        .unwrap();
+    first.cache("/about".to_string(), "about").unwrap();
+
+    second.invalidate_tag("product:42");
+    assert!(first.get("/product/42").unwrap().is_none());
+    assert!(first.get("/products").unwrap().is_none());
+    assert!(first.get("/about").unwrap().is_some());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn stale_renders_are_served_while_revalidating() {
+    let mut renderer = IncrementalRenderer::builder()
+        .memory_cache_limit(0)
+        .invalidate_after(std::time::Duration::from_secs(0))
+        .stale_while_revalidate(std::time::Duration::from_secs(60))
+        .backend(|| SqliteCache::open_in_memory().unwrap())
+        .build();
+
+    renderer.cache("/".to_string(), "stale").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+
+    let freshness = renderer.get("/").unwrap().unwrap().freshness;
+    assert!(freshness.is_stale());
+    assert!(!freshness.is_expired());
+    assert_eq!(freshness.stale_while_revalidate(), Some(60));
+
+    assert!(renderer.begin_revalidation("/"));
+    assert!(!renderer.begin_revalidation("/"));
+    renderer.cache("/".to_string(), "fresh").unwrap();
+    assert!(renderer.begin_revalidation("/"));
+
+    let mut headers = http::HeaderMap::new();
+    freshness.write(&mut headers);
+    assert_eq!(
+        headers[http::header::CACHE_CONTROL],
+        "max-age=0, stale-while-revalidate=60"
+    );
+}
+
+#[test]
+fn starting_a_renderer_keeps_the_shared_cache() {
+    let path = std::env::temp_dir().join(format!("dioxus-isrg-restart-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut first = shared_renderer(path.clone());
+    first.cache("/".to_string(), "home").unwrap();
+
+    // Another replica starting up must not wipe the renders of the others
+    let mut second = shared_renderer(path.clone());
+    assert_eq!(second.get("/").unwrap().unwrap().response, b"home");
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn freshness_comes_from_the_backend() {
+    let path = std::env::temp_dir().join(format!("dioxus-isrg-fresh-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut writer = {
+        let path = path.clone();
+        IncrementalRenderer::builder()
+            .invalidate_after(std::time::Duration::from_secs(60))
+            .stale_while_revalidate(std::time::Duration::from_secs(30))
+            .backend(move || SqliteCache::open(&path).unwrap())
+            .build()
+    };
+    writer.cache("/".to_string(), "home").unwrap();
+
+    // The reader has no expiry of its own and still sees the expiry the render was stored with
+    let mut reader = {
+        let path = path.clone();
+        IncrementalRenderer::builder()
+            .backend(move || SqliteCache::open(&path).unwrap())
+            .build()
+    };
+    let freshness = reader.get("/").unwrap().unwrap().freshness;
+    assert_eq!(freshness.max_age(), Some(60));
+    assert_eq!(freshness.stale_while_revalidate(), Some(30));
+
+    let _ = std::fs::remove_file(&path);
+}
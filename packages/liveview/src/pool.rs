@@ -228,6 +228,10 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
                         // usually only web gets this message - what are we supposed to do?
                         // Maybe we could just binary patch ourselves in place without losing window state?
                     },
+                    dioxus_devtools::DevserverMsg::Inspect(query) => {
+                        let response = dioxus_devtools::inspect(&vdom, &query);
+                        dioxus_devtools::send_to_devserver(dioxus_devtools::ClientMsg::Inspector(response));
+                    },
                 }
                 #[cfg(not(all(feature = "devtools", debug_assertions)))]
                 let () = msg;
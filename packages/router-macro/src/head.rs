@@ -0,0 +1,112 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parenthesized, parse::ParseStream, punctuated::Punctuated, Ident, LitStr, Token};
+
+/// The attributes of a meta tag that can be set with `meta(..)`
+const META_ATTRIBUTES: &[&str] = &["property", "name", "charset", "http_equiv", "content"];
+
+/// The head of a route declared with `title = ".."` and `meta(..)` arguments
+#[derive(Debug, Default)]
+pub struct Head {
+    pub title: Option<LitStr>,
+    pub meta: Vec<Vec<(Ident, LitStr)>>,
+}
+
+impl Head {
+    /// Parse every `title = ".."` and `meta(..)` argument at the start of the input
+    pub fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut head = Self::default();
+        while Self::peek(input) {
+            let ident: Ident = input.parse()?;
+            if ident == "title" {
+                if head.title.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "A route can only have one title",
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                head.title = Some(input.parse()?);
+            } else {
+                let content;
+                parenthesized!(content in input);
+                let attributes =
+                    Punctuated::<MetaAttribute, Token![,]>::parse_terminated(&content)?;
+                head.meta.push(
+                    attributes
+                        .into_iter()
+                        .map(|attribute| (attribute.name, attribute.value))
+                        .collect(),
+                );
+            }
+            let _ = input.parse::<Token![,]>();
+        }
+
+        Ok(head)
+    }
+
+    /// Check if the input starts with a `title = ..` or `meta(..)` argument
+    pub fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        match fork.parse::<Ident>() {
+            Ok(ident) if ident == "title" => fork.peek(Token![=]),
+            Ok(ident) if ident == "meta" => fork.peek(syn::token::Paren),
+            _ => false,
+        }
+    }
+
+    /// Check if the route doesn't declare anything in the head
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.meta.is_empty()
+    }
+
+    /// Create the `RouteHead` of the route. The parameters of the route must be bound in the surrounding scope so
+    /// they can be interpolated into the title and meta tags
+    pub fn to_tokens(&self) -> TokenStream {
+        let title = match &self.title {
+            Some(title) => quote! { Some(format!(#title)) },
+            None => quote! { None },
+        };
+        let meta = self.meta.iter().map(|attributes| {
+            let attributes = attributes.iter().map(|(name, value)| {
+                quote! { #name: Some(format!(#value)), }
+            });
+            quote! {
+                dioxus_router::head::RouteMeta {
+                    #(#attributes)*
+                    ..Default::default()
+                }
+            }
+        });
+        quote! {
+            dioxus_router::head::RouteHead {
+                title: #title,
+                meta: vec![#(#meta),*],
+            }
+        }
+    }
+}
+
+/// A `name = "value"` attribute of a meta tag
+struct MetaAttribute {
+    name: Ident,
+    value: LitStr,
+}
+
+impl syn::parse::Parse for MetaAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if !META_ATTRIBUTES.iter().any(|attribute| name == attribute) {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format!(
+                    "Unknown meta attribute `{name}`. Expected one of: {}",
+                    META_ATTRIBUTES.join(", ")
+                ),
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Self { name, value })
+    }
+}
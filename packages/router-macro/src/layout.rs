@@ -1,8 +1,10 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Path;
+use syn::{Ident, Path};
 
+use crate::loader::Loader;
 use crate::nest::{Nest, NestId};
+use crate::route::Route;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LayoutId(pub usize);
@@ -11,6 +13,7 @@ pub struct LayoutId(pub usize);
 pub struct Layout {
     pub comp: Path,
     pub active_nests: Vec<NestId>,
+    pub loader: Option<Loader>,
 }
 
 impl Layout {
@@ -27,14 +30,85 @@ impl Layout {
             }
         }
     }
+
+    /// Render the layout for every route inside of it. All of the routes share one arm, so the layout stays mounted
+    /// when the user navigates between them. Returns `None` if the layout doesn't have any routes
+    pub fn render_match(
+        &self,
+        id: LayoutId,
+        routes: &[&Route],
+        nests: &[Nest],
+    ) -> Option<TokenStream> {
+        let level = routes
+            .first()?
+            .layouts
+            .iter()
+            .position(|layout| *layout == id)?;
+        let dynamic_segments = self.dynamic_segment_names(nests);
+        let patterns = routes.iter().map(|route| {
+            let name = &route.route_name;
+            quote! { Self::#name { #(#dynamic_segments,)* .. } }
+        });
+        let render = Route::wrap_loader(self.loader.is_some(), level, self.routable_match(nests));
+
+        Some(quote! {
+            #[allow(unused)]
+            (#level, #(#patterns)|*) => {
+                #render
+            }
+        })
+    }
+
+    /// Create the loader entry for this layout if it has a loader. The layout only loads again when the parameters of
+    /// its nests change
+    pub fn loader_entry(&self, id: LayoutId, level: usize, nests: &[Nest]) -> Option<TokenStream> {
+        let loader = self.loader.as_ref()?;
+        let layout_index = id.0.to_string();
+        let dynamic_segments = self.dynamic_segment_names(nests);
+        let key = quote! {
+            {
+                let mut key = String::from(#layout_index);
+                #(
+                    key.push('/');
+                    key.push_str(&#dynamic_segments.to_string());
+                )*
+                key
+            }
+        };
+        Some(loader.entry(level, key))
+    }
+
+    fn dynamic_segment_names(&self, nests: &[Nest]) -> Vec<Ident> {
+        self.active_nests
+            .iter()
+            .flat_map(|id| nests[id.0].dynamic_segments_names())
+            .collect()
+    }
 }
 
 impl Layout {
-    pub fn parse(input: syn::parse::ParseStream, active_nests: Vec<NestId>) -> syn::Result<Self> {
+    pub fn parse(
+        input: syn::parse::ParseStream,
+        active_nests: Vec<NestId>,
+        nests: &[Nest],
+    ) -> syn::Result<Self> {
         // Then parse the component name
         let _ = input.parse::<syn::Token![,]>();
         let comp: Path = input.parse()?;
 
-        Ok(Self { comp, active_nests })
+        // Then parse the loader
+        let _ = input.parse::<syn::Token![,]>();
+        let loader = Loader::parse(input)?;
+
+        let layout = Self {
+            comp,
+            active_nests,
+            loader,
+        };
+        if let Some(loader) = &layout.loader {
+            loader.validate(&layout.dynamic_segment_names(nests))?;
+        }
+
+        Ok(layout)
     }
 }
@@ -17,8 +17,12 @@ use proc_macro2::TokenStream as TokenStream2;
 use crate::{layout::LayoutId, route_tree::ParseRouteTree};
 
 mod hash;
+mod head;
 mod layout;
+mod loader;
+mod locale;
 mod nest;
+mod preload;
 mod query;
 mod redirect;
 mod route;
@@ -91,11 +95,15 @@ mod segment;
 /// # fn IndexComponent() -> Element { VNode::empty() }
 /// ```
 ///
-/// # `#[route("path", component)]`
+/// # `#[route("path", component, loader = |param| future, preload = [file, ..], title = "..", meta(..))]`
 ///
-/// The `#[route]` attribute is used to define a route. It takes up to 2 parameters:
+/// The `#[route]` attribute is used to define a route. It takes up to 6 parameters:
 /// - `path`: The path to the enum variant (relative to the parent nest)
 /// - (optional) `component`: The component to render when the route is matched. If not specified, the name of the variant is used
+/// - (optional) `loader`: A closure that takes parameters of the route by name and returns a future. The route is rendered once the future finishes, and the component can read the result with `use_loader_data`
+/// - (optional) `preload`: A list of files the route needs, like assets. They are downloaded early when a `Link` prefetches the route
+/// - (optional) `title`: The title of the page. Parameters of the route can be used in the title like in `format!`: `title = "Post {id}"`
+/// - (optional) `meta`: A meta tag in the head of the page, like `meta(name = "description", content = "Post {id}")`. The attributes can be `property`, `name`, `charset`, `http_equiv` and `content`. A route can have any number of meta tags
 ///
 /// Routes are the most basic attribute. They allow you to define a route and the component to render when the route is matched. The component must take all dynamic parameters of the route and all parent nests.
 /// The next variant will be tied to the component. If you link to that variant, the component will be rendered.
@@ -187,10 +195,11 @@ mod segment;
 /// # fn Home() -> Element { VNode::empty() }
 /// ```
 ///
-/// # `#[layout(component)]`
+/// # `#[layout(component, loader = |param| future)]`
 ///
-/// The `#[layout]` attribute is used to define a layout. It takes 1 parameter:
+/// The `#[layout]` attribute is used to define a layout. It takes up to 2 parameters:
 /// - `component`: The component to render when the route is matched. If not specified, the name of the variant is used
+/// - (optional) `loader`: A closure that takes dynamic parameters of the parent nests by name and returns a future. The loader only runs again when those parameters change
 ///
 /// The layout component allows you to wrap all children of the layout in a component. The child routes are rendered in the Outlet of the layout component. The layout component must take all dynamic parameters of the nests it is nested in.
 ///
@@ -238,10 +247,58 @@ mod segment;
 /// # #[component]
 /// # fn Home() -> Element { VNode::empty() }
 /// ```
+///
+/// # `#[locale("locale", .., default = "locale")]`
+///
+/// The `#[locale]` attribute is placed on the enum itself. It adds a locale segment in front of every route. It takes:
+/// - `locales`: Every locale the routes are served in
+/// - (optional) `default`: The locale of urls without a locale segment. If not specified, the locale segment is required
+///
+/// The locale is not a field of the variants. Routes are formatted in the default or first locale and move to the locale of the current url when they are navigated to. Components can read the locale with `use_locale`.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// #[locale("en", "de", default = "en")]
+/// enum Route {
+///     // Matches `/`, `/en` and `/de`
+///     #[route("/")]
+///     Index {},
+/// }
+/// # #[component]
+/// # fn Index() -> Element { VNode::empty() }
+/// ```
+///
+/// # `#[lazy]`
+///
+/// The `#[lazy]` attribute is placed on a variant with a `#[route]` attribute. It takes no parameters.
+///
+/// With the `wasm-split` feature, `dx` moves the component of the route and everything only it uses into a separate WASM module that is downloaded the first time the route is rendered. The route suspends while the module loads.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     // The editor is only downloaded when the user opens it
+///     #[lazy]
+///     #[route("/editor")]
+///     Editor {},
+/// }
+/// # #[component]
+/// # fn Index() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Editor() -> Element { VNode::empty() }
+/// ```
 #[doc(alias = "route")]
 #[proc_macro_derive(
     Routable,
-    attributes(route, nest, end_nest, layout, end_layout, redirect, child)
+    attributes(
+        route, nest, end_nest, layout, end_layout, redirect, child, locale, lazy
+    )
 )]
 pub fn routable(input: TokenStream) -> TokenStream {
     let routes_enum = parse_macro_input!(input as syn::ItemEnum);
@@ -274,6 +331,7 @@ struct RouteEnum {
     nests: Vec<Nest>,
     layouts: Vec<Layout>,
     site_map: Vec<SiteMapSegment>,
+    locales: Option<locale::Locales>,
 }
 
 impl RouteEnum {
@@ -373,7 +431,7 @@ impl RouteEnum {
                     let parser = |input: ParseStream| {
                         let bang: Option<Token![!]> = input.parse().ok();
                         let exclude = bang.is_some();
-                        Ok((exclude, Layout::parse(input, nest_stack.clone())?))
+                        Ok((exclude, Layout::parse(input, nest_stack.clone(), &nests)?))
                     };
                     let (exclude, layout): (bool, Layout) = attr.parse_args_with(parser)?;
 
@@ -465,12 +523,20 @@ impl RouteEnum {
             children.push(current);
         }
 
+        let locales = data
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("locale"))
+            .map(|attr| attr.parse_args::<locale::Locales>())
+            .transpose()?;
+
         let myself = Self {
             name: name.clone(),
             endpoints,
             nests,
             layouts,
             site_map,
+            locales,
         };
 
         // If we're on the web, only the URL history is preserved between navigation. We need to warn the user that the segment is not present in the URL.
@@ -559,10 +625,17 @@ impl RouteEnum {
         }
 
         let name = &self.name;
+        let write_locale = self.locales.as_ref().map(|locales| {
+            let locales = locales.to_tokens();
+            quote! {
+                dioxus_router::locale::write_locale(&#locales, f)?;
+            }
+        });
 
         quote! {
             impl std::fmt::Display for #name {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    #write_locale
                     #[allow(unused)]
                     match self {
                         #(#display_match)*
@@ -578,6 +651,23 @@ impl RouteEnum {
         let name = &self.name;
 
         let error_name = format_ident!("{}MatchError", self.name);
+        // Parse the rest of the route after the locale segment
+        let strip_locale = self.locales.as_ref().map(|locales| {
+            let locales = locales.to_tokens();
+            quote! {
+                let Some((_, s)) = #locales.split(s) else {
+                    return Err(dioxus_router::routable::RouteParseError {
+                        attempted_routes: vec![#error_name::MissingLocaleSegment(
+                            dioxus_router::locale::MissingLocaleError {
+                                locales: #locales.locales,
+                                route: s.to_string(),
+                            },
+                        )],
+                    });
+                };
+                let s: &str = &s;
+            }
+        });
         let tokens = tree.roots.iter().map(|&id| {
             let route = tree.get(id).unwrap();
             route.to_tokens(&self.nests, &tree, self.name.clone(), error_name.clone())
@@ -596,6 +686,7 @@ impl RouteEnum {
                 type Err = dioxus_router::routable::RouteParseError<#error_name>;
 
                 fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                    #strip_locale
                     let route = s;
                     let (route, hash) = route.split_once('#').unwrap_or((route, ""));
                     let (route, query) = route.split_once('?').unwrap_or((route, ""));
@@ -694,6 +785,14 @@ impl RouteEnum {
             type_defs.push(nest.error_type());
         }
 
+        if self.locales.is_some() {
+            error_variants.push(quote! {
+                #[doc = " The route doesn't start with a locale segment."]
+                MissingLocaleSegment(dioxus_router::locale::MissingLocaleError)
+            });
+            display_match.push(quote! { Self::MissingLocaleSegment(err) => write!(f, "{}", err)? });
+        }
+
         let comment = format!(
             " An error that can occur when trying to parse the route enum [`{}`].",
             self.name
@@ -731,20 +830,98 @@ impl RouteEnum {
         let site_map = &self.site_map;
 
         let mut matches = Vec::new();
+        let mut loader_matches = Vec::new();
+        let mut loader_at_matches = Vec::new();
+        let mut prefetch_hints_matches = Vec::new();
+        let mut level_key_matches = Vec::new();
+        let mut head_matches = Vec::new();
+
+        // Collect all layout matches
+        for (id, layout) in self.layouts.iter().enumerate() {
+            let id = LayoutId(id);
+            let routes: Vec<_> = self
+                .endpoints
+                .iter()
+                .filter_map(|route| match route {
+                    RouteEndpoint::Route(route) if route.layouts.contains(&id) => Some(route),
+                    _ => None,
+                })
+                .collect();
+            matches.extend(layout.render_match(id, &routes, &self.nests));
+        }
 
         // Collect all routes matches
         for route in &self.endpoints {
             if let RouteEndpoint::Route(route) = route {
-                matches.push(route.routable_match(&self.layouts, &self.nests));
+                matches.push(route.routable_match(name));
+                loader_matches.extend(route.loaders_match(&self.layouts, &self.nests));
+                loader_at_matches.extend(route.loader_at_matches(&self.layouts, &self.nests));
+                prefetch_hints_matches.extend(route.prefetch_hints_match(name));
+                level_key_matches.push(route.level_key_match());
+                head_matches.extend(route.head_match());
             }
         }
 
+        // Only override the default implementation if there are loaders
+        let loaders = (!loader_matches.is_empty()).then(|| {
+            quote! {
+                fn loaders(&self) -> Vec<dioxus_router::loader::LoaderEntry> {
+                    match self {
+                        #(#loader_matches)*
+                        _ => Vec::new(),
+                    }
+                }
+
+                fn loader_at(&self, level: usize) -> Option<dioxus_router::loader::LoaderEntry> {
+                    match (level, self) {
+                        #(#loader_at_matches)*
+                        _ => None,
+                    }
+                }
+            }
+        });
+
+        let locales = self.locales.as_ref().map(|locales| {
+            let locales = locales.to_tokens();
+            quote! {
+                const LOCALES: Option<dioxus_router::locale::Locales> = Some(#locales);
+            }
+        });
+
+        // Only override the default implementation if there are routes that preload files or are lazy
+        let prefetch_hints = (!prefetch_hints_matches.is_empty()).then(|| {
+            quote! {
+                fn prefetch_hints(&self) -> Vec<dioxus_router::prefetch::PrefetchHint> {
+                    match self {
+                        #(#prefetch_hints_matches)*
+                        _ => Vec::new(),
+                    }
+                }
+            }
+        });
+
+        // Only override the default implementation if there are routes with a title, meta tags or child routes
+        let head = (!head_matches.is_empty()).then(|| {
+            quote! {
+                #[allow(clippy::useless_format)]
+                fn head(&self) -> dioxus_router::head::RouteHead {
+                    match self {
+                        #(#head_matches)*
+                        #[allow(unreachable_patterns)]
+                        _ => Default::default(),
+                    }
+                }
+            }
+        });
+
         quote! {
             impl dioxus_router::routable::Routable for #name where Self: Clone {
                 const SITE_MAP: &'static [dioxus_router::routable::SiteMapSegment] = &[
                     #(#site_map,)*
                 ];
 
+                #locales
+
                 fn render(&self, level: usize) -> dioxus_core::Element {
                     let myself = self.clone();
                     match (level, myself) {
@@ -752,6 +929,20 @@ impl RouteEnum {
                         _ => VNode::empty()
                     }
                 }
+
+                #loaders
+
+                #prefetch_hints
+
+                #head
+
+                fn level_key(&self, level: usize) -> &'static str {
+                    match (level, self) {
+                        #(#level_key_matches)*
+                        #[allow(unreachable_patterns)]
+                        _ => "",
+                    }
+                }
             }
         }
     }
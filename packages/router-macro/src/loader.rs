@@ -0,0 +1,76 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::ParseStream, ExprClosure, Ident, Token};
+
+/// A loader for a route or layout declared with `loader = |param| future`
+#[derive(Debug)]
+pub struct Loader {
+    pub closure: ExprClosure,
+    pub arguments: Vec<Ident>,
+}
+
+impl Loader {
+    /// Parse a `loader = |..| ..` argument if the input starts with one
+    pub fn parse(input: ParseStream) -> syn::Result<Option<Self>> {
+        if !Self::peek(input) {
+            return Ok(None);
+        }
+        let _: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let closure: ExprClosure = input.parse()?;
+
+        let mut arguments = Vec::new();
+        for arg in closure.inputs.iter() {
+            let pat = match arg {
+                syn::Pat::Type(pat) => &*pat.pat,
+                pat => pat,
+            };
+            match pat {
+                syn::Pat::Ident(ident) => arguments.push(ident.ident.clone()),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        arg,
+                        "Expected loader arguments to be the names of parameters of the route",
+                    ))
+                }
+            }
+        }
+
+        Ok(Some(Self { closure, arguments }))
+    }
+
+    /// Check if the input starts with a `loader = ..` argument
+    pub fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        matches!(fork.parse::<Ident>(), Ok(ident) if ident == "loader") && fork.peek(Token![=])
+    }
+
+    /// Make sure every argument of the loader is one of the parameters that are available to it
+    pub fn validate(&self, parameters: &[Ident]) -> syn::Result<()> {
+        for argument in &self.arguments {
+            if !parameters.contains(argument) {
+                return Err(syn::Error::new_spanned(
+                    argument,
+                    format!("Loader arguments must be parameters of the route. `{argument}` is not a parameter of the route"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Create the `LoaderEntry` for the loader. The arguments of the loader must be bound by reference in the
+    /// surrounding scope
+    pub fn entry(&self, level: usize, key: TokenStream) -> TokenStream {
+        let closure = &self.closure;
+        let arguments = &self.arguments;
+        quote! {
+            {
+                #(let #arguments = #arguments.clone();)*
+                // The closure is only used to name the parameters of the loader
+                #[allow(clippy::redundant_closure)]
+                let loader = #closure;
+                dioxus_router::loader::LoaderEntry::new::<Self, _>(#level, #key, loader(#(#arguments),*))
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::Parse, Ident, LitStr, Token};
+
+/// The locales of the route tree, declared with `#[locale("en", "de", default = "en")]`
+#[derive(Debug)]
+pub struct Locales {
+    pub locales: Vec<LitStr>,
+    pub default: Option<LitStr>,
+}
+
+impl Parse for Locales {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut locales = Vec::new();
+        let mut default = None;
+        while !input.is_empty() {
+            if input.peek(Ident) {
+                let ident: Ident = input.parse()?;
+                if ident != "default" {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "Expected a locale or `default = \"locale\"`",
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                default = Some(input.parse::<LitStr>()?);
+            } else {
+                locales.push(input.parse::<LitStr>()?);
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        if locales.is_empty() {
+            return Err(input.error("Expected at least one locale"));
+        }
+        for locale in &locales {
+            let value = locale.value();
+            if value.is_empty() || value.contains(['/', '?', '#']) {
+                return Err(syn::Error::new_spanned(
+                    locale,
+                    "Locales must be a single segment of the url",
+                ));
+            }
+        }
+        if let Some(default) = &default {
+            if !locales
+                .iter()
+                .any(|locale| locale.value() == default.value())
+            {
+                return Err(syn::Error::new_spanned(
+                    default,
+                    "The default locale must be one of the locales",
+                ));
+            }
+        }
+
+        Ok(Self { locales, default })
+    }
+}
+
+impl Locales {
+    pub fn to_tokens(&self) -> TokenStream {
+        let locales = &self.locales;
+        let default = match &self.default {
+            Some(default) => quote! { Some(#default) },
+            None => quote! { None },
+        };
+        quote! {
+            dioxus_router::locale::Locales {
+                locales: &[#(#locales),*],
+                default: #default,
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{bracketed, parse::ParseStream, punctuated::Punctuated, Expr, Ident, Token};
+
+/// The files a route needs, declared with `preload = [asset, ..]`
+#[derive(Debug)]
+pub struct Preload {
+    pub files: Vec<Expr>,
+}
+
+impl Preload {
+    /// Parse a `preload = [..]` argument if the input starts with one
+    pub fn parse(input: ParseStream) -> syn::Result<Option<Self>> {
+        if !Self::peek(input) {
+            return Ok(None);
+        }
+        let _: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let content;
+        bracketed!(content in input);
+        let files = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+
+        Ok(Some(Self {
+            files: files.into_iter().collect(),
+        }))
+    }
+
+    /// Check if the input starts with a `preload = ..` argument
+    pub fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        matches!(fork.parse::<Ident>(), Ok(ident) if ident == "preload") && fork.peek(Token![=])
+    }
+
+    /// Create the prefetch hints for the files
+    pub fn hints(&self) -> TokenStream {
+        let files = &self.files;
+        quote! {
+            vec![#(dioxus_router::prefetch::PrefetchHint::prefetch(#files),)*]
+        }
+    }
+}
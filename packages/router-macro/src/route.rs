@@ -10,10 +10,13 @@ use syn::{Ident, LitStr};
 use proc_macro2::TokenStream as TokenStream2;
 
 use crate::hash::HashFragment;
+use crate::head::Head;
 use crate::layout::Layout;
 use crate::layout::LayoutId;
+use crate::loader::Loader;
 use crate::nest::Nest;
 use crate::nest::NestId;
+use crate::preload::Preload;
 use crate::query::QuerySegment;
 use crate::segment::create_error_type;
 use crate::segment::parse_route_segments;
@@ -22,18 +25,35 @@ use crate::segment::RouteSegment;
 struct RouteArgs {
     route: LitStr,
     comp_name: Option<Path>,
+    loader: Option<Loader>,
+    preload: Option<Preload>,
+    head: Head,
 }
 
 impl Parse for RouteArgs {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let route = input.parse::<LitStr>()?;
 
+        let _ = input.parse::<syn::Token![,]>();
+        let mut comp_name = None;
+        // The component name is optional, so `loader`, `preload`, `title` or `meta` could be parsed as a component path
+        if !Loader::peek(input) && !Preload::peek(input) && !Head::peek(input) {
+            comp_name = input.parse().ok();
+            let _ = input.parse::<syn::Token![,]>();
+        }
+
+        let loader = Loader::parse(input)?;
+        let _ = input.parse::<syn::Token![,]>();
+        let preload = Preload::parse(input)?;
+        let _ = input.parse::<syn::Token![,]>();
+        let head = Head::parse(input)?;
+
         Ok(RouteArgs {
             route,
-            comp_name: {
-                let _ = input.parse::<syn::Token![,]>();
-                input.parse().ok()
-            },
+            comp_name,
+            loader,
+            preload,
+            head,
         })
     }
 }
@@ -60,6 +80,12 @@ pub(crate) struct Route {
     pub hash: Option<HashFragment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    pub loader: Option<Loader>,
+    pub preload: Option<Preload>,
+    /// The title and meta tags of the route
+    pub head: Head,
+    /// Whether the component of the route is split into a separate WASM module with `#[lazy]`
+    pub lazy: bool,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -75,7 +101,20 @@ impl Route {
             .find(|attr| attr.path().is_ident("route"));
         let route;
         let ty;
+        let mut loader = None;
+        let mut preload = None;
+        let mut head = Head::default();
         let route_name = variant.ident.clone();
+        let lazy_attr = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("lazy"));
+        if let (Some(lazy_attr), None) = (lazy_attr, route_attr) {
+            return Err(syn::Error::new_spanned(
+                lazy_attr,
+                "Only routes with a #[route(..)] attribute can be lazy",
+            ));
+        }
         match route_attr {
             Some(attr) => {
                 let args = attr.parse_args::<RouteArgs>()?;
@@ -84,6 +123,9 @@ impl Route {
                     component: comp_name,
                 };
                 route = args.route.value();
+                loader = args.loader;
+                preload = args.preload;
+                head = args.head;
             }
             None => {
                 if let Some(route_attr) = variant
@@ -146,6 +188,11 @@ impl Route {
             _ => Vec::new(),
         };
 
+        if let Some(loader) = &loader {
+            let parameters: Vec<_> = fields.iter().map(|(name, _)| name.clone()).collect();
+            loader.validate(&parameters)?;
+        }
+
         let (route_segments, query, hash) = {
             parse_route_segments(
                 variant.ident.span(),
@@ -163,6 +210,10 @@ impl Route {
             hash,
             nests,
             layouts,
+            loader,
+            preload,
+            head,
+            lazy: lazy_attr.is_some(),
             fields,
         })
     }
@@ -211,32 +262,12 @@ impl Route {
         }
     }
 
-    pub fn routable_match(&self, layouts: &[Layout], nests: &[Nest]) -> TokenStream2 {
+    pub fn routable_match(&self, enum_name: &Ident) -> TokenStream2 {
         let name = &self.route_name;
 
-        let mut tokens = TokenStream2::new();
-
-        // First match all layouts
-        for (idx, layout_id) in self.layouts.iter().copied().enumerate() {
-            let render_layout = layouts[layout_id.0].routable_match(nests);
-            let dynamic_segments = self.dynamic_segments();
-            let mut field_name = None;
-            if let RouteType::Child(field) = &self.ty {
-                field_name = field.ident.as_ref();
-            }
-            let field_name = field_name.map(|f| quote!(#f,));
-            // This is a layout
-            tokens.extend(quote! {
-                #[allow(unused)]
-                (#idx, Self::#name { #(#dynamic_segments,)* #field_name .. }) => {
-                    #render_layout
-                }
-            });
-        }
-
-        // Then match the route
+        // Layouts are matched for all of their routes at once in Layout::render_match
         let last_index = self.layouts.len();
-        tokens.extend(match &self.ty {
+        match &self.ty {
             RouteType::Child(field) => {
                 let field_name = field.ident.as_ref().unwrap();
                 quote! {
@@ -265,20 +296,175 @@ impl Route {
             RouteType::Leaf { component } => {
                 let dynamic_segments = self.dynamic_segments();
                 let dynamic_segments_from_route = self.dynamic_segments();
+                let mut render = quote! {
+                    rsx! {
+                        #component {
+                            #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
+                        }
+                    }
+                };
+                if self.lazy {
+                    let chunk = format!("{enum_name}_{name}");
+                    let fields: Vec<_> = self.dynamic_segments().collect();
+                    render = quote! {
+                        dioxus_router::lazy_route!(
+                            #chunk,
+                            #enum_name,
+                            self.clone(),
+                            #component,
+                            |__route: &#enum_name| match __route {
+                                #enum_name::#name { #(#fields,)* } => {
+                                    dioxus_core::prelude::fc_to_builder(#component)
+                                        #(.#fields(#fields.clone()))*
+                                        .build()
+                                }
+                                _ => unreachable!(),
+                            },
+                            #render
+                        )
+                    };
+                }
+                let render_route = Self::wrap_loader(self.loader.is_some(), last_index, render);
                 quote! {
                     #[allow(unused)]
                     (#last_index, Self::#name { #(#dynamic_segments,)* }) => {
-                        rsx! {
-                            #component {
-                                #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
-                            }
-                        }
+                        #render_route
                     }
                 }
             }
+        }
+    }
+
+    /// Wrap the element rendered at a level in the boundary that waits for the loaders of the route
+    pub(crate) fn wrap_loader(
+        has_loader: bool,
+        level: usize,
+        render: TokenStream2,
+    ) -> TokenStream2 {
+        match has_loader {
+            true => quote! {
+                dioxus_router::loader::LoaderEntry::wrap(self.loader_at(#level), #render)
+            },
+            false => render,
+        }
+    }
+
+    /// Create the loaders of the route and the layouts it is rendered in along with the level they are rendered at
+    fn loader_entries(&self, layouts: &[Layout], nests: &[Nest]) -> Vec<(usize, TokenStream2)> {
+        let mut entries: Vec<_> = self
+            .layouts
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &id)| Some((idx, layouts[id.0].loader_entry(id, idx, nests)?)))
+            .collect();
+        if let Some(loader) = &self.loader {
+            let last_index = self.layouts.len();
+            entries.push((
+                last_index,
+                loader.entry(last_index, quote! { self.to_string() }),
+            ));
+        }
+        entries
+    }
+
+    /// Create the loaders of the route and the layouts it is rendered in. Returns `None` if none of them have a loader
+    pub fn loaders_match(&self, layouts: &[Layout], nests: &[Nest]) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        let entries: Vec<_> = self
+            .loader_entries(layouts, nests)
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect();
+        if entries.is_empty() {
+            return None;
+        }
+
+        let dynamic_segments = self.dynamic_segments();
+        Some(quote! {
+            #[allow(unused)]
+            Self::#name { #(#dynamic_segments,)* .. } => vec![#(#entries),*],
+        })
+    }
+
+    /// Create the loader of each level of the route that has one, so rendering a level only creates its own loader
+    pub fn loader_at_matches(&self, layouts: &[Layout], nests: &[Nest]) -> Vec<TokenStream2> {
+        let name = &self.route_name;
+        self.loader_entries(layouts, nests)
+            .into_iter()
+            .map(|(level, entry)| {
+                let dynamic_segments = self.dynamic_segments();
+                quote! {
+                    #[allow(unused)]
+                    (#level, Self::#name { #(#dynamic_segments,)* .. }) => Some(#entry),
+                }
+            })
+            .collect()
+    }
+
+    /// Create the prefetch hints of the route. Returns `None` if the route doesn't preload any files and isn't lazy
+    pub fn prefetch_hints_match(&self, enum_name: &Ident) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        let files = self.preload.as_ref().map(Preload::hints);
+        let module = self.lazy.then(|| {
+            let chunk = format!("{enum_name}_{name}");
+            quote! { dioxus_router::lazy::hints(#chunk) }
         });
+        let hints = match (files, module) {
+            (None, None) => return None,
+            (Some(files), None) => files,
+            (None, Some(module)) => module,
+            (Some(files), Some(module)) => quote! {
+                {
+                    let mut hints = #files;
+                    hints.extend(#module);
+                    hints
+                }
+            },
+        };
+        Some(quote! {
+            Self::#name { .. } => #hints,
+        })
+    }
 
-        tokens
+    /// Create the head of the route. Child routes use the head of the child route. Returns `None` if the route doesn't
+    /// declare a title or meta tags
+    pub fn head_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #child, .. } => #child.head(),
+                })
+            }
+            RouteType::Leaf { .. } => {
+                if self.head.is_empty() {
+                    return None;
+                }
+                let dynamic_segments = self.dynamic_segments();
+                let head = self.head.to_tokens();
+                Some(quote! {
+                    #[allow(unused)]
+                    Self::#name { #(#dynamic_segments,)* } => #head,
+                })
+            }
+        }
+    }
+
+    /// Create the keys of the layouts the route is rendered in and the key of the route itself
+    pub fn level_key_match(&self) -> TokenStream2 {
+        let name = &self.route_name;
+        let layouts = self.layouts.iter().enumerate().map(|(idx, id)| {
+            let key = format!("layout:{}", id.0);
+            quote! {
+                (#idx, Self::#name { .. }) => #key,
+            }
+        });
+        let last_index = self.layouts.len();
+        quote! {
+            #(#layouts)*
+            (#last_index.., Self::#name { .. }) => stringify!(#name),
+        }
     }
 
     fn dynamic_segments(&self) -> impl Iterator<Item = TokenStream2> + '_ {
@@ -2,7 +2,7 @@
 /// to the child router that maps child routes to root routes and vice versa.
 use dioxus_lib::prelude::*;
 
-use crate::prelude::Routable;
+use crate::{loader::LoaderCache, prelude::Routable};
 
 /// Maps a child route into the root router and vice versa
 // NOTE: Currently child routers only support simple static prefixes, but this
@@ -61,7 +61,8 @@ pub fn ChildRouter<R: Routable>(props: ChildRouterProps<R>) -> Element {
         provide_context(ChildRouteMapping {
             format_route_as_root_route: props.format_route_as_root_route,
             parse_route_from_root_route: props.parse_route_from_root_route,
-        })
+        });
+        provide_context(LoaderCache::new());
     });
 
     props.route.render(0)
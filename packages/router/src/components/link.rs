@@ -7,6 +7,7 @@ use dioxus_lib::prelude::*;
 use tracing::error;
 
 use crate::navigation::NavigationTarget;
+use crate::prefetch::{Prefetch, PrefetchHint};
 use crate::utils::use_router_internal::use_router_internal;
 
 /// The properties for a [`Link`].
@@ -43,6 +44,12 @@ pub struct LinkProps {
     /// 3. If `onclick_only` is [`true`], only the provided `onclick` handler will be executed.
     pub onclick_only: bool,
 
+    /// When the route of an internal `target` starts loading before the link is clicked. See the
+    /// [`prefetch`](crate::prefetch) module for more information.
+    ///
+    /// By default, routes are only loaded once the user navigates to them.
+    pub prefetch: Option<Prefetch>,
+
     /// The rel attribute for the generated HTML anchor tag.
     ///
     /// For external `a`s, this defaults to `noopener noreferrer`.
@@ -52,6 +59,13 @@ pub struct LinkProps {
     #[props(into)]
     pub to: NavigationTarget,
 
+    /// The locale to link to, if the routes have a `#[locale]` attribute. See the [`locale`](crate::locale) module
+    /// for more information.
+    ///
+    /// By default, internal links keep the locale of the current route.
+    #[props(into)]
+    pub locale: Option<String>,
+
     #[props(extends = GlobalAttributes)]
     attributes: Vec<Attribute>,
 }
@@ -65,6 +79,8 @@ impl Debug for LinkProps {
             .field("new_tab", &self.new_tab)
             .field("onclick", &self.onclick.as_ref().map(|_| "onclick is set"))
             .field("onclick_only", &self.onclick_only)
+            .field("locale", &self.locale)
+            .field("prefetch", &self.prefetch)
             .field("rel", &self.rel)
             .finish()
     }
@@ -135,13 +151,15 @@ pub fn Link(props: LinkProps) -> Element {
     let LinkProps {
         active_class,
         children,
-        attributes,
         new_tab,
         onclick,
         onclick_only,
+        locale,
+        prefetch,
         rel,
-        to,
+        mut to,
         class,
+        mut attributes,
         ..
     } = props;
 
@@ -158,6 +176,12 @@ pub fn Link(props: LinkProps) -> Element {
         }
     };
 
+    if let (Some(locale), Some(locales), NavigationTarget::Internal(route)) =
+        (locale, router.locales(), &mut to)
+    {
+        *route = locales.localize(route, &locale);
+    }
+
     let current_url = router.full_route_string();
     let href = match &to {
         NavigationTarget::Internal(url) => url.clone(),
@@ -195,6 +219,59 @@ pub fn Link(props: LinkProps) -> Element {
 
     let do_default = onclick.is_none() || !onclick_only;
 
+    // The route this link prefetched and the files it needs
+    let mut prefetched = use_hook(|| CopyValue::new(None::<(String, Vec<PrefetchHint>)>));
+    let update = use_hook(schedule_update);
+    if let (Some(prefetch), NavigationTarget::Internal(route)) = (prefetch, &to) {
+        let already_prefetched = move |route: &str| {
+            prefetched
+                .peek()
+                .as_ref()
+                .is_some_and(|(prefetched, _)| prefetched == route)
+        };
+        match prefetch {
+            Prefetch::Render => {
+                if !already_prefetched(route) {
+                    let hints = router.prefetch(route);
+                    prefetched.set(Some((route.clone(), hints)));
+                }
+            }
+            Prefetch::Hover | Prefetch::Viewport => {
+                let route = route.clone();
+                let mut prefetch_route = move || {
+                    if !already_prefetched(&route) {
+                        let hints = router.prefetch(&route);
+                        prefetched.set(Some((route.clone(), hints)));
+                        // Rerender to add the hints to the head
+                        update();
+                    }
+                };
+                if prefetch == Prefetch::Hover {
+                    let mut on_focus = prefetch_route.clone();
+                    attributes.push(dioxus_elements::events::onmouseenter(
+                        move |_: MouseEvent| prefetch_route(),
+                    ));
+                    attributes.push(dioxus_elements::events::onfocus(move |_: FocusEvent| {
+                        on_focus()
+                    }));
+                } else {
+                    attributes.push(dioxus_elements::events::onvisible(
+                        move |event: VisibleEvent| {
+                            if event.is_intersecting().unwrap_or_default() {
+                                prefetch_route();
+                            }
+                        },
+                    ));
+                }
+            }
+        }
+    }
+    let prefetch_hints = prefetched
+        .read()
+        .as_ref()
+        .map(|(_, hints)| hints.clone())
+        .unwrap_or_default();
+
     let action = move |event: MouseEvent| {
         // Only handle events without modifiers
         if !event.modifiers().is_empty() {
@@ -251,5 +328,8 @@ pub fn Link(props: LinkProps) -> Element {
             ..attributes,
             {children}
         }
+        for hint in prefetch_hints {
+            dioxus_lib::document::Link { rel: hint.rel(), href: hint.href() }
+        }
     }
 }
@@ -2,7 +2,13 @@ use dioxus_lib::prelude::*;
 
 use std::str::FromStr;
 
+use dioxus_history::history;
+
 use crate::{
+    head::RouteHeadTags,
+    hooks::router,
+    loader::LoaderCache,
+    locale::{LocaleAlternateLinks, LocaleContext},
     prelude::{provide_router_context, Outlet},
     routable::Routable,
     router_cfg::RouterConfig,
@@ -52,7 +58,43 @@ where
             current_level: 0,
             _marker: std::marker::PhantomData,
         });
+
+        provide_context(LoaderCache::new());
+    });
+
+    use_hook(|| {
+        let Some(locales) = R::LOCALES else {
+            return;
+        };
+        let router = router();
+        provide_context(LocaleContext(Memo::new(move || {
+            locales
+                .locale_of(&router.full_route_string())
+                .unwrap_or_default()
+        })));
     });
 
-    rsx! { Outlet::<R> {} }
+    // Preload the module of the current route if it is lazy. This only matters for the head the server sends
+    use_hook(|| {
+        let Ok(route) = R::from_str(&history().current_route()) else {
+            return;
+        };
+        let document = dioxus_lib::document::document();
+        for hint in route.prefetch_hints() {
+            if hint.rel() == "modulepreload" {
+                document.create_link(
+                    dioxus_lib::document::LinkProps::builder()
+                        .rel(hint.rel().to_string())
+                        .href(hint.href().to_string())
+                        .build(),
+                );
+            }
+        }
+    });
+
+    rsx! {
+        RouteHeadTags::<R> {}
+        LocaleAlternateLinks::<R> {}
+        Outlet::<R> {}
+    }
 }
@@ -1,3 +1,5 @@
+use serde::{de::DeserializeOwned, Serialize};
+
 use crate::prelude::{ExternalNavigationFailure, NavigationTarget, RouterContext};
 
 /// Acquire the navigator without subscribing to updates.
@@ -52,6 +54,55 @@ impl Navigator {
         self.0.push(target)
     }
 
+    /// Push a new location and attach state to it, like the scroll position of a list or a draft. The state is
+    /// serialized into the history, so it is still there when the user comes back to the location.
+    ///
+    /// Not every platform stores state. Web, desktop and mobile apps do.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # #[derive(Clone, Routable, Debug, PartialEq)]
+    /// # enum Route {
+    /// #     #[route("/")]
+    /// #     Search {},
+    /// # }
+    /// # #[component]
+    /// # fn Search() -> Element { VNode::empty() }
+    /// #[component]
+    /// fn SearchBox() -> Element {
+    ///     // The query of the current location, if it was searched for
+    ///     let query = navigator().current_state::<String>().unwrap_or_default();
+    ///     rsx! {
+    ///         input {
+    ///             value: query,
+    ///             onchange: move |event| {
+    ///                 navigator().push_with_state(Route::Search {}, &event.value());
+    ///             },
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn push_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: &impl Serialize,
+    ) -> Option<ExternalNavigationFailure> {
+        match serde_json::to_string(state) {
+            Ok(state) => self.0.push_with_state(target, state),
+            Err(err) => {
+                tracing::error!("Failed to serialize the state of the location: {err}");
+                self.0.push(target)
+            }
+        }
+    }
+
+    /// Get the state attached to the current location with [`Navigator::push_with_state`].
+    ///
+    /// Returns [`None`] if the location doesn't have state, or if the state is not a `T`.
+    pub fn current_state<T: DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_str(&self.0.current_state()?).ok()
+    }
+
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
@@ -1,6 +1,9 @@
 use dioxus_lib::prelude::*;
 
-use crate::{routable::Routable, utils::use_router_internal::use_router_internal};
+use crate::{
+    routable::Routable, transition::use_outlet_transitions,
+    utils::use_router_internal::use_router_internal,
+};
 
 pub(crate) struct OutletContext<R> {
     pub current_level: usize,
@@ -39,6 +42,7 @@ impl<R> OutletContext<R> {
                 _marker: std::marker::PhantomData,
             }
         });
+        let transitions = use_outlet_transitions::<R>();
 
         if let Some(error) = router.render_error() {
             return if current_level == 0 {
@@ -48,6 +52,10 @@ impl<R> OutletContext<R> {
             };
         }
 
-        router.current::<R>().render(current_level)
+        let route = router.current::<R>();
+        match router.transition() {
+            Some(transition) => transitions.render(transition, route, current_level),
+            None => route.render(current_level),
+        }
     }
 }
@@ -1,14 +1,24 @@
 use std::{
     collections::HashSet,
-    sync::{Arc, Mutex},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use dioxus_history::history;
 use dioxus_lib::prelude::*;
 
 use crate::{
-    components::child_router::consume_child_route_mapping, navigation::NavigationTarget,
-    prelude::SiteMapSegment, routable::Routable, router_cfg::RouterConfig,
+    components::child_router::consume_child_route_mapping,
+    locale::Locales,
+    navigation::NavigationTarget,
+    prefetch::{prefetch_route, PrefetchHint},
+    prelude::SiteMapSegment,
+    routable::Routable,
+    router_cfg::RouterConfig,
+    transition::Transition,
 };
 
 /// This context is set in the root of the virtual dom if there is a router present.
@@ -46,17 +56,51 @@ pub(crate) type RoutingCallback<R> =
     Arc<dyn Fn(GenericRouterContext<R>) -> Option<NavigationTarget<R>>>;
 pub(crate) type AnyRoutingCallback = Arc<dyn Fn(RouterContext) -> Option<NavigationTarget>>;
 
+/// A navigation blocker registered with [`use_navigation_blocker`](crate::hooks::use_navigation_blocker). It is
+/// called with the current route, the route that is being navigated to, and the navigation to run if the user
+/// confirms. Returns `true` if the navigation should be blocked.
+pub(crate) type NavigationBlockerCallback =
+    Rc<dyn Fn(&str, Option<&str>, &PendingNavigation) -> bool>;
+
+/// A navigation that was blocked and can be resumed later
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PendingNavigation {
+    Push(NavigationTarget),
+    PushWithState(NavigationTarget, String),
+    Replace(NavigationTarget),
+    Go(isize),
+}
+
 struct RouterContextInner {
     unresolved_error: Option<ExternalNavigationFailure>,
 
     subscribers: Arc<Mutex<HashSet<ReactiveContext>>>,
+    /// Set by the history when it changes the route outside of the router. The update is checked against the
+    /// navigation blockers the next time the route is read
+    external_update: Arc<AtomicBool>,
     routing_callback: Option<AnyRoutingCallback>,
 
     failure_external_navigation: fn() -> Element,
 
     internal_route: fn(&str) -> bool,
 
+    prefetch: fn(&str) -> Vec<PrefetchHint>,
+
     site_map: &'static [SiteMapSegment],
+
+    locales: Option<Locales>,
+
+    transition: Option<Transition>,
+
+    /// The route the router last navigated to. Updates from outside the router are compared against this route
+    current_route: String,
+
+    blockers: Vec<(usize, NavigationBlockerCallback)>,
+    next_blocker_id: usize,
+    /// The blockers that block the user from leaving the app on the current page
+    unload_blockers: HashSet<usize>,
+    /// Skip the blockers for the next navigation because the user confirmed it
+    proceeding: bool,
 }
 
 impl RouterContextInner {
@@ -99,11 +143,13 @@ impl RouterContext {
         <R as std::str::FromStr>::Err: std::fmt::Display,
     {
         let subscribers = Arc::new(Mutex::new(HashSet::new()));
+        let external_update = Arc::new(AtomicBool::new(false));
         let mapping = consume_child_route_mapping();
 
         let myself = RouterContextInner {
             unresolved_error: None,
             subscribers: subscribers.clone(),
+            external_update: external_update.clone(),
             routing_callback: cfg.on_update.map(|update| {
                 Arc::new(move |ctx| {
                     let ctx = GenericRouterContext {
@@ -126,11 +172,25 @@ impl RouterContext {
 
             internal_route: |route| R::from_str(route).is_ok(),
 
+            prefetch: prefetch_route::<R>,
+
             site_map: R::SITE_MAP,
+
+            locales: R::LOCALES,
+
+            transition: cfg.transition,
+
+            current_route: history().current_route(),
+
+            blockers: Vec::new(),
+            next_blocker_id: 0,
+            unload_blockers: HashSet::new(),
+            proceeding: false,
         };
 
         // set the updater
         history().updater(Arc::new(move || {
+            external_update.store(true, Ordering::SeqCst);
             for &rc in subscribers.lock().unwrap().iter() {
                 rc.mark_dirty();
             }
@@ -163,19 +223,43 @@ impl RouterContext {
     ///
     /// Will fail silently if there is no previous location to go to.
     pub fn go_back(&self) {
-        history().go_back();
-        self.change_route();
+        self.go(-1);
     }
 
     /// Go back to the next location.
     ///
     /// Will fail silently if there is no next location to go to.
     pub fn go_forward(&self) {
-        history().go_forward();
+        self.go(1);
+    }
+
+    fn go(&self, delta: isize) {
+        let history = history();
+        let from = history.current_route();
+        // Check the blockers before moving if the history knows where it is going
+        if let Some(to) = history.route_at(delta) {
+            if from != to && self.navigation_blocked(&from, Some(&to), PendingNavigation::Go(delta))
+            {
+                return;
+            }
+            history.go(delta);
+            self.change_route();
+            return;
+        }
+        history.go(delta);
+        let to = history.current_route();
+        // Histories that move asynchronously are checked when they report the update
+        if from != to && self.navigation_blocked(&from, Some(&to), PendingNavigation::Go(delta)) {
+            history.go(-delta);
+            return;
+        }
         self.change_route();
     }
 
     pub(crate) fn push_any(&self, target: NavigationTarget) -> Option<ExternalNavigationFailure> {
+        if self.target_blocked(&target, PendingNavigation::Push(target.clone())) {
+            return None;
+        }
         {
             let mut write = self.inner.write_unchecked();
             match target {
@@ -192,6 +276,9 @@ impl RouterContext {
     /// The previous location will be available to go back to.
     pub fn push(&self, target: impl Into<NavigationTarget>) -> Option<ExternalNavigationFailure> {
         let target = target.into();
+        if self.target_blocked(&target, PendingNavigation::Push(target.clone())) {
+            return None;
+        }
         {
             let mut write = self.inner.write_unchecked();
             match target {
@@ -206,6 +293,32 @@ impl RouterContext {
         self.change_route()
     }
 
+    /// Push a new location with serialized state attached to it.
+    ///
+    /// The state of external locations is ignored.
+    pub fn push_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: String,
+    ) -> Option<ExternalNavigationFailure> {
+        let target = target.into();
+        if self.target_blocked(
+            &target,
+            PendingNavigation::PushWithState(target.clone(), state.clone()),
+        ) {
+            return None;
+        }
+        {
+            let mut write = self.inner.write_unchecked();
+            match target {
+                NavigationTarget::Internal(p) => history().push_with_state(p, state),
+                NavigationTarget::External(e) => return write.external(e),
+            }
+        }
+
+        self.change_route()
+    }
+
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
@@ -214,6 +327,9 @@ impl RouterContext {
         target: impl Into<NavigationTarget>,
     ) -> Option<ExternalNavigationFailure> {
         let target = target.into();
+        if self.target_blocked(&target, PendingNavigation::Replace(target.clone())) {
+            return None;
+        }
         {
             let mut state = self.inner.write_unchecked();
             match target {
@@ -249,8 +365,18 @@ impl RouterContext {
     pub fn full_route_string(&self) -> String {
         let inner = self.inner.read();
         inner.subscribe_to_current_context();
-        let history = history();
-        history.current_route()
+        let external_update = inner.external_update.swap(false, Ordering::SeqCst);
+        drop(inner);
+        if external_update {
+            self.external_update();
+        }
+        self.inner.peek().current_route.clone()
+    }
+
+    /// The serialized state attached to the current location with [`RouterContext::push_with_state`].
+    pub fn current_state(&self) -> Option<String> {
+        self.inner.read().subscribe_to_current_context();
+        history().current_state()
     }
 
     /// The prefix that is currently active.
@@ -272,6 +398,15 @@ impl RouterContext {
         self.inner.read().site_map
     }
 
+    /// Get the locales of the router if its routes have a `#[locale]` attribute.
+    pub fn locales(&self) -> Option<Locales> {
+        self.inner.read().locales
+    }
+
+    pub(crate) fn transition(&self) -> Option<Transition> {
+        self.inner.read().transition
+    }
+
     pub(crate) fn render_error(&self) -> Option<Element> {
         let inner_write = self.inner.write_unchecked();
         inner_write.subscribe_to_current_context();
@@ -282,11 +417,9 @@ impl RouterContext {
     }
 
     fn change_route(&self) -> Option<ExternalNavigationFailure> {
-        let self_read = self.inner.read();
-        if let Some(callback) = &self_read.routing_callback {
+        let callback = self.inner.read().routing_callback.clone();
+        if let Some(callback) = callback {
             let myself = *self;
-            let callback = callback.clone();
-            drop(self_read);
             if let Some(new) = callback(myself) {
                 let mut self_write = self.inner.write_unchecked();
                 match new {
@@ -299,14 +432,139 @@ impl RouterContext {
             }
         }
 
+        self.inner.write_unchecked().current_route = history().current_route();
         self.inner.read().update_subscribers();
 
         None
     }
 
+    /// Handle an update from outside the router, like the browser back button. The subscribers were already marked
+    /// dirty by the history updater.
+    fn external_update(&self) {
+        let history = history();
+        let from = self.inner.peek().current_route.clone();
+        let to = history.current_route();
+        if from != to {
+            // Undo the update if it is blocked. If the history doesn't know how far it moved, it can't be blocked
+            if let Some(delta) = history.last_update_delta() {
+                // The route is read while components render, so the blockers may write to signals here
+                use ::warnings::Warning;
+                let blocked = warnings::signal_write_in_component_body::allow(|| {
+                    self.navigation_blocked(&from, Some(&to), PendingNavigation::Go(delta))
+                });
+                if blocked {
+                    history.go(-delta);
+                    return;
+                }
+            }
+        }
+
+        self.inner.write_unchecked().current_route = to;
+    }
+
+    fn target_blocked(&self, target: &NavigationTarget, navigation: PendingNavigation) -> bool {
+        let from = history().current_route();
+        let to = match target {
+            NavigationTarget::Internal(route) => Some(route.as_str()),
+            NavigationTarget::External(_) => None,
+        };
+        self.navigation_blocked(&from, to, navigation)
+    }
+
+    /// Check if any navigation blocker blocks a navigation. The first blocker that blocks the navigation keeps it
+    /// until the user confirms or cancels it.
+    fn navigation_blocked(
+        &self,
+        from: &str,
+        to: Option<&str>,
+        navigation: PendingNavigation,
+    ) -> bool {
+        if std::mem::take(&mut self.inner.write_unchecked().proceeding) {
+            return false;
+        }
+        // Blockers may read the router, so they can't be called while the router is borrowed
+        let blockers: Vec<_> = self
+            .inner
+            .peek()
+            .blockers
+            .iter()
+            .map(|(_, blocker)| blocker.clone())
+            .collect();
+        blockers
+            .iter()
+            .any(|blocker| blocker(from, to, &navigation))
+    }
+
+    pub(crate) fn add_navigation_blocker(&self, blocker: NavigationBlockerCallback) -> usize {
+        let mut inner = self.inner.write_unchecked();
+        let id = inner.next_blocker_id;
+        inner.next_blocker_id += 1;
+        inner.blockers.push((id, blocker));
+        id
+    }
+
+    pub(crate) fn remove_navigation_blocker(&self, id: usize) {
+        self.inner
+            .write_unchecked()
+            .blockers
+            .retain(|(blocker, _)| *blocker != id);
+        self.set_unload_blocked(id, false);
+    }
+
+    /// Set whether a navigation blocker blocks the user from leaving the app on the current page
+    pub(crate) fn set_unload_blocked(&self, id: usize, blocked: bool) {
+        let mut inner = self.inner.write_unchecked();
+        let was_blocked = !inner.unload_blockers.is_empty();
+        match blocked {
+            true => inner.unload_blockers.insert(id),
+            false => inner.unload_blockers.remove(&id),
+        };
+        let is_blocked = !inner.unload_blockers.is_empty();
+        drop(inner);
+        if was_blocked != is_blocked {
+            history().block_unload(is_blocked);
+        }
+    }
+
+    /// Run a navigation that was blocked without checking the blockers again
+    pub(crate) fn proceed(&self, navigation: PendingNavigation) {
+        self.inner.write_unchecked().proceeding = true;
+        match navigation {
+            PendingNavigation::Push(target) => {
+                // The user already confirmed leaving the app
+                if let NavigationTarget::External(_) = target {
+                    history().block_unload(false);
+                }
+                self.push(target);
+            }
+            PendingNavigation::PushWithState(target, state) => {
+                if let NavigationTarget::External(_) = target {
+                    history().block_unload(false);
+                }
+                self.push_with_state(target, state);
+            }
+            PendingNavigation::Replace(target) => {
+                if let NavigationTarget::External(_) = target {
+                    history().block_unload(false);
+                }
+                self.replace(target);
+            }
+            PendingNavigation::Go(delta) => self.go(delta),
+        }
+        // Navigations that don't move, like going to the current entry, never check the blockers. The flag is
+        // cleared so it doesn't let the next navigation skip them
+        self.inner.write_unchecked().proceeding = false;
+    }
+
     pub(crate) fn internal_route(&self, route: &str) -> bool {
         (self.inner.read().internal_route)(route)
     }
+
+    /// Start loading an internal route before navigating to it. Returns the files the route needs
+    pub(crate) fn prefetch(&self, route: &str) -> Vec<PrefetchHint> {
+        let prefetch = self.inner.read().prefetch;
+        prefetch(route)
+    }
 }
 
 pub struct GenericRouterContext<R> {
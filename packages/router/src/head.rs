@@ -0,0 +1,125 @@
+//! # Route metadata
+//!
+//! Routes can declare the title and meta tags of their page with the `title` and `meta` arguments of the `#[route]`
+//! attribute instead of rendering [`document::Title`] and [`document::Meta`] in every page component. Parameters of the
+//! route can be used in both like in `format!`:
+//!
+//! ```rust
+//! use dioxus::prelude::*;
+//!
+//! #[derive(Clone, Routable, PartialEq, Debug)]
+//! enum Route {
+//!     #[route("/", title = "Home")]
+//!     Home {},
+//!     #[route(
+//!         "/blog/:id",
+//!         title = "Post {id}",
+//!         meta(name = "description", content = "Read post {id} of the blog"),
+//!         meta(property = "og:title", content = "Post {id}"),
+//!     )]
+//!     BlogPost { id: usize },
+//! }
+//!
+//! #[component]
+//! fn Home() -> Element {
+//!     rsx! { h1 { "Home" } }
+//! }
+//!
+//! #[component]
+//! fn BlogPost(id: usize) -> Element {
+//!     rsx! { h1 { "Post {id}" } }
+//! }
+//! ```
+//!
+//! The [`Router`](crate::components::Router) applies the head of the current route while it renders, so it is part of
+//! the head the server sends during server side rendering and static site generation. When the route changes, the meta
+//! tags of the previous route are removed and the title is replaced, or cleared if the new route doesn't have one.
+//!
+//! The head is declared on the route itself, so it is available without rendering the page with
+//! [`Routable::head`](crate::routable::Routable::head):
+//!
+//! ```rust
+//! # use dioxus::prelude::*;
+//! # #[derive(Clone, Routable, PartialEq, Debug)]
+//! # enum Route {
+//! #     #[route("/blog/:id", title = "Post {id}")]
+//! #     BlogPost { id: usize },
+//! # }
+//! # #[component]
+//! # fn BlogPost(id: usize) -> Element { VNode::empty() }
+//! let head = Route::BlogPost { id: 7 }.head();
+//! assert_eq!(head.title.as_deref(), Some("Post 7"));
+//! ```
+
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus_lib::{document, prelude::*};
+
+use crate::{hooks::use_route, routable::Routable};
+
+/// The title and meta tags of a route. The derive macro creates this from the `title` and `meta` arguments of the
+/// `#[route]` attribute.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteHead {
+    /// The title of the page
+    pub title: Option<String>,
+    /// The meta tags of the page
+    pub meta: Vec<RouteMeta>,
+}
+
+/// A meta tag of a route declared with `meta(..)`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteMeta {
+    /// The `property` attribute of the meta tag
+    pub property: Option<String>,
+    /// The `name` attribute of the meta tag
+    pub name: Option<String>,
+    /// The `charset` attribute of the meta tag
+    pub charset: Option<String>,
+    /// The `http-equiv` attribute of the meta tag
+    pub http_equiv: Option<String>,
+    /// The `content` attribute of the meta tag
+    pub content: Option<String>,
+}
+
+impl RouteMeta {
+    /// Get the props of the [`document::Meta`] component for the meta tag.
+    pub fn to_props(&self) -> document::MetaProps {
+        document::MetaProps::builder()
+            .property(self.property.clone())
+            .name(self.name.clone())
+            .charset(self.charset.clone())
+            .http_equiv(self.http_equiv.clone())
+            .content(self.content.clone())
+            .build()
+    }
+}
+
+/// Keep the title and meta tags of the document in sync with the head of the current route.
+pub(crate) fn RouteHeadTags<R: Routable + Clone>() -> Element {
+    let head = use_route::<R>().head();
+    let document = use_hook(document::document);
+    // The head of the route that was last applied to the document
+    let applied = use_hook(|| Rc::new(RefCell::new(RouteHead::default())));
+
+    // NOTE: We don't use use_effect here because the head needs to be applied on the server
+    let mut applied = applied.borrow_mut();
+    if head != *applied {
+        for meta in &applied.meta {
+            if !head.meta.contains(meta) {
+                document.remove_meta(meta.to_props());
+            }
+        }
+        if head.title != applied.title {
+            document.set_title(head.title.clone().unwrap_or_default());
+        }
+        for meta in &head.meta {
+            if !applied.meta.contains(meta) {
+                document.create_meta(meta.to_props());
+            }
+        }
+        *applied = head;
+    }
+
+    VNode::empty()
+}
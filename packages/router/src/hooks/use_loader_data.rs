@@ -0,0 +1,44 @@
+use dioxus_lib::prelude::*;
+
+use crate::loader::{LoaderData, RouteData};
+
+/// Get the data returned from the loader of the current route or the closest layout with a loader that returns `T`.
+///
+/// The route is only rendered after its loaders finish, so the data is always available.
+///
+/// # Panic
+/// - When the calling component is not rendered inside of a route or layout with a loader that returns `T`.
+///
+/// # Example
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[derive(Clone, Routable, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/", loader = || load_greeting())]
+///     Home {},
+/// }
+///
+/// async fn load_greeting() -> String {
+///     "Hello from the loader".to_string()
+/// }
+///
+/// #[component]
+/// fn Home() -> Element {
+///     let greeting: String = use_loader_data();
+///     rsx! { "{greeting}" }
+/// }
+/// ```
+#[must_use]
+pub fn use_loader_data<T: LoaderData>() -> T {
+    let data = use_hook(|| {
+        try_consume_context::<RouteData<T>>().unwrap_or_else(|| {
+            panic!(
+                "`use_loader_data::<{}>` must be called inside of a route or layout with a loader that returns that type",
+                std::any::type_name::<T>()
+            )
+        })
+    });
+    data.0
+        .cloned()
+        .expect("routes are only rendered after their loaders finish")
+}
@@ -0,0 +1,19 @@
+use dioxus_lib::prelude::*;
+
+use crate::locale::LocaleContext;
+
+/// A hook that provides the locale of the current route.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`] for routes with a `#[locale]` attribute.
+///
+/// [`Router`]: crate::components::Router
+pub fn use_locale() -> Memo<&'static str> {
+    use_hook(|| {
+        try_consume_context::<LocaleContext>()
+            .expect(
+                "`use_locale` must be called in a descendant of a Router for routes with a #[locale] attribute",
+            )
+            .0
+    })
+}
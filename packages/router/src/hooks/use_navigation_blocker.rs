@@ -0,0 +1,171 @@
+use std::rc::Rc;
+
+use dioxus_lib::prelude::*;
+
+use crate::{
+    components::child_router::consume_child_route_mapping,
+    contexts::{NavigationBlockerCallback, PendingNavigation},
+    prelude::{Routable, RouterContext},
+    utils::use_router_internal::use_router_internal,
+};
+
+/// A hook that blocks navigation away from the current page, for example while a form has unsaved changes.
+///
+/// `should_block` is called with the current route and the route the user is trying to navigate to. If it returns
+/// `true`, the navigation is blocked. This applies to pushes, replaces and moving through the history, including the
+/// back and forward buttons of the browser.
+///
+/// Blocked navigations are kept in the returned [`NavigationBlocker`] until you call [`NavigationBlocker::proceed`] or
+/// [`NavigationBlocker::reset`], so you can ask the user for confirmation with your own modal.
+///
+/// The target route is [`None`] when the user tries to leave the app. This covers external navigations through the
+/// router, and closing or reloading the page. Closing and reloading can't wait for your modal, so the platform asks
+/// the user instead: the browser shows its `beforeunload` prompt on web, and desktop shows a confirmation dialog
+/// before the window closes.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`] component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Clone, Routable, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/")]
+///     Editor {},
+///     #[route("/about")]
+///     About {},
+/// }
+///
+/// #[component]
+/// fn Editor() -> Element {
+///     let mut text = use_signal(String::new);
+///     let mut saved = use_signal(String::new);
+///     let blocker = use_navigation_blocker(move |_from: Route, _to| text() != saved());
+///
+///     rsx! {
+///         textarea { value: text, oninput: move |event| text.set(event.value()) }
+///         button { onclick: move |_| saved.set(text()), "Save" }
+///         Link { to: Route::About {}, "About" }
+///         if blocker.is_blocked() {
+///             div {
+///                 "You have unsaved changes. Leave anyway?"
+///                 button { onclick: move |_| blocker.proceed(), "Leave" }
+///                 button { onclick: move |_| blocker.reset(), "Stay" }
+///             }
+///         }
+///     }
+/// }
+/// # #[component]
+/// # fn About() -> Element { VNode::empty() }
+/// ```
+///
+/// [`Router`]: crate::components::Router
+pub fn use_navigation_blocker<R: Routable>(
+    mut should_block: impl FnMut(R, Option<R>) -> bool + 'static,
+) -> NavigationBlocker<R> {
+    let router = use_router_internal()
+        .expect("`use_navigation_blocker` must be called in a descendant of a Router component");
+    let should_block = use_callback(move |(from, to)| should_block(from, to));
+    let blocked = use_signal(|| None::<BlockedNavigation<R>>);
+
+    let id = use_hook(|| {
+        let mapping = consume_child_route_mapping::<R>();
+        let parse = move |route: &str| match &mapping {
+            Some(mapping) => mapping.parse_route_from_root_route(route),
+            None => R::from_str(route).ok(),
+        };
+        let blocker: NavigationBlockerCallback = Rc::new(move |from, to, navigation| {
+            // Routes that don't belong to this router can't be blocked
+            let Some(from) = parse(from) else {
+                return false;
+            };
+            let to = match to.map(&parse) {
+                Some(None) => return false,
+                Some(to) => to,
+                None => None,
+            };
+            if !should_block.call((from.clone(), to.clone())) {
+                return false;
+            }
+            let mut blocked = blocked;
+            blocked.set(Some(BlockedNavigation {
+                from,
+                to,
+                navigation: navigation.clone(),
+            }));
+            true
+        });
+        router.add_navigation_blocker(blocker)
+    });
+
+    // Keep track of whether leaving the app from the current page is blocked
+    use_effect(move || {
+        let blocked = should_block.call((router.current::<R>(), None));
+        router.set_unload_blocked(id, blocked);
+    });
+
+    use_drop(move || router.remove_navigation_blocker(id));
+
+    NavigationBlocker { router, blocked }
+}
+
+/// A handle to the navigation that is blocked by [`use_navigation_blocker`].
+pub struct NavigationBlocker<R: 'static> {
+    router: RouterContext,
+    blocked: Signal<Option<BlockedNavigation<R>>>,
+}
+
+impl<R> Clone for NavigationBlocker<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for NavigationBlocker<R> {}
+
+impl<R: Routable> NavigationBlocker<R> {
+    /// Get the navigation that is waiting for confirmation, if there is one.
+    pub fn blocked(&self) -> Option<BlockedNavigation<R>> {
+        self.blocked.cloned()
+    }
+
+    /// Check if a navigation is waiting for confirmation.
+    pub fn is_blocked(&self) -> bool {
+        self.blocked.read().is_some()
+    }
+
+    /// Run the navigation that is waiting for confirmation. Other navigation blockers are not checked again.
+    pub fn proceed(&self) {
+        let mut blocked = self.blocked;
+        if let Some(navigation) = blocked.take() {
+            self.router.proceed(navigation.navigation);
+        }
+    }
+
+    /// Cancel the navigation that is waiting for confirmation and stay on the current page.
+    pub fn reset(&self) {
+        let mut blocked = self.blocked;
+        blocked.set(None);
+    }
+}
+
+/// A navigation that was blocked by [`use_navigation_blocker`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockedNavigation<R> {
+    from: R,
+    to: Option<R>,
+    navigation: PendingNavigation,
+}
+
+impl<R> BlockedNavigation<R> {
+    /// The route the navigation started from.
+    pub fn from(&self) -> &R {
+        &self.from
+    }
+
+    /// The route the navigation goes to, or [`None`] if it leaves the app.
+    pub fn to(&self) -> Option<&R> {
+        self.to.as_ref()
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt::Display;
+
+use dioxus_history::history;
+use dioxus_lib::prelude::*;
+use urlencoding::{decode, encode};
+
+use crate::{
+    navigation::NavigationTarget, routable::FromQueryArgument,
+    utils::use_router_internal::use_router_internal,
+};
+
+/// A hook that keeps a signal in sync with a parameter in the query string of the current url.
+///
+/// The parameter is parsed with [`FromQueryArgument`] and falls back to the default value when it is missing or
+/// invalid. Writing to the signal formats the new value with [`Display`] and replaces the current url, so the
+/// parameter doesn't add entries to the history. Use [`use_query_param_push`] to push a new entry instead.
+///
+/// Other query parameters and the rest of the url are kept when the parameter changes. Components that read the
+/// signal only rerun when this parameter changes, not when other parts of the url change.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`] component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Clone, Routable, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/")]
+///     Posts {},
+/// }
+///
+/// #[component]
+/// fn Posts() -> Element {
+///     // The page is read from and written to `/?page=2`
+///     let mut page = use_query_param::<u32>("page");
+///
+///     rsx! {
+///         h1 { "Page {page}" }
+///         button { onclick: move |_| page += 1, "Next page" }
+///     }
+/// }
+/// ```
+///
+/// [`Router`]: crate::components::Router
+pub fn use_query_param<T>(name: &str) -> Signal<T>
+where
+    T: FromQueryArgument + Display + 'static,
+{
+    use_query_param_with_history(name, false)
+}
+
+/// A hook that keeps a signal in sync with a parameter in the query string of the current url. Writing to the signal
+/// pushes a new entry to the history, so the user can go back to the old value.
+///
+/// See [`use_query_param`] for more information.
+pub fn use_query_param_push<T>(name: &str) -> Signal<T>
+where
+    T: FromQueryArgument + Display + 'static,
+{
+    use_query_param_with_history(name, true)
+}
+
+fn use_query_param_with_history<T>(name: &str, push: bool) -> Signal<T>
+where
+    T: FromQueryArgument + Display + 'static,
+{
+    let router = use_router_internal()
+        .expect("`use_query_param` must be called in a descendant of a Router component");
+    let name = use_hook(|| name.to_string());
+
+    // Only notify when this parameter changes, not the rest of the url
+    let url_value = use_memo({
+        let name = name.clone();
+        move || query_param(&router.full_route_string(), &name)
+    });
+    let mut value = use_signal(|| parse_query_param::<T>(url_value.peek().as_deref()));
+    // The formatted value that the signal and the url last agreed on
+    let mut synced = use_hook(|| CopyValue::new(value.peek().to_string()));
+
+    // Update the signal when the parameter changes in the url
+    use_effect(move || {
+        let parsed = parse_query_param::<T>(url_value.read().as_deref());
+        let formatted = parsed.to_string();
+        if *synced.peek() != formatted {
+            synced.set(formatted);
+            value.set(parsed);
+        }
+    });
+
+    // Update the url when the signal is written to
+    use_effect(move || {
+        let formatted = value.read().to_string();
+        if *synced.peek() == formatted {
+            return;
+        }
+        let route = with_query_param(&history().current_route(), &name, &formatted);
+        let target = NavigationTarget::Internal(route.clone());
+        match push {
+            true => router.push(target),
+            false => router.replace(target),
+        };
+        if history().current_route() == route {
+            synced.set(formatted);
+        } else {
+            // A navigation blocker stopped the navigation, so the signal goes back to the value in the url. If the
+            // navigation proceeds later, the url change updates the signal again
+            use ::warnings::Warning;
+            let parsed = parse_query_param::<T>(url_value.peek().as_deref());
+            warnings::signal_read_and_write_in_reactive_scope::allow(|| value.set(parsed));
+        }
+    });
+
+    value
+}
+
+fn parse_query_param<T: FromQueryArgument>(value: Option<&str>) -> T {
+    value
+        .and_then(|value| T::from_query_argument(value).ok())
+        .unwrap_or_default()
+}
+
+/// Split a route into the part before the query, the query and the hash fragment with its `#`
+fn split_query(route: &str) -> (&str, &str, &str) {
+    let (route, hash) = match route.find('#') {
+        Some(index) => route.split_at(index),
+        None => (route, ""),
+    };
+    let (path, query) = route.split_once('?').unwrap_or((route, ""));
+    (path, query, hash)
+}
+
+fn param_name(param: &str) -> &str {
+    param.split_once('=').map_or(param, |(name, _)| name)
+}
+
+/// Get the decoded value of a query parameter in a route
+fn query_param(route: &str, name: &str) -> Option<String> {
+    let (_, query, _) = split_query(route);
+    query
+        .split('&')
+        .find(|param| decode(param_name(param)).is_ok_and(|param| param == name))
+        .map(|param| {
+            let value = param.split_once('=').map_or("", |(_, value)| value);
+            decode(value).map_or_else(|_| value.to_string(), |value| value.into_owned())
+        })
+}
+
+/// Set a query parameter in a route, keeping the position of the parameter if it already exists
+fn with_query_param(route: &str, name: &str, value: &str) -> String {
+    let (path, query, hash) = split_query(route);
+    let new_param = format!("{}={}", encode(name), encode(value));
+    let mut params: Vec<String> = Vec::new();
+    let mut replaced = false;
+    for param in query.split('&').filter(|param| !param.is_empty()) {
+        if decode(param_name(param)).is_ok_and(|param| param == name) {
+            if !replaced {
+                params.push(new_param.clone());
+                replaced = true;
+            }
+        } else {
+            params.push(param.to_string());
+        }
+    }
+    if !replaced {
+        params.push(new_param);
+    }
+    format!("{path}?{}{hash}", params.join("&"))
+}
@@ -0,0 +1,31 @@
+use dioxus_lib::prelude::*;
+
+use crate::transition::RouteTransition;
+
+/// A hook that provides the transition of the route rendered by the closest [`Outlet`].
+///
+/// Routes are always [`Entered`](crate::transition::TransitionPhase::Entered) if the router doesn't have a
+/// [`Transition`](crate::transition::Transition). See the [`transition`](crate::transition) module for more
+/// information.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn Page() -> Element {
+///     let transition = use_route_transition();
+///     rsx! {
+///         div {
+///             class: if transition.is_exiting() { "slide-out" } else { "slide-in" },
+///             // End the transition once the animation is done
+///             onanimationend: move |_| transition.finish(),
+///             "Page"
+///         }
+///     }
+/// }
+/// ```
+///
+/// [`Outlet`]: crate::components::Outlet
+pub fn use_route_transition() -> RouteTransition {
+    use_hook(|| try_consume_context::<RouteTransition>().unwrap_or_else(RouteTransition::entered))
+}
@@ -0,0 +1,259 @@
+//! # Lazy routes
+//!
+//! Routes with a `#[lazy]` attribute are split into a separate WASM module that the browser only downloads when the
+//! user navigates to the route, so rarely visited routes don't make the initial bundle larger:
+//!
+//! ```rust
+//! use dioxus::prelude::*;
+//!
+//! #[derive(Clone, Routable, PartialEq, Debug)]
+//! enum Route {
+//!     #[route("/")]
+//!     Home {},
+//!     #[lazy]
+//!     #[route("/settings")]
+//!     Settings {},
+//! }
+//!
+//! #[component]
+//! fn Home() -> Element {
+//!     rsx! {
+//!         // Hovering the link downloads the module of the settings route
+//!         Link { to: Route::Settings {}, prefetch: Prefetch::Hover, "Settings" }
+//!     }
+//! }
+//!
+//! #[component]
+//! fn Settings() -> Element {
+//!     rsx! { h1 { "Settings" } }
+//! }
+//! ```
+//!
+//! Splitting is enabled with the `wasm-split` feature and requires building the app with `dx`, which moves the code
+//! that is only used by the component of a lazy route into its own module. While the module of a route downloads,
+//! the route suspends, so the closest [`SuspenseBoundary`](dioxus_lib::prelude::SuspenseBoundary) shows its loading state.
+//! When the page is hydrated, the modules of the routes the server rendered are loaded before hydration starts, so
+//! those routes render the same html as the server without suspending.
+//!
+//! The component of a lazy route is rendered like any other route in builds without the `wasm-split` feature, and on
+//! platforms other than the web. Servers still add a `<link rel="modulepreload">` hint for the module of the current
+//! route to the head of the page, and [`Link`](crate::components::Link)s that prefetch a lazy route preload its
+//! module.
+
+use crate::prefetch::PrefetchHint;
+
+/// The url of the javascript module that loads the WASM module of a lazy route
+pub fn chunk_url(chunk: &str) -> String {
+    let base_path = dioxus_cli_config::base_path().unwrap_or_default();
+    let base_path = base_path.trim_matches('/');
+    match base_path.is_empty() {
+        true => format!("/wasm/chunks/{chunk}.js"),
+        false => format!("/{base_path}/wasm/chunks/{chunk}.js"),
+    }
+}
+
+/// Get the hints that preload the module of a lazy route. This is used by the derive macro.
+#[doc(hidden)]
+pub fn hints(chunk: &str) -> Vec<PrefetchHint> {
+    match cfg!(feature = "wasm-split") {
+        true => vec![PrefetchHint::module_preload(chunk_url(chunk))],
+        false => Vec::new(),
+    }
+}
+
+/// Render the component of a lazy route. This is used by the derive macro.
+///
+/// With splitting enabled, the component is rendered by an exported function that `dx` moves into the module of the
+/// route along with everything only it uses. The main module never calls the function directly. It calls it through
+/// the function table once the module of the route is loaded.
+#[cfg(all(feature = "wasm-split", target_arch = "wasm32"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! lazy_route {
+    ($chunk:literal, $route_ty:ty, $route:expr, $component:path, $props:expr, $render:expr) => {{
+        #[export_name = concat!("__dx_lazy_", $chunk)]
+        extern "C" fn __dx_lazy_entry(
+            route: *const $route_ty,
+            out: *mut $crate::exports::dioxus_lib::prelude::Element,
+        ) {
+            let props: fn(&$route_ty) -> _ = $props;
+            // SAFETY: The router passes a valid route and a valid place for the element
+            unsafe {
+                out.write(
+                    $crate::exports::dioxus_lib::prelude::ComponentFunction::rebuild(
+                        &$component,
+                        props(&*route),
+                    ),
+                )
+            }
+        }
+        $crate::lazy::render::<$route_ty>($chunk, ::std::any::type_name_of_val(&$component), $route)
+    }};
+}
+
+/// Render the component of a lazy route. This is used by the derive macro.
+#[cfg(not(all(feature = "wasm-split", target_arch = "wasm32")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! lazy_route {
+    ($chunk:literal, $route_ty:ty, $route:expr, $component:path, $props:expr, $render:expr) => {
+        $render
+    };
+}
+
+#[cfg(all(feature = "wasm-split", target_arch = "wasm32"))]
+pub use split::render;
+
+#[cfg(all(feature = "wasm-split", target_arch = "wasm32"))]
+mod split {
+    use std::mem::MaybeUninit;
+
+    use dioxus_lib::prelude::{
+        dioxus_core::{CapturedError, DynamicNode, VComponent},
+        *,
+    };
+    use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+
+    use crate::routable::Routable;
+
+    #[wasm_bindgen(inline_js = r#"
+export function load_chunk(chunk) {
+    if (!window.__dx_lazy) {
+        return Promise.reject(new Error("the app was not built with dx"));
+    }
+    return window.__dx_lazy.load(chunk);
+}
+
+export function loaded_slot(chunk) {
+    return window.__dx_lazy?.slots[chunk];
+}
+"#)]
+    extern "C" {
+        /// Load the module of a lazy route. The promise resolves to the slot of its entry in the function table
+        #[wasm_bindgen(js_name = load_chunk)]
+        fn load_chunk_promise(chunk: &str) -> js_sys::Promise;
+
+        /// The slot of the entry of a lazy route in the function table if its module is already loaded
+        fn loaded_slot(chunk: &str) -> Option<u32>;
+    }
+
+    async fn load_chunk(chunk: &'static str) -> Result<u32, String> {
+        let slot = wasm_bindgen_futures::JsFuture::from(load_chunk_promise(chunk))
+            .await
+            .map_err(error_message)?;
+        slot.as_f64()
+            .map(|slot| slot as u32)
+            .ok_or_else(|| "the module didn't return the slot of its entry".to_string())
+    }
+
+    fn error_message(error: JsValue) -> String {
+        match error.dyn_into::<js_sys::Error>() {
+            Ok(error) => error.message().into(),
+            Err(error) => format!("{error:?}"),
+        }
+    }
+
+    /// Render the component of a lazy route once the module of the route is loaded
+    #[doc(hidden)]
+    pub fn render<R: Routable>(chunk: &'static str, component: &'static str, route: R) -> Element {
+        rsx! { LazyRoute::<R> { chunk, component, route } }
+    }
+
+    #[derive(Props)]
+    struct LazyRouteProps<R: Routable> {
+        chunk: &'static str,
+        component: &'static str,
+        route: R,
+    }
+
+    impl<R: Routable> Clone for LazyRouteProps<R> {
+        fn clone(&self) -> Self {
+            Self {
+                chunk: self.chunk,
+                component: self.component,
+                route: self.route.clone(),
+            }
+        }
+    }
+
+    // Routes are not comparable, so the route always reruns with the outlet
+    impl<R: Routable> PartialEq for LazyRouteProps<R> {
+        fn eq(&self, _: &Self) -> bool {
+            false
+        }
+    }
+
+    #[allow(missing_docs)]
+    fn LazyRoute<R: Routable>(props: LazyRouteProps<R>) -> Element {
+        let chunk = props.chunk;
+        let resource = use_resource(move || load_chunk(chunk));
+        // Modules that are already loaded render without suspending. This includes the modules of the routes the
+        // server rendered, which are loaded before hydration starts
+        let slot = match loaded_slot(chunk) {
+            Some(slot) => slot,
+            None => resource.suspend()?.cloned().map_err(|err| {
+                RenderError::Aborted(CapturedError::from_display(format!(
+                    "Failed to load the module of the route: {err}"
+                )))
+            })?,
+        };
+
+        // Render the component in a scope with the name of the component, so it runs like any other component
+        let entry = ChunkEntryProps::<R> {
+            slot,
+            route: props.route.clone(),
+        };
+        let node = DynamicNode::Component(VComponent::new(ChunkEntry::<R>, entry, props.component));
+        rsx! { {node} }
+    }
+
+    #[derive(Props)]
+    struct ChunkEntryProps<R: Routable> {
+        slot: u32,
+        route: R,
+    }
+
+    impl<R: Routable> Clone for ChunkEntryProps<R> {
+        fn clone(&self) -> Self {
+            Self {
+                slot: self.slot,
+                route: self.route.clone(),
+            }
+        }
+    }
+
+    impl<R: Routable> PartialEq for ChunkEntryProps<R> {
+        fn eq(&self, _: &Self) -> bool {
+            false
+        }
+    }
+
+    #[allow(missing_docs)]
+    fn ChunkEntry<R: Routable>(props: ChunkEntryProps<R>) -> Element {
+        // The module of the route placed its entry at this slot of the function table when it was loaded. The entry is
+        // called through javascript, which checks that the slot holds a function
+        let table: js_sys::WebAssembly::Table = wasm_bindgen::function_table().unchecked_into();
+        let entry = table
+            .get(props.slot)
+            .ok()
+            .filter(|entry| entry.is_function())
+            .ok_or_else(|| {
+                RenderError::Aborted(CapturedError::from_display(
+                    "The module of the route didn't place its entry in the function table",
+                ))
+            })?;
+
+        // The entry reads the route and writes the element through the pointers it is passed
+        let mut element = MaybeUninit::<Element>::uninit();
+        let route = JsValue::from(&props.route as *const R as u32);
+        let out = JsValue::from(element.as_mut_ptr() as u32);
+        entry.call2(&JsValue::NULL, &route, &out).map_err(|err| {
+            RenderError::Aborted(CapturedError::from_display(format!(
+                "Failed to render the route: {}",
+                error_message(err)
+            )))
+        })?;
+        // SAFETY: The entry returned without an error, so it wrote the element
+        unsafe { element.assume_init() }
+    }
+}
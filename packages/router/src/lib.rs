@@ -5,8 +5,15 @@
 #![deny(missing_docs)]
 #![allow(non_snake_case)]
 
+pub mod head;
+pub mod lazy;
+pub mod loader;
+pub mod locale;
 pub mod navigation;
+pub mod prefetch;
 pub mod routable;
+pub mod sitemap;
+pub mod transition;
 
 /// Components interacting with the router.
 pub mod components {
@@ -53,6 +60,21 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_loader_data;
+    pub use use_loader_data::*;
+
+    mod use_navigation_blocker;
+    pub use use_navigation_blocker::*;
+
+    mod use_query_param;
+    pub use use_query_param::*;
+
+    mod use_locale;
+    pub use use_locale::*;
+
+    mod use_route_transition;
+    pub use use_route_transition::*;
 }
 
 pub use hooks::router;
@@ -65,7 +87,10 @@ pub mod prelude {
     };
     pub use crate::contexts::*;
     pub use crate::hooks::*;
+    pub use crate::loader::LoaderData;
+    pub use crate::locale::with_locale;
     pub use crate::navigation::*;
+    pub use crate::prefetch::Prefetch;
     pub use crate::routable::*;
     pub use crate::router_cfg::RouterConfig;
     pub use dioxus_router_macro::Routable;
@@ -88,5 +113,6 @@ mod utils {
 
 #[doc(hidden)]
 pub mod exports {
+    pub use dioxus_lib;
     pub use urlencoding;
 }
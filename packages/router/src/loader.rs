@@ -0,0 +1,370 @@
+//! # Route loaders
+//!
+//! Loaders fetch the data for a route before it is rendered. Routes and layouts declare a loader with the `loader`
+//! argument of the `#[route]` and `#[layout]` attributes. The loader is a closure that takes some of the parameters of
+//! the route by name and returns a future:
+//!
+//! ```rust, no_run
+//! use dioxus::prelude::*;
+//!
+//! #[derive(Clone, Routable, PartialEq, Debug)]
+//! enum Route {
+//!     #[nest("/user/:user_id")]
+//!         #[layout(UserFrame, loader = |user_id| load_user(user_id))]
+//!             #[route("/post/:post_id", loader = |post_id| load_post(post_id))]
+//!             Post { user_id: u32, post_id: u32 },
+//! }
+//!
+//! #[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! #[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+//! struct BlogPost {
+//!     title: String,
+//! }
+//!
+//! async fn load_user(user_id: u32) -> User {
+//!     User { name: format!("User {user_id}") }
+//! }
+//!
+//! async fn load_post(post_id: u32) -> BlogPost {
+//!     BlogPost { title: format!("Post {post_id}") }
+//! }
+//!
+//! #[component]
+//! fn UserFrame(user_id: u32) -> Element {
+//!     let user: User = use_loader_data();
+//!     rsx! {
+//!         h1 { "{user.name}" }
+//!         Outlet::<Route> {}
+//!     }
+//! }
+//!
+//! #[component]
+//! fn Post(user_id: u32, post_id: u32) -> Element {
+//!     let post: BlogPost = use_loader_data();
+//!     rsx! { h2 { "{post.title}" } }
+//! }
+//! ```
+//!
+//! Every loader for a navigation starts at the same time, and the route is only rendered once all of them finish.
+//! While the loaders are running, the route is suspended and the closest
+//! [`SuspenseBoundary`] shows its fallback.
+//!
+//! A loader only runs again when the parameters it depends on change. Navigating between two posts of the same user
+//! reuses the data of the user layout.
+//!
+//! ## Fullstack
+//!
+//! With the `fullstack` feature, loaders run on the server during server side rendering and their data is serialized
+//! into the HTML. The client reuses that data while hydrating instead of running the loaders again. The data returned
+//! from loaders must implement `Serialize` and `DeserializeOwned` when the feature is enabled.
+
+use std::{any::Any, future::Future, marker::PhantomData, pin::Pin, rc::Rc, time::Duration};
+
+use dioxus_lib::prelude::*;
+use futures_util::{
+    future::{join_all, Either, Shared},
+    FutureExt,
+};
+use web_time::Instant;
+
+use dioxus_history::history;
+
+use crate::{components::child_router::consume_child_route_mapping, routable::Routable};
+
+/// Data that can be returned from a route loader.
+#[cfg(feature = "fullstack")]
+pub trait LoaderData: Clone + serde::Serialize + serde::de::DeserializeOwned + 'static {}
+
+#[cfg(feature = "fullstack")]
+impl<T: Clone + serde::Serialize + serde::de::DeserializeOwned + 'static> LoaderData for T {}
+
+/// Data that can be returned from a route loader.
+#[cfg(not(feature = "fullstack"))]
+pub trait LoaderData: Clone + 'static {}
+
+#[cfg(not(feature = "fullstack"))]
+impl<T: Clone + 'static> LoaderData for T {}
+
+type LoaderFuture = Pin<Box<dyn Future<Output = Rc<dyn Any>>>>;
+
+/// How long the data of a prefetched route is kept if the user doesn't navigate to it
+const PREFETCH_EXPIRY: Duration = Duration::from_secs(30);
+
+/// The most loaders that are kept prefetched at once. The oldest prefetched loaders are dropped first
+const MAX_PREFETCHED: usize = 16;
+
+/// The loader for one level of a route. This is created by the `Routable` derive macro for every route and layout
+/// with a `loader`.
+pub struct LoaderEntry {
+    level: usize,
+    key: String,
+    future: LoaderFuture,
+    boundary: fn(usize, String, Element) -> Element,
+}
+
+impl LoaderEntry {
+    /// Create a new loader for the route or layout rendered at `level`.
+    ///
+    /// The key identifies the parameters the loader depends on. If the key of a level doesn't change between two
+    /// navigations, the data from the first navigation is reused.
+    pub fn new<R: Routable, T: LoaderData>(
+        level: usize,
+        key: String,
+        future: impl Future<Output = T> + 'static,
+    ) -> Self {
+        Self {
+            level,
+            key,
+            future: Box::pin(async move { Rc::new(future.await) as Rc<dyn Any> }),
+            boundary: |level, key, children| {
+                rsx! {
+                    LoaderBoundary::<R, T> { level, loader_key: key, children }
+                }
+            },
+        }
+    }
+
+    /// The level of the route the loader belongs to
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Wrap the element rendered at a level in a boundary that suspends until the loaders of the route finish
+    #[doc(hidden)]
+    pub fn wrap(loader: Option<Self>, element: Element) -> Element {
+        match loader {
+            Some(loader) => (loader.boundary)(loader.level, loader.key, element),
+            None => element,
+        }
+    }
+}
+
+/// The running loaders of the levels of the current route
+#[derive(Clone, Copy)]
+pub(crate) struct LoaderCache {
+    levels: CopyValue<Vec<Option<CachedLoader>>>,
+    /// Loaders of routes that were prefetched but not navigated to yet
+    prefetched: CopyValue<Vec<PrefetchedLoader>>,
+}
+
+struct CachedLoader {
+    key: String,
+    future: Shared<LoaderFuture>,
+}
+
+struct PrefetchedLoader {
+    level: usize,
+    loader: CachedLoader,
+    started: Instant,
+}
+
+impl PrefetchedLoader {
+    fn is_expired(&self) -> bool {
+        self.started.elapsed() > PREFETCH_EXPIRY
+    }
+}
+
+impl LoaderCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            levels: CopyValue::new(Vec::new()),
+            prefetched: CopyValue::new(Vec::new()),
+        }
+    }
+
+    /// Start every loader of the route that isn't already running and wait for all of them to finish. Returns the
+    /// data of the loader at `level`. If the loader at `level` is already running, only that loader is waited for.
+    ///
+    /// If the route replaces the data of any level, the loaders that were prefetched from the previous route and not
+    /// used are dropped.
+    fn load<R: Routable>(&self, route: &R, level: usize) -> impl Future<Output = Rc<dyn Any>> {
+        let mut levels = self.levels.write_unchecked();
+
+        // If the loader of this level is already running, the loaders of the other levels were started with it
+        let running = route.loader_at(level).and_then(|loader| {
+            let cached = levels.get(level)?.as_ref()?;
+            (cached.key == loader.key).then(|| cached.future.clone())
+        });
+        if let Some(future) = running {
+            return Either::Left(future);
+        }
+
+        let mut invalidated = false;
+        let futures: Vec<_> = route
+            .loaders()
+            .into_iter()
+            .map(|loader| {
+                if levels.len() <= loader.level {
+                    levels.resize_with(loader.level + 1, || None);
+                }
+                let cached = &mut levels[loader.level];
+                let future = match cached {
+                    Some(cached) if cached.key == loader.key => cached.future.clone(),
+                    _ => {
+                        invalidated = true;
+                        let future = self
+                            .take_prefetched(loader.level, &loader.key)
+                            .unwrap_or_else(|| loader.future.shared());
+                        *cached = Some(CachedLoader {
+                            key: loader.key,
+                            future: future.clone(),
+                        });
+                        future
+                    }
+                };
+                (loader.level, future)
+            })
+            .collect();
+        if invalidated {
+            self.prefetched.write_unchecked().clear();
+        }
+
+        Either::Right(async move {
+            let (loader_levels, futures): (Vec<_>, Vec<_>) = futures.into_iter().unzip();
+            let data = join_all(futures).await;
+            loader_levels
+                .into_iter()
+                .zip(data)
+                .find_map(|(loader_level, data)| (loader_level == level).then_some(data))
+                .expect("the loader of a level is part of the loaders of the route")
+        })
+    }
+
+    /// Start every loader of a route that isn't already running, without waiting for them. The data is kept until the
+    /// route is navigated to, until the next navigation, or until [`PREFETCH_EXPIRY`] passes.
+    pub(crate) fn prefetch<R: Routable>(&self, route: &R) {
+        let levels = self.levels.read();
+        let mut prefetched = self.prefetched.write_unchecked();
+        prefetched.retain(|prefetched| !prefetched.is_expired());
+        for loader in route.loaders() {
+            let running = levels
+                .get(loader.level)
+                .and_then(Option::as_ref)
+                .is_some_and(|cached| cached.key == loader.key)
+                || prefetched.iter().any(|prefetched| {
+                    prefetched.level == loader.level && prefetched.loader.key == loader.key
+                });
+            if running {
+                continue;
+            }
+
+            let future = loader.future.shared();
+            // Shared futures only make progress while they are polled
+            spawn({
+                let future = future.clone();
+                async move {
+                    future.await;
+                }
+            });
+            if prefetched.len() >= MAX_PREFETCHED {
+                prefetched.remove(0);
+            }
+            prefetched.push(PrefetchedLoader {
+                level: loader.level,
+                loader: CachedLoader {
+                    key: loader.key,
+                    future,
+                },
+                started: Instant::now(),
+            });
+        }
+    }
+
+    fn take_prefetched(&self, level: usize, key: &str) -> Option<Shared<LoaderFuture>> {
+        let mut prefetched = self.prefetched.write_unchecked();
+        let index = prefetched
+            .iter()
+            .position(|prefetched| prefetched.level == level && prefetched.loader.key == key)?;
+        let prefetched = prefetched.remove(index);
+        (!prefetched.is_expired()).then_some(prefetched.loader.future)
+    }
+}
+
+/// The data returned from the loader of a route or layout
+pub(crate) struct RouteData<T: 'static>(pub(crate) Resource<T>);
+
+impl<T> Clone for RouteData<T> {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+/// The props for [`LoaderBoundary`]
+#[derive(Props)]
+#[doc(hidden)]
+pub struct LoaderBoundaryProps<R: Routable, T: LoaderData> {
+    level: usize,
+    loader_key: ReadOnlySignal<String>,
+    children: Element,
+    #[props(default)]
+    route: PhantomData<(R, T)>,
+}
+
+impl<R: Routable, T: LoaderData> Clone for LoaderBoundaryProps<R, T> {
+    fn clone(&self) -> Self {
+        Self {
+            level: self.level,
+            loader_key: self.loader_key,
+            children: self.children.clone(),
+            route: PhantomData,
+        }
+    }
+}
+
+impl<R: Routable, T: LoaderData> PartialEq for LoaderBoundaryProps<R, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.level == other.level
+            && self.loader_key == other.loader_key
+            && self.children == other.children
+    }
+}
+
+/// A component that suspends until the loaders of the current route finish and provides the data of the loader at
+/// its level to its children.
+#[doc(hidden)]
+#[allow(missing_docs)]
+pub fn LoaderBoundary<R: Routable, T: LoaderData>(props: LoaderBoundaryProps<R, T>) -> Element {
+    let cache = use_hook(consume_context::<LoaderCache>);
+    let level = props.level;
+    let key = props.loader_key;
+
+    let load = move || {
+        // Rerun the loader only when the parameters it depends on change
+        key.read();
+        let route = current_route::<R>();
+        let data = cache.load(&route, level);
+        async move {
+            let data = data.await;
+            data.downcast_ref::<T>()
+                .expect("the data of a loader has the type of the loader")
+                .clone()
+        }
+    };
+
+    #[cfg(feature = "fullstack")]
+    let resource = dioxus_fullstack::prelude::use_server_future(load)?;
+    #[cfg(not(feature = "fullstack"))]
+    let resource = {
+        let resource = use_resource(load);
+        resource.suspend()?;
+        resource
+    };
+
+    use_context_provider(|| RouteData(resource));
+
+    props.children
+}
+
+/// Get the current route without subscribing to changes. The boundary only reloads when its key changes
+fn current_route<R: Routable>() -> R {
+    let route = history().current_route();
+    let route = match consume_child_route_mapping::<R>() {
+        Some(mapping) => mapping.parse_route_from_root_route(&route),
+        None => R::from_str(&route).ok(),
+    };
+    route.expect("route's display implementation must be parsable by FromStr")
+}
@@ -0,0 +1,226 @@
+//! # Localized routes
+//!
+//! The `#[locale]` attribute on a [`Routable`] enum adds a locale segment in front of every route, so the same route
+//! tree is served under `/en/...`, `/de/...` and so on without repeating any variants:
+//!
+//! ```rust
+//! use dioxus::prelude::*;
+//!
+//! #[derive(Clone, Routable, PartialEq, Debug)]
+//! #[locale("en", "de", "fr")]
+//! enum Route {
+//!     #[route("/")]
+//!     Home {},
+//!     #[route("/about")]
+//!     About {},
+//! }
+//!
+//! #[component]
+//! fn Home() -> Element {
+//!     let locale = use_locale();
+//!     rsx! {
+//!         h1 { "Home ({locale})" }
+//!         // Links keep the locale of the current route
+//!         Link { to: Route::About {}, "About" }
+//!         // Or switch to another locale
+//!         Link { to: Route::About {}, locale: "de", "Über uns" }
+//!     }
+//! }
+//!
+//! #[component]
+//! fn About() -> Element {
+//!     rsx! { h1 { "About" } }
+//! }
+//! ```
+//!
+//! By default the locale segment is required, and urls without one don't match any route. With
+//! `#[locale("en", "de", default = "en")]`, the segment becomes optional: urls without a locale use the default locale,
+//! and routes in the default locale are formatted without the segment.
+//!
+//! The locale is not a field of the route. Formatting a route with [`Display`] always uses the default locale, or the
+//! first locale if the segment is required. Navigating to a route moves it to the locale of the current url, so a route
+//! keeps its locale when you navigate to it. Use [`with_locale`] or the `locale` prop of
+//! [`Link`](crate::components::Link) to navigate to a route in another locale.
+//!
+//! The [`Router`](crate::components::Router) adds `<link rel="alternate" hreflang="..">` links for every locale of the
+//! current route to the head of the document, and replaces them when the route changes.
+//!
+//! Locales that are not declared in the `#[locale]` attribute are replaced with the default locale, or the first
+//! locale if the segment is required.
+
+use std::{borrow::Cow, cell::RefCell, fmt::Display, rc::Rc};
+
+use dioxus_history::{history, History};
+use dioxus_lib::{document, prelude::*};
+
+use crate::{hooks::router, navigation::NavigationTarget, routable::Routable};
+
+/// The locales of a [`Routable`] enum with a `#[locale]` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locales {
+    /// Every locale the routes are served in.
+    pub locales: &'static [&'static str],
+    /// The locale of urls without a locale segment. If this is [`None`], the locale segment is required.
+    pub default: Option<&'static str>,
+}
+
+impl Locales {
+    /// Split the locale segment from a route. Returns the locale and the rest of the route, or [`None`] if the route
+    /// doesn't have a locale segment and the segment is required.
+    pub fn split<'a>(&self, route: &'a str) -> Option<(&'static str, Cow<'a, str>)> {
+        let segment = route.strip_prefix('/').unwrap_or(route);
+        let end = segment.find(['/', '?', '#']).unwrap_or(segment.len());
+        let (locale, rest) = segment.split_at(end);
+        match self.locales.iter().find(|known| **known == locale) {
+            Some(&locale) => {
+                let rest = match rest.starts_with('/') {
+                    true => Cow::Borrowed(rest),
+                    false => Cow::Owned(format!("/{rest}")),
+                };
+                Some((locale, rest))
+            }
+            None => self.default.map(|default| (default, Cow::Borrowed(route))),
+        }
+    }
+
+    /// Get the locale of a route. Returns [`None`] if the route doesn't have a locale segment and the segment is
+    /// required.
+    pub fn locale_of(&self, route: &str) -> Option<&'static str> {
+        self.split(route).map(|(locale, _)| locale)
+    }
+
+    /// Move a route with or without a locale segment to another locale. If the locale is not one of
+    /// [`Locales::locales`], the route is moved to the [primary](Locales::primary) locale instead.
+    pub fn localize(&self, route: &str, locale: &str) -> String {
+        let locale = match self.locales.contains(&locale) {
+            true => locale,
+            false => {
+                tracing::warn!(
+                    "The locale '{locale}' is not one of the locales {}, using '{}' instead",
+                    self.locales.join(", "),
+                    self.primary()
+                );
+                self.primary()
+            }
+        };
+        let route = self
+            .split(route)
+            .map_or(Cow::Borrowed(route), |(_, route)| route);
+        match self.default == Some(locale) {
+            true => route.into_owned(),
+            false => format!("/{locale}{route}"),
+        }
+    }
+
+    /// The locale routes are formatted in: the default locale, or the first locale if the segment is required.
+    pub fn primary(&self) -> &'static str {
+        self.default
+            .or_else(|| self.locales.first().copied())
+            .unwrap_or_default()
+    }
+}
+
+/// Write the locale segment of the primary locale in front of a route. This is used by the derive macro.
+#[doc(hidden)]
+pub fn write_locale(locales: &Locales, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match locales.default {
+        Some(_) => Ok(()),
+        None => write!(f, "/{}", locales.primary()),
+    }
+}
+
+/// Move a formatted route to the locale of the current url. Outside of a router, the route is not changed.
+pub(crate) fn localize_to_current(locales: &Locales, route: String) -> String {
+    let current = try_consume_context::<Rc<dyn History>>()
+        .and_then(|history| locales.locale_of(&history.current_route()));
+    match current {
+        Some(locale) => locales.localize(&route, locale),
+        None => route,
+    }
+}
+
+/// The error when a route doesn't start with a locale segment and the segment is required.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingLocaleError {
+    /// The locales the route could start with.
+    pub locales: &'static [&'static str],
+    /// The route that was parsed.
+    pub route: String,
+}
+
+impl Display for MissingLocaleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Route '{}' does not start with one of the locales {}",
+            self.route,
+            self.locales.join(", ")
+        )
+    }
+}
+
+/// Get a navigation target for a route in a specific locale.
+///
+/// # Panic
+/// - When the route doesn't have a `#[locale]` attribute.
+pub fn with_locale<R: Routable>(route: R, locale: impl Display) -> NavigationTarget {
+    let locales = R::LOCALES.expect("`with_locale` requires a route with a #[locale] attribute");
+    match NavigationTarget::<String>::from(route) {
+        NavigationTarget::Internal(route) => {
+            NavigationTarget::Internal(locales.localize(&route, &locale.to_string()))
+        }
+        external => external,
+    }
+}
+
+/// The active locale, provided by routers for routes with a `#[locale]` attribute
+#[derive(Clone, Copy)]
+pub(crate) struct LocaleContext(pub(crate) Memo<&'static str>);
+
+/// Keep the `<link rel="alternate" hreflang="..">` links in the head in sync with the current route.
+pub(crate) fn LocaleAlternateLinks<R: Routable>() -> Element {
+    let Some(locales) = R::LOCALES else {
+        return VNode::empty();
+    };
+    // Subscribe to route changes
+    router().full_route_string();
+    let document = use_hook(document::document);
+    // The hreflang and href of the links that were last added to the document
+    let applied = use_hook(|| Rc::new(RefCell::new(Vec::<(String, String)>::new())));
+
+    let history = history();
+    let prefix = history.current_prefix().unwrap_or_default();
+    let route = history.current_route();
+    let default = locales.default.map(|default| ("x-default", default));
+    let alternates: Vec<_> = locales
+        .locales
+        .iter()
+        .map(|locale| (*locale, *locale))
+        .chain(default)
+        .map(|(hreflang, locale)| {
+            let href = format!("{prefix}{}", locales.localize(&route, locale));
+            (hreflang.to_string(), href)
+        })
+        .collect();
+
+    // NOTE: We don't use use_effect here because the links need to be added on the server
+    let mut applied = applied.borrow_mut();
+    if alternates != *applied {
+        let props = |(hreflang, href): &(String, String)| {
+            document::LinkProps::builder()
+                .rel("alternate".to_string())
+                .hreflang(hreflang.clone())
+                .href(href.clone())
+                .build()
+        };
+        for link in applied.iter().filter(|link| !alternates.contains(link)) {
+            document.remove_link(props(link));
+        }
+        for link in alternates.iter().filter(|link| !applied.contains(link)) {
+            document.create_link(props(link));
+        }
+        *applied = alternates;
+    }
+
+    VNode::empty()
+}
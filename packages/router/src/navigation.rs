@@ -7,7 +7,10 @@ use std::{
 
 use url::{ParseError, Url};
 
-use crate::{components::child_router::consume_child_route_mapping, routable::Routable, router};
+use crate::{
+    components::child_router::consume_child_route_mapping, locale::localize_to_current,
+    routable::Routable, router,
+};
 
 impl<R: Routable> From<R> for NavigationTarget {
     fn from(value: R) -> Self {
@@ -15,8 +18,11 @@ impl<R: Routable> From<R> for NavigationTarget {
         let mapping = consume_child_route_mapping();
         match mapping.as_ref() {
             Some(mapping) => NavigationTarget::Internal(mapping.format_route_as_root_route(value)),
-            // Otherwise, just use the internal route
-            None => NavigationTarget::Internal(value.to_string()),
+            // Otherwise, just use the internal route in the current locale
+            None => NavigationTarget::Internal(match R::LOCALES {
+                Some(locales) => localize_to_current(&locales, value.to_string()),
+                None => value.to_string(),
+            }),
         }
     }
 }
@@ -0,0 +1,98 @@
+//! # Prefetching
+//!
+//! A [`Link`](crate::components::Link) with a `prefetch` mode starts loading the route it links to before the user
+//! clicks it. Prefetching a route starts the [loaders](crate::loader) of the route and its layouts, and adds hints for
+//! the files the route needs to the head of the document so the browser can download them early.
+//!
+//! Routes declare the files they need with the `preload` argument of the `#[route]` attribute. Each file can be an
+//! asset from `asset!` or anything else that converts to a url with [`ToString`]:
+//!
+//! ```rust, no_run
+//! use dioxus::prelude::*;
+//!
+//! const HERO: &str = "/assets/hero.png";
+//!
+//! #[derive(Clone, Routable, PartialEq, Debug)]
+//! enum Route {
+//!     #[route("/")]
+//!     Home {},
+//!     #[route("/gallery", preload = [HERO])]
+//!     Gallery {},
+//! }
+//!
+//! #[component]
+//! fn Home() -> Element {
+//!     rsx! {
+//!         Link { to: Route::Gallery {}, prefetch: Prefetch::Hover, "Gallery" }
+//!     }
+//! }
+//!
+//! #[component]
+//! fn Gallery() -> Element {
+//!     rsx! { img { src: HERO } }
+//! }
+//! ```
+//!
+//! During server side rendering, the hints of links that prefetch on [`Prefetch::Render`] are part of the head of the
+//! page the server sends.
+
+use dioxus_lib::prelude::*;
+
+use crate::{loader::LoaderCache, routable::Routable};
+
+/// When a [`Link`](crate::components::Link) starts loading the route it links to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prefetch {
+    /// Prefetch the route when the pointer moves over the link or the link is focused.
+    Hover,
+    /// Prefetch the route when the link scrolls into view.
+    Viewport,
+    /// Prefetch the route as soon as the link is rendered.
+    Render,
+}
+
+/// A file a route needs that the browser can download before the user navigates to the route.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrefetchHint {
+    rel: &'static str,
+    href: String,
+}
+
+impl PrefetchHint {
+    /// A file that is fetched with a `<link rel="prefetch">` hint, like an image or a stylesheet.
+    pub fn prefetch(href: impl ToString) -> Self {
+        Self {
+            rel: "prefetch",
+            href: href.to_string(),
+        }
+    }
+
+    /// A javascript module that is fetched and compiled with a `<link rel="modulepreload">` hint.
+    pub fn module_preload(href: impl ToString) -> Self {
+        Self {
+            rel: "modulepreload",
+            href: href.to_string(),
+        }
+    }
+
+    /// The `rel` attribute of the hint.
+    pub fn rel(&self) -> &'static str {
+        self.rel
+    }
+
+    /// The url of the file.
+    pub fn href(&self) -> &str {
+        &self.href
+    }
+}
+
+/// Start the loaders of a route and get the files it needs. Routes that don't belong to the router are ignored
+pub(crate) fn prefetch_route<R: Routable>(route: &str) -> Vec<PrefetchHint> {
+    let Ok(route) = R::from_str(route) else {
+        return Vec::new();
+    };
+    if let Some(cache) = try_consume_context::<LoaderCache>() {
+        cache.prefetch(&route);
+    }
+    route.prefetch_hints()
+}
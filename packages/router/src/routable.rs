@@ -7,6 +7,11 @@ use std::iter::FlatMap;
 use std::slice::Iter;
 use std::{fmt::Display, str::FromStr};
 
+use crate::head::RouteHead;
+use crate::loader::LoaderEntry;
+use crate::locale::Locales;
+use crate::prefetch::PrefetchHint;
+
 /// An error that occurs when parsing a route.
 #[derive(Debug, PartialEq)]
 pub struct RouteParseError<E: Display> {
@@ -596,9 +601,48 @@ pub trait Routable: FromStr + Display + Clone + 'static {
     /// The error that can occur when parsing a route.
     const SITE_MAP: &'static [SiteMapSegment];
 
+    /// The locales of the routes if the enum has a `#[locale]` attribute. See the [`locale`](crate::locale) module
+    /// for more information.
+    const LOCALES: Option<Locales> = None;
+
     /// Render the route at the given level
     fn render(&self, level: usize) -> Element;
 
+    /// Get the loaders of the route and every layout it is rendered in. The derive macro implements this for routes
+    /// and layouts with a `loader`. See the [`loader`](crate::loader) module for more information.
+    fn loaders(&self) -> Vec<LoaderEntry> {
+        Vec::new()
+    }
+
+    /// Get the loader of the route or layout rendered at the given level, if it has one. The derive macro implements
+    /// this so rendering a level doesn't create the loaders of every other level.
+    fn loader_at(&self, level: usize) -> Option<LoaderEntry> {
+        self.loaders()
+            .into_iter()
+            .find(|loader| loader.level() == level)
+    }
+
+    /// Get the files the route needs, so they can be downloaded before the user navigates to the route. The derive
+    /// macro implements this for routes with a `preload` argument. See the [`prefetch`](crate::prefetch) module for
+    /// more information.
+    fn prefetch_hints(&self) -> Vec<PrefetchHint> {
+        Vec::new()
+    }
+
+    /// Get the title and meta tags of the route. The derive macro implements this for routes with a `title` or `meta`
+    /// argument. See the [`head`](crate::head) module for more information.
+    fn head(&self) -> RouteHead {
+        RouteHead::default()
+    }
+
+    /// Get a key for the layout or route rendered at the given level. Outlets with a
+    /// [`Transition`](crate::transition::Transition) only transition when the key at their level changes. The derive
+    /// macro implements this for every route.
+    fn level_key(&self, level: usize) -> &'static str {
+        _ = level;
+        ""
+    }
+
     /// Checks if this route is a child of the given route.
     ///
     /// # Example
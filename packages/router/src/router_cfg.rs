@@ -1,3 +1,4 @@
+use crate::transition::Transition;
 use crate::{components::FailureExternalNavigation, prelude::*};
 use dioxus_lib::prelude::*;
 use std::sync::Arc;
@@ -29,6 +30,7 @@ use std::sync::Arc;
 pub struct RouterConfig<R> {
     pub(crate) failure_external_navigation: fn() -> Element,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) transition: Option<Transition>,
 }
 
 impl<R> Default for RouterConfig<R> {
@@ -36,6 +38,7 @@ impl<R> Default for RouterConfig<R> {
         Self {
             failure_external_navigation: FailureExternalNavigation,
             on_update: None,
+            transition: None,
         }
     }
 }
@@ -77,4 +80,14 @@ where
             ..self
         }
     }
+
+    /// How outlets animate between routes. See the [`transition`](crate::transition) module for more information.
+    ///
+    /// Defaults to [`None`], which replaces the old route right away.
+    pub fn transition(self, transition: Transition) -> Self {
+        Self {
+            transition: Some(transition),
+            ..self
+        }
+    }
 }
@@ -0,0 +1,290 @@
+//! Generate a `sitemap.xml` from the routes of a [`Routable`] enum.
+
+use std::fmt::{Display, Write};
+
+use crate::routable::{Routable, SegmentType};
+
+/// How often the page at a url is likely to change. This is a hint for search engines and does not affect how often they crawl the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeFrequency {
+    /// The page changes every time it is accessed
+    Always,
+    /// The page changes about once an hour
+    Hourly,
+    /// The page changes about once a day
+    Daily,
+    /// The page changes about once a week
+    Weekly,
+    /// The page changes about once a month
+    Monthly,
+    /// The page changes about once a year
+    Yearly,
+    /// The page is archived and never changes
+    Never,
+}
+
+impl Display for ChangeFrequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ChangeFrequency::Always => "always",
+            ChangeFrequency::Hourly => "hourly",
+            ChangeFrequency::Daily => "daily",
+            ChangeFrequency::Weekly => "weekly",
+            ChangeFrequency::Monthly => "monthly",
+            ChangeFrequency::Yearly => "yearly",
+            ChangeFrequency::Never => "never",
+        })
+    }
+}
+
+/// A single url in a [`SiteMap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteMapEntry {
+    /// The path of the page relative to the base url of the site. For example `/blog/1`
+    pub path: String,
+    /// The date the page was last modified in the [W3C Datetime](https://www.w3.org/TR/NOTE-datetime) format. For example `2024-11-04`
+    pub lastmod: Option<String>,
+    /// How often the page is likely to change
+    pub changefreq: Option<ChangeFrequency>,
+    /// The priority of the page relative to other pages on the site between `0.0` and `1.0`. Priorities outside of that
+    /// range are left out of the sitemap
+    pub priority: Option<f32>,
+}
+
+impl SiteMapEntry {
+    /// Create a new entry for a path without any annotations
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            lastmod: None,
+            changefreq: None,
+            priority: None,
+        }
+    }
+
+    /// Set the date the page was last modified in the [W3C Datetime](https://www.w3.org/TR/NOTE-datetime) format
+    pub fn lastmod(mut self, lastmod: impl Into<String>) -> Self {
+        self.lastmod = Some(lastmod.into());
+        self
+    }
+
+    /// Set how often the page is likely to change
+    pub fn changefreq(mut self, changefreq: ChangeFrequency) -> Self {
+        self.changefreq = Some(changefreq);
+        self
+    }
+
+    /// Set the priority of the page relative to other pages on the site
+    ///
+    /// # Panics
+    /// - When the priority is not between `0.0` and `1.0`
+    pub fn priority(mut self, priority: f32) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&priority),
+            "the priority of a sitemap entry must be between 0.0 and 1.0, found {priority}"
+        );
+        self.priority = Some(priority);
+        self
+    }
+}
+
+/// A sitemap that can be serialized into the [sitemap xml format](https://www.sitemaps.org/protocol.html).
+///
+/// Static routes are read from [`Routable::SITE_MAP`] automatically. Routes with dynamic or catch all segments are filled in by
+/// the [`SiteMapBuilder::dynamic_routes`] hook, which is called with the pattern of the route.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_router::sitemap::*;
+///
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/blog/:id")]
+///     Blog { id: usize },
+/// }
+///
+/// # #[component] fn Home() -> Element { VNode::empty() }
+/// # #[component] fn Blog(id: usize) -> Element { VNode::empty() }
+/// let posts = [(1, "2024-11-01"), (2, "2024-11-04")];
+///
+/// let sitemap = SiteMap::builder::<Route>("https://example.com")
+///     // Fill in the dynamic segments of the blog route from the list of posts
+///     .dynamic_routes(|pattern| match pattern {
+///         "/blog/:id" => posts
+///             .iter()
+///             .map(|(id, updated)| SiteMapEntry::new(Route::Blog { id: *id }.to_string()).lastmod(*updated))
+///             .collect(),
+///         _ => Vec::new(),
+///     })
+///     // Annotate every entry
+///     .annotate(|entry| entry.changefreq = Some(ChangeFrequency::Weekly))
+///     .build();
+///
+/// assert_eq!(sitemap.entries().len(), 3);
+/// assert!(sitemap.to_xml().contains("<loc>https://example.com/blog/2</loc>"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteMap {
+    base_url: String,
+    entries: Vec<SiteMapEntry>,
+}
+
+impl SiteMap {
+    /// Create an empty sitemap for a site hosted at the base url
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Create a builder that fills the sitemap with the routes of a [`Routable`] enum
+    pub fn builder<'a, R: Routable>(base_url: impl Into<String>) -> SiteMapBuilder<'a> {
+        SiteMapBuilder {
+            sitemap: Self::new(base_url),
+            static_routes: R::static_routes()
+                .into_iter()
+                .map(|route| route.to_string())
+                .collect(),
+            patterns: R::flatten_site_map()
+                .filter(|segments| {
+                    segments.iter().any(|segment| {
+                        matches!(segment, SegmentType::Dynamic(_) | SegmentType::CatchAll(_))
+                    })
+                })
+                .map(|segments| route_pattern(&segments))
+                .collect(),
+            dynamic_routes: None,
+            annotate: None,
+        }
+    }
+
+    /// Add an entry to the sitemap
+    pub fn push(&mut self, entry: SiteMapEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Get the entries in the sitemap
+    pub fn entries(&self) -> &[SiteMapEntry] {
+        &self.entries
+    }
+
+    /// Get the base url every path in the sitemap is relative to
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Serialize the sitemap into the sitemap xml format
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for entry in &self.entries {
+            let path = entry.path.trim_start_matches('/');
+            let loc = format!("{}/{}", self.base_url, path);
+            _ = writeln!(xml, "  <url>\n    <loc>{}</loc>", escape_xml(&loc));
+            if let Some(lastmod) = &entry.lastmod {
+                _ = writeln!(xml, "    <lastmod>{}</lastmod>", escape_xml(lastmod));
+            }
+            if let Some(changefreq) = entry.changefreq {
+                _ = writeln!(xml, "    <changefreq>{changefreq}</changefreq>");
+            }
+            if let Some(priority) = entry
+                .priority
+                .filter(|priority| (0.0..=1.0).contains(priority))
+            {
+                // The debug format keeps every digit of the priority and always includes the decimal point
+                _ = writeln!(xml, "    <priority>{priority:?}</priority>");
+            }
+            xml.push_str("  </url>\n");
+        }
+        xml.push_str("</urlset>\n");
+        xml
+    }
+}
+
+impl Display for SiteMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_xml())
+    }
+}
+
+/// A builder for a [`SiteMap`] created with [`SiteMap::builder`]
+#[allow(clippy::type_complexity)]
+pub struct SiteMapBuilder<'a> {
+    sitemap: SiteMap,
+    static_routes: Vec<String>,
+    patterns: Vec<String>,
+    dynamic_routes: Option<Box<dyn FnMut(&str) -> Vec<SiteMapEntry> + 'a>>,
+    annotate: Option<Box<dyn FnMut(&mut SiteMapEntry) + 'a>>,
+}
+
+impl<'a> SiteMapBuilder<'a> {
+    /// Set the hook that fills in routes with dynamic or catch all segments. The hook is called once for each route with the
+    /// pattern of the route, for example `/blog/:id` or `/docs/:..path`, and returns the entries for that route.
+    ///
+    /// Routes with dynamic segments are left out of the sitemap if this hook is not set.
+    pub fn dynamic_routes(mut self, hook: impl FnMut(&str) -> Vec<SiteMapEntry> + 'a) -> Self {
+        self.dynamic_routes = Some(Box::new(hook));
+        self
+    }
+
+    /// Set a function that is called with every entry in the sitemap before it is added. This can be used to set the
+    /// default annotations of static routes.
+    pub fn annotate(mut self, annotate: impl FnMut(&mut SiteMapEntry) + 'a) -> Self {
+        self.annotate = Some(Box::new(annotate));
+        self
+    }
+
+    /// Build the sitemap
+    pub fn build(mut self) -> SiteMap {
+        let mut entries: Vec<_> = self
+            .static_routes
+            .into_iter()
+            .map(SiteMapEntry::new)
+            .collect();
+        if let Some(dynamic_routes) = &mut self.dynamic_routes {
+            for pattern in &self.patterns {
+                entries.extend(dynamic_routes(pattern));
+            }
+        }
+        for mut entry in entries {
+            if let Some(annotate) = &mut self.annotate {
+                annotate(&mut entry);
+            }
+            self.sitemap.push(entry);
+        }
+        self.sitemap
+    }
+}
+
+/// Format the segments of a route as a pattern like `/blog/:id`
+fn route_pattern(segments: &[SegmentType]) -> String {
+    let pattern = segments
+        .iter()
+        .filter(|segment| !matches!(segment, SegmentType::Static("")))
+        .map(|segment| segment.to_string())
+        .collect::<String>();
+    if pattern.is_empty() {
+        "/".to_string()
+    } else {
+        pattern
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,590 @@
+//! # Route transitions
+//!
+//! By default, an [`Outlet`](crate::components::Outlet) replaces the old route with the new route as soon as the
+//! user navigates. With a [`Transition`] in the [`RouterConfig`](crate::prelude::RouterConfig), the outlet keeps the
+//! old route mounted next to the new route while it animates out:
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use dioxus::prelude::*;
+//! use dioxus_router::transition::Transition;
+//!
+//! #[derive(Clone, Routable, PartialEq, Debug)]
+//! enum Route {
+//!     #[route("/")]
+//!     Home {},
+//!     #[route("/about")]
+//!     About {},
+//! }
+//!
+//! fn App() -> Element {
+//!     rsx! {
+//!         Router::<Route> {
+//!             config: || RouterConfig::default().transition(Transition::duration(Duration::from_millis(300)))
+//!         }
+//!     }
+//! }
+//!
+//! #[component]
+//! fn Home() -> Element {
+//!     let transition = use_route_transition();
+//!     rsx! {
+//!         div {
+//!             class: if transition.is_exiting() { "page fade-out" } else { "page fade-in" },
+//!             "Home"
+//!         }
+//!     }
+//! }
+//!
+//! #[component]
+//! fn About() -> Element {
+//!     rsx! { div { class: "page fade-in", "About" } }
+//! }
+//! ```
+//!
+//! Every route the outlet renders can read its [`TransitionPhase`] with
+//! [`use_route_transition`](crate::hooks::use_route_transition). The new route is
+//! [`Entering`](TransitionPhase::Entering) and the old route is [`Exiting`](TransitionPhase::Exiting) until the
+//! transition ends, then the old route is unmounted and the new route is [`Entered`](TransitionPhase::Entered).
+//! A transition ends after its duration, when the signal passed to [`Transition::until`] becomes true, or when a route
+//! calls [`RouteTransition::finish`], for example at the end of a css animation.
+//!
+//! Outlets only transition when the layout or route rendered at their level changes. A layout that stays mounted
+//! between two of its child routes doesn't animate, but the outlet inside of it does. Outlets inside an exiting route
+//! keep rendering the old route.
+//!
+//! On the web, [`Transition::view_transition`] animates between routes with the
+//! [View Transitions API](https://developer.mozilla.org/en-US/docs/Web/API/View_Transition_API) instead. The browser
+//! takes a snapshot of the old route and animates it to the new route with css, so the old route is unmounted right
+//! away. Browsers and renderers without the API fall back to the rest of the transition.
+
+use std::{sync::Arc, time::Duration};
+
+use dioxus_lib::prelude::*;
+use futures_channel::oneshot;
+use futures_util::StreamExt;
+
+use crate::routable::Routable;
+
+/// How outlets animate between routes. See the [`transition`](crate::transition) module for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Transition {
+    exit: Exit,
+    view_transition: bool,
+}
+
+/// How long the old route stays mounted
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Exit {
+    #[default]
+    Immediate,
+    After(Duration),
+    Until(ReadOnlySignal<bool>),
+    Finished,
+}
+
+impl Transition {
+    /// Keep the old route mounted for a duration after navigating, or until [`RouteTransition::finish`] is called.
+    pub fn duration(duration: Duration) -> Self {
+        Self {
+            exit: Exit::After(duration),
+            ..Default::default()
+        }
+    }
+
+    /// Keep the old route mounted until the signal is true, or until [`RouteTransition::finish`] is called. This is
+    /// useful for routes that should stay visible until the data or assets of the new route are ready.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_router::transition::Transition;
+    /// # #[derive(Clone, Routable, PartialEq, Debug)]
+    /// # enum Route {
+    /// #     #[route("/")]
+    /// #     Home {},
+    /// # }
+    /// # #[component]
+    /// # fn Home() -> Element { VNode::empty() }
+    /// fn App() -> Element {
+    ///     let images_loaded = use_signal(|| false);
+    ///     rsx! {
+    ///         Router::<Route> {
+    ///             config: move || RouterConfig::default().transition(Transition::until(images_loaded.into()))
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn until(condition: ReadOnlySignal<bool>) -> Self {
+        Self {
+            exit: Exit::Until(condition),
+            ..Default::default()
+        }
+    }
+
+    /// Keep the old route mounted until [`RouteTransition::finish`] is called.
+    pub fn manual() -> Self {
+        Self {
+            exit: Exit::Finished,
+            ..Default::default()
+        }
+    }
+
+    /// Animate between routes with the View Transitions API when the renderer supports it. Otherwise, the outlet
+    /// falls back to the rest of the transition.
+    pub fn view_transition(self) -> Self {
+        Self {
+            view_transition: true,
+            ..self
+        }
+    }
+}
+
+/// The phase of the route an outlet renders during a [`Transition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionPhase {
+    /// The route was navigated to and the transition hasn't ended yet.
+    Entering,
+    /// The route is the current route and no transition is running.
+    Entered,
+    /// The route was navigated away from and is unmounted when the transition ends.
+    Exiting,
+}
+
+/// The transition of the route rendered by the closest outlet, returned by
+/// [`use_route_transition`](crate::hooks::use_route_transition).
+#[derive(Clone, Copy)]
+pub struct RouteTransition {
+    phase: ReadOnlySignal<TransitionPhase>,
+    finish: Callback,
+}
+
+impl RouteTransition {
+    /// A transition of a route that is always entered
+    pub(crate) fn entered() -> Self {
+        Self {
+            phase: ReadOnlySignal::new(Signal::new(TransitionPhase::Entered)),
+            finish: Callback::new(|_| {}),
+        }
+    }
+
+    /// Get the phase of the route. Reading the phase subscribes the current component to changes.
+    pub fn phase(&self) -> TransitionPhase {
+        *self.phase.read()
+    }
+
+    /// Check if the route was navigated to and the transition is still running.
+    pub fn is_entering(&self) -> bool {
+        self.phase() == TransitionPhase::Entering
+    }
+
+    /// Check if the route was navigated away from and the transition is still running.
+    pub fn is_exiting(&self) -> bool {
+        self.phase() == TransitionPhase::Exiting
+    }
+
+    /// End the running transition of the outlet and unmount the old route.
+    pub fn finish(&self) {
+        self.finish.call(());
+    }
+}
+
+/// Start a view transition and switch the routes in its update callback
+const START_VIEW_TRANSITION: &str = r#"
+if (document.startViewTransition) {
+    const transition = document.startViewTransition(async () => {
+        // The browser captured the old route, switch to the new route
+        dioxus.send(true);
+        // Wait until the new route is in the document
+        await dioxus.recv();
+    });
+    await transition.finished.catch(() => {});
+    dioxus.send(true);
+} else {
+    dioxus.send(false);
+}
+"#;
+
+/// The route the outlets inside an exiting route keep rendering
+struct FrozenRoute<R: 'static>(CopyValue<Option<R>>);
+
+impl<R> Clone for FrozenRoute<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for FrozenRoute<R> {}
+
+/// A route rendered by an outlet
+struct Slot<R> {
+    id: usize,
+    key: &'static str,
+    route: R,
+    phase: TransitionPhase,
+}
+
+enum ViewTransition {
+    Idle,
+    /// Waiting for the browser to capture the old route
+    Starting,
+    /// The browser captured the old route if the renderer supports view transitions
+    Ready(bool),
+}
+
+struct TransitionState<R> {
+    slots: Vec<Slot<R>>,
+    next_id: usize,
+    /// Incremented for every transition, so timers of earlier transitions don't end the current transition
+    generation: usize,
+    view_transition: ViewTransition,
+    /// Notified when the new route of a view transition is mounted
+    mounted: Option<oneshot::Sender<()>>,
+}
+
+/// The routes an outlet renders during transitions
+pub(crate) struct OutletTransitions<R: 'static> {
+    state: CopyValue<TransitionState<R>>,
+    update: Arc<dyn Fn() + Send + Sync>,
+    finish: Callback,
+    mounted: Callback,
+    frozen: Option<FrozenRoute<R>>,
+}
+
+/// Create the transition state of an outlet. This must be called on every render of the outlet, even if the router
+/// doesn't have a transition.
+pub(crate) fn use_outlet_transitions<R: Routable>() -> OutletTransitions<R> {
+    let state = use_hook(|| {
+        CopyValue::new(TransitionState {
+            slots: Vec::new(),
+            next_id: 0,
+            generation: 0,
+            view_transition: ViewTransition::Idle,
+            mounted: None,
+        })
+    });
+    let update = use_hook(schedule_update);
+    let finish = use_callback({
+        let update = update.clone();
+        move |_| end_transition(state, &*update, None)
+    });
+    let mounted = use_callback(move |_| {
+        if let Some(mounted) = state.write_unchecked().mounted.take() {
+            _ = mounted.send(());
+        }
+    });
+    let frozen = use_hook(try_consume_context::<FrozenRoute<R>>);
+
+    OutletTransitions {
+        state,
+        update,
+        finish,
+        mounted,
+        frozen,
+    }
+}
+
+/// End the running transition of an outlet. If a generation is passed, only the transition of that generation ends.
+fn end_transition<R>(
+    state: CopyValue<TransitionState<R>>,
+    update: &dyn Fn(),
+    generation: Option<usize>,
+) {
+    let mut state = state.write_unchecked();
+    if generation.is_some_and(|generation| generation != state.generation) {
+        return;
+    }
+    if state
+        .slots
+        .iter()
+        .all(|slot| slot.phase == TransitionPhase::Entered)
+    {
+        return;
+    }
+    state
+        .slots
+        .retain(|slot| slot.phase != TransitionPhase::Exiting);
+    for slot in &mut state.slots {
+        slot.phase = TransitionPhase::Entered;
+    }
+    update();
+}
+
+impl<R: Routable> OutletTransitions<R> {
+    /// Render the current route at a level, along with the routes that are still exiting
+    pub(crate) fn render(&self, transition: Transition, route: R, level: usize) -> Element {
+        // Outlets inside an exiting route keep rendering the old route without transitions
+        let frozen = self.frozen.and_then(|frozen| frozen.0.cloned());
+        let is_frozen = frozen.is_some();
+        let route = frozen.unwrap_or(route);
+        let key = route.level_key(level);
+
+        {
+            let mut state = self.state.write_unchecked();
+            match state.slots.last_mut() {
+                Some(current) if current.key == key || is_frozen => {
+                    current.key = key;
+                    current.route = route;
+                    // The route changed back before a view transition started
+                    if let ViewTransition::Ready(_) = state.view_transition {
+                        state.view_transition = ViewTransition::Idle;
+                        state.mounted.take();
+                    }
+                }
+                Some(_) => self.navigate(&mut state, transition, key, route),
+                None => {
+                    let id = state.next_id;
+                    state.next_id += 1;
+                    state.slots.push(Slot {
+                        id,
+                        key,
+                        route,
+                        phase: TransitionPhase::Entered,
+                    });
+                }
+            }
+        }
+
+        let state = self.state.read();
+        let slots = state.slots.iter().map(|slot| {
+            rsx! {
+                TransitionSlot::<R> {
+                    key: "{slot.id}",
+                    level,
+                    route: slot.route.clone(),
+                    // Routes inside an exiting route are exiting as well
+                    phase: if is_frozen { TransitionPhase::Exiting } else { slot.phase },
+                    frozen: is_frozen || slot.phase == TransitionPhase::Exiting,
+                    finish: self.finish,
+                    mounted: self.mounted,
+                }
+            }
+        });
+        rsx! { {slots} }
+    }
+
+    fn navigate(
+        &self,
+        state: &mut TransitionState<R>,
+        transition: Transition,
+        key: &'static str,
+        route: R,
+    ) {
+        if transition.view_transition {
+            match state.view_transition {
+                // Keep the old route until the browser captured it
+                ViewTransition::Idle => {
+                    state.view_transition = ViewTransition::Starting;
+                    self.start_view_transition();
+                    return;
+                }
+                ViewTransition::Starting => return,
+                ViewTransition::Ready(supported) => {
+                    state.view_transition = ViewTransition::Idle;
+                    // The browser animates the snapshot of the old route, so it doesn't need to stay mounted
+                    if supported {
+                        self.swap(state, Exit::Immediate, true, key, route);
+                        return;
+                    }
+                }
+            }
+        }
+        self.swap(state, transition.exit, false, key, route);
+    }
+
+    /// Start transitioning from the current route to a new route
+    fn swap(
+        &self,
+        state: &mut TransitionState<R>,
+        exit: Exit,
+        view_transition: bool,
+        key: &'static str,
+        route: R,
+    ) {
+        state.generation += 1;
+        state
+            .slots
+            .retain(|slot| slot.phase != TransitionPhase::Exiting);
+        match exit {
+            Exit::Immediate => state.slots.clear(),
+            _ => {
+                for slot in &mut state.slots {
+                    slot.phase = TransitionPhase::Exiting;
+                }
+            }
+        }
+        let phase = match exit == Exit::Immediate && !view_transition {
+            true => TransitionPhase::Entered,
+            false => TransitionPhase::Entering,
+        };
+        let id = state.next_id;
+        state.next_id += 1;
+        state.slots.push(Slot {
+            id,
+            key,
+            route,
+            phase,
+        });
+
+        let generation = state.generation;
+        let state = self.state;
+        let update = self.update.clone();
+        match exit {
+            Exit::After(duration) => {
+                spawn(async move {
+                    sleep(duration).await;
+                    end_transition(state, &*update, Some(generation));
+                });
+            }
+            Exit::Until(condition) => {
+                spawn(async move {
+                    let (rc, mut changed) = ReactiveContext::new();
+                    while !rc.reset_and_run_in(|| *condition.read()) {
+                        if changed.next().await.is_none() {
+                            return;
+                        }
+                    }
+                    end_transition(state, &*update, Some(generation));
+                });
+            }
+            Exit::Immediate | Exit::Finished => {}
+        }
+    }
+
+    fn start_view_transition(&self) {
+        let mut eval = dioxus_lib::document::eval(START_VIEW_TRANSITION);
+        let (mounted, mounted_rx) = oneshot::channel();
+        let state = self.state;
+        let update = self.update.clone();
+        spawn(async move {
+            let supported = eval.recv::<bool>().await.unwrap_or(false);
+            {
+                let mut state = state.write_unchecked();
+                state.view_transition = ViewTransition::Ready(supported);
+                if supported {
+                    state.mounted = Some(mounted);
+                }
+            }
+            update();
+            if !supported {
+                return;
+            }
+
+            // Let the browser animate to the new route once it is mounted
+            _ = mounted_rx.await;
+            let generation = state.read().generation;
+            _ = eval.send(true);
+            _ = eval.recv::<bool>().await;
+            end_transition(state, &*update, Some(generation));
+        });
+    }
+}
+
+/// Wait on a timer thread shared by every transition so transitions work with any async executor
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    use std::sync::{mpsc, OnceLock};
+    use std::time::Instant;
+
+    /// The deadline of a timer and the channel that is notified when it expires
+    type Timer = (Instant, oneshot::Sender<()>);
+
+    static TIMERS: OnceLock<mpsc::Sender<Timer>> = OnceLock::new();
+
+    let timers = TIMERS.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Timer>();
+        std::thread::spawn(move || {
+            let mut pending: Vec<Timer> = Vec::new();
+            loop {
+                // Wait until the next timer expires or a new timer is added
+                let next = pending.iter().map(|(deadline, _)| *deadline).min();
+                let timer = match next {
+                    Some(deadline) => {
+                        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                            Ok(timer) => Some(timer),
+                            Err(mpsc::RecvTimeoutError::Timeout) => None,
+                            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                        }
+                    }
+                    None => match rx.recv() {
+                        Ok(timer) => Some(timer),
+                        Err(_) => return,
+                    },
+                };
+                pending.extend(timer);
+
+                let now = Instant::now();
+                let (expired, waiting) = std::mem::take(&mut pending)
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|(deadline, _)| *deadline <= now);
+                pending = waiting;
+                for (_, done) in expired {
+                    _ = done.send(());
+                }
+            }
+        });
+        tx
+    });
+
+    let (tx, rx) = oneshot::channel();
+    let deadline = Instant::now() + duration;
+    if timers.send((deadline, tx)).is_ok() {
+        _ = rx.await;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+#[derive(Props)]
+struct TransitionSlotProps<R: Routable> {
+    level: usize,
+    route: R,
+    phase: TransitionPhase,
+    /// Whether the outlets inside the route keep rendering this route
+    frozen: bool,
+    finish: Callback,
+    mounted: Callback,
+}
+
+impl<R: Routable> Clone for TransitionSlotProps<R> {
+    fn clone(&self) -> Self {
+        Self {
+            level: self.level,
+            route: self.route.clone(),
+            phase: self.phase,
+            frozen: self.frozen,
+            finish: self.finish,
+            mounted: self.mounted,
+        }
+    }
+}
+
+// Routes are not comparable, so the slot always reruns with the outlet like an outlet without transitions would
+impl<R: Routable> PartialEq for TransitionSlotProps<R> {
+    fn eq(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+/// A route rendered by an outlet that provides its transition to the route
+#[allow(missing_docs)]
+fn TransitionSlot<R: Routable>(props: TransitionSlotProps<R>) -> Element {
+    let mut phase = use_signal(|| props.phase);
+    if *phase.peek() != props.phase {
+        // The outlet only changes the phase when a transition starts or ends
+        use ::warnings::Warning;
+        warnings::signal_write_in_component_body::allow(|| phase.set(props.phase));
+    }
+    use_context_provider(|| RouteTransition {
+        phase: phase.into(),
+        finish: props.finish,
+    });
+    let mut frozen = use_context_provider(|| FrozenRoute::<R>(CopyValue::new(None)));
+    frozen.0.set(props.frozen.then(|| props.route.clone()));
+    let mounted = props.mounted;
+    use_effect(move || mounted.call(()));
+
+    props.route.render(props.level)
+}
@@ -51,3 +51,165 @@ fn with_class() {
         ],
     );
 }
+
+#[test]
+fn sitemap_xml() {
+    use dioxus_router::sitemap::{ChangeFrequency, SiteMap, SiteMapEntry};
+
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum ChildRoute {
+        #[route("/")]
+        ChildRoot {},
+        #[route("/:not_static")]
+        NotStatic { not_static: String },
+    }
+
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum Route {
+        #[route("/")]
+        Root {},
+        #[route("/test")]
+        Test {},
+        #[route("/blog/:id")]
+        Blog { id: usize },
+        #[child("/child")]
+        Nested { child: ChildRoute },
+    }
+
+    #[component]
+    fn Test() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Root() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Blog(id: usize) -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn ChildRoot() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn NotStatic(not_static: String) -> Element {
+        unimplemented!()
+    }
+
+    let mut patterns = Vec::new();
+    let sitemap = SiteMap::builder::<Route>("https://example.com/")
+        .dynamic_routes(|pattern| {
+            patterns.push(pattern.to_string());
+            match pattern {
+                "/blog/:id" => vec![SiteMapEntry::new("/blog/1")
+                    .lastmod("2024-11-04")
+                    .changefreq(ChangeFrequency::Monthly)
+                    .priority(0.3)],
+                _ => vec![SiteMapEntry::new("/child/a&b")],
+            }
+        })
+        .annotate(|entry| {
+            if entry.path == "/" {
+                entry.priority = Some(1.0);
+            }
+        })
+        .build();
+
+    assert_eq!(patterns, vec!["/blog/:id", "/child/:not_static"]);
+    assert_eq!(
+        sitemap
+            .entries()
+            .iter()
+            .map(|entry| entry.path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["/", "/test", "/child/", "/blog/1", "/child/a&b"]
+    );
+    assert_eq!(
+        sitemap.to_xml(),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url>
+    <loc>https://example.com/</loc>
+    <priority>1.0</priority>
+  </url>
+  <url>
+    <loc>https://example.com/test</loc>
+  </url>
+  <url>
+    <loc>https://example.com/child/</loc>
+  </url>
+  <url>
+    <loc>https://example.com/blog/1</loc>
+    <lastmod>2024-11-04</lastmod>
+    <changefreq>monthly</changefreq>
+    <priority>0.3</priority>
+  </url>
+  <url>
+    <loc>https://example.com/child/a&amp;b</loc>
+  </url>
+</urlset>
+"#
+    );
+}
+
+#[test]
+fn sitemap_priorities() {
+    use dioxus_router::sitemap::{SiteMap, SiteMapEntry};
+
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum Route {
+        #[route("/:page")]
+        Page { page: String },
+    }
+
+    #[component]
+    fn Page(page: String) -> Element {
+        unimplemented!()
+    }
+
+    let sitemap = SiteMap::builder::<Route>("https://example.com")
+        .dynamic_routes(|_| {
+            vec![
+                SiteMapEntry::new("/a").priority(0.25),
+                SiteMapEntry::new("/b").priority(0.75),
+                SiteMapEntry::new("/c"),
+            ]
+        })
+        .annotate(|entry| {
+            if entry.path == "/c" {
+                entry.priority = Some(1.5);
+            }
+        })
+        .build();
+
+    // Priorities are written as given, and priorities outside of 0.0..=1.0 are left out
+    assert_eq!(
+        sitemap.to_xml(),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url>
+    <loc>https://example.com/a</loc>
+    <priority>0.25</priority>
+  </url>
+  <url>
+    <loc>https://example.com/b</loc>
+    <priority>0.75</priority>
+  </url>
+  <url>
+    <loc>https://example.com/c</loc>
+  </url>
+</urlset>
+"#
+    );
+}
+
+#[test]
+#[should_panic]
+fn sitemap_priority_out_of_range() {
+    dioxus_router::sitemap::SiteMapEntry::new("/").priority(1.5);
+}
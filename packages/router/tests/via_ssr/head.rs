@@ -0,0 +1,186 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::document::{Document, Eval, MetaProps, NoOpDocument};
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/", title = "Home")]
+    Home {},
+    #[route(
+        "/blog/:id",
+        title = "Post {id}",
+        meta(name = "description", content = "Read post {id}"),
+        meta(property = "og:title", content = "Post {id}")
+    )]
+    BlogPost { id: usize },
+    #[route("/about", AboutPage)]
+    About {},
+    #[child("/docs")]
+    Docs { child: DocsRoute },
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum DocsRoute {
+    #[route("/:page", title = "Docs: {page}")]
+    Page { page: String },
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "home" }
+}
+
+#[component]
+fn BlogPost(id: usize) -> Element {
+    rsx! { "post {id}" }
+}
+
+#[component]
+fn AboutPage() -> Element {
+    rsx! { "about" }
+}
+
+#[component]
+fn Page(page: String) -> Element {
+    rsx! { "{page}" }
+}
+
+/// A document that records the title and meta tags added to the head
+#[derive(Default)]
+struct HeadDocument {
+    title: RefCell<Option<String>>,
+    meta: RefCell<Vec<Vec<(&'static str, String)>>>,
+}
+
+impl Document for HeadDocument {
+    fn eval(&self, js: String) -> Eval {
+        NoOpDocument.eval(js)
+    }
+
+    fn set_title(&self, title: String) {
+        *self.title.borrow_mut() = Some(title);
+    }
+
+    fn create_meta(&self, props: MetaProps) {
+        self.meta.borrow_mut().push(meta_attributes(props));
+    }
+
+    fn remove_meta(&self, props: MetaProps) {
+        let attributes = meta_attributes(props);
+        self.meta.borrow_mut().retain(|meta| *meta != attributes);
+    }
+}
+
+fn meta_attributes(props: MetaProps) -> Vec<(&'static str, String)> {
+    let mut attributes = Vec::new();
+    if let Some(property) = props.property {
+        attributes.push(("property", property));
+    }
+    if let Some(name) = props.name {
+        attributes.push(("name", name));
+    }
+    if let Some(content) = props.content {
+        attributes.push(("content", content));
+    }
+    attributes
+}
+
+fn prepare(path: &str) -> (VirtualDom, Rc<HeadDocument>) {
+    let mut vdom = VirtualDom::new(|| rsx! { Router::<Route> {} });
+    let history = Rc::new(MemoryHistory::with_initial_path(path));
+    vdom.provide_root_context(history as Rc<dyn History>);
+    let document = Rc::new(HeadDocument::default());
+    vdom.provide_root_context(document.clone() as Rc<dyn Document>);
+    vdom.rebuild_in_place();
+    (vdom, document)
+}
+
+#[test]
+fn head_is_available_without_rendering() {
+    let head = Route::BlogPost { id: 7 }.head();
+    assert_eq!(head.title.as_deref(), Some("Post 7"));
+    assert_eq!(head.meta.len(), 2);
+    assert_eq!(head.meta[0].name.as_deref(), Some("description"));
+    assert_eq!(head.meta[0].content.as_deref(), Some("Read post 7"));
+    assert_eq!(head.meta[1].property.as_deref(), Some("og:title"));
+
+    assert_eq!(Route::About {}.head(), Default::default());
+}
+
+#[test]
+fn child_routes_use_the_head_of_the_child() {
+    let route = Route::Docs {
+        child: DocsRoute::Page {
+            page: "intro".to_string(),
+        },
+    };
+    assert_eq!(route.head().title.as_deref(), Some("Docs: intro"));
+}
+
+#[test]
+fn router_applies_the_head_of_the_route() {
+    let (vdom, document) = prepare("/blog/3");
+    assert_eq!(dioxus_ssr::render(&vdom), "post 3");
+    assert_eq!(document.title.borrow().as_deref(), Some("Post 3"));
+    assert_eq!(
+        *document.meta.borrow(),
+        [
+            vec![
+                ("name", "description".to_string()),
+                ("content", "Read post 3".to_string())
+            ],
+            vec![
+                ("property", "og:title".to_string()),
+                ("content", "Post 3".to_string())
+            ],
+        ]
+    );
+}
+
+fn navigate(vdom: &mut VirtualDom, route: Route) {
+    vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            root_router().unwrap().push(route);
+        })
+    });
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+}
+
+#[test]
+fn title_follows_the_route() {
+    let (mut vdom, document) = prepare("/");
+    assert_eq!(document.title.borrow().as_deref(), Some("Home"));
+
+    navigate(&mut vdom, Route::BlogPost { id: 5 });
+    assert_eq!(document.title.borrow().as_deref(), Some("Post 5"));
+
+    // Routes without a title clear the title of the previous route
+    navigate(&mut vdom, Route::About {});
+    assert_eq!(document.title.borrow().as_deref(), Some(""));
+}
+
+#[test]
+fn meta_tags_follow_the_route() {
+    let (mut vdom, document) = prepare("/blog/3");
+    assert_eq!(document.meta.borrow().len(), 2);
+
+    navigate(&mut vdom, Route::BlogPost { id: 4 });
+    assert_eq!(
+        *document.meta.borrow(),
+        [
+            vec![
+                ("name", "description".to_string()),
+                ("content", "Read post 4".to_string())
+            ],
+            vec![
+                ("property", "og:title".to_string()),
+                ("content", "Post 4".to_string())
+            ],
+        ]
+    );
+
+    navigate(&mut vdom, Route::Home {});
+    assert!(document.meta.borrow().is_empty());
+}
@@ -0,0 +1,143 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_history::{History, HistorySnapshot, MemoryHistory, ScrollPosition};
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/search")]
+    Search {},
+}
+
+/// The navigator of the router, provided by the app so the tests can navigate from outside of the router
+#[derive(Clone, Copy)]
+struct CapturedNavigator(Signal<Option<Navigator>>);
+
+/// Keep the navigator of the router so the tests can navigate from outside of it
+fn capture_navigator() {
+    use_hook(|| {
+        let mut captured = consume_context::<CapturedNavigator>();
+        captured.0.set(Some(navigator()));
+    });
+}
+
+#[component]
+fn Home() -> Element {
+    capture_navigator();
+    rsx! { "home" }
+}
+
+#[component]
+fn Search() -> Element {
+    capture_navigator();
+    let query = navigator().current_state::<String>().unwrap_or_default();
+    rsx! { "search {query}" }
+}
+
+fn prepare(history: Rc<MemoryHistory>) -> VirtualDom {
+    let mut vdom = VirtualDom::new(|| {
+        use_context_provider(|| CapturedNavigator(Signal::new(None)));
+        rsx! { Router::<Route> {} }
+    });
+    vdom.provide_root_context(history as Rc<dyn History>);
+    vdom.rebuild_in_place();
+    vdom
+}
+
+fn navigate(vdom: &mut VirtualDom, f: impl FnOnce(Navigator)) {
+    let navigator = vdom
+        .in_runtime(|| {
+            ScopeId::APP
+                .consume_context::<CapturedNavigator>()
+                .and_then(|captured| captured.0.cloned())
+        })
+        .expect("the router was not rendered");
+    vdom.in_runtime(|| ScopeId::ROOT.in_runtime(|| f(navigator)));
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+}
+
+#[test]
+fn state_is_attached_to_the_pushed_location() {
+    let history = Rc::new(MemoryHistory::default());
+    let mut vdom = prepare(history.clone());
+
+    navigate(&mut vdom, |nav| {
+        nav.push_with_state(Route::Search {}, &"dioxus");
+    });
+    assert_eq!(dioxus_ssr::render(&vdom), "search dioxus");
+    assert_eq!(history.current_state().as_deref(), Some("\"dioxus\""));
+
+    navigate(&mut vdom, |nav| {
+        nav.push_with_state(Route::Search {}, &"router");
+    });
+    assert_eq!(dioxus_ssr::render(&vdom), "search router");
+    assert!(history.can_go_back());
+}
+
+#[test]
+fn state_survives_going_back_and_forward() {
+    let history = Rc::new(MemoryHistory::default());
+    let mut vdom = prepare(history.clone());
+
+    navigate(&mut vdom, |nav| {
+        nav.push_with_state(Route::Search {}, &"dioxus");
+    });
+    navigate(&mut vdom, |nav| {
+        nav.push(Route::Home {});
+    });
+    navigate(&mut vdom, |nav| {
+        assert_eq!(nav.current_state::<String>(), None);
+        nav.go_back();
+    });
+    assert_eq!(dioxus_ssr::render(&vdom), "search dioxus");
+
+    navigate(&mut vdom, |nav| nav.go_forward());
+    navigate(&mut vdom, |nav| nav.go_back());
+    assert_eq!(dioxus_ssr::render(&vdom), "search dioxus");
+}
+
+#[test]
+fn state_of_another_type_is_ignored() {
+    let history = Rc::new(MemoryHistory::default());
+    let mut vdom = prepare(history);
+
+    navigate(&mut vdom, |nav| {
+        nav.push_with_state(Route::Search {}, &42);
+        assert_eq!(nav.current_state::<u32>(), Some(42));
+        assert_eq!(nav.current_state::<String>(), None);
+    });
+}
+
+#[test]
+fn snapshots_restore_the_whole_history() {
+    let history = MemoryHistory::default();
+    history.push_with_state("/search".to_string(), "\"dioxus\"".to_string());
+    history.set_scroll(1, ScrollPosition { x: 0.0, y: 120.0 });
+    history.push("/".to_string());
+    history.go_back();
+
+    let snapshot = history.snapshot();
+    assert_eq!(snapshot.back.len(), 1);
+    assert_eq!(snapshot.current.state.as_deref(), Some("\"dioxus\""));
+    assert_eq!(
+        snapshot.current.scroll,
+        Some(ScrollPosition { x: 0.0, y: 120.0 })
+    );
+    assert_eq!(snapshot.current.route, "/search");
+    assert_eq!(snapshot.forward.len(), 1);
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let snapshot: HistorySnapshot = serde_json::from_str(&json).unwrap();
+    let restored = Rc::new(MemoryHistory::from_snapshot(snapshot));
+    assert_eq!(restored.index(), 1);
+    assert!(restored.can_go_forward());
+    assert_eq!(restored.scroll(), Some(ScrollPosition { x: 0.0, y: 120.0 }));
+
+    let mut vdom = prepare(restored.clone());
+    assert_eq!(dioxus_ssr::render(&vdom), "search dioxus");
+
+    navigate(&mut vdom, |nav| nav.go_back());
+    assert_eq!(dioxus_ssr::render(&vdom), "home");
+}
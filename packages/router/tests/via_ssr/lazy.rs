@@ -0,0 +1,122 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::document::{Document, Eval, LinkProps, NoOpDocument};
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[layout(Nav)]
+    #[lazy]
+    #[route("/post/:id")]
+    Post { id: u32 },
+    #[end_layout]
+    #[lazy]
+    #[route("/settings")]
+    Settings {},
+}
+
+#[component]
+fn Nav() -> Element {
+    rsx! {
+        nav { "nav" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "home" }
+}
+
+#[component]
+fn Post(id: u32) -> Element {
+    rsx! { "post {id}" }
+}
+
+#[component]
+fn Settings() -> Element {
+    rsx! { "settings" }
+}
+
+/// A document that records the links added to the head
+#[derive(Default)]
+struct HeadDocument {
+    links: RefCell<Vec<(String, String)>>,
+}
+
+impl Document for HeadDocument {
+    fn eval(&self, js: String) -> Eval {
+        NoOpDocument.eval(js)
+    }
+
+    fn create_link(&self, props: LinkProps) {
+        self.links.borrow_mut().push((
+            props.rel.unwrap_or_default(),
+            props.href.unwrap_or_default(),
+        ));
+    }
+}
+
+fn prepare(path: &str) -> (VirtualDom, Rc<HeadDocument>) {
+    let mut vdom = VirtualDom::new(|| rsx! { Router::<Route> {} });
+    vdom.provide_root_context(Rc::new(MemoryHistory::with_initial_path(path)) as Rc<dyn History>);
+    let document = Rc::new(HeadDocument::default());
+    vdom.provide_root_context(document.clone() as Rc<dyn Document>);
+    vdom.rebuild_in_place();
+    (vdom, document)
+}
+
+#[test]
+fn lazy_routes_render_without_splitting() {
+    let (mut vdom, _) = prepare("/post/5");
+    assert_eq!(dioxus_ssr::render(&vdom), "<nav>nav</nav>post 5");
+
+    vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            root_router().unwrap().push(Route::Settings {});
+        })
+    });
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&vdom), "settings");
+}
+
+#[test]
+fn chunk_urls() {
+    assert_eq!(
+        dioxus_router::lazy::chunk_url("Route_Settings"),
+        "/wasm/chunks/Route_Settings.js"
+    );
+}
+
+#[cfg(not(feature = "wasm-split"))]
+#[test]
+fn no_module_preload_without_splitting() {
+    assert!(Route::Settings {}.prefetch_hints().is_empty());
+
+    let (_, document) = prepare("/settings");
+    assert!(document.links.borrow().is_empty());
+}
+
+#[cfg(feature = "wasm-split")]
+#[test]
+fn module_preload_for_the_current_route() {
+    assert_eq!(
+        Route::Post { id: 1 }.prefetch_hints(),
+        [dioxus_router::prefetch::PrefetchHint::module_preload(
+            "/wasm/chunks/Route_Post.js"
+        )]
+    );
+    assert!(Route::Home {}.prefetch_hints().is_empty());
+
+    let (_, document) = prepare("/settings");
+    assert_eq!(
+        *document.links.borrow(),
+        [(
+            "modulepreload".to_string(),
+            "/wasm/chunks/Route_Settings.js".to_string()
+        )]
+    );
+}
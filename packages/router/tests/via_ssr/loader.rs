@@ -0,0 +1,119 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+
+use crate::settle;
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn log(message: String) {
+    LOG.with(|log| log.borrow_mut().push(message));
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|log| std::mem::take(&mut *log.borrow_mut()))
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[nest("/user/:user_id")]
+        #[layout(UserFrame, loader = |user_id| load_user(user_id))]
+            #[route("/post/:post_id", loader = |post_id: u32| load_post(post_id))]
+            Post { user_id: u32, post_id: u32 },
+}
+
+async fn load_user(user_id: u32) -> String {
+    log(format!("start user {user_id}"));
+    tokio::task::yield_now().await;
+    log(format!("end user {user_id}"));
+    format!("User {user_id}")
+}
+
+async fn load_post(post_id: u32) -> u32 {
+    log(format!("start post {post_id}"));
+    tokio::task::yield_now().await;
+    log(format!("end post {post_id}"));
+    post_id * 10
+}
+
+#[component]
+fn UserFrame(user_id: u32) -> Element {
+    let user: String = use_loader_data();
+    rsx! {
+        h1 { "{user}" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Post(user_id: u32, post_id: u32) -> Element {
+    let views: u32 = use_loader_data();
+    rsx! {
+        p { "{views} views" }
+    }
+}
+
+fn prepare(path: &str) -> VirtualDom {
+    let vdom = VirtualDom::new(|| rsx! { Router::<Route> {} });
+    vdom.provide_root_context(Rc::new(MemoryHistory::with_initial_path(path)) as Rc<dyn History>);
+    vdom
+}
+
+fn navigate(vdom: &VirtualDom, route: Route) {
+    vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            root_router().unwrap().push(route);
+        })
+    });
+}
+
+#[tokio::test]
+async fn loaders_run_in_parallel_before_render() {
+    let mut vdom = prepare("/user/1/post/2");
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), "");
+
+    settle(&mut vdom).await;
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>User 1</h1><p>20 views</p>");
+    assert_eq!(
+        take_log(),
+        ["start user 1", "start post 2", "end user 1", "end post 2"]
+    );
+}
+
+#[tokio::test]
+async fn layout_data_is_reused_when_its_parameters_do_not_change() {
+    let mut vdom = prepare("/user/1/post/2");
+    vdom.rebuild_in_place();
+    settle(&mut vdom).await;
+    take_log();
+
+    navigate(
+        &vdom,
+        Route::Post {
+            user_id: 1,
+            post_id: 3,
+        },
+    );
+    settle(&mut vdom).await;
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>User 1</h1><p>30 views</p>");
+    assert_eq!(take_log(), ["start post 3", "end post 3"]);
+
+    navigate(
+        &vdom,
+        Route::Post {
+            user_id: 4,
+            post_id: 3,
+        },
+    );
+    settle(&mut vdom).await;
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>User 4</h1><p>30 views</p>");
+    assert_eq!(
+        take_log(),
+        ["start user 4", "start post 3", "end post 3", "end user 4"]
+    );
+}
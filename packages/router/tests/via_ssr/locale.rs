@@ -0,0 +1,216 @@
+use std::{cell::RefCell, rc::Rc, str::FromStr};
+
+use dioxus::document::{Document, Eval, LinkProps, NoOpDocument};
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[locale("en", "de")]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/about")]
+    About {},
+}
+
+#[component]
+fn Home() -> Element {
+    let locale = use_locale();
+    rsx! {
+        h1 { "{locale}" }
+        Link { to: Route::About {}, "about" }
+        Link { to: Route::About {}, locale: "en", "english" }
+    }
+}
+
+#[component]
+fn About() -> Element {
+    rsx! { "about" }
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[locale("en", "de", default = "en")]
+enum OptionalRoute {
+    #[route("/")]
+    Index {},
+    #[route("/contact")]
+    Contact {},
+}
+
+#[component]
+fn Index() -> Element {
+    rsx! {
+        Link { to: OptionalRoute::Contact {}, "contact" }
+    }
+}
+
+#[component]
+fn Contact() -> Element {
+    rsx! { "contact" }
+}
+
+/// A document that records the alternate links in the head
+#[derive(Default)]
+struct HeadDocument {
+    links: RefCell<Vec<(String, String)>>,
+}
+
+impl Document for HeadDocument {
+    fn eval(&self, js: String) -> Eval {
+        NoOpDocument.eval(js)
+    }
+
+    fn create_link(&self, props: LinkProps) {
+        self.links.borrow_mut().push((
+            props.hreflang.unwrap_or_default(),
+            props.href.unwrap_or_default(),
+        ));
+    }
+
+    fn remove_link(&self, props: LinkProps) {
+        let link = (
+            props.hreflang.unwrap_or_default(),
+            props.href.unwrap_or_default(),
+        );
+        self.links.borrow_mut().retain(|existing| *existing != link);
+    }
+}
+
+fn prepare<R: Routable>(path: &str) -> (VirtualDom, Rc<MemoryHistory>, Rc<HeadDocument>)
+where
+    <R as FromStr>::Err: std::fmt::Display,
+{
+    let mut vdom = VirtualDom::new(|| rsx! { Router::<R> {} });
+    let history = Rc::new(MemoryHistory::with_initial_path(path));
+    vdom.provide_root_context(history.clone() as Rc<dyn History>);
+    let document = Rc::new(HeadDocument::default());
+    vdom.provide_root_context(document.clone() as Rc<dyn Document>);
+    vdom.rebuild_in_place();
+    (vdom, history, document)
+}
+
+#[test]
+fn required_locale_segment() {
+    assert_eq!(Route::from_str("/de/about").ok(), Some(Route::About {}));
+    assert_eq!(Route::from_str("/en").ok(), Some(Route::Home {}));
+    assert!(Route::from_str("/fr/about").is_err());
+
+    // The error lists the locales the route could start with
+    let error = Route::from_str("/about").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Route did not match:\nAttempted Matches:\n1) Route '/about' does not start with one of the locales en, de\n"
+    );
+
+    // Routes are formatted in the first locale
+    assert_eq!(Route::About {}.to_string(), "/en/about");
+}
+
+#[test]
+fn links_keep_the_current_locale() {
+    let (mut vdom, history, _) = prepare::<Route>("/de");
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        r#"<h1>de</h1><a href="/de/about">about</a><a href="/en/about">english</a>"#
+    );
+
+    vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            root_router().unwrap().push(Route::About {});
+        })
+    });
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(history.current_route(), "/de/about");
+    assert_eq!(dioxus_ssr::render(&vdom), "about");
+
+    // Formatting a route doesn't depend on the current url
+    vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| assert_eq!(Route::About {}.to_string(), "/en/about"))
+    });
+
+    vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            root_router()
+                .unwrap()
+                .push(with_locale(Route::Home {}, "en"));
+        })
+    });
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(history.current_route(), "/en/");
+
+    // Locales that are not declared use the first locale
+    vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            root_router()
+                .unwrap()
+                .push(with_locale(Route::About {}, "fr"));
+        })
+    });
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(history.current_route(), "/en/about");
+}
+
+#[test]
+fn optional_locale_segment() {
+    assert_eq!(
+        OptionalRoute::from_str("/contact").ok(),
+        Some(OptionalRoute::Contact {})
+    );
+    assert_eq!(
+        OptionalRoute::from_str("/de/contact").ok(),
+        Some(OptionalRoute::Contact {})
+    );
+    assert_eq!(OptionalRoute::Contact {}.to_string(), "/contact");
+
+    let (vdom, _, _) = prepare::<OptionalRoute>("/de");
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        r#"<a href="/de/contact">contact</a>"#
+    );
+    let (vdom, _, _) = prepare::<OptionalRoute>("/");
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        r#"<a href="/contact">contact</a>"#
+    );
+}
+
+#[test]
+fn alternate_links_for_every_locale() {
+    let (_, _, document) = prepare::<Route>("/de/about");
+    assert_eq!(
+        *document.links.borrow(),
+        [
+            ("en".to_string(), "/en/about".to_string()),
+            ("de".to_string(), "/de/about".to_string()),
+        ]
+    );
+
+    let (_, _, document) = prepare::<OptionalRoute>("/de/contact");
+    assert_eq!(
+        *document.links.borrow(),
+        [
+            ("en".to_string(), "/contact".to_string()),
+            ("de".to_string(), "/de/contact".to_string()),
+            ("x-default".to_string(), "/contact".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn alternate_links_follow_the_current_route() {
+    let (mut vdom, _, document) = prepare::<OptionalRoute>("/de");
+    vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            root_router().unwrap().push(OptionalRoute::Contact {});
+        })
+    });
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(
+        *document.links.borrow(),
+        [
+            ("en".to_string(), "/contact".to_string()),
+            ("de".to_string(), "/de/contact".to_string()),
+            ("x-default".to_string(), "/contact".to_string()),
+        ]
+    );
+}
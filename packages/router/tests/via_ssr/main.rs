@@ -1,4 +1,52 @@
+mod head;
+mod history_state;
+mod lazy;
 mod link;
+mod loader;
+mod locale;
+mod navigation_blocker;
 mod outlet;
+mod prefetch;
+mod query_param;
 mod redirect;
+mod transition;
 mod without_index;
+
+/// Rerun everything that changed until the virtual dom has no more work
+async fn settle(vdom: &mut dioxus::prelude::VirtualDom) {
+    use std::{
+        future::Future,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        task::{Context, Wake, Waker},
+    };
+
+    /// A waker that records if the virtual dom was notified of new work
+    #[derive(Default)]
+    struct Woken(AtomicBool);
+
+    impl Wake for Woken {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    loop {
+        let woken = Arc::new(Woken::default());
+        let waker = Waker::from(woken.clone());
+        let ready = std::pin::pin!(vdom.wait_for_work())
+            .poll(&mut Context::from_waker(&waker))
+            .is_ready();
+        if ready {
+            vdom.render_immediate(&mut dioxus::dioxus_core::NoOpMutations);
+            continue;
+        }
+        // Let the tasks that were polled wake up, then stop if none of them queued more work
+        tokio::task::yield_now().await;
+        if !woken.0.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+}
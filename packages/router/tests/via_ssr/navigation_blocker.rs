@@ -0,0 +1,314 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::Arc,
+};
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::prelude::{BlockedNavigation, NavigationBlocker};
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/editor")]
+    Editor {},
+    #[route("/about")]
+    About {},
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "home" }
+}
+
+#[component]
+fn About() -> Element {
+    rsx! { "about" }
+}
+
+#[derive(Clone, Copy)]
+struct EditorState {
+    unsaved: Signal<bool>,
+    blocker: Signal<Option<NavigationBlocker<Route>>>,
+}
+
+#[component]
+fn Editor() -> Element {
+    let mut state = use_context::<EditorState>();
+    let unsaved = state.unsaved;
+    let blocker = use_navigation_blocker(move |_: Route, _| unsaved());
+    use_hook(|| state.blocker.set(Some(blocker)));
+    rsx! { "editor" }
+}
+
+/// A memory history that records whether leaving the app is blocked and every route it moves to
+struct UnloadHistory {
+    history: MemoryHistory,
+    unload_blocked: Rc<Cell<bool>>,
+    visited: Rc<RefCell<Vec<String>>>,
+    updater: RefCell<Option<Arc<dyn Fn() + Send + Sync>>>,
+    last_delta: Cell<Option<isize>>,
+}
+
+impl UnloadHistory {
+    fn record(&self) {
+        self.visited.borrow_mut().push(self.history.current_route());
+    }
+
+    /// Move through the history like the browser back and forward buttons
+    fn external_go(&self, delta: isize) {
+        self.history.go(delta);
+        self.last_delta.set(Some(delta));
+        self.record();
+        if let Some(updater) = &*self.updater.borrow() {
+            updater();
+        }
+    }
+}
+
+impl History for UnloadHistory {
+    fn current_route(&self) -> String {
+        self.history.current_route()
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.history.can_go_back()
+    }
+
+    fn go_back(&self) {
+        self.history.go_back();
+        self.record();
+    }
+
+    fn can_go_forward(&self) -> bool {
+        self.history.can_go_forward()
+    }
+
+    fn go_forward(&self) {
+        self.history.go_forward();
+        self.record();
+    }
+
+    fn route_at(&self, delta: isize) -> Option<String> {
+        self.history.route_at(delta)
+    }
+
+    fn last_update_delta(&self) -> Option<isize> {
+        self.last_delta.get()
+    }
+
+    fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {
+        *self.updater.borrow_mut() = Some(callback);
+    }
+
+    fn push(&self, route: String) {
+        self.history.push(route)
+    }
+
+    fn replace(&self, path: String) {
+        self.history.replace(path)
+    }
+
+    fn block_unload(&self, blocked: bool) {
+        self.unload_blocked.set(blocked)
+    }
+}
+
+struct Test {
+    vdom: VirtualDom,
+    state: EditorState,
+    history: Rc<UnloadHistory>,
+}
+
+impl Test {
+    fn new() -> Self {
+        let history = Rc::new(UnloadHistory {
+            history: MemoryHistory::with_initial_path("/editor"),
+            unload_blocked: Rc::new(Cell::new(false)),
+            visited: Rc::default(),
+            updater: RefCell::new(None),
+            last_delta: Cell::new(None),
+        });
+        let mut vdom = VirtualDom::new(|| {
+            use_context_provider(|| EditorState {
+                unsaved: Signal::new(false),
+                blocker: Signal::new(None),
+            });
+            rsx! { Router::<Route> {} }
+        });
+        vdom.provide_root_context(history.clone() as Rc<dyn History>);
+        vdom.rebuild_in_place();
+        let state = vdom.in_runtime(|| ScopeId::APP.consume_context::<EditorState>().unwrap());
+        let mut test = Self {
+            vdom,
+            state,
+            history,
+        };
+        test.render();
+        test
+    }
+
+    fn render(&mut self) {
+        self.vdom.render_immediate(&mut NoOpMutations);
+    }
+
+    fn html(&self) -> String {
+        dioxus_ssr::render(&self.vdom)
+    }
+
+    fn set_unsaved(&mut self, unsaved: bool) {
+        let mut signal = self.state.unsaved;
+        self.vdom.in_runtime(|| signal.set(unsaved));
+        self.render();
+    }
+
+    fn with_router(&mut self, f: impl FnOnce(RouterContext)) {
+        self.vdom
+            .in_runtime(|| ScopeId::ROOT.in_runtime(|| f(root_router().unwrap())));
+        self.render();
+    }
+
+    fn blocker(&self) -> NavigationBlocker<Route> {
+        self.vdom
+            .in_runtime(|| self.state.blocker.cloned().unwrap())
+    }
+
+    fn blocked(&self) -> Option<BlockedNavigation<Route>> {
+        self.vdom.in_runtime(|| self.blocker().blocked())
+    }
+
+    fn take_visited(&self) -> Vec<String> {
+        std::mem::take(&mut *self.history.visited.borrow_mut())
+    }
+}
+
+#[test]
+fn blocks_pushes_until_confirmed() {
+    let mut test = Test::new();
+    assert_eq!(test.html(), "editor");
+
+    test.set_unsaved(true);
+    test.with_router(|router| {
+        router.push(Route::About {});
+    });
+    assert_eq!(test.html(), "editor");
+    let blocked = test.blocked().unwrap();
+    assert_eq!(blocked.from(), &Route::Editor {});
+    assert_eq!(blocked.to(), Some(&Route::About {}));
+
+    let blocker = test.blocker();
+    test.with_router(|_| blocker.proceed());
+    assert_eq!(test.html(), "about");
+}
+
+#[test]
+fn reset_cancels_the_navigation() {
+    let mut test = Test::new();
+    test.with_router(|router| {
+        router.push(Route::About {});
+        router.go_back();
+    });
+    assert_eq!(test.html(), "editor");
+
+    assert_eq!(test.take_visited(), ["/editor"]);
+
+    test.set_unsaved(true);
+    test.with_router(|router| router.go_forward());
+    assert_eq!(test.html(), "editor");
+    assert_eq!(test.blocked().unwrap().to(), Some(&Route::About {}));
+    // The history never moved to the blocked route
+    assert!(test.take_visited().is_empty());
+
+    let blocker = test.blocker();
+    test.with_router(|_| blocker.reset());
+    assert!(test.blocked().is_none());
+
+    // The history was moved back to the editor, so going forward again is blocked again
+    test.with_router(|router| {
+        assert!(router.can_go_forward());
+        router.go_forward();
+    });
+    assert_eq!(test.html(), "editor");
+    assert!(test.blocked().is_some());
+
+    test.set_unsaved(false);
+    test.with_router(|router| router.go_forward());
+    assert_eq!(test.html(), "about");
+}
+
+#[test]
+fn blocks_leaving_the_app() {
+    let mut test = Test::new();
+    assert!(!test.history.unload_blocked.get());
+
+    test.set_unsaved(true);
+    assert!(test.history.unload_blocked.get());
+
+    test.set_unsaved(false);
+    assert!(!test.history.unload_blocked.get());
+
+    // Unmounting the blocker stops blocking
+    test.set_unsaved(true);
+    test.with_router(|router| {
+        router.replace(Route::Home {});
+    });
+    assert_eq!(test.html(), "editor");
+    let blocker = test.blocker();
+    test.with_router(|_| blocker.proceed());
+    assert_eq!(test.html(), "home");
+    assert!(!test.history.unload_blocked.get());
+}
+
+#[test]
+fn blocks_updates_from_outside_the_router() {
+    let mut test = Test::new();
+    test.with_router(|router| {
+        router.push(Route::About {});
+        router.go_back();
+    });
+    test.take_visited();
+
+    // The browser forward button is blocked and undone before the route renders
+    test.set_unsaved(true);
+    test.history.external_go(1);
+    test.render();
+    assert_eq!(test.html(), "editor");
+    assert_eq!(test.history.current_route(), "/editor");
+    assert_eq!(test.blocked().unwrap().to(), Some(&Route::About {}));
+
+    let blocker = test.blocker();
+    test.with_router(|_| blocker.proceed());
+    assert_eq!(test.html(), "about");
+}
+
+#[test]
+fn proceeding_without_moving_keeps_blocking() {
+    let mut test = Test::new();
+    test.with_router(|router| {
+        router.push(Route::About {});
+        router.go_back();
+    });
+
+    test.set_unsaved(true);
+    test.with_router(|router| router.go_forward());
+    assert!(test.blocked().is_some());
+
+    // The page the navigation was going to is gone from the history by the time it proceeds, so the history stays on
+    // the current page
+    test.history.history.push("/about".to_string());
+    test.history.history.replace("/editor".to_string());
+    assert_eq!(test.history.route_at(1), None);
+    let blocker = test.blocker();
+    test.with_router(|_| blocker.proceed());
+    assert_eq!(test.html(), "editor");
+
+    // The next navigation still checks the blockers
+    test.with_router(|router| {
+        router.push(Route::Home {});
+    });
+    assert_eq!(test.html(), "editor");
+    assert_eq!(test.blocked().unwrap().to(), Some(&Route::Home {}));
+}
@@ -0,0 +1,158 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::document::{Document, Eval, LinkProps, NoOpDocument};
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+
+use crate::settle;
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn log(message: String) {
+    LOG.with(|log| log.borrow_mut().push(message));
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|log| std::mem::take(&mut *log.borrow_mut()))
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/hover")]
+    HoverHome {},
+    #[route("/both")]
+    BothHome {},
+    #[route("/post/:id", loader = |id: u32| load_post(id), preload = ["/assets/post.css"])]
+    Post { id: u32 },
+}
+
+async fn load_post(id: u32) -> String {
+    log(format!("start post {id}"));
+    tokio::task::yield_now().await;
+    log(format!("end post {id}"));
+    format!("Post {id}")
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! {
+        Link { to: Route::Post { id: 1 }, prefetch: Prefetch::Render, "post" }
+    }
+}
+
+#[component]
+fn HoverHome() -> Element {
+    rsx! {
+        Link { to: Route::Post { id: 1 }, prefetch: Prefetch::Hover, "post" }
+    }
+}
+
+#[component]
+fn BothHome() -> Element {
+    rsx! {
+        Link { to: Route::Post { id: 1 }, prefetch: Prefetch::Render, "first" }
+        Link { to: Route::Post { id: 2 }, prefetch: Prefetch::Render, "second" }
+    }
+}
+
+#[component]
+fn Post(id: u32) -> Element {
+    let title: String = use_loader_data();
+    rsx! {
+        p { "{title}" }
+    }
+}
+
+/// A document that records the links added to the head
+#[derive(Default)]
+struct HeadDocument {
+    links: RefCell<Vec<(String, String)>>,
+}
+
+impl Document for HeadDocument {
+    fn eval(&self, js: String) -> Eval {
+        NoOpDocument.eval(js)
+    }
+
+    fn create_link(&self, props: LinkProps) {
+        self.links.borrow_mut().push((
+            props.rel.unwrap_or_default(),
+            props.href.unwrap_or_default(),
+        ));
+    }
+}
+
+fn prepare(path: &str) -> (VirtualDom, Rc<HeadDocument>) {
+    let vdom = VirtualDom::new(|| rsx! { Router::<Route> {} });
+    vdom.provide_root_context(Rc::new(MemoryHistory::with_initial_path(path)) as Rc<dyn History>);
+    let document = Rc::new(HeadDocument::default());
+    vdom.provide_root_context(document.clone() as Rc<dyn Document>);
+    (vdom, document)
+}
+
+#[tokio::test]
+async fn render_prefetch_loads_the_route_before_navigating() {
+    let (mut vdom, document) = prepare("/");
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), r#"<a href="/post/1">post</a>"#);
+    assert_eq!(
+        *document.links.borrow(),
+        [("prefetch".to_string(), "/assets/post.css".to_string())]
+    );
+
+    settle(&mut vdom).await;
+    assert_eq!(take_log(), ["start post 1", "end post 1"]);
+
+    vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            root_router().unwrap().push(Route::Post { id: 1 });
+        })
+    });
+    settle(&mut vdom).await;
+    assert_eq!(dioxus_ssr::render(&vdom), "<p>Post 1</p>");
+    // The data from the prefetch is reused
+    assert!(take_log().is_empty());
+}
+
+#[tokio::test]
+async fn hover_prefetch_waits_for_the_pointer() {
+    let (mut vdom, document) = prepare("/hover");
+    vdom.rebuild_in_place();
+    settle(&mut vdom).await;
+    assert_eq!(dioxus_ssr::render(&vdom), r#"<a href="/post/1">post</a>"#);
+    assert!(document.links.borrow().is_empty());
+    assert!(take_log().is_empty());
+}
+
+#[tokio::test]
+async fn unused_prefetches_are_dropped_after_navigating() {
+    let (mut vdom, _) = prepare("/both");
+    vdom.rebuild_in_place();
+    settle(&mut vdom).await;
+    assert_eq!(
+        take_log(),
+        ["start post 1", "start post 2", "end post 1", "end post 2"]
+    );
+
+    let navigate = |vdom: &mut VirtualDom, id: u32| {
+        vdom.in_runtime(|| {
+            ScopeId::ROOT.in_runtime(|| {
+                root_router().unwrap().push(Route::Post { id });
+            })
+        });
+    };
+    navigate(&mut vdom, 1);
+    settle(&mut vdom).await;
+    assert_eq!(dioxus_ssr::render(&vdom), "<p>Post 1</p>");
+    assert!(take_log().is_empty());
+
+    // The prefetched data of the second post was dropped with the first navigation
+    navigate(&mut vdom, 2);
+    settle(&mut vdom).await;
+    assert_eq!(dioxus_ssr::render(&vdom), "<p>Post 2</p>");
+    assert_eq!(take_log(), ["start post 2", "end post 2"]);
+}
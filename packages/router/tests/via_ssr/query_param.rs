@@ -0,0 +1,190 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::prelude::NavigationBlocker;
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/search")]
+    Search {},
+}
+
+#[derive(Clone, Copy)]
+struct Params {
+    page: Signal<Option<Signal<u32>>>,
+    tab: Signal<Option<Signal<String>>>,
+    query_renders: Signal<u32>,
+    blocked: Signal<bool>,
+    blocker: Signal<Option<NavigationBlocker<Route>>>,
+}
+
+#[component]
+fn Search() -> Element {
+    let mut params = use_context::<Params>();
+    let page = use_query_param::<u32>("page");
+    let tab = use_query_param_push::<String>("tab");
+    let blocked = params.blocked;
+    let blocker = use_navigation_blocker(move |_: Route, _| blocked());
+    use_hook(|| {
+        params.page.set(Some(page));
+        params.tab.set(Some(tab));
+        params.blocker.set(Some(blocker));
+    });
+    rsx! {
+        p { "page {page}" }
+        p { "tab {tab}" }
+        SearchQuery {}
+    }
+}
+
+#[component]
+fn SearchQuery() -> Element {
+    let query = use_query_param::<String>("q");
+    let renders = use_context::<Params>().query_renders;
+    *renders.write_unchecked() += 1;
+    rsx! {
+        p { "query {query}" }
+    }
+}
+
+struct Test {
+    vdom: VirtualDom,
+    params: Params,
+    history: Rc<MemoryHistory>,
+}
+
+impl Test {
+    async fn new(path: &str) -> Self {
+        let mut vdom = VirtualDom::new(|| {
+            use_context_provider(|| Params {
+                page: Signal::new(None),
+                tab: Signal::new(None),
+                query_renders: Signal::new(0),
+                blocked: Signal::new(false),
+                blocker: Signal::new(None),
+            });
+            rsx! { Router::<Route> {} }
+        });
+        let history = Rc::new(MemoryHistory::with_initial_path(path));
+        vdom.provide_root_context(history.clone() as Rc<dyn History>);
+        vdom.rebuild_in_place();
+        let params = vdom.in_runtime(|| ScopeId::APP.consume_context::<Params>().unwrap());
+        let mut test = Self {
+            vdom,
+            params,
+            history,
+        };
+        test.settle().await;
+        test
+    }
+
+    async fn settle(&mut self) {
+        crate::settle(&mut self.vdom).await;
+    }
+
+    fn html(&self) -> String {
+        dioxus_ssr::render(&self.vdom)
+    }
+
+    fn query_renders(&self) -> u32 {
+        self.vdom.in_runtime(|| *self.params.query_renders.peek())
+    }
+
+    async fn set_page(&mut self, page: u32) {
+        self.vdom
+            .in_runtime(|| self.params.page.cloned().unwrap().set(page));
+        self.settle().await;
+    }
+
+    async fn set_tab(&mut self, tab: &str) {
+        self.vdom
+            .in_runtime(|| self.params.tab.cloned().unwrap().set(tab.to_string()));
+        self.settle().await;
+    }
+}
+
+#[tokio::test]
+async fn reads_params_from_the_url() {
+    let test = Test::new("/search?q=dioxus%20router&page=2").await;
+    assert_eq!(
+        test.html(),
+        "<p>page 2</p><p>tab </p><p>query dioxus router</p>"
+    );
+
+    // Missing and invalid parameters use the default value
+    let test = Test::new("/search?page=two").await;
+    assert_eq!(test.html(), "<p>page 0</p><p>tab </p><p>query </p>");
+}
+
+#[tokio::test]
+async fn writing_replaces_the_param_in_the_url() {
+    let mut test = Test::new("/search?q=rust&page=2#results").await;
+    let renders = test.query_renders();
+
+    test.set_page(3).await;
+    assert_eq!(
+        test.history.current_route(),
+        "/search?q=rust&page=3#results"
+    );
+    assert!(!test.history.can_go_back());
+    assert_eq!(test.html(), "<p>page 3</p><p>tab </p><p>query rust</p>");
+    // Components that only read other parameters don't rerender
+    assert_eq!(test.query_renders(), renders);
+}
+
+#[tokio::test]
+async fn push_params_add_history_entries() {
+    let mut test = Test::new("/search?q=rust").await;
+
+    test.set_tab("docs").await;
+    assert_eq!(test.history.current_route(), "/search?q=rust&tab=docs");
+    assert!(test.history.can_go_back());
+
+    // Going back updates the signal from the url
+    test.vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| root_router().unwrap().go_back());
+    });
+    test.settle().await;
+    assert_eq!(test.history.current_route(), "/search?q=rust");
+    assert_eq!(test.html(), "<p>page 0</p><p>tab </p><p>query rust</p>");
+}
+
+#[tokio::test]
+async fn url_changes_update_the_signal() {
+    let mut test = Test::new("/search?q=rust&page=1").await;
+    let renders = test.query_renders();
+
+    test.vdom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            root_router().unwrap().push(NavigationTarget::Internal(
+                "/search?q=rust&page=5".to_string(),
+            ));
+        })
+    });
+    test.settle().await;
+    assert_eq!(test.html(), "<p>page 5</p><p>tab </p><p>query rust</p>");
+    assert_eq!(test.query_renders(), renders);
+}
+
+#[tokio::test]
+async fn blocked_writes_keep_the_url_value() {
+    let mut test = Test::new("/search?page=2").await;
+    let mut blocked = test.params.blocked;
+    test.vdom.in_runtime(|| blocked.set(true));
+
+    // The signal goes back to the value in the url when the navigation is blocked
+    test.set_page(3).await;
+    assert_eq!(test.history.current_route(), "/search?page=2");
+    assert_eq!(test.html(), "<p>page 2</p><p>tab </p><p>query </p>");
+
+    // Proceeding updates the signal from the url
+    let blocker = test
+        .vdom
+        .in_runtime(|| test.params.blocker.cloned().unwrap());
+    test.vdom
+        .in_runtime(|| ScopeId::ROOT.in_runtime(|| blocker.proceed()));
+    test.settle().await;
+    assert_eq!(test.history.current_route(), "/search?page=3");
+    assert_eq!(test.html(), "<p>page 3</p><p>tab </p><p>query </p>");
+}
@@ -0,0 +1,204 @@
+use std::{rc::Rc, time::Duration};
+
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::transition::{RouteTransition, Transition};
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Shell)]
+        #[route("/")]
+        Home {},
+        #[route("/about")]
+        About {},
+    #[end_layout]
+    #[route("/post/:id")]
+    Post { id: u32 },
+}
+
+/// The transition of the last route that rendered
+#[derive(Clone, Copy)]
+struct LastTransition(Signal<Option<RouteTransition>>);
+
+fn use_phase() -> String {
+    let transition = use_route_transition();
+    let mut last = use_context::<LastTransition>();
+    use_hook(|| last.0.set(Some(transition)));
+    format!("{:?}", transition.phase())
+}
+
+#[component]
+fn Shell() -> Element {
+    let phase = use_phase();
+    rsx! {
+        nav { "{phase}" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Home() -> Element {
+    let phase = use_phase();
+    rsx! { p { "home {phase}" } }
+}
+
+#[component]
+fn About() -> Element {
+    let phase = use_phase();
+    rsx! { p { "about {phase}" } }
+}
+
+#[component]
+fn Post(id: u32) -> Element {
+    let phase = use_phase();
+    rsx! { p { "post {id} {phase}" } }
+}
+
+/// A signal the transition of the test can wait for
+#[derive(Clone, Copy)]
+struct Ready(Signal<bool>);
+
+struct Test {
+    vdom: VirtualDom,
+    last: LastTransition,
+    ready: Ready,
+}
+
+impl Test {
+    async fn new(path: &str, transition: fn(ReadOnlySignal<bool>) -> Transition) -> Self {
+        let mut vdom = VirtualDom::new_with_props(
+            |transition: fn(ReadOnlySignal<bool>) -> Transition| {
+                use_context_provider(|| LastTransition(Signal::new(None)));
+                let ready = use_context_provider(|| Ready(Signal::new(false)));
+                rsx! {
+                    Router::<Route> {
+                        config: move || RouterConfig::default().transition(transition(ready.0.into())),
+                    }
+                }
+            },
+            transition,
+        );
+        vdom.provide_root_context(
+            Rc::new(MemoryHistory::with_initial_path(path)) as Rc<dyn History>
+        );
+        vdom.rebuild_in_place();
+        let last = vdom.in_runtime(|| ScopeId::APP.consume_context::<LastTransition>().unwrap());
+        let ready = vdom.in_runtime(|| ScopeId::APP.consume_context::<Ready>().unwrap());
+        let mut test = Self { vdom, last, ready };
+        test.settle().await;
+        test
+    }
+
+    async fn settle(&mut self) {
+        crate::settle(&mut self.vdom).await;
+    }
+
+    /// Wait until the timer of the running transition ends it
+    async fn wait_for_timer(&mut self) {
+        self.vdom.wait_for_work().await;
+        self.settle().await;
+    }
+
+    async fn push(&mut self, route: Route) {
+        self.vdom.in_runtime(|| {
+            ScopeId::ROOT.in_runtime(|| {
+                root_router().unwrap().push(route);
+            })
+        });
+        self.settle().await;
+    }
+
+    fn html(&self) -> String {
+        dioxus_ssr::render(&self.vdom)
+    }
+}
+
+#[tokio::test]
+async fn exiting_route_stays_mounted_for_the_duration() {
+    let mut test = Test::new("/", |_| Transition::duration(Duration::from_millis(200))).await;
+    assert_eq!(test.html(), "<nav>Entered</nav><p>home Entered</p>");
+
+    // The layout stays mounted, only the outlet inside of it transitions
+    test.push(Route::About {}).await;
+    assert_eq!(
+        test.html(),
+        "<nav>Entered</nav><p>home Exiting</p><p>about Entering</p>"
+    );
+
+    test.wait_for_timer().await;
+    assert_eq!(test.html(), "<nav>Entered</nav><p>about Entered</p>");
+}
+
+#[tokio::test]
+async fn outlets_inside_an_exiting_route_keep_the_old_route() {
+    let mut test = Test::new("/about", |_| {
+        Transition::duration(Duration::from_millis(200))
+    })
+    .await;
+
+    test.push(Route::Post { id: 1 }).await;
+    assert_eq!(
+        test.html(),
+        "<nav>Exiting</nav><p>about Exiting</p><p>post 1 Entering</p>"
+    );
+
+    // Changing the parameters of a route doesn't start a new transition
+    test.push(Route::Post { id: 2 }).await;
+    assert_eq!(
+        test.html(),
+        "<nav>Exiting</nav><p>about Exiting</p><p>post 2 Entering</p>"
+    );
+
+    test.wait_for_timer().await;
+    assert_eq!(test.html(), "<p>post 2 Entered</p>");
+}
+
+#[tokio::test]
+async fn manual_transitions_end_when_finished() {
+    let mut test = Test::new("/", |_| Transition::manual()).await;
+
+    test.push(Route::About {}).await;
+    assert_eq!(
+        test.html(),
+        "<nav>Entered</nav><p>home Exiting</p><p>about Entering</p>"
+    );
+
+    let transition = test.vdom.in_runtime(|| test.last.0.cloned().unwrap());
+    test.vdom.in_runtime(|| transition.finish());
+    test.settle().await;
+    assert_eq!(test.html(), "<nav>Entered</nav><p>about Entered</p>");
+}
+
+#[tokio::test]
+async fn transitions_end_when_the_signal_is_true() {
+    let mut test = Test::new("/", Transition::until).await;
+
+    test.push(Route::About {}).await;
+    assert_eq!(
+        test.html(),
+        "<nav>Entered</nav><p>home Exiting</p><p>about Entering</p>"
+    );
+
+    let mut ready = test.ready.0;
+    test.vdom.in_runtime(|| ready.set(true));
+    test.settle().await;
+    assert_eq!(test.html(), "<nav>Entered</nav><p>about Entered</p>");
+
+    // The next transition ends right away because the signal is already true
+    test.push(Route::Home {}).await;
+    assert_eq!(test.html(), "<nav>Entered</nav><p>home Entered</p>");
+}
+
+#[tokio::test]
+async fn routes_are_replaced_without_a_transition() {
+    let mut test = Test::new("/", |_| Transition::default()).await;
+
+    test.push(Route::About {}).await;
+    assert_eq!(test.html(), "<nav>Entered</nav><p>about Entered</p>");
+
+    // View transitions fall back to the rest of the transition without a renderer that supports them
+    let mut test = Test::new("/", |_| Transition::default().view_transition()).await;
+    test.push(Route::Post { id: 1 }).await;
+    assert_eq!(test.html(), "<p>post 1 Entered</p>");
+}
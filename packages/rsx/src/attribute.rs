@@ -237,8 +237,7 @@ impl Attribute {
                     AttributeName::BuiltIn(name) => {
                         let event_tokens_is_closure =
                             syn::parse2::<ExprClosure>(tokens.to_token_stream()).is_ok();
-                        let function_name =
-                            quote_spanned! { tokens.span() => dioxus_elements::events::#name };
+                        let function_name = event_function(name, tokens.span());
                         let function = if event_tokens_is_closure {
                             // If we see an explicit closure, we can call the `call_with_explicit_closure` version of the event for better type inference
                             quote_spanned! { tokens.span() => #function_name::call_with_explicit_closure }
@@ -253,7 +252,11 @@ impl Attribute {
                     AttributeName::Spread(_) => unreachable!("Handled elsewhere in the macro"),
                 },
                 _ => {
-                    quote_spanned! { value.span() => dioxus_elements::events::#name(#value) }
+                    let function_name = match name {
+                        AttributeName::BuiltIn(name) => event_function(name, value.span()),
+                        _ => quote_spanned! { value.span() => dioxus_elements::events::#name },
+                    };
+                    quote_spanned! { value.span() => #function_name(#value) }
                 }
             }
         };
@@ -341,6 +344,19 @@ impl Attribute {
     }
 }
 
+/// Get the path of the function that creates the listener for an event. Capture listeners like `onclick_capture` use
+/// the `capture` function of the event
+fn event_function(name: &Ident, span: proc_macro2::Span) -> TokenStream2 {
+    let as_string = name.to_string();
+    match as_string.strip_suffix("_capture") {
+        Some(event) => {
+            let event = Ident::new(event, name.span());
+            quote_spanned! { span => dioxus_elements::events::#event::capture }
+        }
+        None => quote_spanned! { span => dioxus_elements::events::#name },
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub enum AttributeName {
     Spread(Token![..]),
@@ -6,8 +6,11 @@
 //! See the [server_fn_macro] crate for more information.
 
 use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
+use quote::quote;
 use server_fn_macro::server_macro_impl;
 use syn::__private::ToTokens;
+use syn::{parse::Parser, punctuated::Punctuated, Token};
 
 /// Declares that a function is a [server function](https://docs.rs/server_fn/).
 /// This means that its body will only run on the server, i.e., when the `ssr`
@@ -39,7 +42,11 @@ use syn::__private::ToTokens;
 ///   relative to the prefix (defaults to the function name followed by unique hash)
 /// - `input`: the encoding for the arguments (defaults to `PostUrl`)
 /// - `output`: the encoding for the response (defaults to `Json`)
+///
+///   The encodings built into `server_fn` can be used without importing them. Use the binary `Cbor` or `MsgPack`
+///   encodings for large payloads, and `StreamingCbor` to return a stream of values with `CborStream`
 /// - `client`: a custom `Client` implementation that will be used for this server fn
+/// - `middleware`: a list of guards and tower layers that run before the server function, in the order they are listed
 /// - `encoding`: (legacy, may be deprecated in future) specifies the encoding, which may be one
 ///   of the following (not case sensitive)
 ///     - `"Url"`: `POST` request with URL-encoded arguments and JSON response
@@ -73,6 +80,31 @@ use syn::__private::ToTokens;
 /// }
 /// ```
 ///
+/// ## Binary and streaming encodings
+///
+/// JSON is slow to encode and decode for large or numeric payloads. You can switch to a binary encoding with the
+/// `input` and `output` arguments:
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// #[server(input = Cbor, output = MsgPack)]
+/// pub async fn normalize(samples: Vec<f32>) -> Result<Vec<f32>, ServerFnError> {
+///     let max = samples.iter().copied().fold(f32::MIN, f32::max);
+///     Ok(samples.into_iter().map(|sample| sample / max).collect())
+/// }
+/// ```
+///
+/// Server functions with the `StreamingCbor` output encoding return a stream of values that the client receives as
+/// soon as the server produces them. Use `use_server_stream` to read the values in a component:
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// #[server(output = StreamingCbor)]
+/// pub async fn readings() -> Result<CborStream<f64>, ServerFnError> {
+///     Ok(CborStream::from(futures::stream::iter([1.0, 2.0, 3.0])))
+/// }
+/// ```
+///
 /// ## Adding layers to server functions
 ///
 /// Layers allow you to transform the request and response of a server function. You can use layers
@@ -98,6 +130,28 @@ use syn::__private::ToTokens;
 /// }
 /// ```
 ///
+/// ## Guarding server functions
+///
+/// Guards are checks that run before the body of a server function. If a guard returns an error, the error is sent
+/// to the client and the server function never runs. A guard can be an async function that takes the server context
+/// or any type that implements `Guard`. Guards and layers can be declared together with the `middleware` argument:
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// async fn require_auth(context: DioxusServerContext) -> Result<(), ServerFnError> {
+///     match context.request_parts().headers.contains_key("authorization") {
+///         true => Ok(()),
+///         false => Err(ServerFnError::ServerError("Unauthorized".to_string())),
+///     }
+/// }
+///
+/// #[server(middleware = [require_auth, tower_http::timeout::TimeoutLayer::new(std::time::Duration::from_secs(5))])]
+/// pub async fn delete_post(id: u32) -> Result<(), ServerFnError> {
+///     // Only authorized requests get here
+///     Ok(())
+/// }
+/// ```
+///
 /// ## Extracting additional data from requests
 ///
 /// Server functions automatically handle serialization and deserialization of arguments and responses.
@@ -143,9 +197,23 @@ use syn::__private::ToTokens;
 /// ```
 #[proc_macro_attribute]
 pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+    let (args, middleware) = match split_middleware(args.into()) {
+        Ok(split) => split,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let mut body = proc_macro2::TokenStream::new();
+    // The list is chained into a single middleware attribute so it runs in the order it is written. Like `server_fn`,
+    // the middleware type is resolved from the fullstack prelude so the macro works without depending on `dioxus`
+    if let Some((first, rest)) = middleware.split_first() {
+        body.extend(quote! {
+            #[middleware(ServerFnMiddleware::new(#first) #(.and(#rest))*)]
+        });
+    }
+    body.extend(proc_macro2::TokenStream::from(s));
+
     match server_macro_impl(
-        args.into(),
-        s.into(),
+        resolve_server_fn_encodings(args),
+        body,
         Some(syn::parse_quote!(server_fn)),
         "/api",
         None,
@@ -155,3 +223,83 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
         Ok(s) => s.to_token_stream().into(),
     }
 }
+
+/// Remove the `middleware = [...]` argument from the arguments of the macro and parse the guards and layers in the list
+fn split_middleware(
+    args: proc_macro2::TokenStream,
+) -> syn::Result<(proc_macro2::TokenStream, Vec<syn::Expr>)> {
+    let tokens: Vec<TokenTree> = args.into_iter().collect();
+    let mut remaining = proc_macro2::TokenStream::new();
+    let mut middleware = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if let [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Group(list), rest @ ..] =
+            &tokens[i..]
+        {
+            if key == "middleware"
+                && eq.as_char() == '='
+                && list.delimiter() == proc_macro2::Delimiter::Bracket
+            {
+                let parser = Punctuated::<syn::Expr, Token![,]>::parse_terminated;
+                middleware.extend(parser.parse2(list.stream())?);
+                i += 3;
+                // Skip the comma after the list
+                if matches!(rest.first(), Some(TokenTree::Punct(punct)) if punct.as_char() == ',') {
+                    i += 1;
+                }
+                continue;
+            }
+        }
+        remaining.extend([tokens[i].clone()]);
+        i += 1;
+    }
+    Ok((remaining, middleware))
+}
+
+/// Encodings from `server_fn::codec` that can be used in the `input` and `output` arguments without importing them
+const SERVER_FN_ENCODINGS: &[&str] = &[
+    "PostUrl",
+    "GetUrl",
+    "Json",
+    "StreamingJson",
+    "Cbor",
+    "PostCbor",
+    "MsgPack",
+    "Streaming",
+    "StreamingText",
+    "MultipartFormData",
+];
+
+/// Replace `input = Cbor` and `output = Cbor` with the full path to the encoding in `server_fn::codec` so the
+/// built in encodings don't need to be imported. Only arguments that are exactly `input = <encoding>` or
+/// `output = <encoding>` are changed. Encodings that are already written as a path are left alone.
+fn resolve_server_fn_encodings(args: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let mut arguments: Vec<Vec<TokenTree>> = vec![Vec::new()];
+    for token in args {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => arguments.push(Vec::new()),
+            _ => arguments.last_mut().unwrap().push(token),
+        }
+    }
+
+    let arguments = arguments
+        .into_iter()
+        .map(|argument| match argument.as_slice() {
+            [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Ident(encoding)]
+                if (key == "input" || key == "output")
+                    && eq.as_char() == '='
+                    && SERVER_FN_ENCODINGS.contains(&encoding.to_string().as_str()) =>
+            {
+                quote! { #key = server_fn::codec::#encoding }
+            }
+            _ => argument.into_iter().collect(),
+        });
+    let mut resolved = proc_macro2::TokenStream::new();
+    for (i, argument) in arguments.enumerate() {
+        if i > 0 {
+            resolved.extend(quote! { , });
+        }
+        resolved.extend(argument);
+    }
+    resolved
+}
@@ -25,6 +25,9 @@ pub use memo::*;
 mod global;
 pub use global::*;
 
+mod store;
+pub use store::*;
+
 mod impls;
 
 pub use generational_box::{
@@ -155,6 +155,11 @@ impl<T: 'static> Memo<T> {
     pub fn id(&self) -> generational_box::GenerationalBoxId {
         self.inner.id()
     }
+
+    /// Get the scopes of the reactive contexts that are subscribed to this memo.
+    pub fn subscribers(&self) -> Vec<ScopeId> {
+        self.inner.subscribers()
+    }
 }
 
 impl<T> Readable for Memo<T>
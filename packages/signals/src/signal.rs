@@ -242,6 +242,19 @@ impl<T: 'static, S: Storage<SignalData<T>>> Signal<T, S> {
         self.inner.origin_scope()
     }
 
+    /// Get the scopes of the reactive contexts that are subscribed to this signal. This is useful for debugging tools
+    /// that show what will rerun when the signal changes.
+    pub fn subscribers(&self) -> Vec<ScopeId> {
+        let Ok(inner) = self.inner.try_read() else {
+            return Vec::new();
+        };
+        let subscribers = inner.subscribers.lock().unwrap();
+        subscribers
+            .iter()
+            .map(|reactive_context| reactive_context.origin_scope())
+            .collect()
+    }
+
     fn update_subscribers(&self) {
         {
             let inner = self.inner.read();
@@ -440,7 +453,7 @@ impl<T: 'static, S: Storage<SignalData<T>>> Writable for Signal<T, S> {
     fn try_write_unchecked(
         &self,
     ) -> Result<WritableRef<'static, Self>, generational_box::BorrowMutError> {
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "profiling"))]
         let origin = std::panic::Location::caller();
         self.inner.try_write_unchecked().map(|inner| {
             let borrow = S::map_mut(inner, |v| &mut v.value);
@@ -448,7 +461,7 @@ impl<T: 'static, S: Storage<SignalData<T>>> Writable for Signal<T, S> {
                 write: borrow,
                 drop_signal: Box::new(SignalSubscriberDrop {
                     signal: *self,
-                    #[cfg(debug_assertions)]
+                    #[cfg(any(debug_assertions, feature = "profiling"))]
                     origin,
                 }),
             }
@@ -621,8 +634,8 @@ impl<'de, T: serde::Deserialize<'de> + 'static, Store: Storage<SignalData<T>>>
 /// - T is the current type of the write
 /// - S is the storage type of the signal. This type determines if the signal is local to the current thread, or it can be shared across threads.
 pub struct Write<'a, T: ?Sized + 'static, S: AnyStorage = UnsyncStorage> {
-    write: S::Mut<'a, T>,
-    drop_signal: Box<dyn Any>,
+    pub(crate) write: S::Mut<'a, T>,
+    pub(crate) drop_signal: Box<dyn Any>,
 }
 
 impl<'a, T: ?Sized + 'static, S: AnyStorage> Write<'a, T, S> {
@@ -679,7 +692,7 @@ impl<T: ?Sized, S: AnyStorage> DerefMut for Write<'_, T, S> {
 
 struct SignalSubscriberDrop<T: 'static, S: Storage<SignalData<T>>> {
     signal: Signal<T, S>,
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "profiling"))]
     origin: &'static std::panic::Location<'static>,
 }
 
@@ -698,6 +711,11 @@ impl<T: 'static, S: Storage<SignalData<T>>> Drop for SignalSubscriberDrop<T, S>
                 self.signal,
             );
         }
+
+        // Record the write as the cause of any renders it triggers in the profiler
+        #[cfg(feature = "profiling")]
+        dioxus_core::profiling::with_write_origin(self.origin, || self.signal.update_subscribers());
+        #[cfg(not(feature = "profiling"))]
         self.signal.update_subscribers();
     }
 }
@@ -0,0 +1,454 @@
+use crate::{read::*, write::*, CopyValue, ReadableRef, WritableRef, Write};
+use crate::{read_impls, write_impls};
+use dioxus_core::prelude::*;
+use generational_box::{AnyStorage, BorrowResult, UnsyncStorage};
+use rustc_hash::FxHashMap;
+use std::{
+    any::Any,
+    collections::HashSet,
+    marker::PhantomData,
+    ops::Deref,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+/// A Copy state management solution with fine grained dependency tracking for the fields of a struct or the items of a [`Vec`].
+///
+/// A [`Signal`](crate::Signal) tracks the whole value. Writing to any part of it reruns everything that read it. A store
+/// is split into nested lenses. Each lens implements [`Readable`] and [`Writable`] for a part of the value, and writing
+/// to a lens only reruns the readers of that lens, the readers of the values that contain it, and the readers of the
+/// values inside of it.
+///
+/// Deriving `Store` on a struct adds a method for each field that returns the lens for that field:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Store, Clone)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// fn App() -> Element {
+///     let user = use_store(|| User { name: "Alice".to_string(), age: 30 });
+///
+///     rsx! {
+///         Name { name: user.name() }
+///         button {
+///             // Only the Age component will rerun when the age changes
+///             onclick: move |_| *user.age().write() += 1,
+///             "Happy birthday"
+///         }
+///         Age { age: user.age() }
+///     }
+/// }
+///
+/// #[component]
+/// fn Name(name: Store<String>) -> Element {
+///     rsx! { "{name}" }
+/// }
+///
+/// #[component]
+/// fn Age(age: Store<u32>) -> Element {
+///     rsx! { "{age}" }
+/// }
+/// ```
+///
+/// A store of a [`Vec`] has a lens for each item. Reading the length of the vec only reruns when items are added or
+/// removed, not when an item changes:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Store, Clone, PartialEq)]
+/// struct Todo {
+///     text: String,
+///     done: bool,
+/// }
+///
+/// fn App() -> Element {
+///     let mut todos = use_store(Vec::<Todo>::new);
+///
+///     rsx! {
+///         for todo in todos.iter() {
+///             TodoItem { todo }
+///         }
+///         button {
+///             onclick: move |_| todos.push(Todo { text: "New todo".to_string(), done: false }),
+///             "Add todo"
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn TodoItem(todo: Store<Todo>) -> Element {
+///     rsx! {
+///         input {
+///             r#type: "checkbox",
+///             checked: todo.done(),
+///             onchange: move |event| todo.done().set(event.checked()),
+///         }
+///         "{todo.text()}"
+///     }
+/// }
+/// ```
+pub struct Store<T: 'static> {
+    value: CopyValue<Box<dyn Any>>,
+    tree: CopyValue<StoreTree>,
+    node: usize,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static> Store<T> {
+    /// Create a new store owned by the current scope.
+    ///
+    /// Like [`Signal::new`](crate::Signal::new), this should generally only be called inside hooks. Use `use_store` to
+    /// create a store in a component.
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        Self {
+            value: CopyValue::new(Box::new(value)),
+            tree: CopyValue::new(StoreTree::new()),
+            node: StoreTree::ROOT,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Create a lens into a part of the value of this store. The lens implements [`Readable`] and [`Writable`] for the
+    /// part of the value the projections return.
+    ///
+    /// The key identifies the lens among the other lenses of this store, for example the index of a field or item. Every
+    /// call with the same key returns the same lens, so the projections are only used the first time the key is seen.
+    ///
+    /// This is used by the `Store` derive macro to create a lens for each field.
+    pub fn scope<U: 'static>(
+        &self,
+        key: usize,
+        project: impl Fn(&T) -> &U + 'static,
+        project_mut: impl Fn(&mut T) -> &mut U + 'static,
+    ) -> Store<U> {
+        let existing = self.tree.peek().nodes[self.node]
+            .children
+            .get(&key)
+            .copied();
+        let node = match existing {
+            Some(node) => node,
+            None => {
+                let project: Projection = Rc::new(move |value| project(downcast_ref(value)));
+                let project_mut: ProjectionMut =
+                    Rc::new(move |value| project_mut(downcast_mut(value)));
+                self.tree
+                    .write_unchecked()
+                    .insert(self.node, key, project, project_mut)
+            }
+        };
+        Store {
+            value: self.value,
+            tree: self.tree,
+            node,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the scope the store was created in.
+    pub fn origin_scope(&self) -> ScopeId {
+        self.value.origin_scope()
+    }
+
+    /// Subscribe the current reactive context to this lens
+    fn subscribe(&self, shallow: bool) {
+        if let Some(reactive_context) = ReactiveContext::current() {
+            let subscribers = {
+                let tree = self.tree.peek();
+                let node = &tree.nodes[self.node];
+                match shallow {
+                    true => node.shallow_subscribers.clone(),
+                    false => node.subscribers.clone(),
+                }
+            };
+            reactive_context.subscribe(subscribers);
+        }
+    }
+
+    /// Get the projections from the root of the store to this lens
+    fn projections(&self) -> Vec<(Projection, ProjectionMut)> {
+        let tree = self.tree.peek();
+        let mut projections = Vec::new();
+        let mut node = self.node;
+        while let Some(parent) = tree.nodes[node].parent {
+            projections.push(tree.nodes[node].projections.clone());
+            node = parent;
+        }
+        projections.reverse();
+        projections
+    }
+}
+
+impl<T: 'static> Store<Vec<T>> {
+    /// Get the lens for the item at an index. Reading the lens panics if the index is out of bounds.
+    pub fn index(&self, index: usize) -> Store<T> {
+        self.scope(index, move |vec| &vec[index], move |vec| &mut vec[index])
+    }
+
+    /// Get the lenses for every item in the vec. This only subscribes to items being added or removed.
+    pub fn iter(&self) -> impl Iterator<Item = Store<T>> {
+        let store = *self;
+        (0..self.len()).map(move |index| store.index(index))
+    }
+
+    /// Get the length of the vec. This only subscribes to items being added or removed, not to changes of the items.
+    pub fn len(&self) -> usize {
+        self.subscribe(true);
+        self.peek().len()
+    }
+
+    /// Check if the vec is empty. This only subscribes to items being added or removed, not to changes of the items.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: 'static> Readable for Store<T> {
+    type Target = T;
+    type Storage = UnsyncStorage;
+
+    #[track_caller]
+    fn try_read_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        self.subscribe(false);
+        self.try_peek_unchecked()
+    }
+
+    #[track_caller]
+    fn try_peek_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        let projections = self.projections();
+        self.value.try_read_unchecked().map(|value| {
+            UnsyncStorage::map(value, move |value| {
+                let mut value: &dyn Any = &**value;
+                for (project, _) in &projections {
+                    value = project(value);
+                }
+                downcast_ref(value)
+            })
+        })
+    }
+}
+
+impl<T: 'static> Writable for Store<T> {
+    type Mut<'a, R: ?Sized + 'static> = Write<'a, R, UnsyncStorage>;
+
+    fn map_mut<I: ?Sized, U: ?Sized + 'static, F: FnOnce(&mut I) -> &mut U>(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Self::Mut<'_, U> {
+        Write::map(ref_, f)
+    }
+
+    fn try_map_mut<
+        I: ?Sized + 'static,
+        U: ?Sized + 'static,
+        F: FnOnce(&mut I) -> Option<&mut U>,
+    >(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Option<Self::Mut<'_, U>> {
+        Write::filter_map(ref_, f)
+    }
+
+    fn downcast_lifetime_mut<'a: 'b, 'b, R: ?Sized + 'static>(
+        mut_: Self::Mut<'a, R>,
+    ) -> Self::Mut<'b, R> {
+        Write::downcast_lifetime(mut_)
+    }
+
+    #[track_caller]
+    fn try_write_unchecked(
+        &self,
+    ) -> Result<WritableRef<'static, Self>, generational_box::BorrowMutError> {
+        #[cfg(debug_assertions)]
+        let origin = std::panic::Location::caller();
+        let projections = self.projections();
+        self.value.try_write_unchecked().map(|value| {
+            let write = UnsyncStorage::map_mut(value, move |value| {
+                let mut value: &mut dyn Any = &mut **value;
+                for (_, project_mut) in &projections {
+                    value = project_mut(value);
+                }
+                downcast_mut(value)
+            });
+            Write {
+                write,
+                drop_signal: Box::new(StoreSubscriberDrop {
+                    tree: self.tree,
+                    node: self.node,
+                    #[cfg(debug_assertions)]
+                    origin,
+                }),
+            }
+        })
+    }
+}
+
+impl<T> IntoAttributeValue for Store<T>
+where
+    T: Clone + IntoAttributeValue,
+{
+    fn into_value(self) -> dioxus_core::AttributeValue {
+        self.with(|f| f.clone().into_value())
+    }
+}
+
+impl<T> IntoDynNode for Store<T>
+where
+    T: Clone + IntoDynNode,
+{
+    fn into_dyn_node(self) -> dioxus_core::DynamicNode {
+        self().into_dyn_node()
+    }
+}
+
+impl<T: 'static> PartialEq for Store<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.node == other.node
+    }
+}
+
+impl<T: 'static> Eq for Store<T> {}
+
+/// Allow calling a store with store() syntax
+///
+/// Currently only limited to copy types, though could probably specialize for string/arc/rc
+impl<T: Clone + 'static> Deref for Store<T> {
+    type Target = dyn Fn() -> T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { Readable::deref_impl(self) }
+    }
+}
+
+read_impls!(Store<T>);
+write_impls!(Store<T>);
+
+impl<T: 'static> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for Store<T> {}
+
+type Projection = Rc<dyn Fn(&dyn Any) -> &dyn Any>;
+type ProjectionMut = Rc<dyn Fn(&mut dyn Any) -> &mut dyn Any>;
+type Subscribers = Arc<Mutex<HashSet<ReactiveContext>>>;
+
+fn downcast_ref<T: 'static>(value: &dyn Any) -> &T {
+    value
+        .downcast_ref()
+        .expect("the projections of a store lens always return the type of the lens")
+}
+
+fn downcast_mut<T: 'static>(value: &mut dyn Any) -> &mut T {
+    value
+        .downcast_mut()
+        .expect("the projections of a store lens always return the type of the lens")
+}
+
+/// The lenses of a store and their subscribers. Lenses are never removed, so each lens is only created once.
+struct StoreTree {
+    nodes: Vec<StoreNode>,
+}
+
+struct StoreNode {
+    parent: Option<usize>,
+    children: FxHashMap<usize, usize>,
+    projections: (Projection, ProjectionMut),
+    // Readers of the whole value of the lens
+    subscribers: Subscribers,
+    // Readers of the shape of the value, like the length of a vec, that don't care about changes inside of the children
+    shallow_subscribers: Subscribers,
+}
+
+impl StoreTree {
+    const ROOT: usize = 0;
+
+    fn new() -> Self {
+        Self {
+            nodes: vec![StoreNode {
+                parent: None,
+                children: FxHashMap::default(),
+                projections: (Rc::new(|value| value), Rc::new(|value| value)),
+                subscribers: Default::default(),
+                shallow_subscribers: Default::default(),
+            }],
+        }
+    }
+
+    fn insert(
+        &mut self,
+        parent: usize,
+        key: usize,
+        project: Projection,
+        project_mut: ProjectionMut,
+    ) -> usize {
+        let node = self.nodes.len();
+        self.nodes.push(StoreNode {
+            parent: Some(parent),
+            children: FxHashMap::default(),
+            projections: (project, project_mut),
+            subscribers: Default::default(),
+            shallow_subscribers: Default::default(),
+        });
+        self.nodes[parent].children.insert(key, node);
+        node
+    }
+
+    /// Get the subscribers that need to rerun after the value of a lens is written to. This is every reader of the lens
+    /// and the lenses inside of it, and the readers of the whole value of every lens that contains it.
+    fn written_subscribers(&self, node: usize) -> Vec<Subscribers> {
+        let mut subscribers = Vec::new();
+        let mut ancestor = self.nodes[node].parent;
+        while let Some(parent) = ancestor {
+            subscribers.push(self.nodes[parent].subscribers.clone());
+            ancestor = self.nodes[parent].parent;
+        }
+        let mut descendants = vec![node];
+        while let Some(node) = descendants.pop() {
+            let node = &self.nodes[node];
+            subscribers.push(node.subscribers.clone());
+            subscribers.push(node.shallow_subscribers.clone());
+            descendants.extend(node.children.values());
+        }
+        subscribers
+    }
+}
+
+struct StoreSubscriberDrop {
+    tree: CopyValue<StoreTree>,
+    node: usize,
+    #[cfg(debug_assertions)]
+    origin: &'static std::panic::Location<'static>,
+}
+
+#[allow(clippy::no_effect)]
+impl Drop for StoreSubscriberDrop {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        {
+            tracing::trace!(
+                "Write on store at {} finished, updating subscribers",
+                self.origin
+            );
+            crate::warnings::signal_write_in_component_body(self.origin);
+        }
+        let Ok(tree) = self.tree.try_peek() else {
+            return;
+        };
+        let written = tree.written_subscribers(self.node);
+        drop(tree);
+        for subscribers in written {
+            // We cannot hold the subscribers lock while calling mark_dirty, because mark_dirty can run user code which may cause a new subscriber to be added
+            #[allow(clippy::mutable_key_type)]
+            let mut taken = std::mem::take(&mut *subscribers.lock().unwrap());
+            taken.retain(|reactive_context| reactive_context.mark_dirty());
+            subscribers.lock().unwrap().extend(taken);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! The profiler records every render and the signal write that caused it. Run with `--features profiling`.
+#![cfg(feature = "profiling")]
+
+use dioxus::prelude::*;
+use dioxus_core::{profiling::RenderCause, NoOpMutations};
+use std::{cell::Cell, rc::Rc};
+
+fn app() -> Element {
+    let count = use_signal(|| 0);
+    let label = use_signal(|| "count");
+    provide_context(count);
+    let renders = use_hook(|| Rc::new(Cell::new(0)));
+    renders.set(renders.get() + 1);
+    rsx! {
+        Label { label }
+        Counter {}
+        RenderCount { count: renders.get() }
+    }
+}
+
+#[component]
+fn Label(label: ReadOnlySignal<&'static str>) -> Element {
+    rsx! { "{label}" }
+}
+
+#[component]
+fn RenderCount(count: usize) -> Element {
+    rsx! { "{count}" }
+}
+
+#[component]
+fn Counter() -> Element {
+    let count = use_context::<Signal<i32>>();
+    rsx! { "{count}" }
+}
+
+fn write_count(dom: &VirtualDom) {
+    dom.in_runtime(|| {
+        let mut count = ScopeId::APP.consume_context::<Signal<i32>>().unwrap();
+        count += 1;
+    })
+}
+
+#[test]
+fn records_first_render() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    let profile = dom.take_render_profile();
+    assert_eq!(profile.scope_render_count(ScopeId::APP), 1);
+    assert_eq!(profile.render_count("Label"), 1);
+    assert_eq!(profile.render_count("profiling::Counter"), 1);
+    assert!(profile
+        .records()
+        .iter()
+        .all(|record| record.causes == [RenderCause::Mount]));
+
+    // Taking the profile clears it
+    assert!(dom.render_profile().records().is_empty());
+}
+
+#[test]
+fn records_the_write_that_caused_a_render() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.take_render_profile();
+
+    write_count(&dom);
+    dom.render_immediate(&mut NoOpMutations);
+
+    let profile = dom.take_render_profile();
+    // Only the component that reads the signal reruns
+    assert_eq!(profile.render_count("Counter"), 1);
+    assert_eq!(profile.render_count("Label"), 0);
+    assert_eq!(profile.scope_render_count(ScopeId::APP), 0);
+
+    let record = profile.renders_of("Counter").next().unwrap();
+    let [RenderCause::Write(location)] = record.causes[..] else {
+        panic!("expected a single write, got {:?}", record.causes);
+    };
+    assert_eq!(location.file(), file!());
+}
+
+#[test]
+fn records_manual_updates_and_props_changes() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.take_render_profile();
+
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut NoOpMutations);
+
+    let profile = dom.take_render_profile();
+    let causes: Vec<_> = profile
+        .records()
+        .iter()
+        .map(|record| (record.scope, record.causes.clone()))
+        .collect();
+    let render_count = profile.renders_of("RenderCount").next().unwrap().scope;
+    // The other children are memoized and their props didn't change
+    assert_eq!(
+        causes,
+        [
+            (ScopeId::APP, vec![RenderCause::MarkedDirty]),
+            (render_count, vec![RenderCause::PropsChanged]),
+        ]
+    );
+}
+
+#[test]
+fn exports_chrome_traces() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    write_count(&dom);
+    dom.render_immediate(&mut NoOpMutations);
+
+    let trace: serde_json::Value =
+        serde_json::from_str(&dom.render_profile().to_chrome_trace()).unwrap();
+    let events = trace["traceEvents"].as_array().unwrap();
+    assert_eq!(events.len(), dom.render_profile().records().len());
+
+    let last = events.last().unwrap();
+    assert_eq!(last["ph"], "X");
+    assert!(last["name"].as_str().unwrap().ends_with("Counter"));
+    assert!(last["args"]["causes"][0]
+        .as_str()
+        .unwrap()
+        .starts_with("write at "));
+}
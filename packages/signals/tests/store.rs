@@ -0,0 +1,149 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_signals::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+#[derive(Store, Clone, PartialEq)]
+struct Profile {
+    user: User,
+    todos: Vec<String>,
+}
+
+#[derive(Store, Clone, PartialEq)]
+struct User {
+    name: String,
+    age: u32,
+}
+
+thread_local! {
+    static RUNS: RefCell<HashMap<&'static str, usize>> = RefCell::new(HashMap::new());
+}
+
+fn record_run(name: &'static str) {
+    RUNS.with(|runs| *runs.borrow_mut().entry(name).or_default() += 1);
+}
+
+fn runs(name: &'static str) -> usize {
+    RUNS.with(|runs| runs.borrow().get(name).copied().unwrap_or_default())
+}
+
+#[test]
+fn writing_a_field_only_reruns_readers_of_that_field() {
+    let mut dom = VirtualDom::new(|| {
+        let profile = use_store(|| Profile {
+            user: User {
+                name: "Alice".to_string(),
+                age: 30,
+            },
+            todos: vec!["Write tests".to_string()],
+        });
+
+        match generation() {
+            1 => profile.user().name().set("Bob".to_string()),
+            2 => *profile.user().age().write() += 1,
+            3 => profile.todos().push("Ship it".to_string()),
+            4 => profile.todos().index(0).set("Write more tests".to_string()),
+            _ => {}
+        }
+
+        rsx! {
+            Name { name: profile.user().name() }
+            Age { age: profile.user().age() }
+            Whole { profile }
+            TodoCount { todos: profile.todos() }
+            FirstTodo { todo: profile.todos().index(0) }
+        }
+    });
+
+    #[component]
+    fn Name(name: Store<String>) -> Element {
+        record_run("name");
+        rsx! { "{name}" }
+    }
+
+    #[component]
+    fn Age(age: Store<u32>) -> Element {
+        record_run("age");
+        rsx! { "{age}" }
+    }
+
+    #[component]
+    fn Whole(profile: Store<Profile>) -> Element {
+        record_run("whole");
+        let profile = profile.read();
+        rsx! { "{profile.user.name}" }
+    }
+
+    #[component]
+    fn TodoCount(todos: Store<Vec<String>>) -> Element {
+        record_run("todo count");
+        rsx! { "{todos.len()}" }
+    }
+
+    #[component]
+    fn FirstTodo(todo: Store<String>) -> Element {
+        record_run("first todo");
+        rsx! { "{todo}" }
+    }
+
+    let mut rerender = |dom: &mut VirtualDom| {
+        dom.mark_dirty(ScopeId::APP);
+        dom.render_immediate(&mut NoOpMutations);
+        dom.render_immediate(&mut NoOpMutations);
+    };
+    let counts = || {
+        [
+            runs("name"),
+            runs("age"),
+            runs("whole"),
+            runs("todo count"),
+            runs("first todo"),
+        ]
+    };
+
+    dom.rebuild_in_place();
+    assert_eq!(counts(), [1, 1, 1, 1, 1]);
+
+    // Writing to the name reruns the readers of the name and the whole profile
+    rerender(&mut dom);
+    assert_eq!(counts(), [2, 1, 2, 1, 1]);
+
+    rerender(&mut dom);
+    assert_eq!(counts(), [2, 2, 3, 1, 1]);
+
+    // Pushing to the vec changes the length and may move every item
+    rerender(&mut dom);
+    assert_eq!(counts(), [2, 2, 4, 2, 2]);
+
+    // Changing an item doesn't change the length of the vec
+    rerender(&mut dom);
+    assert_eq!(counts(), [2, 2, 5, 2, 3]);
+}
+
+#[test]
+fn lenses_are_reused() {
+    let mut dom = VirtualDom::new(|| rsx! {});
+    dom.rebuild_in_place();
+    dom.in_runtime(|| {
+        ScopeId::APP.in_runtime(|| {
+            let profile = Store::new(Profile {
+                user: User {
+                    name: "Alice".to_string(),
+                    age: 30,
+                },
+                todos: Vec::new(),
+            });
+
+            assert_eq!(profile.user().name(), profile.user().name());
+            assert_ne!(profile.user().name(), profile.todos().index(0));
+            assert_eq!(profile.user().age().cloned(), 30);
+
+            let mut name = profile.user().name();
+            name.write().push_str(" Smith");
+            assert_eq!(profile.read().user.name, "Alice Smith");
+        })
+    });
+}
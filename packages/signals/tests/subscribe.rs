@@ -93,3 +93,37 @@ fn reading_subscribes() {
         }
     }
 }
+
+#[test]
+fn dropped_readers_unsubscribe() {
+    fn app() -> Element {
+        let signal = use_signal(|| 0);
+        let show = use_signal(|| true);
+        use_context_provider(|| (signal, show));
+
+        rsx! {
+            if show() {
+                Reader { signal }
+            }
+        }
+    }
+
+    #[component]
+    fn Reader(signal: Signal<usize>) -> Element {
+        rsx! { "{signal}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let (signal, mut show) = dom.in_runtime(|| {
+        ScopeId::APP
+            .consume_context::<(Signal<usize>, Signal<bool>)>()
+            .unwrap()
+    });
+    assert_eq!(dom.in_runtime(|| signal.subscribers().len()), 1);
+
+    // Once the reader is unmounted, the signal no longer holds onto its reactive context
+    dom.in_runtime(|| show.set(false));
+    dom.render_immediate(&mut NoOpMutations);
+    assert!(dom.in_runtime(|| signal.subscribers().is_empty()));
+}
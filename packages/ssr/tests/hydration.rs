@@ -65,6 +65,23 @@ fn listeners() {
     );
 }
 
+#[test]
+fn capture_listeners() {
+    fn app() -> Element {
+        rsx! {
+            div { onclick_capture: |_| {}, onfocus_capture: |_| {} }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::pre_render(&dom),
+        r#"<div data-node-hydration="0,click:capture:1,focus:capture:0"></div>"#
+    );
+}
+
 #[test]
 fn text_nodes() {
     fn app() -> Element {
@@ -0,0 +1,650 @@
+//! An in-memory DOM that the mutations of a [`VirtualDom`](dioxus_core::VirtualDom) are applied to.
+
+use askama_escape::{escape, Html};
+use dioxus_core::{
+    AttributeValue, ElementId, Template, TemplateAttribute, TemplateNode, WriteMutations,
+};
+use rustc_hash::FxHashMap;
+use std::fmt::{Debug, Display, Write};
+
+/// Attributes that are removed instead of set when they are set to a falsy value. This matches the attributes the
+/// interpreter treats as booleans.
+const BOOL_ATTRS: &[&str] = &[
+    "allowfullscreen",
+    "allowpaymentrequest",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+    "truespeed",
+    "webkitdirectory",
+];
+
+/// The id of a node in a [`Document`]. Unlike an [`ElementId`], a node id is never reused for another node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub(crate) usize);
+
+#[derive(Debug)]
+pub(crate) enum NodeKind {
+    Root,
+    Element(ElementData),
+    Text(String),
+    Placeholder,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ElementData {
+    pub(crate) tag: &'static str,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) styles: Vec<(String, String)>,
+    pub(crate) inner_html: Option<String>,
+    pub(crate) listeners: Vec<&'static str>,
+}
+
+impl ElementData {
+    fn set_attribute(&mut self, name: &str, value: Option<String>) {
+        let existing = self.attributes.iter().position(|(key, _)| key == name);
+        match (existing, value) {
+            (Some(index), Some(value)) => self.attributes[index].1 = value,
+            (None, Some(value)) => self.attributes.push((name.to_string(), value)),
+            (Some(index), None) => {
+                self.attributes.remove(index);
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn set_style(&mut self, name: &str, value: Option<String>) {
+        self.styles.retain(|(key, _)| key != name);
+        if let Some(value) = value {
+            self.styles.push((name.to_string(), value));
+        }
+    }
+
+    /// The value of an attribute. Styles set with the style namespace are merged into the `style` attribute.
+    fn attribute(&self, name: &str) -> Option<String> {
+        let value = self
+            .attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone());
+
+        if name != "style" || self.styles.is_empty() {
+            return value;
+        }
+
+        let mut style = value.unwrap_or_default();
+        for (key, value) in &self.styles {
+            if !style.is_empty() && !style.ends_with(';') {
+                style.push(';');
+            }
+            write!(style, "{key}:{value};").unwrap();
+        }
+        Some(style)
+    }
+
+    /// Every attribute with the styles merged into the `style` attribute
+    fn attributes(&self) -> Vec<(String, String)> {
+        let mut attributes = self.attributes.clone();
+        if let Some(style) = self.attribute("style").filter(|_| !self.styles.is_empty()) {
+            match attributes.iter_mut().find(|(key, _)| key == "style") {
+                Some((_, value)) => *value = style,
+                None => attributes.push(("style".to_string(), style)),
+            }
+        }
+        attributes
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Node {
+    pub(crate) kind: NodeKind,
+    pub(crate) parent: Option<NodeId>,
+    pub(crate) children: Vec<NodeId>,
+    pub(crate) element_id: Option<ElementId>,
+}
+
+/// An in-memory DOM tree that mutations from a [`VirtualDom`](dioxus_core::VirtualDom) are written to.
+///
+/// The document follows the same rules as the interpreter used by the web and desktop renderers, so the tree matches
+/// what the component would render in a browser, minus anything that requires a browser like layout or focus.
+pub struct Document {
+    pub(crate) nodes: Vec<Node>,
+    elements: FxHashMap<ElementId, NodeId>,
+    stack: Vec<NodeId>,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Document {
+    /// Create an empty document with just a root node
+    pub fn new() -> Self {
+        let mut document = Self {
+            nodes: Vec::new(),
+            elements: FxHashMap::default(),
+            stack: Vec::new(),
+        };
+        let root = document.create(NodeKind::Root);
+        document.assign(root, ElementId(0));
+        document
+    }
+
+    /// The root node of the document. The roots of the app are the children of this node.
+    pub fn root(&self) -> NodeRef<'_> {
+        self.node(NodeId(0))
+    }
+
+    /// Get a node by its id
+    ///
+    /// # Panics
+    ///
+    /// This panics if the node id is from a different document
+    pub fn node(&self, id: NodeId) -> NodeRef<'_> {
+        assert!(
+            id.0 < self.nodes.len(),
+            "{id:?} is not part of this document"
+        );
+        NodeRef { document: self, id }
+    }
+
+    /// Get the node the virtual dom currently has mounted with an element id
+    pub fn get_element(&self, id: ElementId) -> Option<NodeRef<'_>> {
+        self.elements.get(&id).map(|&id| self.node(id))
+    }
+
+    /// Render the document to a string of html without any whitespace between nodes
+    pub fn to_html(&self) -> String {
+        self.root().to_html()
+    }
+
+    /// Render the document to indented html with one node per line. This is the format used for snapshots.
+    pub fn to_pretty_html(&self) -> String {
+        self.root().to_pretty_html()
+    }
+
+    /// Set the value of an input like a user typing into it would
+    pub(crate) fn set_value(&mut self, node: NodeId, value: &str) {
+        if let NodeKind::Element(data) = &mut self.nodes[node.0].kind {
+            data.set_attribute("value", Some(value.to_string()));
+        }
+    }
+
+    fn create(&mut self, kind: NodeKind) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            kind,
+            parent: None,
+            children: Vec::new(),
+            element_id: None,
+        });
+        id
+    }
+
+    fn assign(&mut self, node: NodeId, id: ElementId) {
+        self.nodes[node.0].element_id = Some(id);
+        self.elements.insert(id, node);
+    }
+
+    fn element(&self, id: ElementId) -> NodeId {
+        *self
+            .elements
+            .get(&id)
+            .unwrap_or_else(|| panic!("{id:?} is not mounted in the document"))
+    }
+
+    fn element_data(&mut self, id: ElementId) -> Option<&mut ElementData> {
+        let node = self.element(id);
+        match &mut self.nodes[node.0].kind {
+            NodeKind::Element(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Clone a node from a template into the document
+    fn create_template_node(&mut self, node: &TemplateNode) -> NodeId {
+        match node {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                let mut data = ElementData {
+                    tag,
+                    ..Default::default()
+                };
+                for attr in attrs.iter() {
+                    if let TemplateAttribute::Static {
+                        name,
+                        value,
+                        namespace,
+                    } = attr
+                    {
+                        match *namespace {
+                            Some("style") => data.set_style(name, Some(value.to_string())),
+                            _ => data.set_attribute(name, Some(value.to_string())),
+                        }
+                    }
+                }
+                let id = self.create(NodeKind::Element(data));
+                let children = children
+                    .iter()
+                    .map(|child| self.create_template_node(child))
+                    .collect();
+                self.insert(id, 0, children);
+                id
+            }
+            TemplateNode::Text { text } => self.create(NodeKind::Text(text.to_string())),
+            TemplateNode::Dynamic { .. } => self.create(NodeKind::Placeholder),
+        }
+    }
+
+    /// Follow a path of child indexes from the top of the stack
+    fn load_child(&self, path: &[u8]) -> NodeId {
+        let mut node = *self.stack.last().expect("the stack should not be empty");
+        for &index in path {
+            node = self.nodes[node.0].children[index as usize];
+        }
+        node
+    }
+
+    fn pop_nodes(&mut self, m: usize) -> Vec<NodeId> {
+        self.stack.split_off(self.stack.len() - m)
+    }
+
+    fn detach(&mut self, node: NodeId) {
+        if let Some(parent) = self.nodes[node.0].parent.take() {
+            self.nodes[parent.0].children.retain(|&child| child != node);
+        }
+    }
+
+    fn insert(&mut self, parent: NodeId, index: usize, nodes: Vec<NodeId>) {
+        for &node in &nodes {
+            self.nodes[node.0].parent = Some(parent);
+        }
+        self.nodes[parent.0].children.splice(index..index, nodes);
+    }
+
+    /// Insert nodes next to a sibling. `offset` is 0 to insert before the sibling and 1 to insert after it.
+    fn insert_next_to(&mut self, sibling: NodeId, offset: usize, nodes: Vec<NodeId>) {
+        for &node in &nodes {
+            self.detach(node);
+        }
+        let parent = self.nodes[sibling.0]
+            .parent
+            .expect("the sibling should be mounted");
+        let index = self.index_in_parent(sibling) + offset;
+        self.insert(parent, index, nodes);
+    }
+
+    fn index_in_parent(&self, node: NodeId) -> usize {
+        let parent = self.nodes[node.0]
+            .parent
+            .expect("the node should be mounted");
+        self.nodes[parent.0]
+            .children
+            .iter()
+            .position(|&child| child == node)
+            .unwrap()
+    }
+
+    fn replace(&mut self, old: NodeId, nodes: Vec<NodeId>) {
+        self.insert_next_to(old, 0, nodes);
+        self.detach(old);
+    }
+}
+
+impl WriteMutations for Document {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        let parent = self.element(id);
+        let nodes = self.pop_nodes(m);
+        for &node in &nodes {
+            self.detach(node);
+        }
+        let index = self.nodes[parent.0].children.len();
+        self.insert(parent, index, nodes);
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        let node = self.load_child(path);
+        self.assign(node, id);
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        let node = self.create(NodeKind::Placeholder);
+        self.assign(node, id);
+        self.stack.push(node);
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        let node = self.create(NodeKind::Text(value.to_string()));
+        self.assign(node, id);
+        self.stack.push(node);
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        let node = self.create_template_node(&template.roots[index]);
+        self.assign(node, id);
+        self.stack.push(node);
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        let old = self.element(id);
+        let nodes = self.pop_nodes(m);
+        self.replace(old, nodes);
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        let nodes = self.pop_nodes(m);
+        let old = self.load_child(path);
+        self.replace(old, nodes);
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        let sibling = self.element(id);
+        let nodes = self.pop_nodes(m);
+        self.insert_next_to(sibling, 1, nodes);
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        let sibling = self.element(id);
+        let nodes = self.pop_nodes(m);
+        self.insert_next_to(sibling, 0, nodes);
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        let Some(data) = self.element_data(id) else {
+            return;
+        };
+        let value = match value {
+            AttributeValue::Text(value) => Some(value.clone()),
+            AttributeValue::Float(value) => Some(value.to_string()),
+            AttributeValue::Int(value) => Some(value.to_string()),
+            AttributeValue::Bool(value) => Some(value.to_string()),
+            AttributeValue::None => None,
+            AttributeValue::Listener(_) | AttributeValue::Any(_) => return,
+        };
+
+        match (name, ns) {
+            (_, Some("style")) => data.set_style(name, value),
+            ("dangerous_inner_html", _) => data.inner_html = value,
+            _ => {
+                let falsy = value
+                    .as_deref()
+                    .is_some_and(|value| value.is_empty() || value == "false");
+                if falsy && BOOL_ATTRS.contains(&name) {
+                    data.set_attribute(name, None);
+                } else {
+                    data.set_attribute(name, value);
+                }
+            }
+        }
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        let node = self.element(id);
+        if let NodeKind::Text(text) = &mut self.nodes[node.0].kind {
+            *text = value.to_string();
+        }
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        if let Some(data) = self.element_data(id) {
+            data.listeners.push(name);
+        }
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        if let Some(data) = self.element_data(id) {
+            if let Some(index) = data.listeners.iter().position(|&listener| listener == name) {
+                data.listeners.remove(index);
+            }
+        }
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        let node = self.element(id);
+        self.detach(node);
+        self.elements.remove(&id);
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        let node = self.element(id);
+        self.stack.push(node);
+    }
+}
+
+impl Debug for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_pretty_html())
+    }
+}
+
+/// A reference to a node in a [`Document`]
+#[derive(Clone, Copy)]
+pub struct NodeRef<'a> {
+    pub(crate) document: &'a Document,
+    pub(crate) id: NodeId,
+}
+
+impl<'a> NodeRef<'a> {
+    /// The id of the node. Use the id to fire events at the node after the document changes.
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// The id the virtual dom assigned to the node, if it has one
+    pub fn element_id(&self) -> Option<ElementId> {
+        self.node().element_id
+    }
+
+    /// The tag of the node if it is an element
+    pub fn tag(&self) -> Option<&'static str> {
+        self.element_data().map(|data| data.tag)
+    }
+
+    /// Check if the node is a text node
+    pub fn is_text(&self) -> bool {
+        matches!(self.node().kind, NodeKind::Text(_))
+    }
+
+    /// Check if the node is still part of the document
+    pub fn is_mounted(&self) -> bool {
+        let mut node = self.id;
+        while let Some(parent) = self.document.nodes[node.0].parent {
+            node = parent;
+        }
+        node == NodeId(0)
+    }
+
+    /// The value of an attribute of the element. Styles set with the style namespace are merged into the `style`
+    /// attribute.
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        self.element_data().and_then(|data| data.attribute(name))
+    }
+
+    /// Every attribute of the element in the order they were first set
+    pub fn attributes(&self) -> Vec<(String, String)> {
+        self.element_data()
+            .map(ElementData::attributes)
+            .unwrap_or_default()
+    }
+
+    /// Check if the element has a listener for an event like `click`. Capture listeners are named like `click:capture`.
+    pub fn has_listener(&self, event: &str) -> bool {
+        self.element_data()
+            .is_some_and(|data| data.listeners.contains(&event))
+    }
+
+    pub(crate) fn has_any_listener(&self) -> bool {
+        self.element_data()
+            .is_some_and(|data| !data.listeners.is_empty())
+    }
+
+    /// The text of the node and all of its descendants
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        self.write_text(&mut text);
+        text
+    }
+
+    fn write_text(&self, text: &mut String) {
+        match &self.node().kind {
+            NodeKind::Text(value) => text.push_str(value),
+            _ => self.children().for_each(|child| child.write_text(text)),
+        }
+    }
+
+    /// The parent of the node
+    pub fn parent(&self) -> Option<NodeRef<'a>> {
+        self.node().parent.map(|id| self.document.node(id))
+    }
+
+    /// The children of the node, including text nodes and placeholders
+    pub fn children(&self) -> impl Iterator<Item = NodeRef<'a>> + 'a {
+        let document = self.document;
+        document.nodes[self.id.0]
+            .children
+            .iter()
+            .map(move |&id| document.node(id))
+    }
+
+    /// Every descendant of the node in document order, not including the node itself
+    pub fn descendants(&self) -> Vec<NodeRef<'a>> {
+        let mut descendants = Vec::new();
+        let mut stack: Vec<_> = self.children().collect();
+        stack.reverse();
+        while let Some(node) = stack.pop() {
+            descendants.push(node);
+            let len = stack.len();
+            stack.extend(node.children());
+            stack[len..].reverse();
+        }
+        descendants
+    }
+
+    /// Render the node and its descendants to html without any whitespace between nodes
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        self.write_html(&mut html, None).unwrap();
+        html
+    }
+
+    /// Render the node and its descendants to indented html with one node per line
+    pub fn to_pretty_html(&self) -> String {
+        let mut html = String::new();
+        self.write_html(&mut html, Some(0)).unwrap();
+        html
+    }
+
+    fn write_html(&self, out: &mut String, indent: Option<usize>) -> std::fmt::Result {
+        let pad = |out: &mut String, indent: Option<usize>| {
+            if let Some(indent) = indent {
+                out.push_str(&"  ".repeat(indent));
+            }
+        };
+        let newline = |out: &mut String| {
+            if indent.is_some() {
+                out.push('\n');
+            }
+        };
+
+        match &self.node().kind {
+            NodeKind::Root => {
+                for child in self.children() {
+                    child.write_html(out, indent)?;
+                }
+            }
+            NodeKind::Placeholder => {}
+            NodeKind::Text(text) => {
+                if indent.is_some() && text.trim().is_empty() {
+                    return Ok(());
+                }
+                pad(out, indent);
+                write!(out, "{}", escape(text, Html))?;
+                newline(out);
+            }
+            NodeKind::Element(data) => {
+                pad(out, indent);
+                write!(out, "<{}", data.tag)?;
+                for (name, value) in data.attributes() {
+                    write!(out, " {name}=\"{}\"", escape(&value, Html))?;
+                }
+                out.push('>');
+
+                let inline = data.inner_html.is_some()
+                    || self
+                        .children()
+                        .all(|child| !matches!(child.node().kind, NodeKind::Element(_)));
+                if let Some(inner_html) = &data.inner_html {
+                    out.push_str(inner_html);
+                } else if inline {
+                    for child in self.children() {
+                        child.write_html(out, None)?;
+                    }
+                } else {
+                    newline(out);
+                    for child in self.children() {
+                        child.write_html(out, indent.map(|indent| indent + 1))?;
+                    }
+                    pad(out, indent);
+                }
+                write!(out, "</{}>", data.tag)?;
+                newline(out);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn node(&self) -> &'a Node {
+        &self.document.nodes[self.id.0]
+    }
+
+    pub(crate) fn element_data(&self) -> Option<&'a ElementData> {
+        match &self.node().kind {
+            NodeKind::Element(data) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+impl Debug for NodeRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_html())
+    }
+}
+
+impl Display for NodeRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_html())
+    }
+}
@@ -0,0 +1,188 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+
+mod document;
+mod query;
+pub mod snapshot;
+
+pub use document::{Document, NodeId, NodeRef};
+pub use query::{Query, Target};
+
+use dioxus_core::{ComponentFunction, Element, ElementId, Event, VirtualDom};
+use dioxus_html::{
+    prelude::{Code, Key, Location, Modifiers},
+    PlatformEventData, SerializedFormData, SerializedHtmlEventConverter, SerializedKeyboardData,
+    SerializedMouseData,
+};
+use futures_util::FutureExt;
+use std::{any::Any, collections::HashMap, ops::Deref, rc::Rc};
+
+/// A [`VirtualDom`] rendered to an in-memory [`Document`] that tests can query and fire events at.
+///
+/// The test dom dereferences to its [`Document`], so queries like [`Document::get_by_text`] can be called on it
+/// directly.
+pub struct TestDom {
+    dom: VirtualDom,
+    document: Document,
+}
+
+impl TestDom {
+    /// Render a component without props and run any work that is ready
+    pub fn new(app: fn() -> Element) -> Self {
+        Self::from_virtual_dom(VirtualDom::new(app))
+    }
+
+    /// Render a component with props and run any work that is ready
+    pub fn new_with_props<P: Clone + 'static, M: 'static>(
+        root: impl ComponentFunction<P, M>,
+        root_props: P,
+    ) -> Self {
+        Self::from_virtual_dom(VirtualDom::new_with_props(root, root_props))
+    }
+
+    /// Render a virtual dom that was already created, for example with extra context, and run any work that is ready
+    pub fn from_virtual_dom(mut dom: VirtualDom) -> Self {
+        // Events are fired with serialized event data
+        dioxus_html::set_event_converter(Box::new(SerializedHtmlEventConverter));
+
+        let mut document = Document::new();
+        dom.rebuild(&mut document);
+        let mut test_dom = Self { dom, document };
+        test_dom.flush();
+        test_dom
+    }
+
+    /// The virtual dom that is rendered to the document
+    pub fn virtual_dom(&self) -> &VirtualDom {
+        &self.dom
+    }
+
+    /// The virtual dom that is rendered to the document. Changes made directly to the virtual dom are rendered the
+    /// next time the test dom is flushed.
+    pub fn virtual_dom_mut(&mut self) -> &mut VirtualDom {
+        &mut self.dom
+    }
+
+    /// The document the virtual dom is rendered to
+    pub fn document(&self) -> &Document {
+        &self.document
+    }
+
+    /// Run every task and render every scope that is ready without waiting for anything. This is called
+    /// automatically after events, so it is only needed after changing state outside of the test dom.
+    pub fn flush(&mut self) {
+        while self.dom.wait_for_work().now_or_never().is_some() {
+            self.dom.render_immediate(&mut self.document);
+        }
+    }
+
+    /// Wait until the virtual dom has new work, for example from a future that resolved, then render it and flush
+    /// anything else that is ready
+    pub async fn wait_for_work(&mut self) {
+        self.dom.wait_for_work().await;
+        self.dom.render_immediate(&mut self.document);
+        self.flush();
+    }
+
+    /// Render the document until no component is suspended
+    ///
+    /// This waits forever if a component never stops suspending. Wrap it in a timeout like `tokio::time::timeout` to
+    /// fail the test instead.
+    pub async fn wait_for_suspense(&mut self) {
+        self.flush();
+        while self.dom.suspended_tasks_remaining() {
+            self.wait_for_work().await;
+        }
+    }
+
+    /// Render the document until a condition is true
+    ///
+    /// This waits forever if the condition never becomes true. Wrap it in a timeout like `tokio::time::timeout` to fail
+    /// the test instead.
+    pub async fn wait_until(&mut self, mut condition: impl FnMut(&Document) -> bool) {
+        self.flush();
+        while !condition(&self.document) {
+            self.wait_for_work().await;
+        }
+    }
+
+    /// Fire an event at a node and render the changes. The event goes to the closest element with a listener, just like
+    /// an event in the browser would, and bubbles if the event type bubbles.
+    ///
+    /// The data must be the serialized data type for the event, like [`SerializedMouseData`] for a `click` event.
+    ///
+    /// # Panics
+    ///
+    /// This panics with the html of the document if neither the node nor any of its parents listen for the event
+    #[track_caller]
+    pub fn fire_event<T: 'static>(&mut self, target: impl Target, name: &str, data: T) {
+        let node = target.resolve(&self.document);
+        let Some(element) = self.listening_element(node, name) else {
+            panic!(
+                "no listener for the `{name}` event on {} or its parents in:\n{}",
+                self.document.node(node).to_html(),
+                self.document.to_pretty_html()
+            );
+        };
+
+        let data = Rc::new(PlatformEventData::new(Box::new(data))) as Rc<dyn Any>;
+        let event = Event::new(data, dioxus_core_types::event_bubbles(name));
+        self.dom.runtime().handle_event(name, event, element);
+        self.flush();
+    }
+
+    /// Click a node
+    #[track_caller]
+    pub fn click(&mut self, target: impl Target) {
+        self.fire_event(target, "click", SerializedMouseData::default());
+    }
+
+    /// Type into an input. This sets the `value` of the input and fires an `input` event.
+    #[track_caller]
+    pub fn input(&mut self, target: impl Target, value: &str) {
+        let node = target.resolve(&self.document);
+        self.document.set_value(node, value);
+        let data = SerializedFormData::new(value.to_string(), HashMap::new());
+        self.fire_event(node, "input", data);
+    }
+
+    /// Press a key while a node is focused. This fires a `keydown` event.
+    #[track_caller]
+    pub fn key_down(&mut self, target: impl Target, key: Key) {
+        let data = SerializedKeyboardData::new(
+            key,
+            Code::Unidentified,
+            Location::Standard,
+            false,
+            Modifiers::empty(),
+            false,
+        );
+        self.fire_event(target, "keydown", data);
+    }
+
+    /// The closest element to a node that the virtual dom attached a listener to, if the node or one of its parents
+    /// listens for the event
+    fn listening_element(&self, node: NodeId, name: &str) -> Option<ElementId> {
+        let capture = format!("{name}:capture");
+        let mut closest = None;
+        let mut listens = false;
+        let mut node = Some(self.document.node(node));
+        while let Some(current) = node {
+            if closest.is_none() && current.has_any_listener() {
+                closest = current.element_id();
+            }
+            listens |= current.has_listener(name) || current.has_listener(&capture);
+            node = current.parent();
+        }
+        closest.filter(|_| listens)
+    }
+}
+
+impl Deref for TestDom {
+    type Target = Document;
+
+    fn deref(&self) -> &Self::Target {
+        &self.document
+    }
+}
@@ -0,0 +1,211 @@
+//! Find nodes in a [`Document`] the way a user would: by their text, their role or a test id.
+
+use crate::document::{Document, NodeId, NodeRef};
+use std::fmt::Display;
+
+/// A way to find elements in a [`Document`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Query<'a> {
+    /// Elements whose own text nodes read this text. Whitespace is collapsed before the text is compared, so
+    /// `button { "Count: {count}" }` is found with `Query::Text("Count: 0")`.
+    Text(&'a str),
+    /// Elements with this [ARIA role](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Roles). The role
+    /// is either set explicitly with the `role` attribute or implied by the tag, like `button` for a `button` element
+    /// or `heading` for an `h1` element.
+    Role(&'a str),
+    /// Elements with this `data-testid` attribute
+    TestId(&'a str),
+}
+
+impl Query<'_> {
+    fn matches(&self, node: NodeRef) -> bool {
+        if node.tag().is_none() {
+            return false;
+        }
+        match *self {
+            Query::Text(text) => {
+                let own_text: String = node
+                    .children()
+                    .filter(|child| child.is_text())
+                    .map(|child| child.text())
+                    .collect();
+                !own_text.trim().is_empty() && normalize(&own_text) == normalize(text)
+            }
+            Query::Role(role) => node_role(node).as_deref() == Some(role),
+            Query::TestId(id) => node.attribute("data-testid").as_deref() == Some(id),
+        }
+    }
+}
+
+impl Display for Query<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Query::Text(text) => write!(f, "text {text:?}"),
+            Query::Role(role) => write!(f, "role {role:?}"),
+            Query::TestId(id) => write!(f, "test id {id:?}"),
+        }
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The explicit or implicit role of an element
+fn node_role(node: NodeRef) -> Option<String> {
+    if let Some(role) = node.attribute("role") {
+        return role.split_whitespace().next().map(str::to_string);
+    }
+
+    let role = match node.tag()? {
+        "button" | "summary" => "button",
+        "a" | "area" if node.attribute("href").is_some() => "link",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "input" => match node.attribute("type").as_deref().unwrap_or("text") {
+            "button" | "submit" | "reset" | "image" => "button",
+            "checkbox" => "checkbox",
+            "radio" => "radio",
+            "range" => "slider",
+            "number" => "spinbutton",
+            "search" => "searchbox",
+            "text" | "email" | "tel" | "url" => "textbox",
+            _ => return None,
+        },
+        "textarea" => "textbox",
+        "select" => match node.attribute("multiple") {
+            Some(_) => "listbox",
+            None => "combobox",
+        },
+        "option" => "option",
+        "ul" | "ol" | "menu" => "list",
+        "li" => "listitem",
+        "img" => "img",
+        "nav" => "navigation",
+        "main" => "main",
+        "header" => "banner",
+        "footer" => "contentinfo",
+        "aside" => "complementary",
+        "article" => "article",
+        "form" => "form",
+        "dialog" => "dialog",
+        "table" => "table",
+        "tr" => "row",
+        "td" => "cell",
+        "th" => "columnheader",
+        "progress" => "progressbar",
+        "hr" => "separator",
+        _ => return None,
+    };
+    Some(role.to_string())
+}
+
+impl<'a> NodeRef<'a> {
+    /// Every descendant of the node that matches the query in document order
+    pub fn find_all(&self, query: Query) -> Vec<NodeRef<'a>> {
+        self.descendants()
+            .into_iter()
+            .filter(|node| query.matches(*node))
+            .collect()
+    }
+
+    /// The first descendant of the node that matches the query
+    pub fn find(&self, query: Query) -> Option<NodeRef<'a>> {
+        self.descendants()
+            .into_iter()
+            .find(|node| query.matches(*node))
+    }
+
+    /// The only descendant of the node that matches the query
+    ///
+    /// # Panics
+    ///
+    /// This panics with the html of the node if no descendant or more than one descendant matches the query
+    #[track_caller]
+    pub fn get(&self, query: Query) -> NodeRef<'a> {
+        match self.find_all(query)[..] {
+            [node] => node,
+            [] => panic!(
+                "no element with {query} found in:\n{}",
+                self.to_pretty_html()
+            ),
+            ref nodes => panic!(
+                "{} elements with {query} found in:\n{}",
+                nodes.len(),
+                self.to_pretty_html()
+            ),
+        }
+    }
+
+    /// The only descendant of the node with this text. See [`Query::Text`].
+    #[track_caller]
+    pub fn get_by_text(&self, text: &str) -> NodeRef<'a> {
+        self.get(Query::Text(text))
+    }
+
+    /// The only descendant of the node with this role. See [`Query::Role`].
+    #[track_caller]
+    pub fn get_by_role(&self, role: &str) -> NodeRef<'a> {
+        self.get(Query::Role(role))
+    }
+
+    /// The only descendant of the node with this `data-testid`. See [`Query::TestId`].
+    #[track_caller]
+    pub fn get_by_test_id(&self, id: &str) -> NodeRef<'a> {
+        self.get(Query::TestId(id))
+    }
+}
+
+impl Document {
+    /// Every element in the document that matches the query
+    pub fn find_all(&self, query: Query) -> Vec<NodeRef<'_>> {
+        self.root().find_all(query)
+    }
+
+    /// The first element in the document that matches the query
+    pub fn find(&self, query: Query) -> Option<NodeRef<'_>> {
+        self.root().find(query)
+    }
+
+    /// The only element in the document that matches the query. See [`NodeRef::get`].
+    #[track_caller]
+    pub fn get(&self, query: Query) -> NodeRef<'_> {
+        self.root().get(query)
+    }
+
+    /// The only element in the document with this text. See [`Query::Text`].
+    #[track_caller]
+    pub fn get_by_text(&self, text: &str) -> NodeRef<'_> {
+        self.root().get_by_text(text)
+    }
+
+    /// The only element in the document with this role. See [`Query::Role`].
+    #[track_caller]
+    pub fn get_by_role(&self, role: &str) -> NodeRef<'_> {
+        self.root().get_by_role(role)
+    }
+
+    /// The only element in the document with this `data-testid`. See [`Query::TestId`].
+    #[track_caller]
+    pub fn get_by_test_id(&self, id: &str) -> NodeRef<'_> {
+        self.root().get_by_test_id(id)
+    }
+}
+
+/// Something events can be fired at: a [`NodeId`] or a [`Query`] that matches exactly one element
+pub trait Target {
+    /// Find the node in the document
+    fn resolve(&self, document: &Document) -> NodeId;
+}
+
+impl Target for NodeId {
+    fn resolve(&self, _: &Document) -> NodeId {
+        *self
+    }
+}
+
+impl Target for Query<'_> {
+    #[track_caller]
+    fn resolve(&self, document: &Document) -> NodeId {
+        document.get(*self).id()
+    }
+}
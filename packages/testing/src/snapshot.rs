@@ -0,0 +1,93 @@
+//! Compare rendered html against snapshots saved next to the tests.
+
+use std::path::Path;
+
+/// Set this environment variable to overwrite snapshots that don't match instead of failing
+pub const UPDATE_SNAPSHOTS_ENV: &str = "DIOXUS_UPDATE_SNAPSHOTS";
+
+/// The environment variable CI services set. Missing snapshots fail in CI instead of being written
+const CI_ENV: &str = "CI";
+
+/// Compare the pretty html of a [`TestDom`](crate::TestDom), [`Document`](crate::Document) or
+/// [`NodeRef`](crate::NodeRef) with the snapshot saved in `tests/snapshots/<name>.html` of the current crate.
+///
+/// Missing snapshots are written on the first run, except in CI where the `CI` environment variable is set and missing
+/// snapshots fail. To accept changes to existing snapshots, or write missing snapshots in CI, run the tests with the
+/// `DIOXUS_UPDATE_SNAPSHOTS` environment variable set.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_testing::{assert_snapshot, TestDom};
+/// fn app() -> Element {
+///     rsx! { h1 { "Hello world" } }
+/// }
+///
+/// let dom = TestDom::new(app);
+/// assert_snapshot!(dom, "hello_world");
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($html:expr, $name:expr $(,)?) => {
+        $crate::snapshot::assert_snapshot(
+            &::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{}.html", $name)),
+            &$html.to_pretty_html(),
+        )
+    };
+}
+
+/// Compare html with the snapshot saved at a path. See [`assert_snapshot!`](crate::assert_snapshot!).
+#[track_caller]
+pub fn assert_snapshot(path: &Path, actual: &str) {
+    assert_snapshot_inner(
+        path,
+        actual,
+        std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some(),
+        std::env::var_os(CI_ENV).is_some(),
+    )
+}
+
+/// Compare html with the snapshot saved at a path without reading the environment. If `update` is true, snapshots that
+/// are missing or don't match are written. Otherwise missing snapshots are only written if `ci` is false.
+#[track_caller]
+pub fn assert_snapshot_inner(path: &Path, actual: &str, update: bool, ci: bool) {
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => Some(expected.replace("\r\n", "\n")),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => panic!("failed to read snapshot {}: {err}", path.display()),
+    };
+
+    if expected.as_deref() == Some(actual) {
+        return;
+    }
+
+    if expected.is_none() && !update && ci {
+        panic!(
+            "snapshot {} is missing\n\nactual:\n{actual}\nRun the tests with {UPDATE_SNAPSHOTS_ENV}=1 to write the snapshot",
+            path.display(),
+        );
+    }
+
+    if update || expected.is_none() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(path, actual)
+            .unwrap_or_else(|err| panic!("failed to write snapshot {}: {err}", path.display()));
+        return;
+    }
+
+    let expected = expected.unwrap_or_default();
+    let first_difference = expected
+        .lines()
+        .zip(actual.lines())
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+    panic!(
+        "snapshot {} does not match the rendered html (first difference on line {})\n\nexpected:\n{expected}\nactual:\n{actual}\nRun the tests with {UPDATE_SNAPSHOTS_ENV}=1 to update the snapshot",
+        path.display(),
+        first_difference + 1,
+    );
+}
@@ -0,0 +1,64 @@
+//! Async work is only run when the test dom is flushed or waits for work, so tests control when futures resolve.
+
+use dioxus::prelude::*;
+use dioxus_testing::{Query, TestDom};
+use std::time::Duration;
+
+#[tokio::test]
+async fn waits_for_futures() {
+    fn app() -> Element {
+        let mut status = use_signal(|| "loading");
+        use_future(move || async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            status.set("done");
+        });
+        rsx! { "{status}" }
+    }
+
+    let mut dom = TestDom::new(app);
+    assert_eq!(dom.to_html(), "loading");
+
+    dom.wait_until(|document| document.to_html() == "done")
+        .await;
+}
+
+#[tokio::test]
+async fn ready_work_is_flushed_immediately() {
+    fn app() -> Element {
+        let mut count = use_signal(|| 0);
+        // Tasks that don't wait on anything run before the test dom is created
+        use_future(move || async move { count += 1 });
+        rsx! { "{count}" }
+    }
+
+    let dom = TestDom::new(app);
+    assert_eq!(dom.to_html(), "1");
+}
+
+#[tokio::test]
+async fn waits_for_suspense() {
+    fn app() -> Element {
+        rsx! {
+            SuspenseBoundary { fallback: |_| rsx! { p { "Loading..." } },
+                User {}
+            }
+        }
+    }
+
+    #[component]
+    fn User() -> Element {
+        let name = use_resource(|| async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            "Alice"
+        })
+        .suspend()?;
+        rsx! { h1 { "Hello {name}" } }
+    }
+
+    let mut dom = TestDom::new(app);
+    assert!(dom.find(Query::Text("Loading...")).is_some());
+
+    dom.wait_for_suspense().await;
+    assert!(dom.find(Query::Text("Loading...")).is_none());
+    assert_eq!(dom.get_by_role("heading").text(), "Hello Alice");
+}
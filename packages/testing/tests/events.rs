@@ -0,0 +1,91 @@
+use dioxus::prelude::*;
+use dioxus_testing::{Query, TestDom};
+
+#[test]
+fn click_updates_the_document() {
+    fn app() -> Element {
+        let mut count = use_signal(|| 0);
+        rsx! {
+            p { "Count: {count}" }
+            button { onclick: move |_| count += 1, span { "Increment" } }
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    assert!(dom.find(Query::Text("Count: 0")).is_some());
+
+    // The click on the span goes to the button that is listening for it
+    dom.click(Query::Text("Increment"));
+    dom.click(Query::Role("button"));
+    assert!(dom.find(Query::Text("Count: 2")).is_some());
+}
+
+#[test]
+fn events_bubble_to_parents() {
+    fn app() -> Element {
+        let mut clicks = use_signal(Vec::new);
+        rsx! {
+            div { onclick: move |_| clicks.push("outer"),
+                button { onclick: move |_| clicks.push("inner"), "Click" }
+            }
+            "{clicks:?}"
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    let button = dom.get_by_role("button").id();
+    dom.click(button);
+    assert!(dom.root().text().ends_with(r#"["inner", "outer"]"#));
+}
+
+#[test]
+fn input_sets_the_value() {
+    fn app() -> Element {
+        let mut name = use_signal(String::new);
+        rsx! {
+            input {
+                "data-testid": "name",
+                oninput: move |event| name.set(event.value()),
+            }
+            p { "Hello {name}" }
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    dom.input(Query::TestId("name"), "Alice");
+    assert_eq!(
+        dom.get_by_test_id("name").attribute("value").as_deref(),
+        Some("Alice")
+    );
+    assert!(dom.find(Query::Text("Hello Alice")).is_some());
+}
+
+#[test]
+fn key_down_sends_the_key() {
+    fn app() -> Element {
+        let mut keys = use_signal(String::new);
+        rsx! {
+            input { onkeydown: move |event| keys.write().push_str(&event.key().to_string()) }
+            p { "{keys}" }
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    dom.key_down(Query::Role("textbox"), Key::Character("a".into()));
+    dom.key_down(Query::Role("textbox"), Key::Enter);
+    assert_eq!(dom.get_by_text("aEnter").tag(), Some("p"));
+}
+
+#[test]
+#[should_panic(expected = "no listener for the `click` event")]
+fn events_without_listeners_panic() {
+    fn app() -> Element {
+        rsx! {
+            input { oninput: |_| {} }
+            button { "Click" }
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    dom.click(Query::Role("button"));
+}
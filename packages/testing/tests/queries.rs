@@ -0,0 +1,81 @@
+use dioxus::prelude::*;
+use dioxus_testing::{Query, TestDom};
+
+fn app() -> Element {
+    rsx! {
+        nav {
+            a { href: "/", "Home" }
+            a { href: "/about", "About" }
+        }
+        main {
+            h1 { "Todos" }
+            ul {
+                for todo in ["Write tests", "Fix bugs"] {
+                    li { "data-testid": "todo", "{todo}" }
+                }
+            }
+            div { role: "status", "2 left" }
+            input { r#type: "checkbox", checked: true }
+            button { disabled: false, "Clear   completed" }
+        }
+    }
+}
+
+#[test]
+fn queries_by_text() {
+    let dom = TestDom::new(app);
+
+    assert_eq!(dom.get_by_text("Todos").tag(), Some("h1"));
+    // Whitespace is collapsed
+    assert_eq!(dom.get_by_text("Clear completed").tag(), Some("button"));
+    // Only the element that holds the text matches, not its parents
+    assert_eq!(dom.find_all(Query::Text("Write tests")).len(), 1);
+    assert!(dom.find(Query::Text("Missing")).is_none());
+}
+
+#[test]
+fn queries_by_role() {
+    let dom = TestDom::new(app);
+
+    assert_eq!(dom.get_by_role("heading").text(), "Todos");
+    assert_eq!(dom.get_by_role("status").text(), "2 left");
+    assert_eq!(
+        dom.get_by_role("checkbox").attribute("checked").as_deref(),
+        Some("true")
+    );
+    assert_eq!(dom.find_all(Query::Role("link")).len(), 2);
+    assert_eq!(dom.find_all(Query::Role("listitem")).len(), 2);
+
+    // Queries can be scoped to a node
+    let nav = dom.get_by_role("navigation");
+    assert_eq!(
+        nav.get_by_text("About").attribute("href").as_deref(),
+        Some("/about")
+    );
+    assert!(nav.find(Query::Role("heading")).is_none());
+}
+
+#[test]
+fn queries_by_test_id() {
+    let dom = TestDom::new(app);
+
+    let todos: Vec<_> = dom
+        .find_all(Query::TestId("todo"))
+        .iter()
+        .map(|todo| todo.text())
+        .collect();
+    assert_eq!(todos, ["Write tests", "Fix bugs"]);
+}
+
+#[test]
+#[should_panic(expected = "2 elements with role \"link\" found")]
+fn get_panics_with_multiple_matches() {
+    let dom = TestDom::new(app);
+    dom.get_by_role("link");
+}
+
+#[test]
+fn falsy_boolean_attributes_are_removed() {
+    let dom = TestDom::new(app);
+    assert_eq!(dom.get_by_role("button").attribute("disabled"), None);
+}
@@ -0,0 +1,87 @@
+use dioxus::prelude::*;
+use dioxus_testing::{assert_snapshot, snapshot::assert_snapshot_inner, Query, TestDom};
+use std::path::{Path, PathBuf};
+
+fn app() -> Element {
+    let mut todos = use_signal(|| vec!["Write tests", "Fix bugs"]);
+    rsx! {
+        div { class: "todos", style: "display: flex",
+            h1 { "Todos" }
+            ul { color: "red",
+                for todo in todos() {
+                    li { key: "{todo}", "{todo}" }
+                }
+            }
+            button { onclick: move |_| todos.write().reverse(), "Reverse" }
+            if todos.len() > 2 {
+                p { "Too many todos" }
+            }
+            button { onclick: move |_| todos.push("Ship it"), "Add" }
+        }
+    }
+}
+
+#[test]
+fn matches_snapshots() {
+    let mut dom = TestDom::new(app);
+    assert_snapshot!(dom, "todos");
+
+    dom.click(Query::Text("Reverse"));
+    dom.click(Query::Text("Add"));
+    assert_snapshot!(dom, "todos_reversed");
+
+    // Snapshots can be taken of part of the document
+    assert_snapshot!(dom.get_by_role("list"), "todo_list");
+}
+
+#[test]
+fn matches_ssr() {
+    let mut dom = TestDom::new(app);
+    assert_eq!(dom.to_html(), dioxus_ssr::render(dom.virtual_dom()));
+
+    dom.click(Query::Text("Add"));
+    dom.click(Query::Text("Reverse"));
+    assert_eq!(dom.to_html(), dioxus_ssr::render(dom.virtual_dom()));
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{name}.html"))
+}
+
+#[test]
+#[should_panic(expected = "does not match the rendered html")]
+fn mismatched_snapshots_panic() {
+    let dom = TestDom::new(app);
+    assert_snapshot_inner(
+        &snapshot_path("todos_reversed"),
+        &dom.to_pretty_html(),
+        false,
+        false,
+    );
+}
+
+#[test]
+#[should_panic(expected = "is missing")]
+fn missing_snapshots_panic_in_ci() {
+    let dom = TestDom::new(app);
+    assert_snapshot_inner(
+        &snapshot_path("missing"),
+        &dom.to_pretty_html(),
+        false,
+        true,
+    );
+}
+
+#[test]
+fn missing_snapshots_are_written_outside_ci() {
+    let dir = std::env::temp_dir().join(format!("dioxus-testing-{}", std::process::id()));
+    let path = dir.join("written.html");
+    let dom = TestDom::new(app);
+    assert_snapshot_inner(&path, &dom.to_pretty_html(), false, false);
+    let written = std::fs::read_to_string(&path);
+    _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(written.unwrap(), dom.to_pretty_html());
+}
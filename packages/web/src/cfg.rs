@@ -1,4 +1,8 @@
 use dioxus_core::LaunchConfig;
+#[cfg(feature = "document")]
+use dioxus_history::History;
+#[cfg(feature = "document")]
+use std::rc::Rc;
 use wasm_bindgen::JsCast as _;
 
 ///  Configuration for the WebSys renderer for the Dioxus VirtualDOM.
@@ -13,6 +17,8 @@ use wasm_bindgen::JsCast as _;
 pub struct Config {
     pub(crate) hydrate: bool,
     pub(crate) root: ConfigRoot,
+    #[cfg(feature = "document")]
+    pub(crate) history: Option<Rc<dyn History>>,
 }
 
 impl LaunchConfig for Config {}
@@ -67,6 +73,20 @@ impl Config {
         self.root = ConfigRoot::RootNode(node);
         self
     }
+
+    #[cfg(feature = "document")]
+    /// Set the history the router uses.
+    ///
+    /// By default, the app uses a [`WebHistory`](crate::WebHistory), or a [`HashHistory`](crate::HashHistory) if
+    /// `history = "hash"` is set in the `[web.app]` section of `Dioxus.toml`.
+    ///
+    /// ```rust, ignore
+    /// dioxus_web::launch(App, Config::new().history(Rc::new(HashHistory::default())))
+    /// ```
+    pub fn history(mut self, history: Rc<dyn History>) -> Self {
+        self.history = Some(history);
+        self
+    }
 }
 
 impl Default for Config {
@@ -74,6 +94,8 @@ impl Default for Config {
         Self {
             hydrate: false,
             root: ConfigRoot::RootName("main".to_string()),
+            #[cfg(feature = "document")]
+            history: None,
         }
     }
 }
@@ -9,7 +9,7 @@ use std::time::Duration;
 
 use dioxus_core::prelude::RuntimeGuard;
 use dioxus_core::{Runtime, ScopeId};
-use dioxus_devtools::{DevserverMsg, HotReloadMsg};
+use dioxus_devtools::{ClientMsg, DevserverMsg, HotReloadMsg, InspectorQuery, InspectorResponse};
 use dioxus_document::eval;
 use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use js_sys::JsString;
@@ -24,7 +24,19 @@ const POLL_INTERVAL_SCALE_FACTOR: i32 = 2;
 /// Amount of time that toats should be displayed.
 const TOAST_TIMEOUT: Duration = Duration::from_secs(5);
 
-pub(crate) fn init(runtime: Rc<Runtime>) -> UnboundedReceiver<HotReloadMsg> {
+/// A message from the devserver that needs access to the [`dioxus_core::VirtualDom`]
+pub(crate) enum DevtoolsMsg {
+    /// Apply a hot reload
+    HotReload(HotReloadMsg),
+
+    /// Answer an inspector query over the socket it came from
+    Inspect {
+        query: InspectorQuery,
+        socket: WebSocket,
+    },
+}
+
+pub(crate) fn init(runtime: Rc<Runtime>) -> UnboundedReceiver<DevtoolsMsg> {
     // Create the tx/rx pair that we'll use for the top-level future in the dioxus loop
     let (tx, rx) = unbounded();
 
@@ -36,7 +48,7 @@ pub(crate) fn init(runtime: Rc<Runtime>) -> UnboundedReceiver<HotReloadMsg> {
 
 fn make_ws(
     runtime: Rc<Runtime>,
-    tx: UnboundedSender<HotReloadMsg>,
+    tx: UnboundedSender<DevtoolsMsg>,
     poll_interval: i32,
     reload: bool,
 ) {
@@ -57,6 +69,7 @@ fn make_ws(
     // Set the onmessage handler to bounce messages off to the main dioxus loop
     let tx_ = tx.clone();
     let runtime_ = runtime.clone();
+    let ws_ = ws.clone();
     ws.set_onmessage(Some(
         Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
             let Ok(text) = e.data().dyn_into::<JsString>() else {
@@ -68,7 +81,16 @@ fn make_ws(
             // let leaked: &'static str = Box::leak(Box::new(string));
 
             match serde_json::from_str::<DevserverMsg>(&string) {
-                Ok(DevserverMsg::HotReload(hr)) => _ = tx_.unbounded_send(hr),
+                Ok(DevserverMsg::HotReload(hr)) => {
+                    _ = tx_.unbounded_send(DevtoolsMsg::HotReload(hr))
+                }
+
+                Ok(DevserverMsg::Inspect(query)) => {
+                    _ = tx_.unbounded_send(DevtoolsMsg::Inspect {
+                        query,
+                        socket: ws_.clone(),
+                    })
+                }
 
                 // todo: we want to throw a screen here that shows the user that the devserver has disconnected
                 // Would be nice to do that with dioxus itself or some html/css
@@ -180,6 +202,13 @@ fn make_ws(
     }
 }
 
+/// Send the answer to an inspector query back to the devserver
+pub(crate) fn send_inspector_response(socket: &WebSocket, response: InspectorResponse) {
+    if let Ok(text) = serde_json::to_string(&ClientMsg::Inspector(response)) {
+        _ = socket.send_with_str(&text);
+    }
+}
+
 /// Represents what color the toast should have.
 enum ToastLevel {
     /// Green
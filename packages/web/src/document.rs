@@ -10,7 +10,7 @@ use std::pin::Pin;
 use std::{rc::Rc, str::FromStr};
 use wasm_bindgen::prelude::*;
 
-use crate::history::WebHistory;
+use crate::history::{HashHistory, WebHistory};
 
 #[wasm_bindgen::prelude::wasm_bindgen]
 pub struct JSOwner {
@@ -62,8 +62,12 @@ pub fn init_document() {
     if ScopeId::ROOT.has_context::<Rc<dyn Document>>().is_none() {
         ScopeId::ROOT.provide_context(provider);
     }
-    let history_provider: Rc<dyn History> = Rc::new(WebHistory::default());
+    // The history may already be set through the web config
     if ScopeId::ROOT.has_context::<Rc<dyn History>>().is_none() {
+        let history_provider: Rc<dyn History> = match dioxus_cli_config::web_history().as_deref() {
+            Some("hash") => Rc::new(HashHistory::default()),
+            _ => Rc::new(WebHistory::default()),
+        };
         ScopeId::ROOT.provide_context(history_provider);
     }
 }
@@ -0,0 +1,97 @@
+use dioxus_history::History;
+use std::sync::Arc;
+
+use super::WebHistory;
+
+/// A [`History`] that stores the route in the hash of the url, like `/#/blog/1`.
+///
+/// Static hosts serve the same page for every url that only differs in the hash, so apps that use [`HashHistory`]
+/// don't need rewrite rules that send every path to the `index.html` of the app. Like [`WebHistory`], it restores
+/// the scroll position when the user moves through the history.
+///
+/// # Prefix
+/// The route comes after the prefix, which is the base path of the app if it is not set. With a prefix of `/docs`,
+/// the url of the `/guide` route is `/docs/#/guide`.
+///
+/// You can select the hash history with [`Config::history`](crate::Config::history), or for every build of the app
+/// with the `history` setting in the `[web.app]` section of `Dioxus.toml`:
+///
+/// ```toml
+/// [web.app]
+/// history = "hash"
+/// ```
+pub struct HashHistory {
+    history: WebHistory,
+}
+
+impl Default for HashHistory {
+    fn default() -> Self {
+        Self::new(None, true)
+    }
+}
+
+impl HashHistory {
+    /// Create a new [`HashHistory`].
+    ///
+    /// If `do_scroll_restoration` is [`true`], [`HashHistory`] will take control of the history
+    /// state. It'll also set the browsers scroll restoration to `manual`.
+    pub fn new(prefix: Option<String>, do_scroll_restoration: bool) -> Self {
+        Self {
+            history: WebHistory::new_with_urls(prefix, do_scroll_restoration, true),
+        }
+    }
+}
+
+impl History for HashHistory {
+    fn current_route(&self) -> String {
+        self.history.current_route()
+    }
+
+    fn current_prefix(&self) -> Option<String> {
+        self.history.current_prefix()
+    }
+
+    fn go_back(&self) {
+        self.history.go_back()
+    }
+
+    fn go_forward(&self) {
+        self.history.go_forward()
+    }
+
+    fn go(&self, delta: isize) {
+        self.history.go(delta)
+    }
+
+    fn push(&self, route: String) {
+        self.history.push(route)
+    }
+
+    fn push_with_state(&self, route: String, state: String) {
+        self.history.push_with_state(route, state)
+    }
+
+    fn current_state(&self) -> Option<String> {
+        self.history.current_state()
+    }
+
+    fn replace(&self, path: String) {
+        self.history.replace(path)
+    }
+
+    fn external(&self, url: String) -> bool {
+        self.history.external(url)
+    }
+
+    fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {
+        self.history.updater(callback)
+    }
+
+    fn last_update_delta(&self) -> Option<isize> {
+        self.history.last_update_delta()
+    }
+
+    fn block_unload(&self, blocked: bool) {
+        self.history.block_unload(blocked)
+    }
+}
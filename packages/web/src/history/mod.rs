@@ -1,11 +1,15 @@
 use scroll::ScrollPosition;
+use std::{cell::Cell, rc::Rc};
 use wasm_bindgen::JsCast;
 use wasm_bindgen::{prelude::Closure, JsValue};
 use web_sys::{window, Window};
 use web_sys::{Event, History, ScrollRestoration};
 
+mod hash;
 mod scroll;
 
+pub use hash::HashHistory;
+
 fn base_path() -> Option<String> {
     let base_path = dioxus_cli_config::web_base_path();
     tracing::trace!("Using base_path from the CLI: {:?}", base_path);
@@ -15,28 +19,43 @@ fn base_path() -> Option<String> {
 #[allow(clippy::extra_unused_type_parameters)]
 fn update_scroll(window: &Window, history: &History) {
     let scroll = ScrollPosition::of_window(window);
-    if let Err(err) = replace_state_with_url(history, &[scroll.x, scroll.y], None) {
+    let index = get_index(history).unwrap_or_default();
+    let state = get_entry_state(history);
+    if let Err(err) = replace_state_with_url(
+        history,
+        &[scroll.x, scroll.y, index],
+        state.as_deref(),
+        None,
+    ) {
         web_sys::console::error_1(&err);
     }
 }
 
-/// A [`HistoryProvider`] that integrates with a browser via the [History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API).
+/// A [`History`](dioxus_history::History) that integrates with a browser via the [History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API).
 ///
 /// # Prefix
-/// This [`HistoryProvider`] supports a prefix, which can be used for web apps that aren't located
+/// This history supports a prefix, which can be used for web apps that aren't located
 /// at the root of their domain.
 ///
 /// Application developers are responsible for ensuring that right after the prefix comes a `/`. If
-/// that is not the case, this [`HistoryProvider`] will replace the first character after the prefix
+/// that is not the case, this history will replace the first character after the prefix
 /// with one.
 ///
 /// Application developers are responsible for not rendering the router if the prefix is not present
 /// in the URL. Otherwise, if a router navigation is triggered, the prefix will be added.
 pub struct WebHistory {
     do_scroll_restoration: bool,
+    /// Whether the route is stored in the hash of the url instead of the path
+    hash: bool,
     history: History,
     prefix: Option<String>,
     window: Window,
+    /// The position of the current page in the history. This is stored in the history state so the distance of a
+    /// `popstate` event can be calculated
+    index: Rc<Cell<f64>>,
+    /// The number of pages the last `popstate` event moved through the history
+    last_update_delta: Rc<Cell<Option<isize>>>,
+    unload_blocked: Rc<Cell<bool>>,
 }
 
 impl Default for WebHistory {
@@ -51,19 +70,30 @@ impl WebHistory {
     /// If `do_scroll_restoration` is [`true`], [`WebHistory`] will take control of the history
     /// state. It'll also set the browsers scroll restoration to `manual`.
     pub fn new(prefix: Option<String>, do_scroll_restoration: bool) -> Self {
-        let myself = Self::new_inner(prefix, do_scroll_restoration);
+        Self::new_with_urls(prefix, do_scroll_restoration, false)
+    }
+
+    fn new_with_urls(prefix: Option<String>, do_scroll_restoration: bool, hash: bool) -> Self {
+        let myself = Self::new_inner(prefix, do_scroll_restoration, hash);
 
         let current_route = dioxus_history::History::current_route(&myself);
         let current_route_str = current_route.to_string();
         let prefix_str = myself.prefix.as_deref().unwrap_or("");
         let current_url = format!("{prefix_str}{current_route_str}");
         let state = myself.create_state();
-        let _ = replace_state_with_url(&myself.history, &state, Some(&current_url));
+        // Keep the state of the entry if the page was reloaded
+        let entry_state = get_entry_state(&myself.history);
+        let _ = replace_state_with_url(
+            &myself.history,
+            &state,
+            entry_state.as_deref(),
+            Some(&current_url),
+        );
 
         myself
     }
 
-    fn new_inner(prefix: Option<String>, do_scroll_restoration: bool) -> Self {
+    fn new_inner(prefix: Option<String>, do_scroll_restoration: bool, hash: bool) -> Self {
         let window = window().expect("access to `window`");
         let history = window.history().expect("`window` has access to `history`");
 
@@ -73,6 +103,27 @@ impl WebHistory {
                 .expect("`history` can set scroll restoration");
         }
 
+        // If the page was reloaded, keep the position in the history
+        let index = get_index(&history).unwrap_or_default();
+
+        // Ask the user for confirmation before leaving the page if a navigation blocker is active
+        let unload_blocked = Rc::new(Cell::new(false));
+        let blocked = unload_blocked.clone();
+        let on_before_unload = Closure::wrap(Box::new(move |event: Event| {
+            if blocked.get() {
+                event.prevent_default();
+                // Older browsers only show the prompt if the return value is set
+                let _ =
+                    js_sys::Reflect::set(&event, &JsValue::from_str("returnValue"), &JsValue::TRUE);
+            }
+        }) as Box<dyn FnMut(Event)>);
+        window
+            .add_event_listener_with_callback(
+                "beforeunload",
+                &on_before_unload.into_js_value().unchecked_into(),
+            )
+            .unwrap();
+
         let prefix = prefix
             // If there isn't a base path, try to grab one from the CLI
             .or_else(base_path)
@@ -84,11 +135,21 @@ impl WebHistory {
             // Otherwise, start with a slash
             .map(|prefix| format!("/{prefix}"));
 
+        // With hash urls, the route comes after the `#` at the end of the base path
+        let prefix = match hash {
+            true => Some(format!("{}/#", prefix.unwrap_or_default())),
+            false => prefix,
+        };
+
         Self {
             do_scroll_restoration,
+            hash,
             history,
             prefix,
             window,
+            index: Rc::new(Cell::new(index)),
+            last_update_delta: Rc::new(Cell::new(None)),
+            unload_blocked,
         }
     }
 
@@ -98,15 +159,50 @@ impl WebHistory {
             .unwrap_or_default()
     }
 
-    fn create_state(&self) -> [f64; 2] {
+    fn create_state(&self) -> [f64; 3] {
         let scroll = self.scroll_pos();
-        [scroll.x, scroll.y]
+        [scroll.x, scroll.y, self.index.get()]
+    }
+
+    fn push_entry(&self, route: String, entry_state: Option<String>) {
+        if route == self.route_from_location() {
+            // don't push the same route twice, but do update the state attached to it
+            if let Some(entry_state) = entry_state {
+                let state = self.create_state();
+                if let Err(err) =
+                    replace_state_with_url(&self.history, &state, Some(&entry_state), None)
+                {
+                    web_sys::console::error_1(&err);
+                }
+            }
+            return;
+        }
+
+        let w = window().expect("access to `window`");
+        let h = w.history().expect("`window` has access to `history`");
+
+        // update the scroll position before pushing the new state
+        update_scroll(&w, &h);
+
+        let path = self.full_path(&route);
+
+        self.index.set(self.index.get() + 1.0);
+        let state: [f64; 3] = self.create_state();
+        self.handle_nav(push_state_and_url(
+            &self.history,
+            &state,
+            entry_state.as_deref(),
+            path,
+        ));
     }
 }
 
 impl WebHistory {
     fn route_from_location(&self) -> String {
         let location = self.window.location();
+        if self.hash {
+            return route_from_hash(&location.hash().unwrap_or_default());
+        }
         let path = location.pathname().unwrap_or_else(|_| "/".into())
             + &location.search().unwrap_or("".into())
             + &location.hash().unwrap_or("".into());
@@ -178,29 +274,34 @@ impl dioxus_history::History for WebHistory {
         }
     }
 
-    fn push(&self, state: String) {
-        if state == self.current_route() {
-            // don't push the same state twice
-            return;
+    fn go(&self, delta: isize) {
+        if let Err(e) = self.history.go_with_delta(delta as i32) {
+            web_sys::console::error_2(&JsValue::from_str("failed to move through history: "), &e);
         }
+    }
 
-        let w = window().expect("access to `window`");
-        let h = w.history().expect("`window` has access to `history`");
-
-        // update the scroll position before pushing the new state
-        update_scroll(&w, &h);
+    fn push(&self, state: String) {
+        self.push_entry(state, None)
+    }
 
-        let path = self.full_path(&state);
+    fn push_with_state(&self, route: String, state: String) {
+        self.push_entry(route, Some(state))
+    }
 
-        let state: [f64; 2] = self.create_state();
-        self.handle_nav(push_state_and_url(&self.history, &state, path));
+    fn current_state(&self) -> Option<String> {
+        get_entry_state(&self.history)
     }
 
     fn replace(&self, state: String) {
         let path = self.full_path(&state);
 
         let state = self.create_state();
-        self.handle_nav(replace_state_with_url(&self.history, &state, Some(&path)));
+        self.handle_nav(replace_state_with_url(
+            &self.history,
+            &state,
+            None,
+            Some(&path),
+        ));
     }
 
     fn external(&self, url: String) -> bool {
@@ -211,8 +312,15 @@ impl dioxus_history::History for WebHistory {
         let w = self.window.clone();
         let h = self.history.clone();
         let d = self.do_scroll_restoration;
+        let index = self.index.clone();
+        let last_update_delta = self.last_update_delta.clone();
 
         let function = Closure::wrap(Box::new(move |_| {
+            let new_index = get_index(&h);
+            last_update_delta.set(new_index.map(|new_index| (new_index - index.get()) as isize));
+            if let Some(new_index) = new_index {
+                index.set(new_index);
+            }
             (*callback)();
             if d {
                 if let Some([x, y]) = get_current(&h) {
@@ -227,29 +335,46 @@ impl dioxus_history::History for WebHistory {
             )
             .unwrap();
     }
+
+    fn last_update_delta(&self) -> Option<isize> {
+        self.last_update_delta.get()
+    }
+
+    fn block_unload(&self, blocked: bool) {
+        self.unload_blocked.set(blocked);
+    }
 }
 
-pub(crate) fn replace_state_with_url(
-    history: &History,
-    value: &[f64; 2],
-    url: Option<&str>,
-) -> Result<(), JsValue> {
+/// Create the history state of an entry: the scroll position, the index and the state attached with
+/// [`History::push_with_state`](dioxus_history::History::push_with_state)
+fn create_history_state(value: &[f64; 3], entry_state: Option<&str>) -> js_sys::Array {
     let position = js_sys::Array::new();
     position.push(&JsValue::from(value[0]));
     position.push(&JsValue::from(value[1]));
+    position.push(&JsValue::from(value[2]));
+    if let Some(entry_state) = entry_state {
+        position.push(&JsValue::from_str(entry_state));
+    }
+    position
+}
 
+pub(crate) fn replace_state_with_url(
+    history: &History,
+    value: &[f64; 3],
+    entry_state: Option<&str>,
+    url: Option<&str>,
+) -> Result<(), JsValue> {
+    let position = create_history_state(value, entry_state);
     history.replace_state_with_url(&position, "", url)
 }
 
 pub(crate) fn push_state_and_url(
     history: &History,
-    value: &[f64; 2],
+    value: &[f64; 3],
+    entry_state: Option<&str>,
     url: String,
 ) -> Result<(), JsValue> {
-    let position = js_sys::Array::new();
-    position.push(&JsValue::from(value[0]));
-    position.push(&JsValue::from(value[1]));
-
+    let position = create_history_state(value, entry_state);
     history.push_state_with_url(&position, "", Some(&url))
 }
 
@@ -267,3 +392,27 @@ pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
         Some([x, y])
     })
 }
+
+/// Get the route stored in the hash of the url. The route always starts with a slash, so a missing or empty hash is
+/// the root route
+fn route_from_hash(hash: &str) -> String {
+    let route = hash.strip_prefix('#').unwrap_or(hash);
+    match route.starts_with('/') {
+        true => route.to_string(),
+        false => format!("/{route}"),
+    }
+}
+
+/// Get the state attached to the current page from the history state
+fn get_entry_state(history: &History) -> Option<String> {
+    let state = history.state().ok()?;
+    let state = state.dyn_into::<js_sys::Array>().ok()?;
+    state.get(3).as_string()
+}
+
+/// Get the position of the current page in the history from the history state
+fn get_index(history: &History) -> Option<f64> {
+    let state = history.state().ok()?;
+    let state = state.dyn_into::<js_sys::Array>().ok()?;
+    state.get(2).as_f64()
+}
@@ -18,6 +18,11 @@ pub fn take_server_data<T: DeserializeOwned>() -> Result<Option<T>, TakeDataErro
     })
 }
 
+/// Check if components are rendered with data from the server. This is only true while they are hydrating
+pub(crate) fn is_hydrating() -> bool {
+    SERVER_DATA.with_borrow(Option::is_some)
+}
+
 /// Run a closure with the server data
 pub(crate) fn with_server_data<O>(server_data: HTMLDataCursor, f: impl FnOnce() -> O) -> O {
     // Set the server data that will be used during hydration
@@ -41,9 +41,15 @@ mod file_engine;
 #[cfg(feature = "document")]
 mod history;
 #[cfg(feature = "document")]
+mod storage;
+#[cfg(feature = "document")]
 pub use document::WebDocument;
 #[cfg(feature = "file_engine")]
 pub use file_engine::*;
+#[cfg(feature = "document")]
+pub use history::{HashHistory, WebHistory};
+#[cfg(feature = "document")]
+pub use storage::WebStorage;
 
 #[cfg(all(feature = "devtools", debug_assertions))]
 mod devtools;
@@ -63,8 +69,14 @@ pub use hydration::*;
 /// wasm_bindgen_futures::spawn_local(app_fut);
 /// ```
 pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
+    #[cfg(feature = "document")]
+    if let Some(history) = web_config.history.clone() {
+        virtual_dom.provide_root_context(history);
+    }
     #[cfg(feature = "document")]
     virtual_dom.in_runtime(document::init_document);
+    #[cfg(feature = "document")]
+    virtual_dom.in_runtime(storage::init_storage);
 
     let runtime = virtual_dom.runtime();
 
@@ -81,6 +93,22 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
     if should_hydrate {
         #[cfg(feature = "hydrate")]
         {
+            // The modules of the lazy routes the server rendered need to load before the routes hydrate
+            #[wasm_bindgen::prelude::wasm_bindgen(inline_js = r#"
+                export function preload_lazy_routes(wasm) {
+                    return window.__dx_lazy?.preload(wasm) ?? Promise.resolve();
+                }
+            "#)]
+            extern "C" {
+                fn preload_lazy_routes(wasm: wasm_bindgen::JsValue) -> js_sys::Promise;
+            }
+            if let Err(err) =
+                wasm_bindgen_futures::JsFuture::from(preload_lazy_routes(wasm_bindgen::exports()))
+                    .await
+            {
+                tracing::error!("Failed to load the modules of lazy routes: {err:?}");
+            }
+
             websys_dom.skip_mutations = true;
             // Get the initial hydration data from the client
             #[wasm_bindgen::prelude::wasm_bindgen(inline_js = r#"
@@ -170,13 +198,20 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
         }
 
         #[cfg(all(feature = "devtools", debug_assertions))]
-        if let Some(hr_msg) = template {
-            // Replace all templates
-            dioxus_devtools::apply_changes(&virtual_dom, &hr_msg);
+        match template {
+            Some(devtools::DevtoolsMsg::HotReload(hr_msg)) => {
+                // Replace all templates
+                dioxus_devtools::apply_changes(&virtual_dom, &hr_msg);
 
-            if !hr_msg.assets.is_empty() {
-                crate::devtools::invalidate_browser_asset_cache();
+                if !hr_msg.assets.is_empty() {
+                    crate::devtools::invalidate_browser_asset_cache();
+                }
             }
+            Some(devtools::DevtoolsMsg::Inspect { query, socket }) => {
+                let response = dioxus_devtools::inspect(&virtual_dom, &query);
+                devtools::send_inspector_response(&socket, response);
+            }
+            None => {}
         }
 
         #[cfg(feature = "hydrate")]
@@ -184,23 +219,29 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
             websys_dom.rehydrate_streaming(hydration_data, &mut virtual_dom);
         }
 
-        // Todo: This is currently disabled because it has a negative impact on response times for events but it could be re-enabled for tasks
-        // Jank free rendering
-        //
-        // 1. wait for the browser to give us "idle" time
-        // 2. During idle time, diff the dom
-        // 3. Stop diffing if the deadline is exceeded
-        // 4. Wait for the animation frame to patch the dom
-
-        // wait for the mainthread to schedule us in
-        // let deadline = work_loop.wait_for_idle_time().await;
-
-        // run the virtualdom work phase until the frame deadline is reached
-        virtual_dom.render_immediate(&mut websys_dom);
-
-        // wait for the animation frame to fire so we can apply our changes
-        // work_loop.wait_for_raf().await;
+        // Render for at most one frame so large updates don't block user input. Updates from user input are always
+        // rendered, but less urgent work like transitions is interrupted when the deadline passes
+        let deadline = web_time::Instant::now() + FRAME_BUDGET;
+        let finished = virtual_dom.render_with_deadline(&mut websys_dom, deadline);
 
         websys_dom.flush_edits();
+
+        // If rendering was interrupted, let the browser paint and run any event handlers before we continue
+        if !finished {
+            yield_to_browser().await;
+        }
     }
 }
+
+/// The time we render for before yielding to the browser if there is less urgent work left
+const FRAME_BUDGET: std::time::Duration = std::time::Duration::from_millis(8);
+
+/// Wait for a new task in the browser event loop so it can handle events between renders
+async fn yield_to_browser() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        _ = web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0);
+    });
+    _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
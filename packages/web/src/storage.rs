@@ -0,0 +1,89 @@
+use std::rc::Rc;
+
+use dioxus_core::ScopeId;
+use dioxus_document::{PersistentStorage, StorageWatchers};
+use futures_channel::mpsc::UnboundedReceiver;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{window, Storage, StorageEvent};
+
+/// Provides the [`WebStorage`] through [`ScopeId::provide_context`].
+pub(crate) fn init_storage() {
+    if ScopeId::ROOT
+        .has_context::<Rc<dyn PersistentStorage>>()
+        .is_none()
+    {
+        let storage: Rc<dyn PersistentStorage> = Rc::new(WebStorage::new());
+        ScopeId::ROOT.provide_context(storage);
+    }
+}
+
+/// A [`PersistentStorage`] that stores values in [`localStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage).
+///
+/// Values set in other tabs are sent to the watchers in this tab through the
+/// [`storage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/storage_event) event.
+pub struct WebStorage {
+    storage: Option<Storage>,
+    watchers: Rc<StorageWatchers>,
+}
+
+impl WebStorage {
+    /// Create a new [`WebStorage`]. Components that hydrate html from the server only load their values after they
+    /// are hydrated.
+    pub fn new() -> Self {
+        let window = window().unwrap();
+        // localStorage is unavailable in some private browsing modes. Values are still synced within the tab
+        let storage = window.local_storage().ok().flatten();
+        if storage.is_none() {
+            tracing::warn!("localStorage is unavailable. Persistent values will not be saved.");
+        }
+
+        let watchers = Rc::new(StorageWatchers::default());
+        let listener = Closure::wrap(Box::new({
+            let watchers = watchers.clone();
+            move |event: StorageEvent| {
+                if let (Some(key), Some(value)) = (event.key(), event.new_value()) {
+                    watchers.notify(&key, &value);
+                }
+            }
+        }) as Box<dyn FnMut(StorageEvent)>);
+        window
+            .add_event_listener_with_callback("storage", &listener.into_js_value().unchecked_into())
+            .unwrap();
+
+        Self { storage, watchers }
+    }
+}
+
+impl Default for WebStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PersistentStorage for WebStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.storage.as_ref()?.get_item(key).ok().flatten()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        if let Some(storage) = &self.storage {
+            if let Err(err) = storage.set_item(key, value) {
+                web_sys::console::error_1(&err);
+            }
+        }
+        // The storage event is only sent to other tabs, so the watchers in this tab are notified directly
+        self.watchers.notify(key, value);
+    }
+
+    fn watch(&self, key: &str) -> UnboundedReceiver<String> {
+        self.watchers.watch(key)
+    }
+
+    fn hydrating(&self) -> bool {
+        // Components only render with data from the server while they are hydrating
+        #[cfg(feature = "hydrate")]
+        return crate::hydration::is_hydrating();
+        #[cfg(not(feature = "hydrate"))]
+        false
+    }
+}